@@ -4,35 +4,90 @@
 use serde::{Deserialize, Serialize};
 
 // Core MCP message structure (as per mcp.design.md)
-#[derive(Serialize, Deserialize, Debug, Clone)]
+// Unset fields are left out when serialized so responses stay compact.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct McpMessage {
     pub r#type: String, // Using r# to allow "type" as field name. Examples: "vm/create", "model/log"
+
+    // Request id chosen by the client, echoed on the response so it can be matched up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
     
     // Common fields, specific commands might have more under `payload` or directly.
     // These are illustrative based on mcp.design.md examples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub instance_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub os: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cpu: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ram: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,      // e.g., "ollama:llama3"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub query: Option<bool>,        // For vm/state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub role: Option<String>,       // For vm/attach-terminal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,     // For model/log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload: Option<serde_json::Value>, // For generic payloads or complex types
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub meta: Option<McpMeta>,      // Optional metadata block
     
     // Fields for envelope format (mcp.design.md)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub src: Option<String>,        // Source agent/entity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dst: Option<String>,        // Destination VM or model
     
-    // For error responses
+    // For responses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ok: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code: Option<u16>,          // e.g., 403, 503
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,    // Error message
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl McpMessage {
+    /// A successful reply to `request`, carrying `payload`.
+    pub fn response(request: &McpMessage, payload: serde_json::Value) -> Self {
+        McpMessage {
+            r#type: request.r#type.clone(),
+            id: request.id.clone(),
+            ok: Some(true),
+            payload: Some(payload),
+            ..Default::default()
+        }
+    }
+
+    /// An `mcp/error` envelope; `id` is the failed request's id, if it could be read.
+    pub fn error(id: Option<serde_json::Value>, code: u16, message: impl Into<String>) -> Self {
+        McpMessage {
+            r#type: "mcp/error".to_string(),
+            id,
+            ok: Some(false),
+            code: Some(code),
+            message: Some(message.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn heartbeat() -> Self {
+        McpMessage {
+            r#type: "mcp/heartbeat".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct McpMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,           // e.g., "llama-sandbox" for vm/create
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub record_session: Option<bool>,
     // Add other meta fields as needed
-} 
\ No newline at end of file
+} 
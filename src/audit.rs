@@ -2,10 +2,13 @@
 
 use anyhow::Result;
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
 // use chrono::{DateTime, Utc}; // For timestamps
 
 use crate::config::Config;
@@ -22,6 +25,20 @@ pub struct AuditEvent {
     pub risk_level: Option<RiskLevel>,
 }
 
+impl AuditEvent {
+    /// Creates an event stamped with the current time and no session, agent, or details.
+    pub fn new(event_type: AuditEventType, risk_level: Option<RiskLevel>) -> Self {
+        AuditEvent {
+            timestamp_str: chrono::Utc::now().to_rfc3339(),
+            session_id: None,
+            agent_id: None,
+            event_type,
+            details: serde_json::Value::Null,
+            risk_level,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum AuditEventType {
     // System Events
@@ -37,6 +54,9 @@ pub enum AuditEventType {
     ModelAttached { instance_id: String, model_id: String },
     ModelDetached { instance_id: String, model_id: String },
     ResourceAllocation { instance_id: String, resource: String, value: String, success: bool },
+    IdleShutdown { instance_id: String, idle_minutes: u64 }, // `[libvirt] idle_shutdown_minutes` stopped the VM
+    InstanceImported { instance_id: String, name: String, role: Option<String> }, // `vm import` adopted a domain created elsewhere
+    InstanceForgotten { instance_id: String }, // `vm forget`; the domain itself is left alone
 
     // Session Events
     SessionStart { session_id: String },
@@ -82,15 +102,207 @@ pub enum RiskLevel {
     Info, // For non-risky informational events
 }
 
+const LEDGER_FILE_NAME: &str = "audit_ledger.jsonl";
+
+// The hash the first ledger record chains from
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// One line of the ledger: the event plus the hash chain linking it to the previous line.
+// `this_hash = sha256(prev_hash || event)`, where `event` is the exact serialized JSON.
+#[derive(Serialize, Deserialize)]
+struct LedgerRecord<'a> {
+    prev_hash: String,
+    #[serde(borrow)]
+    event: &'a RawValue,
+    this_hash: String,
+}
+
+// Follows a ledger's hash chain one line at a time
+struct ChainVerifier {
+    expected_prev: String,
+}
+
+impl ChainVerifier {
+    fn new() -> Self {
+        ChainVerifier { expected_prev: GENESIS_HASH.to_string() }
+    }
+
+    /// Parses one ledger line and checks its links. A record with a bad link is still returned,
+    /// with the reason, and the chain carries on from its hash so a single break is reported once.
+    fn check<'a>(&mut self, line: &'a str) -> std::result::Result<(LedgerRecord<'a>, Option<String>), String> {
+        let record: LedgerRecord = serde_json::from_str(line).map_err(|e| format!("not a ledger record: {}", e))?;
+        let broken = if record.prev_hash != self.expected_prev {
+            Some("prev_hash does not match the previous record".to_string())
+        } else if chain_hash(&record.prev_hash, record.event.get()) != record.this_hash {
+            Some("this_hash does not match the record contents".to_string())
+        } else {
+            None
+        };
+        self.expected_prev = record.this_hash.clone();
+        Ok((record, broken))
+    }
+}
+
+// Ledgers written before hash chaining hold bare event lines. They can only come before the first
+// record, and are checked for shape only: the raw JSON must look like an event, not a record.
+fn is_pre_chain_event(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line).is_ok_and(|value| {
+        value.get("timestamp_str").is_some() && value.get("event_type").is_some() && value.get("this_hash").is_none()
+    })
+}
+
+// Rewritten next to the ledger after every record, so records cut off the end of an otherwise
+// intact chain are noticed. A ledger written before the marker existed simply has none.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct LedgerTail {
+    records: usize,
+    last_hash: String,
+}
+
+// Outcome of re-checking every link in a ledger file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerVerification {
+    Intact { records: usize, pre_chain: usize, last_hash: String }, // `pre_chain` bare events come before the first record
+    Broken { line: Option<usize>, reason: String }, // 1-based line of the first bad record; None if the tail marker is bad
+    Truncated { records: usize, expected_records: usize }, // The chain holds, but the tail marker counted more
+}
+
+// Which ledger events `hydravisor audit export` keeps; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub since: Option<chrono::DateTime<chrono::Utc>>, // Inclusive
+    pub until: Option<chrono::DateTime<chrono::Utc>>, // Exclusive
+    pub agent_id: Option<String>,
+    pub event_type: Option<String>, // Variant name, e.g. `PolicyDecision`; case-insensitive
+}
+
+impl AuditFilter {
+    // Works on the raw JSON so events from newer or older builds can still be exported
+    fn matches(&self, event: &serde_json::Value) -> bool {
+        if self.since.is_some() || self.until.is_some() {
+            let Some(timestamp) = event["timestamp_str"].as_str()
+                .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+                .map(|ts| ts.with_timezone(&chrono::Utc))
+            else {
+                return false;
+            };
+            if self.since.is_some_and(|since| timestamp < since) || self.until.is_some_and(|until| timestamp >= until) {
+                return false;
+            }
+        }
+        if let Some(event_type) = &self.event_type {
+            if !event_type_name(event).is_some_and(|name| name.eq_ignore_ascii_case(event_type)) {
+                return false;
+            }
+        }
+        if let Some(agent_id) = &self.agent_id {
+            // Most agent events name the agent inside the variant rather than at the top level
+            let in_variant = event_type_fields(event).and_then(|fields| fields.get("agent_id"));
+            if ![Some(&event["agent_id"]), in_variant].into_iter().flatten().any(|id| id.as_str() == Some(agent_id)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Parses an `--since`/`--until` bound: RFC 3339, or a bare `YYYY-MM-DD` meaning midnight UTC.
+pub fn parse_audit_time(value: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| anyhow::anyhow!("'{}' is not a date (YYYY-MM-DD) or RFC 3339 timestamp", value))
+}
+
+// Formats `hydravisor audit export` can write
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditExportFormat {
+    Jsonl, // One event per line, as recorded, without the chain hashes
+    Csv,   // The common fields as columns, the variant's own fields as a JSON `details` column
+}
+
+// What an export read and wrote, and the chain breaks it passed over
+#[derive(Debug, Default)]
+pub struct AuditExportSummary {
+    pub records: usize,
+    pub exported: usize,
+    pub pre_chain: usize, // Of `records`, the bare events from before hash chaining
+    pub breaks: Vec<(usize, String)>, // 1-based line number and reason
+}
+
+// Categories of logs under the log directory, as selected by `hydravisor log list --log-type`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogType {
+    Vm,
+    Container,
+    System,
+    Mcp,
+    Audit,
+}
+
+impl LogType {
+    pub const ALL: [LogType; 5] = [LogType::Vm, LogType::Container, LogType::System, LogType::Mcp, LogType::Audit];
+}
+
+// On-disk log formats: asciinema v2 recordings, JSON lines, and raw terminal output
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Cast,
+    Jsonl,
+    Ansi,
+}
+
+impl LogFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LogFormat::Cast => "cast",
+            LogFormat::Jsonl => "jsonl",
+            LogFormat::Ansi => "log",
+        }
+    }
+
+    /// Guesses the format from a file name; anything unrecognised is treated as plain/ANSI text.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("cast") => LogFormat::Cast,
+            Some("jsonl") => LogFormat::Jsonl,
+            _ => LogFormat::Ansi,
+        }
+    }
+}
+
+// A log file found under the log directories. `session_id` is its path relative to its
+// category directory without the extension, e.g. `my-vm/terminal` or `audit_ledger`.
+#[derive(Serialize, Debug, Clone)]
+pub struct LogFileEntry {
+    pub session_id: String,
+    pub log_type: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub modified: Option<String>, // RFC 3339
+}
+
+// The writer and the hash of the last record written, kept under one lock so
+// concurrent callers can't interleave links.
+struct LedgerWriter {
+    writer: Box<dyn Write + Send>,
+    last_hash: String,
+    records: usize,
+    tail_path: PathBuf,
+}
+
 pub struct AuditEngine {
-    // log_path: PathBuf, // This field is not read
-    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    log_dir: PathBuf,        // Root of instance, MCP, and audit logs
+    system_log_dir: PathBuf, // Where the application's own tracing logs rotate
+    writer: Arc<Mutex<LedgerWriter>>,
+    events_written: AtomicU64, // Since startup, for the metrics endpoint
 }
 
 impl AuditEngine {
     pub fn new(app_config: &Config) -> Result<Self> {
-        let log_dir_str = shellexpand::tilde(&app_config.logging.log_dir).into_owned();
-        let mut audit_log_dir = PathBuf::from(log_dir_str);
+        let mut audit_log_dir = app_config.log_dir();
         
         // Determine the specific subdirectory for audit ledger if needed, or use log_dir directly
         // For now, let's assume audit ledger goes into a subdirectory "audit" within the main log_dir
@@ -102,30 +314,473 @@ impl AuditEngine {
                 .map_err(|e| anyhow::anyhow!("Failed to create audit log directory {:?}: {}", audit_log_dir, e))?;
         }
 
-        let log_file_path = audit_log_dir.join("audit_ledger.jsonl");
+        let log_file_path = audit_log_dir.join(LEDGER_FILE_NAME);
 
-        println!(
-            "AuditEngine initialized. Audit ledger will be at: {:?}",
-            log_file_path
-        );
+        // Logged rather than printed so `--output json` stays parseable
+        tracing::info!("Audit ledger will be at: {:?}", log_file_path);
         
-        Ok(AuditEngine {
-            writer: Arc::new(Mutex::new(Box::new(std::fs::File::create(log_file_path)?))),
-        })
-    }
-
-    // This method is never used
-    // pub fn record_event(&self, event: AuditEvent) -> Result<()> {
-    //     let mut writer = self.writer.lock().unwrap();
-    //     let mut json_string = serde_json::to_string(&event)?;
-    //     json_string.push('\n');
-    //     writer.write_all(json_string.as_bytes())?;
-    //     writer.flush()?;
-    //     Ok(())
-    // }
-
-    // TODO: Add methods for log verification, export, etc., if handled by this engine.
-    // Or these could be CLI-specific functions that use the AuditEngine for data access.
+        // Continue the existing chain; a broken ledger is reported but not rewritten
+        let mut truncation = None;
+        let (records, last_hash) = if log_file_path.exists() {
+            match Self::verify_ledger(&log_file_path)? {
+                LedgerVerification::Intact { records, pre_chain, last_hash } => {
+                    if pre_chain > 0 {
+                        tracing::info!("Audit ledger {:?} starts with {} events from before hash chaining; they cannot be verified", log_file_path, pre_chain);
+                    }
+                    (records, last_hash)
+                }
+                LedgerVerification::Broken { line: Some(line), reason } => {
+                    tracing::warn!("Audit ledger {:?} fails verification at line {}: {}", log_file_path, line, reason);
+                    Self::ledger_end(&log_file_path)?
+                }
+                LedgerVerification::Broken { line: None, reason } => {
+                    tracing::warn!("Audit ledger {:?} fails verification: {}", log_file_path, reason);
+                    Self::ledger_end(&log_file_path)?
+                }
+                LedgerVerification::Truncated { records, expected_records } => {
+                    tracing::warn!("Audit ledger {:?} has {} records but had {}; it has been truncated", log_file_path, records, expected_records);
+                    truncation = Some((records, expected_records));
+                    Self::ledger_end(&log_file_path)?
+                }
+            }
+        } else {
+            (0, GENESIS_HASH.to_string())
+        };
+
+        // Append, never truncate: the ledger must survive restarts
+        let ledger_file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_file_path)
+            .map_err(|e| anyhow::anyhow!("Failed to open audit ledger {:?}: {}", log_file_path, e))?;
+
+        let engine = AuditEngine {
+            log_dir: app_config.log_dir(),
+            system_log_dir: app_config.system_log_dir(),
+            writer: Arc::new(Mutex::new(LedgerWriter {
+                writer: Box::new(ledger_file),
+                last_hash,
+                records,
+                tail_path: tail_path(&log_file_path),
+            })),
+            events_written: AtomicU64::new(0),
+        };
+        // The next record rewrites the tail marker, so the truncation is recorded in the ledger itself
+        if let Some((records, expected_records)) = truncation {
+            engine.record_event(AuditEvent::new(
+                AuditEventType::AnomalyDetected {
+                    description: format!("Audit ledger truncated: {} records found, {} were written", records, expected_records),
+                    severity: RiskLevel::High,
+                },
+                Some(RiskLevel::High),
+            ))?;
+        }
+        Ok(engine)
+    }
+
+    /// Appends an event to the ledger as a single hash-chained JSON line and flushes it.
+    pub fn record_event(&self, event: AuditEvent) -> Result<()> {
+        let event_json = serde_json::to_string(&event)?;
+        let mut ledger = self.writer
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Audit ledger writer lock is poisoned"))?;
+
+        let this_hash = chain_hash(&ledger.last_hash, &event_json);
+        let record = LedgerRecord {
+            prev_hash: ledger.last_hash.clone(),
+            event: &RawValue::from_string(event_json)?,
+            this_hash: this_hash.clone(),
+        };
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        ledger.writer.write_all(line.as_bytes())?;
+        ledger.writer.flush()?;
+        ledger.last_hash = this_hash;
+        ledger.records += 1;
+        let tail = LedgerTail { records: ledger.records, last_hash: ledger.last_hash.clone() };
+        if let Err(e) = write_tail(&ledger.tail_path, &tail) {
+            tracing::warn!("Failed to update audit ledger tail marker {:?}: {}", ledger.tail_path, e);
+        }
+        self.events_written.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Flushes any buffered ledger output. Every record is already flushed as it is written;
+    /// this is the last step of a graceful shutdown in case that ever changes.
+    pub fn flush(&self) -> Result<()> {
+        let mut ledger = self.writer
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Audit ledger writer lock is poisoned"))?;
+        ledger.writer.flush()?;
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    pub fn events_written(&self) -> u64 {
+        self.events_written.load(Ordering::Relaxed)
+    }
+
+    /// Re-reads a ledger file and checks that every record links to the one before it
+    /// and that its hash matches its contents, then that the chain reaches the record its
+    /// tail marker names. Bare events ahead of the first record predate hash chaining; they are
+    /// counted as `pre_chain` and the chain starts after them.
+    pub fn verify_ledger(path: &Path) -> Result<LedgerVerification> {
+        let file = fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open audit ledger {:?}: {}", path, e))?;
+        // A bad marker is a verification failure, not an I/O error: the ledger must stay usable
+        let tail = match read_tail(&tail_path(path)) {
+            Ok(tail) => tail,
+            Err(e) => return Ok(LedgerVerification::Broken { line: None, reason: e.to_string() }),
+        };
+        let mut chain = ChainVerifier::new();
+        let mut records = 0;
+        let mut pre_chain = 0;
+        // The hash at the marker's position, and that record's line; records after it were appended
+        // after the marker was last written, which a crash between the two writes can leave behind
+        let mut hash_at_tail = tail.as_ref().filter(|tail| tail.records == 0).map(|_| GENESIS_HASH.to_string());
+        let mut line_at_tail = None;
+        for (idx, line) in BufReader::new(file).lines().enumerate() {
+            let line_number = idx + 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Ok(LedgerVerification::Broken { line: Some(line_number), reason: format!("unreadable: {}", e) }),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match chain.check(&line) {
+                Ok((_, None)) => records += 1,
+                Err(_) if records == 0 && is_pre_chain_event(&line) => {
+                    pre_chain += 1;
+                    continue;
+                }
+                Ok((_, Some(reason))) | Err(reason) => return Ok(LedgerVerification::Broken { line: Some(line_number), reason }),
+            }
+            if tail.as_ref().is_some_and(|tail| tail.records == records) {
+                hash_at_tail = Some(chain.expected_prev.clone());
+                line_at_tail = Some(line_number);
+            }
+        }
+        if let Some(tail) = tail {
+            if records < tail.records {
+                return Ok(LedgerVerification::Truncated { records, expected_records: tail.records });
+            }
+            if hash_at_tail.as_deref() != Some(tail.last_hash.as_str()) {
+                return Ok(LedgerVerification::Broken {
+                    line: line_at_tail,
+                    reason: "this_hash does not match the tail marker; the ledger was rewritten".to_string(),
+                });
+            }
+        }
+        Ok(LedgerVerification::Intact { records, pre_chain, last_hash: chain.expected_prev })
+    }
+
+    /// Streams the ledger's events that pass `filter` to `out`. The chain is verified on the
+    /// way; a break is logged and returned in the summary rather than stopping the export, and
+    /// lines that aren't records at all are skipped. Bare events from before hash chaining are
+    /// exported as they are.
+    pub fn export_ledger(path: &Path, filter: &AuditFilter, format: AuditExportFormat, out: &mut dyn Write) -> Result<AuditExportSummary> {
+        let file = fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open audit ledger {:?}: {}", path, e))?;
+        let mut chain = ChainVerifier::new();
+        let mut summary = AuditExportSummary::default();
+        let mut chained = false;
+        if format == AuditExportFormat::Csv {
+            writeln!(out, "line,timestamp,session_id,agent_id,event_type,risk_level,details")?;
+        }
+        for (idx, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            let line_number = idx + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event_json = match chain.check(&line) {
+                Ok((record, broken)) => {
+                    if let Some(reason) = broken {
+                        tracing::warn!("Audit ledger {:?} is broken at line {}: {}", path, line_number, reason);
+                        summary.breaks.push((line_number, reason));
+                    }
+                    chained = true;
+                    record.event.get()
+                }
+                Err(_) if !chained && is_pre_chain_event(&line) => {
+                    summary.pre_chain += 1;
+                    line.as_str()
+                }
+                Err(reason) => {
+                    tracing::warn!("Skipping audit ledger {:?} line {}: {}", path, line_number, reason);
+                    summary.breaks.push((line_number, reason));
+                    continue;
+                }
+            };
+            summary.records += 1;
+            let event: serde_json::Value = serde_json::from_str(event_json)?;
+            if !filter.matches(&event) {
+                continue;
+            }
+            match format {
+                AuditExportFormat::Jsonl => writeln!(out, "{}", event_json)?,
+                AuditExportFormat::Csv => writeln!(out, "{}", csv_row(line_number, &event))?,
+            }
+            summary.exported += 1;
+        }
+        out.flush()?;
+        Ok(summary)
+    }
+
+    /// The number of records and the `this_hash` of the last parseable one, so new records keep
+    /// chaining after a break. Bare events from before hash chaining aren't records.
+    fn ledger_end(path: &Path) -> Result<(usize, String)> {
+        let content = fs::read_to_string(path)?;
+        let records = content.lines().filter(|line| !line.trim().is_empty() && !is_pre_chain_event(line)).count();
+        let last_hash = content
+            .lines()
+            .rev()
+            .find_map(|line| serde_json::from_str::<LedgerRecord>(line).ok().map(|r| r.this_hash))
+            .unwrap_or_else(|| GENESIS_HASH.to_string());
+        Ok((records, last_hash))
+    }
+
+    /// Where the hash-chained audit ledger is written.
+    pub fn ledger_path(&self) -> PathBuf {
+        self.log_type_dir(LogType::Audit).join(LEDGER_FILE_NAME)
+    }
+
+    /// The directory holding logs of the given category.
+    pub fn log_type_dir(&self, log_type: LogType) -> PathBuf {
+        match log_type {
+            LogType::Vm => self.log_dir.join("instances"),
+            LogType::Container => self.log_dir.join("containers"),
+            LogType::System => self.system_log_dir.clone(),
+            LogType::Mcp => self.log_dir.join("mcp"),
+            LogType::Audit => self.log_dir.join("audit"),
+        }
+    }
+
+    /// Log files of one category, most recently modified first. A missing directory just means
+    /// nothing has been logged there yet.
+    pub fn list_log_files(&self, log_type: LogType, limit: usize) -> Result<Vec<LogFileEntry>> {
+        let dir = self.log_type_dir(log_type);
+        let mut entries = Vec::new();
+        if dir.is_dir() {
+            collect_log_files(&dir, &dir, log_type, &mut entries)?;
+        }
+        entries.sort_by(|a, b| b.modified.cmp(&a.modified));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    /// Finds the log file for `session_id` across all categories. An exact id wins; otherwise
+    /// a unique match on the file name or its containing directory (e.g. an instance id) is accepted.
+    pub fn find_log_file(&self, session_id: &str) -> Result<LogFileEntry> {
+        let mut all = Vec::new();
+        for log_type in LogType::ALL {
+            all.extend(self.list_log_files(log_type, usize::MAX)?);
+        }
+        if let Some(entry) = all.iter().find(|entry| entry.session_id == session_id) {
+            return Ok(entry.clone());
+        }
+        let candidates: Vec<&LogFileEntry> = all
+            .iter()
+            .filter(|entry| entry.session_id.split('/').any(|part| part == session_id))
+            .collect();
+        match candidates.as_slice() {
+            [entry] => Ok((*entry).clone()),
+            [] => Err(anyhow::anyhow!("No log found for session '{}'. Run `hydravisor log list` to see available logs.", session_id)),
+            _ => {
+                let ids: Vec<&str> = candidates.iter().map(|entry| entry.session_id.as_str()).collect();
+                Err(anyhow::anyhow!("Session '{}' matches several logs: {}", session_id, ids.join(", ")))
+            }
+        }
+    }
+
+    /// Reads a log for display. Recordings are reduced to their terminal output; other
+    /// formats are returned as stored.
+    pub fn read_log(&self, entry: &LogFileEntry) -> Result<Vec<u8>> {
+        let content = fs::read(&entry.path)
+            .map_err(|e| anyhow::anyhow!("Failed to read log {:?}: {}", entry.path, e))?;
+        match LogFormat::from_path(&entry.path) {
+            LogFormat::Cast => convert_log(&content, LogFormat::Cast, LogFormat::Ansi),
+            _ => Ok(content),
+        }
+    }
+
+    /// Writes a log to `output` in `format`. If `output` is an existing directory the file is
+    /// named after the session. Returns the path written.
+    pub fn export_log(&self, entry: &LogFileEntry, format: LogFormat, output: &Path) -> Result<PathBuf> {
+        let content = fs::read(&entry.path)
+            .map_err(|e| anyhow::anyhow!("Failed to read log {:?}: {}", entry.path, e))?;
+        let converted = convert_log(&content, LogFormat::from_path(&entry.path), format)?;
+        let output_path = if output.is_dir() {
+            let file_name = entry.session_id.replace('/', "_");
+            output.join(format!("{}.{}", file_name, format.extension()))
+        } else {
+            output.to_path_buf()
+        };
+        fs::write(&output_path, converted)
+            .map_err(|e| anyhow::anyhow!("Failed to write {:?}: {}", output_path, e))?;
+        Ok(output_path)
+    }
+}
+
+fn collect_log_files(root: &Path, dir: &Path, log_type: LogType, entries: &mut Vec<LogFileEntry>) -> Result<()> {
+    for dir_entry in fs::read_dir(dir)? {
+        let dir_entry = dir_entry?;
+        let path = dir_entry.path();
+        let metadata = dir_entry.metadata()?;
+        if metadata.is_dir() {
+            collect_log_files(root, &path, log_type, entries)?;
+            continue;
+        }
+        let is_log = matches!(path.extension().and_then(|ext| ext.to_str()), Some("log" | "jsonl" | "cast"))
+            || path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.contains(".log"));
+        if !is_log {
+            continue;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let relative = match relative.extension().and_then(|ext| ext.to_str()) {
+            Some("log" | "jsonl" | "cast") => relative.with_extension(""),
+            _ => relative.to_path_buf(), // e.g. rotated `hydravisor.log.2025-06-01`
+        };
+        entries.push(LogFileEntry {
+            session_id: relative.to_string_lossy().replace('\\', "/"),
+            log_type: format!("{:?}", log_type),
+            size_bytes: metadata.len(),
+            modified: metadata.modified().ok().map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339()),
+            path,
+        });
+    }
+    Ok(())
+}
+
+/// Converts log content between formats. Same-format conversion copies through; recordings can
+/// become raw output or JSON lines, and JSON lines can become raw output.
+fn convert_log(content: &[u8], from: LogFormat, to: LogFormat) -> Result<Vec<u8>> {
+    if from == to {
+        return Ok(content.to_vec());
+    }
+    let text = String::from_utf8_lossy(content);
+    match (from, to) {
+        (LogFormat::Cast, _) => {
+            // asciinema v2: a header object, then `[time, "o", data]` events
+            let mut converted = String::new();
+            for line in text.lines().skip(1).filter(|line| !line.trim().is_empty()) {
+                let (time, kind, data): (f64, String, String) = serde_json::from_str(line)
+                    .map_err(|e| anyhow::anyhow!("Malformed asciinema event '{}': {}", line, e))?;
+                if to == LogFormat::Ansi {
+                    if kind == "o" {
+                        converted.push_str(&data);
+                    }
+                } else {
+                    converted.push_str(&serde_json::json!({ "time": time, "type": kind, "data": data }).to_string());
+                    converted.push('\n');
+                }
+            }
+            Ok(converted.into_bytes())
+        }
+        (LogFormat::Jsonl, LogFormat::Ansi) => {
+            // Print each line's text payload; lines without one are shown as-is
+            let mut converted = String::new();
+            for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                let payload = serde_json::from_str::<serde_json::Value>(line).ok().and_then(|value| {
+                    ["data", "output", "message", "text"]
+                        .iter()
+                        .find_map(|key| value.get(*key).and_then(|v| v.as_str()).map(str::to_string))
+                });
+                match payload {
+                    Some(payload) => {
+                        converted.push_str(&payload);
+                        if !payload.ends_with('\n') {
+                            converted.push('\n');
+                        }
+                    }
+                    None => {
+                        converted.push_str(line);
+                        converted.push('\n');
+                    }
+                }
+            }
+            Ok(converted.into_bytes())
+        }
+        (from, to) => Err(anyhow::anyhow!("Converting {:?} logs to {:?} is not supported", from, to)),
+    }
+}
+
+/// `audit_ledger.jsonl` → `audit_ledger.tail`, beside it.
+fn tail_path(ledger_path: &Path) -> PathBuf {
+    ledger_path.with_extension("tail")
+}
+
+fn read_tail(path: &Path) -> Result<Option<LedgerTail>> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("Audit ledger tail marker {:?} is unreadable: {}", path, e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(anyhow::anyhow!("Failed to read audit ledger tail marker {:?}: {}", path, e)),
+    }
+}
+
+/// Replaces the tail marker through a rename, so a crash never leaves it half-written.
+fn write_tail(path: &Path, tail: &LedgerTail) -> Result<()> {
+    let tmp_path = path.with_extension("tail.tmp");
+    fs::write(&tmp_path, serde_json::to_string(tail)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// An event's variant name: the string for unit variants, the single key for the others.
+fn event_type_name(event: &serde_json::Value) -> Option<&str> {
+    match &event["event_type"] {
+        serde_json::Value::String(name) => Some(name),
+        serde_json::Value::Object(variant) => variant.keys().next().map(String::as_str),
+        _ => None,
+    }
+}
+
+fn event_type_fields(event: &serde_json::Value) -> Option<&serde_json::Map<String, serde_json::Value>> {
+    event["event_type"].as_object()?.values().next()?.as_object()
+}
+
+/// One CSV line for an export. `details` holds the variant's own fields, plus the event's
+/// free-form `details` when it has any.
+fn csv_row(line_number: usize, event: &serde_json::Value) -> String {
+    let mut details = event_type_fields(event).cloned().unwrap_or_default();
+    if !event["details"].is_null() {
+        details.insert("details".to_string(), event["details"].clone());
+    }
+    let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+    [
+        line_number.to_string(),
+        text(&event["timestamp_str"]),
+        text(&event["session_id"]),
+        text(&event["agent_id"]),
+        event_type_name(event).unwrap_or_default().to_string(),
+        text(&event["risk_level"]),
+        if details.is_empty() { String::new() } else { serde_json::Value::Object(details).to_string() },
+    ]
+    .iter()
+    .map(|field| csv_field(field))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Quotes a CSV field when it contains a separator, quote, or line break (RFC 4180).
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Hex-encoded `sha256(prev_hash || event_json)`.
+fn chain_hash(prev_hash: &str, event_json: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(event_json.as_bytes());
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 // TODO: Add tests for AuditEngine, including:
@@ -6,44 +6,118 @@
 use anyhow::Result;
 use aws_config::{meta::region::RegionProviderChain, BehaviorVersion};
 use aws_sdk_bedrock::{config::Region, Client};
+use aws_sdk_bedrock::error::DisplayErrorContext;
 use aws_sdk_bedrock::types::FoundationModelSummary;
+use aws_sdk_bedrockruntime::types::{
+    ContentBlock, ContentBlockDelta, ConversationRole, ConverseStreamOutput, InferenceConfiguration, Message,
+    SystemContentBlock,
+};
+use futures::stream::{self, Stream};
 use tracing::{info, error, debug};
 
+use crate::config::ModelParams;
+use crate::errors::HydraError;
+use crate::tui::app::ChatMessage as TuiChatMessage;
+
 pub struct BedrockManager {
     client: Client,
+    runtime_client: aws_sdk_bedrockruntime::Client,
+    region: String, // Model access is granted per region
     pub bedrock_connected: bool,
+    pub connection_error: Option<String>, // Why the last connection attempt failed
+}
+
+// Whether this account can invoke a foundation model in the current region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelAccess {
+    Granted,
+    Requestable, // Not enabled yet; it can be requested on the console's Model access page
+    Denied,      // Refused by an IAM policy, which a model access request won't change
+}
+
+impl ModelAccess {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModelAccess::Granted => "granted",
+            ModelAccess::Requestable => "requestable",
+            ModelAccess::Denied => "denied",
+        }
+    }
+
+    /// Classifies an AccessDeniedException. IAM refusals name the action the caller "is not
+    /// authorized to perform"; anything else means the model hasn't been enabled for the account.
+    fn from_access_denied(message: &str) -> Self {
+        if message.contains("not authorized to perform") {
+            ModelAccess::Denied
+        } else {
+            ModelAccess::Requestable
+        }
+    }
 }
 
 impl BedrockManager {
-    pub async fn new(aws_region: Option<String>) -> Result<Self> {
+    /// Builds the SDK clients for `aws_region` with credentials from `profile`, and tries them
+    /// once. A failed connection (bad credentials included) leaves a disconnected manager.
+    pub async fn new(aws_region: Option<String>, profile: Option<String>) -> Result<Self> {
         let region_provider = RegionProviderChain::first_try(aws_region.map(Region::new))
             .or_default_provider()
             .or_else("us-east-1");
 
         info!("Attempting to connect to AWS Bedrock in region: {:?}", region_provider.region().await);
 
-        let config = aws_config::defaults(BehaviorVersion::latest()).region(region_provider).load().await;
+        let mut loader = aws_config::defaults(BehaviorVersion::latest()).region(region_provider);
+        if let Some(profile) = &profile {
+            loader = loader.profile_name(profile);
+        }
+        let config = loader.load().await;
         let client = Client::new(&config);
+        let runtime_client = aws_sdk_bedrockruntime::Client::new(&config);
+        let region = config.region().map(|region| region.to_string()).unwrap_or_default();
 
         let mut bedrock_connected = false;
+        let mut connection_error = None;
         match client.list_foundation_models().send().await {
             Ok(_) => {
                 info!("Successfully connected to AWS Bedrock.");
                 bedrock_connected = true;
             }
             Err(e) => {
-                error!("Failed to connect to AWS Bedrock: {}", e);
+                let detail = DisplayErrorContext(&e).to_string();
+                error!("Failed to connect to AWS Bedrock: {}", detail);
+                // Missing or expired credentials surface as a dispatch failure; name them plainly
+                connection_error = Some(if detail.to_lowercase().contains("credential") {
+                    format!("bedrock: credentials error for profile '{}'", profile.as_deref().unwrap_or("default"))
+                } else {
+                    format!("bedrock: {}", e)
+                });
             }
         }
 
         info!("BedrockManager initialized. Bedrock integration enabled.");
-        Ok(Self { client, bedrock_connected })
+        Ok(Self { client, runtime_client, region, bedrock_connected, connection_error })
     }
 
     pub fn is_bedrock_connected(&self) -> bool {
         self.bedrock_connected
     }
 
+    /// Re-tries the connection with the same call `new` uses, updating the connected flag.
+    pub async fn check_health(&mut self) -> Result<()> {
+        let result = self.client.list_foundation_models().send().await
+            .map(|_| ())
+            .map_err(|e| anyhow::anyhow!("Bedrock health check failed: {}", e));
+        self.bedrock_connected = result.is_ok();
+        if self.bedrock_connected {
+            self.connection_error = None;
+        }
+        result
+    }
+
+    /// Marks Bedrock as down after a failed call, so the next refresh re-checks it with backoff.
+    pub fn mark_disconnected(&mut self) {
+        self.bedrock_connected = false;
+    }
+
     pub async fn list_foundation_models(&self) -> Result<Vec<FoundationModelSummary>> {
         if self.bedrock_connected {
             debug!("Listing foundation models from AWS Bedrock.");
@@ -63,4 +137,157 @@ impl BedrockManager {
             Ok(Vec::new())
         }
     }
-} 
\ No newline at end of file
+
+    /// Profile names from the shared AWS config and credentials files, sorted, for cycling
+    /// through in the Bedrock view. Honors AWS_CONFIG_FILE and AWS_SHARED_CREDENTIALS_FILE.
+    pub fn aws_profile_names() -> Vec<String> {
+        let aws_file = |env_var: &str, default_path: &str| {
+            let path = std::env::var(env_var).unwrap_or_else(|_| default_path.to_string());
+            std::path::PathBuf::from(shellexpand::tilde(&path).into_owned())
+        };
+        let mut names = Vec::new();
+        // The config file writes `[profile name]` (except for `[default]`); credentials write `[name]`
+        for (path, prefixed) in [(aws_file("AWS_CONFIG_FILE", "~/.aws/config"), true), (aws_file("AWS_SHARED_CREDENTIALS_FILE", "~/.aws/credentials"), false)] {
+            let Ok(content) = std::fs::read_to_string(path) else { continue };
+            for line in content.lines() {
+                let Some(section) = line.trim().strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) else { continue };
+                let name = match (prefixed, section.trim().strip_prefix("profile ")) {
+                    (true, Some(name)) => name.trim(),
+                    (true, None) if section.trim() == "default" => "default",
+                    (true, None) => continue, // e.g. [sso-session ...]
+                    (false, _) => section.trim(),
+                };
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// The console page where model access is requested, for the current region.
+    pub fn model_access_url(&self) -> String {
+        format!("https://{0}.console.aws.amazon.com/bedrock/home?region={0}#/modelaccess", self.region)
+    }
+
+    /// The error for a model this account can't invoke, saying what to do about it.
+    fn access_denied_error(&self, model_id: &str, message: &str) -> HydraError {
+        let access = ModelAccess::from_access_denied(message);
+        let what_to_do = match access {
+            ModelAccess::Requestable => format!("Request it at {}, then try again.", self.model_access_url()),
+            _ => "Your IAM policy must allow bedrock:InvokeModel for it.".to_string(),
+        };
+        HydraError::ModelAccessDenied {
+            requestable: access == ModelAccess::Requestable,
+            message: format!("No access to '{}' in {}. {}", model_id, self.region, what_to_do),
+        }
+    }
+
+    /// Checks whether the model can be invoked by sending it a one-token Converse request, since
+    /// the control-plane API doesn't report model access. The check costs that one token.
+    pub async fn model_access_status(&self, model_id: &str) -> Result<ModelAccess> {
+        let message = Message::builder()
+            .role(ConversationRole::User)
+            .content(ContentBlock::Text("hi".to_string()))
+            .build()?;
+        let result = self.runtime_client
+            .converse()
+            .model_id(model_id)
+            .messages(message)
+            .inference_config(InferenceConfiguration::builder().max_tokens(1).build())
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(ModelAccess::Granted),
+            Err(e) => match e.as_service_error() {
+                Some(service_error) if service_error.is_access_denied_exception() => {
+                    Ok(ModelAccess::from_access_denied(service_error.meta().message().unwrap_or_default()))
+                }
+                _ => Err(anyhow::anyhow!("Failed to check access to '{}': {}", model_id, e)),
+            },
+        }
+    }
+
+    /// Streams a reply from a foundation model through the Converse API.
+    /// Items match `OllamaManager::generate_response_stream`: text chunks, or an error message.
+    pub async fn converse_stream(
+        &self,
+        model_id: String,
+        history: Vec<TuiChatMessage>,
+        system_prompt: Option<String>,
+        params: ModelParams,
+    ) -> Result<impl Stream<Item = Result<String, HydraError>>> {
+        // Converse needs alternating user/assistant turns, so consecutive messages from one side are merged
+        let mut turns: Vec<(ConversationRole, String)> = Vec::new();
+        for tui_msg in history.iter() {
+            if tui_msg.content.is_empty() {
+                debug!("Skipping empty message from history for model: {}", tui_msg.sender);
+                continue;
+            }
+            let role = if tui_msg.sender == "user" { ConversationRole::User } else { ConversationRole::Assistant };
+            match turns.last_mut() {
+                Some((last_role, content)) if *last_role == role => {
+                    content.push_str("\n\n");
+                    content.push_str(&tui_msg.content);
+                }
+                _ => turns.push((role, tui_msg.content.clone())),
+            }
+        }
+        // Replies the user never answered (e.g. after an error) can't open the conversation
+        while turns.first().map_or(false, |(role, _)| *role != ConversationRole::User) {
+            turns.remove(0);
+        }
+        if turns.last().map_or(true, |(role, _)| *role != ConversationRole::User) {
+            error!("The last message in the history sent to Bedrock must be from the user. Model: {}", model_id);
+            return Err(anyhow::anyhow!("Last message to Bedrock was not from User."));
+        }
+        let messages = turns
+            .into_iter()
+            .map(|(role, content)| Message::builder().role(role).content(ContentBlock::Text(content)).build())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        debug!("Sending {} messages to Bedrock model: {}", messages.len(), model_id);
+        let mut request = self.runtime_client.converse_stream().model_id(&model_id).set_messages(Some(messages));
+        if let Some(sp) = system_prompt.filter(|sp| !sp.is_empty()) {
+            request = request.system(SystemContentBlock::Text(sp));
+        }
+        // num_ctx and seed have no Converse equivalent
+        if params.temperature.is_some() || params.top_p.is_some() || params.num_predict.is_some() {
+            request = request.inference_config(
+                InferenceConfiguration::builder()
+                    .set_temperature(params.temperature)
+                    .set_top_p(params.top_p)
+                    .set_max_tokens(params.num_predict)
+                    .build(),
+            );
+        }
+
+        let output = request.send().await.map_err(|e| {
+            error!("Failed to start Bedrock stream for model '{}': {}", model_id, e);
+            match e.as_service_error() {
+                Some(service_error) if service_error.is_access_denied_exception() => {
+                    self.access_denied_error(&model_id, service_error.meta().message().unwrap_or_default()).into()
+                }
+                _ => anyhow::anyhow!("Failed to start Bedrock stream: {}", e),
+            }
+        })?;
+        debug!("Successfully started Converse stream for model: {}", model_id);
+
+        // Only text deltas are forwarded; the receiver is dropped after the first error
+        Ok(stream::unfold(Some(output.stream), |receiver| async move {
+            let mut receiver = receiver?;
+            loop {
+                match receiver.recv().await {
+                    Ok(Some(ConverseStreamOutput::ContentBlockDelta(event))) => {
+                        if let Some(ContentBlockDelta::Text(text)) = event.delta {
+                            return Some((Ok(text), Some(receiver)));
+                        }
+                    }
+                    Ok(Some(_)) => {}
+                    Ok(None) => return None,
+                    Err(e) => return Some((Err(HydraError::StreamError(e.to_string())), None)),
+                }
+            }
+        }))
+    }
+}
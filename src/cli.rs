@@ -1,17 +1,34 @@
 // src/cli.rs
 
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::config::Config;
-use crate::policy::PolicyEngine;
-use crate::session_manager::SessionManager;
-use crate::libvirt_manager::LibvirtManager;
-use crate::audit::AuditEngine;
+use crate::config::{resolve_config_dir, Config, APP_NAME, DEFAULT_CONFIG_FILENAME, DEFAULT_CONFIG_TEMPLATE};
+use xdg::BaseDirectories;
+use crate::policy::{AuthDecision, PolicyAction, PolicyConfig, PolicyEngine, PolicyEvaluation};
+use crate::session_manager::{AgentSummary, SessionManager};
+use crate::libvirt_manager::{
+    check_vm_file, check_vm_name, default_cloud_init, parse_vm_cpus, parse_vm_disk_gb, parse_vm_label, parse_vm_memory_mb,
+    ConsoleInfo, ExistingDisk, LibvirtManager, VmConfig, VmDetails, VmResourceChange,
+};
+use crate::audit::{
+    parse_audit_time, AuditEngine, AuditEvent, AuditEventType, AuditExportFormat, AuditFilter, LedgerVerification, LogFormat,
+    LogType, RiskLevel,
+};
+use crate::format::format_bytes;
+use crate::shutdown::wait_for_shutdown_signal;
+use crate::api::McpMessage;
+use crate::mcp::{canonical_message_type, McpClient, McpServer};
 
 use anyhow::Result;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+// How often a headless daemon re-applies `logging.retain_days`
+const LOG_PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// Hydravisor: AI Agent Sandbox Manager
 #[derive(Parser, Debug)]
@@ -21,18 +38,62 @@ pub struct Cli {
     #[clap(long, value_name = "FILE")]
     pub config: Option<PathBuf>,
 
-    /// Suppress TUI auto-launch (e.g., for scripting or headless operation)
+    /// Directory to load config.toml, policy.toml, and ssh.toml from
+    /// (overrides HYDRAVISOR_CONFIG_DIR and the XDG config directory)
+    #[clap(long, value_name = "DIR")]
+    pub config_dir: Option<PathBuf>,
+
+    /// Named config profile, loaded from `<config dir>/profiles/<NAME>/` with files it lacks taken
+    /// from `<config dir>` (overrides HYDRAVISOR_PROFILE)
+    #[clap(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Run as a daemon without the TUI: serve MCP (and metrics, if enabled) until SIGINT/SIGTERM
     #[clap(long)]
     pub headless: bool,
 
+    /// Watch-only TUI: live VM, model and log state, with keys that create, change or destroy
+    /// anything disabled (and no idle shutdown)
+    #[clap(long)]
+    pub read_only: bool,
+
     /// Set log level
     #[clap(long, value_name = "LEVEL", value_enum, default_value_t = LogLevelCli::Info)]
     pub log_level: LogLevelCli,
 
+    /// Output format for list and info commands. Given before the subcommand, since
+    /// `log export` has an `--output` of its own
+    #[clap(long, value_name = "FORMAT", value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Shorthand for `--output json`, accepted anywhere on the command line
+    #[clap(long, global = true)]
+    pub json: bool,
+
     #[clap(subcommand)]
     pub command: Option<Commands>,
 }
 
+impl Cli {
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json { OutputFormat::Json } else { self.output }
+    }
+
+    /// Whether this run shows the TUI: no subcommand and no `--headless`, or `attach`.
+    pub fn is_tui(&self) -> bool {
+        match &self.command {
+            None => !self.headless,
+            Some(command) => matches!(command, Commands::Attach { .. }),
+        }
+    }
+
+    /// Whether this run keeps going until it is stopped (the TUI, `--headless`, `serve` or
+    /// `mcp serve`) rather than doing one command and exiting.
+    pub fn is_long_running(&self) -> bool {
+        matches!(self.command, None | Some(Commands::Serve) | Some(Commands::Mcp(McpCommands::Serve)))
+    }
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Manage policies
@@ -50,9 +111,50 @@ pub enum Commands {
     /// Manage logs
     #[clap(subcommand)]
     Log(LogCommands),
+
+    /// Query the hash-chained audit ledger
+    #[clap(subcommand)]
+    Audit(AuditCommands),
+
+    /// Run or talk to the MCP server
+    #[clap(subcommand)]
+    Mcp(McpCommands),
+
+    /// Check or create config.toml
+    #[clap(subcommand)]
+    Config(ConfigCommands),
+
+    /// Run without the TUI, serving MCP until SIGINT/SIGTERM (same as `--headless`)
+    Serve,
+
+    /// Open a watch-only TUI on a running daemon's VMs; quitting it leaves the daemon running
+    Attach {
+        /// The daemon's MCP socket, instead of `mcp.socket_path`
+        #[clap(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
     // TODO: Add `store` subcommand for encrypted disk management as per cli.design.md
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Parse config.toml and report unknown keys, bad values, and where they are
+    Validate {
+        /// Config file to check instead of the one Hydravisor would load
+        #[clap(long, value_name = "FILE")]
+        path: Option<PathBuf>,
+    },
+    /// Write a commented config.toml with every key at its default value
+    Init {
+        /// Where to write it instead of the XDG config directory
+        #[clap(long, value_name = "FILE")]
+        path: Option<PathBuf>,
+        /// Overwrite an existing file
+        #[clap(long)]
+        force: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum PolicyCommands {
     /// Validate the policy.toml file
@@ -67,18 +169,25 @@ pub enum PolicyCommands {
         agent_id: String,
         #[clap(long)]
         vm_id: String,
-        #[clap(long)] // TODO: Make this an enum based on defined actions
-        action: String,
+        #[clap(long, value_enum)]
+        action: PolicyAction,
     },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum AgentCommands {
     /// List all configured/active agents
-    List,
+    List {
+        /// Agent id to ask the running server as; it needs an entry in policy.toml [permissions]
+        #[clap(long = "as", value_name = "AGENT_ID", default_value = "cli")]
+        caller: String,
+    },
     /// Show status and policy bindings for an agent
     Info {
-        agent_id: String
+        agent_id: String,
+        /// Agent id to ask the running server as; it needs an entry in policy.toml [permissions]
+        #[clap(long = "as", value_name = "AGENT_ID", default_value = "cli")]
+        caller: String,
     },
     // TODO: `agent promote <id>` as per cli.design.md (future)
 }
@@ -87,9 +196,10 @@ pub enum AgentCommands {
 pub enum VmCommands {
     /// List known VM sessions or configurations
     List,
-    /// Show VM state, logs, and bindings
+    /// Show VM state, resources, disks, network interfaces, and consoles
     Info {
-        vm_id: String
+        /// VM name or UUID
+        vm_id: String,
     },
     /// Export current VM as an archive
     Snapshot {
@@ -97,7 +207,136 @@ pub enum VmCommands {
         #[clap(long, short, value_name = "FILE")]
         output: PathBuf,
     },
-    // TODO: `vm create`, `vm delete` from config.toml.md
+    /// Create and boot a VM, with the same checks and defaults as the TUI's new-VM wizard
+    Create {
+        /// Domain name
+        #[clap(long)]
+        name: String,
+        /// vCPUs (default: the policy's default_cpus)
+        #[clap(long, value_name = "N")]
+        cpu: Option<String>,
+        /// Memory, e.g. 4096, 4096MB or 4GB (default: the policy's default_mem_mb)
+        #[clap(long, value_name = "SIZE")]
+        ram: Option<String>,
+        /// Role from policy.toml's [roles] whose VM limits apply (default: [defaults.vm])
+        #[clap(long, value_name = "ROLE")]
+        role: Option<String>,
+        /// Disk size in GB (default: defaults.default_disk_gb)
+        #[clap(long, value_name = "GB")]
+        disk_gb: Option<String>,
+        /// Install from this ISO onto a blank disk
+        #[clap(long, value_name = "FILE", conflicts_with = "image")]
+        iso: Option<String>,
+        /// Clone this cloud image (default: defaults.default_source_image); the SSH key is injected via cloud-init
+        #[clap(long, value_name = "FILE")]
+        image: Option<String>,
+        /// Disk image path (default: <storage pool>/<name>.qcow2)
+        #[clap(long, value_name = "FILE")]
+        disk_path: Option<String>,
+        /// What to do if the disk image already exists
+        #[clap(long, value_enum, default_value = "refuse")]
+        existing_disk: ExistingDisk,
+        /// Network policy from policy.toml, or "default" / "no-network"
+        #[clap(long, value_name = "NAME", default_value = "default")]
+        network_policy: String,
+        /// Define the VM so it survives host reboots; `--persistent=false` makes it transient
+        #[clap(long, value_name = "BOOL", default_value_t = true, default_missing_value = "true", num_args = 0..=1, action = clap::ArgAction::Set)]
+        persistent: bool,
+        /// Label the VM, as KEY=VALUE; repeatable. `pinned` or `no-autostop` exempts it from idle shutdown
+        #[clap(long = "label", value_name = "KEY=VALUE")]
+        labels: Vec<String>,
+        /// Print the disk path and domain XML without creating anything
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Change a VM's vCPUs or memory, or grow its disk, within the policy's limits
+    Resize {
+        /// VM name or UUID
+        vm_id: String,
+        /// New vCPU count; a running VM can't go above the maximum it booted with
+        #[clap(long, value_name = "N")]
+        cpu: Option<String>,
+        /// New memory, e.g. 8192, 8192MB or 8GB; a running VM can't go above what it booted with
+        #[clap(long, value_name = "SIZE")]
+        ram: Option<String>,
+        /// New disk size in GB; the VM must be shut off, and disks only grow
+        #[clap(long, value_name = "GB")]
+        disk_gb: Option<String>,
+    },
+    /// Bring a domain created outside Hydravisor under its management
+    Import {
+        /// VM name or UUID
+        vm_id: String,
+        /// Role from policy.toml's [roles] to record for the VM
+        #[clap(long, value_name = "ROLE")]
+        role: Option<String>,
+        /// Label the VM, as KEY=VALUE; repeatable. Kept beside the domain, not in its XML
+        #[clap(long = "label", value_name = "KEY=VALUE")]
+        labels: Vec<String>,
+    },
+    /// Stop managing an imported VM, leaving the domain itself in place
+    Forget {
+        /// VM name or UUID
+        vm_id: String,
+    },
+    /// Force a VM off and remove it, deleting its disk images
+    Delete {
+        /// VM name
+        vm_id: String,
+        /// Leave the disk images in place
+        #[clap(long)]
+        keep_disk: bool,
+        /// Don't ask for confirmation
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum McpCommands {
+    /// Serve MCP requests on the configured socket until interrupted
+    Serve,
+    /// Send one request to a running MCP server and print the response
+    Call {
+        /// Message type, e.g. `vm/list`, `vm/create`, `vm/delete`, `vm/attach-terminal`
+        method: String,
+        /// Request fields as a JSON object, e.g. '{"instance_id": "my-vm"}'
+        #[clap(long, value_name = "JSON")]
+        params: Option<String>,
+        /// Agent id to send as `src`
+        #[clap(long, default_value = "cli")]
+        agent_id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommands {
+    /// Check every hash link in the ledger, and its tail marker; exits non-zero if the chain is broken or cut short
+    Verify {
+        /// Ledger file to check instead of this profile's
+        #[clap(long, value_name = "FILE")]
+        path: Option<PathBuf>,
+    },
+    /// Write the ledger's events, filtered, as JSON lines or CSV; chain breaks are warned about on stderr
+    Export {
+        /// Only events at or after this time (YYYY-MM-DD or RFC 3339)
+        #[clap(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only events before this time (YYYY-MM-DD or RFC 3339)
+        #[clap(long, value_name = "TIME")]
+        until: Option<String>,
+        /// Only events by this agent
+        #[clap(long, value_name = "ID")]
+        agent: Option<String>,
+        /// Only events of this type, e.g. PolicyDecision
+        #[clap(long = "type", value_name = "EVENT_TYPE")]
+        event_type: Option<String>,
+        #[clap(long, short, value_enum, default_value_t = AuditExportFormat::Jsonl)]
+        format: AuditExportFormat,
+        /// Write here instead of stdout
+        #[clap(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -122,7 +361,6 @@ pub enum LogCommands {
         output: PathBuf,
     }
     // TODO: `log replay` (future)
-    // TODO: `audit verify` from config.toml.md
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -146,24 +384,16 @@ impl std::fmt::Display for LogLevelCli {
     }
 }
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-pub enum LogType {
-    Vm,
-    Container,
-    System,
-    Mcp,
-    Audit,
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table, // Human-readable text
+    Json,  // Serialized structs, for scripts and other tooling
 }
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-pub enum LogFormat {
-    Cast,
-    Jsonl,
-    Ansi,
-}
 
 pub async fn handle_command(
     command: Commands, // Now it's guaranteed to be Some by main.rs
+    output: OutputFormat,
     config: Arc<Config>,
     policy_engine: Arc<PolicyEngine>,
     session_manager: Arc<SessionManager>,
@@ -172,10 +402,104 @@ pub async fn handle_command(
 ) -> Result<()> {
     match command {
         Commands::Policy(policy_cmd) => handle_policy_command(policy_cmd, config, policy_engine).await?,
-        Commands::Agent(agent_cmd) => handle_agent_command(agent_cmd, config, session_manager).await?,
-        Commands::Vm(vm_cmd) => handle_vm_command(vm_cmd, config, libvirt_manager).await?,
-        Commands::Log(log_cmd) => handle_log_command(log_cmd, config, audit_engine).await?,
+        Commands::Agent(agent_cmd) => handle_agent_command(agent_cmd, output, config, session_manager).await?,
+        Commands::Vm(vm_cmd) => handle_vm_command(vm_cmd, output, config, policy_engine, libvirt_manager).await?,
+        Commands::Log(log_cmd) => handle_log_command(log_cmd, output, config, audit_engine).await?,
+        Commands::Audit(audit_cmd) => handle_audit_command(audit_cmd, audit_engine)?,
+        Commands::Mcp(mcp_cmd) => handle_mcp_command(mcp_cmd, config, session_manager).await?,
+        Commands::Serve => run_daemon(config, session_manager, audit_engine).await?,
+        // Handled by main before the config is loaded, so a broken config can still be checked
+        Commands::Config(_) => unreachable!("config commands run before the config is loaded"),
+        Commands::Attach { .. } => unreachable!("attach runs the TUI, which main starts"),
+    }
+    Ok(())
+}
+
+/// Runs a `config` subcommand. The paths are the global `--config`, `--config-dir`, and
+/// `--profile` flags, which choose the file just as they would for loading.
+pub fn handle_config_command(
+    command: &ConfigCommands,
+    config_path_override: Option<&Path>,
+    config_dir_override: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<()> {
+    match command {
+        ConfigCommands::Validate { path } => {
+            let config_path = match path {
+                Some(path) => path.clone(),
+                None => Config::resolve_path(config_path_override, config_dir_override, profile)?,
+            };
+            if !config_path.exists() {
+                anyhow::bail!("No config file at {}. Create one with `hydravisor config init`.", config_path.display());
+            }
+            let config = Config::parse_file(&config_path)
+                .map_err(|e| anyhow::anyhow!("ERROR: {} is INVALID:\n{:#}", config_path.display(), e))?;
+            let mut issues = config.validate();
+            let (_, keybinding_issues) = crate::tui::app::parse_keybindings(&config.keybindings);
+            let mut keybinding_issues: Vec<String> = keybinding_issues.into_iter()
+                .map(|(action, issue)| format!("keybindings.{}: {}", action.replacen("bedrock_", "bedrock.", 1), issue))
+                .collect();
+            keybinding_issues.sort();
+            issues.extend(keybinding_issues);
+            if !issues.is_empty() {
+                let issue_messages: Vec<String> = issues.iter().map(|issue| format!("  - {}", issue)).collect();
+                anyhow::bail!("ERROR: {} has invalid values:\n{}", config_path.display(), issue_messages.join("\n"));
+            }
+            println!("SUCCESS: {} is valid.", config_path.display());
+        }
+        ConfigCommands::Init { path, force } => {
+            let config_path = match path.as_deref().or(config_path_override) {
+                Some(path) => path.to_path_buf(),
+                None => match resolve_config_dir(config_dir_override, profile)? {
+                    Some(dir) => dir.join(DEFAULT_CONFIG_FILENAME),
+                    None => BaseDirectories::with_prefix(APP_NAME)?.get_config_home().join(DEFAULT_CONFIG_FILENAME),
+                },
+            };
+            if config_path.exists() && !force {
+                anyhow::bail!("{} already exists. Pass --force to overwrite it.", config_path.display());
+            }
+            if let Some(parent) = config_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", parent.display(), e))?;
+            }
+            std::fs::write(&config_path, DEFAULT_CONFIG_TEMPLATE)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", config_path.display(), e))?;
+            println!("Wrote default configuration to {}", config_path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Headless daemon mode: serves MCP until SIGINT or SIGTERM, then closes the socket and
+/// flushes the audit ledger. The metrics endpoint, when enabled, was started by main.
+pub async fn run_daemon(
+    config: Arc<Config>,
+    session_manager: Arc<SessionManager>,
+    audit_engine: Arc<AuditEngine>,
+) -> Result<()> {
+    // Startup already pruned once; a daemon that runs for weeks keeps pruning daily
+    let prune_config = Arc::clone(&config);
+    let log_pruner = tokio::spawn(async move {
+        let mut ticks = tokio::time::interval_at(tokio::time::Instant::now() + LOG_PRUNE_INTERVAL, LOG_PRUNE_INTERVAL);
+        loop {
+            ticks.tick().await;
+            match crate::logging::prune_old_logs(&prune_config) {
+                Ok(0) => {}
+                Ok(pruned) => info!("Pruned {} log file(s) older than {} days.", pruned, prune_config.logging.retain_days),
+                Err(e) => warn!("Failed to prune old logs: {:#}", e),
+            }
+        }
+    });
+    let server = McpServer::start(config, session_manager).await?;
+    info!("Hydravisor is running headless. MCP socket: {}. Send SIGINT or SIGTERM to stop.", server.socket_path().display());
+    let signal = wait_for_shutdown_signal().await?;
+    info!("Received {}, shutting down.", signal);
+    log_pruner.abort();
+    server.shutdown();
+    if let Err(e) = audit_engine.record_event(AuditEvent::new(AuditEventType::SystemShutdown, Some(RiskLevel::Info))) {
+        error!("Failed to record shutdown in the audit ledger: {}", e);
     }
+    audit_engine.flush()?;
     Ok(())
 }
 
@@ -188,15 +512,17 @@ async fn handle_policy_command(
         PolicyCommands::Validate { path } => {
             println!("Policy validate command: Path to validate explicitly: {:?}", path);
 
-            // 1. Determine the policy content to validate.
-            let (policy_value, policy_source_description): (serde_json::Value, String) = if let Some(p) = path {
+            // 1. Determine the policy content to validate, both as raw JSON for the schema and
+            // as a PolicyConfig for the consistency checks.
+            let (policy_value, policy_config, policy_source_description): (serde_json::Value, Result<PolicyConfig, String>, String) = if let Some(p) = path {
                 let policy_str = std::fs::read_to_string(&p)
                     .map_err(|e| anyhow::anyhow!("Failed to read policy file {:?}: {}", p, e))?;
                 let parsed_policy_toml: toml::Value = toml::from_str(&policy_str)
                     .map_err(|e| anyhow::anyhow!("Failed to parse TOML from policy file {:?}: {}", p, e))?;
+                let policy_config = toml::from_str::<PolicyConfig>(&policy_str).map_err(|e| e.to_string());
                 // Convert toml::Value to serde_json::Value for jsonschema validation
                 let json_value = serde_json::to_value(parsed_policy_toml)?;
-                (json_value, format!("file '{}'", p.display()))
+                (json_value, policy_config, format!("file '{}'", p.display()))
             } else if let Some(loaded_path_str) = &policy_engine.config.source_path {
                 // If no path override, validate the currently loaded policy.
                 // Need to re-serialize PolicyConfig to toml::Value then to serde_json::Value, 
@@ -205,7 +531,7 @@ async fn handle_policy_command(
                 // This is a bit convoluted; ideally, PolicyConfig itself could be validated if its structure matches the schema directly.
                 let policy_as_toml_value = toml::Value::try_from(&policy_engine.config)?;
                 let json_value = serde_json::to_value(policy_as_toml_value)?;
-                (json_value, format!("currently loaded policy from '{}'", loaded_path_str))
+                (json_value, Ok(policy_engine.config.clone()), format!("currently loaded policy from '{}'", loaded_path_str))
             } else {
                 anyhow::bail!("No policy file specified for validation and no policy file was loaded initially.");
             };
@@ -222,112 +548,542 @@ async fn handle_policy_command(
                 .map_err(|e| anyhow::anyhow!("Failed to compile policy JSON schema: {}", e))?;
 
             // 3. Validate the policy content against the schema.
-            match compiled_schema.validate(&policy_value) {
-                Ok(_) => {
-                    println!("SUCCESS: Policy from {} is valid against the JSON schema.", policy_source_description);
-                    // TODO: Add internal consistency checks from PolicyEngine if needed for the validated content.
-                    // For example: `PolicyEngine::validate_internal_consistency(parsed_policy_config_if_loaded_from_path)?`
-                }
-                Err(errors) => {
-                    let error_messages: Vec<String> = errors.map(|e| format!("  - {}", e)).collect();
-                    anyhow::bail!("ERROR: Policy from {} is INVALID against the JSON schema:\n{}", policy_source_description, error_messages.join("\n"));
-                }
+            if let Err(errors) = compiled_schema.validate(&policy_value) {
+                let error_messages: Vec<String> = errors.map(|e| format!("  - {}", e)).collect();
+                anyhow::bail!("ERROR: Policy from {} is INVALID against the JSON schema:\n{}", policy_source_description, error_messages.join("\n"));
+            }
+            println!("Policy from {} is valid against the JSON schema.", policy_source_description);
+
+            // 4. Check that the policy's sections agree with each other.
+            let issues = match policy_config {
+                Ok(policy_config) => PolicyEngine::validate_internal_consistency(&policy_config),
+                Err(e) => vec![format!("Policy does not match Hydravisor's policy structure: {}", e)],
+            };
+            if !issues.is_empty() {
+                let issue_messages: Vec<String> = issues.iter().map(|issue| format!("  - {}", issue)).collect();
+                anyhow::bail!("ERROR: Policy from {} is INCONSISTENT:\n{}", policy_source_description, issue_messages.join("\n"));
             }
-            // Ok(())
-            todo!("Refine validation output and add internal consistency checks via PolicyEngine method.");
+            println!("SUCCESS: Policy from {} is valid.", policy_source_description);
         }
         PolicyCommands::Check { agent_id, vm_id, action } => {
-            println!("Policy check command: Agent: {}, VM: {}, Action: {}", agent_id, vm_id, action);
-            // TODO: Implement policy check simulation from policy_cli_tools.md
-            // 1. Load policy.toml (already available in policy_engine.config)
-            // 2. Simulate authorization using policy_engine.check_permission()
-            //    - Construct an AuthRequest.
-            //    - Print the AuthDecision.
-            todo!("Implement policy check simulation using PolicyEngine.");
+            let evaluation = policy_engine.evaluate_action(&agent_id, action);
+            println!("Agent:  {} (role: {})", agent_id, evaluation.role.as_deref().unwrap_or("none"));
+            println!("VM:     {}", vm_id);
+            println!("Action: {:?}", action);
+            println!("Result: {}", if evaluation.allowed { "ALLOW" } else { "DENY" });
+            println!("Rule:   {}", evaluation.rule);
+            if let Some(role) = &evaluation.role {
+                println!("Session recording: {}", if policy_engine.should_record_session(role) { "on" } else { "off" });
+            }
+            // A denial is a non-zero exit so scripts can branch on the decision
+            if let AuthDecision::Denied { reason } = policy_engine.check_permission(&agent_id, action, Some(&vm_id)) {
+                anyhow::bail!("Policy denied: {}", reason);
+            }
         }
     }
-    // Ok(())
+    Ok(())
 }
 
 async fn handle_agent_command(
     command: AgentCommands,
-    _config: Arc<Config>,
-    _session_manager: Arc<SessionManager> // Added, marked unused for now
+    output: OutputFormat,
+    config: Arc<Config>,
+    session_manager: Arc<SessionManager>,
 ) -> Result<()> {
+    let caller = match &command {
+        AgentCommands::List { caller } | AgentCommands::Info { caller, .. } => caller.as_str(),
+    };
+    let agents = fetch_agent_summaries(&config, &session_manager, caller).await;
     match command {
-        AgentCommands::List => {
-            println!("List agents command");
-            // TODO: Fetch from SessionManager
+        AgentCommands::List { .. } => {
+            if output == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&agents)?);
+            } else if agents.is_empty() {
+                println!("No agents in policy.toml [permissions], and none seen by the running server.");
+            } else {
+                println!("{:<30} {:<14} {:<27} {:<8}", "AGENT", "ROLE", "LAST ACTIVITY", "SESSIONS");
+                for agent in agents {
+                    println!(
+                        "{:<30} {:<14} {:<27} {:<8}",
+                        agent.agent_id,
+                        agent.role.as_deref().unwrap_or("—"),
+                        agent.activity.as_ref().map_or("—", |activity| activity.last_activity.as_str()),
+                        agent.sessions.len()
+                    );
+                }
+            }
         }
-        AgentCommands::Info { agent_id } => {
-            println!("Agent info command for: {}", agent_id);
-            // TODO: Fetch from SessionManager
+        AgentCommands::Info { agent_id, .. } => {
+            // An agent nobody has heard of still has a policy outcome: everything is denied
+            let agent = agents.into_iter().find(|agent| agent.agent_id == agent_id).unwrap_or_else(|| AgentSummary {
+                agent_id: agent_id.clone(),
+                role: None,
+                activity: None,
+                sessions: Vec::new(),
+            });
+            let policy_engine = session_manager.policy_engine();
+            let evaluations: Vec<(PolicyAction, PolicyEvaluation)> = PolicyAction::value_variants()
+                .iter()
+                .map(|&action| (action, policy_engine.evaluate_action(&agent_id, action)))
+                .collect();
+            if output == OutputFormat::Json {
+                let mut value = serde_json::to_value(&agent)?;
+                value["permissions"] = evaluations
+                    .iter()
+                    .map(|(action, evaluation)| (action.permission_field().to_string(), serde_json::json!({ "allowed": evaluation.allowed, "rule": evaluation.rule })))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into();
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            } else {
+                print_agent_summary(&agent, &evaluations);
+            }
         }
     }
     Ok(())
 }
 
+/// Agents as the running MCP server (`hydravisor serve` or `mcp serve`) knows them. If none
+/// is running, or it refuses `caller`, falls back to this process's registry, which only knows policy.toml.
+async fn fetch_agent_summaries(config: &Config, session_manager: &SessionManager, caller: &str) -> Vec<AgentSummary> {
+    let socket_path = PathBuf::from(shellexpand::tilde(&config.mcp.socket_path).into_owned());
+    let timeout = std::time::Duration::from_millis(config.mcp.timeout_ms);
+    let fetched = async {
+        McpClient::connect(&socket_path, caller, timeout).await?.list_agents().await
+    }
+    .await;
+    match fetched {
+        Ok(agents) => agents,
+        Err(e) => {
+            eprintln!("Note: no agent activity from a running Hydravisor ({}); activity and sessions are unknown.", e);
+            session_manager.agent_summaries().await
+        }
+    }
+}
+
+fn print_agent_summary(agent: &AgentSummary, evaluations: &[(PolicyAction, PolicyEvaluation)]) {
+    println!("Agent:         {}", agent.agent_id);
+    println!("Role:          {}", agent.role.as_deref().unwrap_or("none (not in [permissions]; denied by default)"));
+    match &agent.activity {
+        Some(activity) => {
+            println!("First seen:    {}", activity.first_seen);
+            println!("Last activity: {} ({})", activity.last_activity, activity.last_action);
+        }
+        None => println!("Last activity: none since the server started"),
+    }
+
+    println!("\nPermissions:");
+    for (action, evaluation) in evaluations {
+        println!(
+            "  {:<20} {:<8} {}",
+            action.permission_field(),
+            if evaluation.allowed { "allowed" } else { "denied" },
+            evaluation.rule
+        );
+    }
+
+    println!("\nSessions:");
+    if agent.sessions.is_empty() {
+        println!("  (none)");
+    }
+    for session in &agent.sessions {
+        println!("  {:<38} {:<25} since {}", session.session_id, session.environment_instance_id, session.created_at);
+    }
+}
+
 async fn handle_vm_command(
     command: VmCommands,
-    _config: Arc<Config>,
-    libvirt_manager: Arc<Mutex<LibvirtManager>>, // Added, marked unused for now
+    output: OutputFormat,
+    config: Arc<Config>,
+    policy_engine: Arc<PolicyEngine>,
+    libvirt_manager: Arc<Mutex<LibvirtManager>>,
 ) -> Result<()> {
     match command {
         VmCommands::List => {
-            println!("Listing all known VMs...");
             let libvirt_manager_guard = libvirt_manager.lock().await;
             let vms = libvirt_manager_guard.list_vms()?;
-            if vms.is_empty() {
+            if output == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&vms)?);
+            } else if vms.is_empty() {
                 println!("No VMs found.");
             } else {
                 // TODO: Replace with a proper table using a crate like `prettytable-rs`
-                println!("{:<38} {:<25} {:<12} {:<10}", "ID", "NAME", "STATE", "CORES");
+                println!("{:<38} {:<25} {:<20} {:<16} {:<6} {:<16}", "ID", "NAME", "STATE", "IP", "CORES", "DISK (USED/SIZE)");
                 for vm in vms {
+                    let disk = match (vm.disk_allocated_gb, vm.disk_virtual_gb) {
+                        (Some(allocated), Some(virtual_gb)) => format!("{:.1}/{:.1} GB", allocated, virtual_gb),
+                        _ => "N/A".to_string(),
+                    };
                     println!(
-                        "{:<38} {:<25} {:<12?} {:<10}",
+                        "{:<38} {:<25} {:<20} {:<16} {:<6} {:<16}",
                         vm.instance_id,
                         vm.name,
-                        vm.state,
-                        vm.cpu_cores_used.map_or_else(|| "N/A".to_string(), |c| c.to_string())
+                        vm.state.label(),
+                        vm.ip_address.unwrap_or_else(|| "—".to_string()),
+                        vm.cpu_cores_used.map_or_else(|| "N/A".to_string(), |c| c.to_string()),
+                        disk
                     );
                 }
             }
         }
         VmCommands::Info { vm_id } => {
-            println!("VM info command for: {}", vm_id);
-            // TODO: Fetch from EnvManager and format output
+            let details = libvirt_manager.lock().await.get_vm_details(&vm_id)?;
+            if output == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&details)?);
+            } else {
+                print_vm_details(&details, &config.libvirt.uri);
+            }
         }
         VmCommands::Snapshot { vm_id, output } => {
             println!("VM snapshot command for: {}, Output: {:?}", vm_id, output);
             // TODO: Call EnvManager snapshot method
         }
+        VmCommands::Create { name, cpu, ram, role, disk_gb, iso, image, disk_path, existing_disk, network_policy, persistent, labels, dry_run } => {
+            check_vm_name(&name)?;
+            if let Some(iso) = &iso {
+                check_vm_file("ISO", iso)?;
+            }
+            let base_image = if iso.is_some() {
+                String::new()
+            } else {
+                image.clone().or_else(|| config.defaults.default_source_image.clone())
+                    .ok_or_else(|| anyhow::anyhow!("Pass --iso or --image, or set defaults.default_source_image"))?
+            };
+            if !base_image.is_empty() {
+                check_vm_file("source image", &base_image)?;
+            }
+            let cpus = cpu.as_deref().map(parse_vm_cpus).transpose()?;
+            let mem_mb = ram.as_deref().map(parse_vm_memory_mb).transpose()?;
+            let disk_gb = disk_gb.as_deref().map(parse_vm_disk_gb).transpose()?.unwrap_or(config.defaults.default_disk_gb);
+            if let Some(role) = role.as_deref().filter(|role| !policy_engine.config.roles.contains_key(*role)) {
+                anyhow::bail!("Unknown role '{}'. Roles are defined under [roles] in policy.toml.", role);
+            }
+            let (cpu_cores, memory_mb) = policy_engine.resolve_vm_resources(role.as_deref(), cpus, mem_mb)?;
+            let labels = labels.iter().map(|label| parse_vm_label(label)).collect::<Result<HashMap<_, _>>>()?;
+            let vm_config = VmConfig {
+                instance_id: name.clone(),
+                base_image: shellexpand::tilde(&base_image).into_owned(),
+                cloud_init: if iso.is_some() { None } else { default_cloud_init(&config) },
+                boot_iso: iso.map(|iso| shellexpand::tilde(&iso).into_owned()),
+                cpu_cores,
+                memory_mb,
+                disk_gb: Some(disk_gb),
+                disk_path: disk_path.map(|path| shellexpand::tilde(&path).into_owned()),
+                network_policy,
+                security_policy: "default".to_string(),
+                custom_script: None,
+                template_name: None,
+                labels: Some(labels).filter(|labels| !labels.is_empty()),
+                persistent,
+                existing_disk,
+            };
+
+            let libvirt_manager_guard = libvirt_manager.lock().await;
+            if dry_run {
+                let preview = libvirt_manager_guard.preview_vm(&vm_config)?;
+                if output == OutputFormat::Json {
+                    println!("{}", serde_json::to_string_pretty(&preview)?);
+                } else {
+                    let disk_state = match (preview.disk_exists, preview.disk_used_by.as_slice()) {
+                        (false, _) => "new".to_string(),
+                        (true, []) => "exists, unused".to_string(),
+                        (true, users) => format!("exists, used by {}", users.join(", ")),
+                    };
+                    println!("Disk: {} ({})", preview.disk_path, disk_state);
+                    println!("\n{}", preview.domain_xml);
+                }
+                return Ok(());
+            }
+            if libvirt_manager_guard.list_vms()?.iter().any(|vm| vm.name == name) {
+                anyhow::bail!("A VM named '{}' already exists", name);
+            }
+            let status = libvirt_manager_guard.create_vm(&vm_config)?;
+            if output == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+            } else {
+                println!("Created VM '{}' ({}), {} vCPUs, {} MB.", status.name, status.instance_id, cpu_cores, memory_mb);
+            }
+        }
+        VmCommands::Resize { vm_id, cpu, ram, disk_gb } => {
+            let change = VmResourceChange {
+                cpus: cpu.as_deref().map(parse_vm_cpus).transpose()?,
+                memory_mb: ram.as_deref().map(parse_vm_memory_mb).transpose()?,
+                disk_gb: disk_gb.as_deref().map(parse_vm_disk_gb).transpose()?,
+            };
+            if change.is_empty() {
+                anyhow::bail!("Pass at least one of --cpu, --ram and --disk-gb");
+            }
+            libvirt_manager.lock().await.resize_vm(&vm_id, &change)?;
+            println!("Resized VM '{}': {}.", vm_id, change.describe());
+        }
+        VmCommands::Import { vm_id, role, labels } => {
+            let labels = labels.iter().map(|label| parse_vm_label(label)).collect::<Result<HashMap<_, _>>>()?;
+            let imported = libvirt_manager.lock().await.import_vm(&vm_id, role.as_deref(), labels)?;
+            if output == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&imported)?);
+            } else {
+                println!("Imported VM '{}' ({}).", imported.name, imported.uuid);
+            }
+        }
+        VmCommands::Forget { vm_id } => {
+            libvirt_manager.lock().await.forget_vm(&vm_id)?;
+            println!("Forgot VM '{}'; the domain was left in place.", vm_id);
+        }
+        VmCommands::Delete { vm_id, keep_disk, yes } => {
+            if !yes {
+                let what = if keep_disk { "" } else { " and delete its disk images" };
+                if !confirm(&format!("Destroy VM '{}'{}?", vm_id, what))? {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+            libvirt_manager.lock().await.destroy_vm(&vm_id, !keep_disk)?;
+            println!("Destroyed VM '{}'.", vm_id);
+        }
+    }
+    Ok(())
+}
+
+// Asks a yes/no question on the terminal. Without one (e.g. in a script), it's an error
+// rather than a silent "no", so a missing --yes is noticed.
+fn confirm(question: &str) -> Result<bool> {
+    use std::io::{IsTerminal, Write};
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("{} Pass --yes to confirm when not running interactively.", question);
+    }
+    print!("{} [y/N] ", question);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+async fn handle_mcp_command(
+    command: McpCommands,
+    config: Arc<Config>,
+    session_manager: Arc<SessionManager>,
+) -> Result<()> {
+    match command {
+        McpCommands::Serve => {
+            let server = McpServer::start(config, session_manager).await?;
+            println!("MCP server listening on {}. Press Ctrl-C to stop.", server.socket_path().display());
+            wait_for_shutdown_signal().await?;
+            server.shutdown();
+        }
+        McpCommands::Call { method, params, agent_id } => {
+            let mut fields = match params {
+                Some(params) => serde_json::from_str::<serde_json::Value>(&params)
+                    .map_err(|e| anyhow::anyhow!("--params is not valid JSON: {}", e))?,
+                None => serde_json::json!({}),
+            };
+            let Some(object) = fields.as_object_mut() else {
+                anyhow::bail!("--params must be a JSON object");
+            };
+            object.insert("type".to_string(), serde_json::Value::String(method));
+            let request: McpMessage = serde_json::from_value(fields)
+                .map_err(|e| anyhow::anyhow!("--params does not fit an MCP message: {}", e))?;
+
+            let socket_path = PathBuf::from(shellexpand::tilde(&config.mcp.socket_path).into_owned());
+            let timeout = std::time::Duration::from_millis(config.mcp.timeout_ms);
+            let mut client = McpClient::connect(&socket_path, agent_id, timeout).await?;
+            let payload = match canonical_message_type(&request.r#type) {
+                "mcp/heartbeat" => client.heartbeat().await.map(|_| serde_json::json!({}))?,
+                "agent/list" => serde_json::to_value(client.list_agents().await?)?,
+                "vm/list" => serde_json::to_value(client.list_vms().await?)?,
+                "vm/create" => {
+                    let name = request.meta.and_then(|meta| meta.name);
+                    serde_json::to_value(client.create_vm(name, request.os, request.cpu, request.ram).await?)?
+                }
+                "vm/delete" => {
+                    let instance_id = request.instance_id.ok_or_else(|| anyhow::anyhow!("vm/delete needs \"instance_id\" in --params"))?;
+                    client.destroy_vm(&instance_id).await?;
+                    serde_json::json!({ "instance_id": instance_id })
+                }
+                "vm/attach-terminal" => {
+                    let instance_id = request.instance_id.ok_or_else(|| anyhow::anyhow!("vm/attach-terminal needs \"instance_id\" in --params"))?;
+                    client.attach_session(&instance_id).await?
+                }
+                // Left to the server to accept or refuse
+                _ => client.call(request).await?.payload.unwrap_or(serde_json::Value::Null),
+            };
+            println!("{}", serde_json::to_string_pretty(&payload)?);
+        }
     }
     Ok(())
 }
 
+fn print_vm_details(details: &VmDetails, libvirt_uri: &str) {
+    println!("Name:       {}", details.name);
+    println!("UUID:       {}", details.instance_id);
+    println!("State:      {}", details.state.label());
+    println!("Persistent: {}", if details.persistent { "yes" } else { "no" });
+    println!("Autostart:  {}", if details.autostart { "yes" } else { "no" });
+    println!("vCPUs:      {}", details.vcpus);
+    println!("Memory:     {} MB / {} MB max", details.memory_kb / 1024, details.memory_max_kb / 1024);
+
+    println!("\nDisks:");
+    if details.disks.is_empty() {
+        println!("  (none)");
+    }
+    for disk in &details.disks {
+        println!(
+            "  {:<6} {:<7} {}{}",
+            disk.target,
+            disk.device,
+            disk.source.as_deref().unwrap_or("—"),
+            if disk.readonly { " (read-only)" } else { "" }
+        );
+        if let (Some(capacity), Some(allocation)) = (disk.capacity_bytes, disk.allocation_bytes) {
+            println!(
+                "  {:<6} {:<7} {} virtual, {} allocated",
+                "",
+                "",
+                format_bytes(capacity),
+                format_bytes(allocation)
+            );
+        }
+        if let Some(backing_file) = &disk.backing_file {
+            println!("  {:<6} {:<7} backed by {}", "", "", backing_file);
+        }
+    }
+
+    println!("\nNetwork interfaces:");
+    if details.interfaces.is_empty() {
+        println!("  (none)");
+    }
+    for iface in &details.interfaces {
+        let ip_addresses = if iface.ip_addresses.is_empty() { "—".to_string() } else { iface.ip_addresses.join(", ") };
+        println!(
+            "  {:<17} {:<10} {:<8} {}",
+            iface.mac,
+            iface.source.as_deref().unwrap_or("—"),
+            iface.model.as_deref().unwrap_or("—"),
+            ip_addresses
+        );
+    }
+
+    println!("\nConsoles:");
+    if details.consoles.is_empty() {
+        println!("  (none)");
+    }
+    for console in &details.consoles {
+        match console.kind.as_str() {
+            "serial" => println!(
+                "  serial port {} {}",
+                console.port.as_deref().unwrap_or("?"),
+                console.pty_path.as_deref().map_or_else(String::new, |pty| format!("on {}", pty))
+            ),
+            kind => println!(
+                "  {} {}:{}",
+                kind,
+                console.listen.as_deref().unwrap_or("127.0.0.1"),
+                console.port.as_deref().unwrap_or("?")
+            ),
+        }
+    }
+    let console_info = ConsoleInfo::from_consoles(&details.consoles);
+    if let Some(vnc_url) = console_info.vnc_url() {
+        println!("  Open the display with a VNC viewer at {}", vnc_url);
+    }
+    if console_info.serial_pty.is_some() {
+        println!("  Attach to the serial console with `virsh -c {} console {}`", libvirt_uri, details.name);
+    }
+}
+
 async fn handle_log_command(
     command: LogCommands,
+    output_format: OutputFormat,
     _config: Arc<Config>,
-    _audit_engine: Arc<AuditEngine> // Added, marked unused for now
+    audit_engine: Arc<AuditEngine>,
 ) -> Result<()> {
     match command {
         LogCommands::List { log_type, limit } => {
-            println!("Log list command: Type: {:?}, Limit: {}", log_type, limit);
-            // TODO: Use _audit_engine
-            todo!("Implement log list - requires AuditEngine or direct log file access logic");
+            let entries = audit_engine.list_log_files(log_type, limit)?;
+            if output_format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else if entries.is_empty() {
+                println!("No {:?} logs found in {}.", log_type, audit_engine.log_type_dir(log_type).display());
+            } else {
+                println!("{:<40} {:>10} {:<26}", "SESSION", "SIZE", "MODIFIED");
+                for entry in entries {
+                    println!(
+                        "{:<40} {:>10} {:<26}",
+                        entry.session_id,
+                        entry.size_bytes,
+                        entry.modified.as_deref().unwrap_or("—")
+                    );
+                }
+            }
         }
         LogCommands::View { session_id } => {
-            println!("Log view command: SessionID: {}", session_id);
-            // TODO: Use _audit_engine
-            todo!("Implement log view - requires AuditEngine or direct log file access logic");
+            let entry = audit_engine.find_log_file(&session_id)?;
+            let content = audit_engine.read_log(&entry)?;
+            std::io::Write::write_all(&mut std::io::stdout(), &content)?;
         }
         LogCommands::Export { session_id, format, output } => {
-            println!("Log export command: SessionID: {}, Format: {:?}, Output: {:?}", session_id, format, output);
-            // TODO: Use _audit_engine
-            todo!("Implement log export - requires AuditEngine or direct log file access logic");
+            let entry = audit_engine.find_log_file(&session_id)?;
+            let written = audit_engine.export_log(&entry, format, &output)?;
+            println!("Exported {} to {}", entry.session_id, written.display());
         }
     }
-    // Ok(())
+    Ok(())
+}
+
+fn handle_audit_command(command: AuditCommands, audit_engine: Arc<AuditEngine>) -> Result<()> {
+    match command {
+        AuditCommands::Verify { path } => {
+            let ledger_path = path.unwrap_or_else(|| audit_engine.ledger_path());
+            match AuditEngine::verify_ledger(&ledger_path)? {
+                LedgerVerification::Intact { records, pre_chain: 0, .. } => println!("OK, {} records verified in {}", records, ledger_path.display()),
+                LedgerVerification::Intact { records, pre_chain, .. } => println!(
+                    "OK, {} records verified in {}; the {} events before them are from a pre-chain ledger and cannot be verified",
+                    records,
+                    ledger_path.display(),
+                    pre_chain
+                ),
+                LedgerVerification::Broken { line: Some(line), reason } => {
+                    anyhow::bail!("Audit ledger {} is broken at line {}: {}", ledger_path.display(), line, reason)
+                }
+                LedgerVerification::Broken { line: None, reason } => {
+                    anyhow::bail!("Audit ledger {} is broken: {}", ledger_path.display(), reason)
+                }
+                LedgerVerification::Truncated { records, expected_records } => {
+                    anyhow::bail!(
+                        "Audit ledger {} is truncated: its chain holds {} records, but {} were written",
+                        ledger_path.display(),
+                        records,
+                        expected_records
+                    )
+                }
+            }
+        }
+        AuditCommands::Export { since, until, agent, event_type, format, output } => {
+            let filter = AuditFilter {
+                since: since.as_deref().map(parse_audit_time).transpose()?,
+                until: until.as_deref().map(parse_audit_time).transpose()?,
+                agent_id: agent,
+                event_type,
+            };
+            let ledger_path = audit_engine.ledger_path();
+            let summary = match &output {
+                Some(path) => {
+                    let file = std::fs::File::create(path)
+                        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", path.display(), e))?;
+                    AuditEngine::export_ledger(&ledger_path, &filter, format, &mut std::io::BufWriter::new(file))?
+                }
+                None => AuditEngine::export_ledger(&ledger_path, &filter, format, &mut std::io::stdout().lock())?,
+            };
+            for (line, reason) in &summary.breaks {
+                eprintln!("Warning: audit ledger line {}: {}", line, reason);
+            }
+            if summary.pre_chain > 0 {
+                eprintln!("Note: {} events are from before hash chaining and were exported unverified", summary.pre_chain);
+            }
+            if let Some(path) = output {
+                println!("Exported {} of {} events to {}", summary.exported, summary.records, path.display());
+            }
+        }
+    }
+    Ok(())
 }
 
 // TODO: Add tests for CLI parsing and command handling (mocking components)
@@ -2,14 +2,27 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use toml_edit::{DocumentMut, Item, TableLike};
+use tracing::{debug, info, warn};
 use anyhow::{Context, Result};
 use xdg::BaseDirectories;
 
+use crate::errors::HydraError;
+
 pub const APP_NAME: &str = "hydravisor";
 pub const DEFAULT_CONFIG_FILENAME: &str = "config.toml";
 pub const DEFAULT_POLICY_FILENAME: &str = "policy.toml";
 pub const DEFAULT_SSH_CONFIG_FILENAME: &str = "ssh.toml";
+pub const DEFAULT_THEME_FILENAME: &str = "theme.toml";
+pub const DEFAULT_THEMES_DIRNAME: &str = "themes";
+pub const CONFIG_DIR_ENV_VAR: &str = "HYDRAVISOR_CONFIG_DIR";
+pub const PROFILE_ENV_VAR: &str = "HYDRAVISOR_PROFILE";
+pub const PROFILES_DIR_NAME: &str = "profiles";
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost";
+pub const DEFAULT_OLLAMA_PORT: u16 = 11434;
+
+// A commented config.toml listing every key at its default value, written by `config init`
+pub const DEFAULT_CONFIG_TEMPLATE: &str = include_str!("default_config.toml");
 
 // Main configuration structure, mapping to config.toml
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -26,6 +39,10 @@ pub struct Config {
     #[serde(default)]
     pub tmux: TmuxConfig,
     #[serde(default)]
+    pub libvirt: LibvirtConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
     pub mcp: McpConfig,
     #[serde(default)]
     pub keybindings: KeyBindingsConfig,
@@ -35,10 +52,25 @@ pub struct Config {
     pub policy_file_path: Option<PathBuf>,
     #[serde(skip)]
     pub ssh_config_file_path: Option<PathBuf>,
+    #[serde(skip)]
+    pub theme_file_path: Option<PathBuf>,
+    #[serde(skip)]
+    pub themes_dir: Option<PathBuf>, // Extra palettes for the theme picker, one `<name>.toml` each
+    // Where this config was loaded from; `Config::save` writes back here
+    #[serde(skip)]
+    pub config_file_path: Option<PathBuf>,
+    // Named profile selected with --profile, if any
+    #[serde(skip)]
+    pub profile: Option<String>,
+    // Full Ollama endpoint, e.g. "https://ollama.internal:8443"; takes precedence over host/port
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ollama_url: Option<String>,
     pub ollama_host: Option<String>,
     pub ollama_port: Option<u16>,
     #[serde(default = "default_global_system_prompt")]
     pub default_system_prompt: Option<String>,
+    #[serde(default)]
+    pub favorite_models: Vec<String>, // Ollama model names or Bedrock model IDs
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -50,8 +82,16 @@ pub struct InterfaceConfig {
     pub modal_key: String,
     #[serde(default = "default_refresh_interval_ms")]
     pub refresh_interval_ms: u64,
+    #[serde(default = "default_data_refresh_interval_ms")]
+    pub data_refresh_interval_ms: u64, // How often VM and model lists are re-fetched
     #[serde(default = "default_about_modal_readme_lines")]
-    pub about_modal_readme_lines: usize,
+    pub about_modal_readme_lines: usize, // Height of the About modal; the whole README scrolls within it
+    #[serde(default = "default_mouse_capture")]
+    pub mouse_capture: bool, // When false, the terminal handles selection/scroll natively
+    #[serde(default)]
+    pub quick_actions: bool, // Destroy and shut down VMs without asking; toggled at runtime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>, // Picked in Preferences → Theme; when unset, theme.toml is used
 }
 
 fn default_interface_mode() -> String {
@@ -63,18 +103,29 @@ fn default_modal_key() -> String {
 fn default_refresh_interval_ms() -> u64 {
     500
 }
+fn default_data_refresh_interval_ms() -> u64 {
+    5000
+}
 
 fn default_about_modal_readme_lines() -> usize {
     10
 }
 
+fn default_mouse_capture() -> bool {
+    true
+}
+
 impl Default for InterfaceConfig {
     fn default() -> Self {
         InterfaceConfig {
             mode: default_interface_mode(),
             modal_key: default_modal_key(),
             refresh_interval_ms: default_refresh_interval_ms(),
+            data_refresh_interval_ms: default_data_refresh_interval_ms(),
             about_modal_readme_lines: default_about_modal_readme_lines(),
+            mouse_capture: default_mouse_capture(),
+            quick_actions: false,
+            theme: None,
         }
     }
 }
@@ -98,6 +149,9 @@ pub struct DefaultsConfig {
     pub default_ram: String, // e.g., "4GB"
     #[serde(default = "default_disk_gb")]
     pub default_disk_gb: u64,
+    // Public key injected via cloud-init into VMs built from a source image
+    #[serde(default)]
+    pub ssh_public_key_path: Option<String>,
 }
 
 fn default_vm_image() -> String {
@@ -133,6 +187,7 @@ impl Default for DefaultsConfig {
             default_cpu: default_cpu(),
             default_ram: default_ram(),
             default_disk_gb: default_disk_gb(),
+            ssh_public_key_path: None,
         }
     }
 }
@@ -144,6 +199,8 @@ pub struct ProvidersConfig {
     pub ollama: OllamaConfig,
     #[serde(default)]
     pub bedrock: BedrockConfig,
+    #[serde(default)]
+    pub openai_compat: OpenAiCompatConfig,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -157,6 +214,64 @@ pub struct OllamaConfig {
     pub models: Vec<String>,
     #[serde(default)]
     pub model_system_prompts: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub model_params: HashMap<String, ModelParams>,
+}
+
+// Per-model generation options. Unset fields keep Ollama's own defaults.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModelParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_ctx: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_predict: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i32>,
+}
+
+impl ModelParams {
+    pub fn is_empty(&self) -> bool {
+        *self == ModelParams::default()
+    }
+
+    /// Renders the params as `key=value` pairs, listing every key so unset ones can be filled in.
+    pub fn to_edit_string(&self) -> String {
+        fn show<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(T::to_string).unwrap_or_default()
+        }
+        format!(
+            "temperature={} top_p={} num_ctx={} num_predict={} seed={}",
+            show(&self.temperature), show(&self.top_p), show(&self.num_ctx), show(&self.num_predict), show(&self.seed),
+        )
+    }
+
+    /// Parses whitespace-separated `key=value` pairs; an empty value leaves the param unset.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut params = ModelParams::default();
+        for pair in input.split_whitespace() {
+            let (key, value) = pair
+                .split_once('=')
+                .with_context(|| format!("Expected key=value, got '{}'", pair))?;
+            if value.is_empty() {
+                continue;
+            }
+            let invalid = || format!("Invalid value '{}' for {}", value, key);
+            match key {
+                "temperature" => params.temperature = Some(value.parse().with_context(invalid)?),
+                "top_p" => params.top_p = Some(value.parse().with_context(invalid)?),
+                "num_ctx" => params.num_ctx = Some(value.parse().with_context(invalid)?),
+                "num_predict" => params.num_predict = Some(value.parse().with_context(invalid)?),
+                "seed" => params.seed = Some(value.parse().with_context(invalid)?),
+                _ => anyhow::bail!("Unknown parameter '{}'", key),
+            }
+        }
+        Ok(params)
+    }
 }
 
 fn default_ollama_path() -> String {
@@ -170,6 +285,7 @@ impl Default for OllamaConfig {
             path: default_ollama_path(),
             models: vec!["llama3".to_string(), "mistral".to_string()],
             model_system_prompts: None,
+            model_params: HashMap::new(),
         }
     }
 }
@@ -190,6 +306,36 @@ pub struct BedrockFilterDefinition {
     pub description: String,
 }
 
+// A server speaking the OpenAI chat completions API (vLLM, LM Studio, llama.cpp server, ...)
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpenAiCompatConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_openai_compat_base_url")]
+    pub base_url: String, // Up to and including the version, e.g. "http://localhost:8000/v1"
+    // Sent as a bearer token; unset falls back to $OPENAI_API_KEY, and local servers usually need neither
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub models: Vec<String>, // Listed alongside whatever the server's /models reports
+}
+
+fn default_openai_compat_base_url() -> String {
+    "http://localhost:8000/v1".to_string()
+}
+
+impl Default for OpenAiCompatConfig {
+    fn default() -> Self {
+        OpenAiCompatConfig {
+            enabled: false,
+            base_url: default_openai_compat_base_url(),
+            api_key: None,
+            models: Vec::new(),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct BedrockConfig {
@@ -199,6 +345,8 @@ pub struct BedrockConfig {
     pub region: String,
     #[serde(default = "default_bedrock_profile")]
     pub profile: String,
+    #[serde(default = "default_bedrock_regions")]
+    pub regions: Vec<String>, // What the region key cycles through in the Bedrock view
     #[serde(default)]
     pub filters: BedrockFiltersConfig,
 }
@@ -209,6 +357,9 @@ fn default_bedrock_region() -> String {
 fn default_bedrock_profile() -> String {
     "default".to_string()
 }
+fn default_bedrock_regions() -> Vec<String> {
+    ["us-east-1", "us-west-2", "eu-central-1", "ap-northeast-1"].iter().map(|region| region.to_string()).collect()
+}
 
 impl Default for BedrockConfig {
     fn default() -> Self {
@@ -216,6 +367,7 @@ impl Default for BedrockConfig {
             enabled: false, // Typically opt-in
             region: default_bedrock_region(),
             profile: default_bedrock_profile(),
+            regions: default_bedrock_regions(),
             filters: BedrockFiltersConfig {
                 default: default_bedrock_filter_name(),
                 available_to_request_access: Some(BedrockFilterDefinition {
@@ -240,6 +392,8 @@ pub struct LoggingConfig {
     pub rotate_daily: bool,
     #[serde(default = "default_retain_days")]
     pub retain_days: u32,
+    #[serde(default = "default_log_format")]
+    pub format: String, // "json" (one object per line) or "text"
 }
 
 fn default_log_level() -> String {
@@ -254,6 +408,9 @@ fn default_rotate_daily() -> bool {
 fn default_retain_days() -> u32 {
     7
 }
+fn default_log_format() -> String {
+    "json".to_string()
+}
 
 impl Default for LoggingConfig {
     fn default() -> Self {
@@ -262,6 +419,7 @@ impl Default for LoggingConfig {
             log_dir: default_log_dir(),
             rotate_daily: default_rotate_daily(),
             retain_days: default_retain_days(),
+            format: default_log_format(),
         }
     }
 }
@@ -274,7 +432,7 @@ pub struct TmuxConfig {
     #[serde(default = "default_record_all_sessions")]
     pub record_all_sessions: bool,
     #[serde(default = "default_record_format")]
-    pub record_format: String, // "ansi" or "jsonl"
+    pub record_format: String, // "cast" (asciinema v2), "ansi", or "jsonl"
     #[serde(default = "default_autosave_on_exit")]
     pub autosave_on_exit: bool,
 }
@@ -286,7 +444,7 @@ fn default_record_all_sessions() -> bool {
     false
 }
 fn default_record_format() -> String {
-    "ansi".to_string()
+    "cast".to_string()
 }
 fn default_autosave_on_exit() -> bool {
     true
@@ -303,6 +461,57 @@ impl Default for TmuxConfig {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LibvirtConfig {
+    #[serde(default = "default_libvirt_uri")]
+    pub uri: String, // e.g. "qemu+ssh://user@host/system" to manage a remote hypervisor
+    #[serde(default = "default_storage_pool")]
+    pub storage_pool: String, // Pool whose target directory holds the disk images Hydravisor creates
+    #[serde(default)]
+    pub idle_shutdown_minutes: u64, // Shut down VMs idle this long; 0 never does
+}
+
+fn default_libvirt_uri() -> String {
+    "qemu:///system".to_string()
+}
+
+fn default_storage_pool() -> String {
+    "default".to_string()
+}
+
+impl Default for LibvirtConfig {
+    fn default() -> Self {
+        LibvirtConfig {
+            uri: default_libvirt_uri(),
+            storage_pool: default_storage_pool(),
+            idle_shutdown_minutes: 0,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    #[serde(default)]
+    pub enabled: bool, // Needs a build with the `metrics` feature
+    #[serde(default = "default_metrics_bind_addr")]
+    pub bind_addr: String,
+}
+
+fn default_metrics_bind_addr() -> String {
+    "127.0.0.1:9187".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        MetricsConfig {
+            enabled: false,
+            bind_addr: default_metrics_bind_addr(),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct McpConfig {
@@ -344,12 +553,40 @@ pub struct KeyBindingsConfig {
     #[serde(default = "default_prev_tab")] pub prev_tab: String,
     #[serde(default = "default_new_vm")] pub new_vm: String,
     #[serde(default = "default_destroy_vm")] pub destroy_vm: String,
+    #[serde(default = "default_mark_vm")] pub mark_vm: String,
+    #[serde(default = "default_refresh")] pub refresh: String,
+    #[serde(default = "default_shutdown_vm")] pub shutdown_vm: String,
+    #[serde(default = "default_suspend_vm")] pub suspend_vm: String,
+    #[serde(default = "default_ssh_vm")] pub ssh_vm: String,
+    #[serde(default = "default_console_vm")] pub console_vm: String,
+    #[serde(default = "default_clone_vm")] pub clone_vm: String,
     #[serde(default = "default_edit")] pub edit: String,
     #[serde(default = "default_enter")] pub enter: String,
     #[serde(default = "default_up")] pub up: String,
     #[serde(default = "default_down")] pub down: String,
     #[serde(default = "default_filter")] pub filter: String,
     #[serde(default = "default_sort")] pub sort: String,
+    #[serde(default = "default_toggle_mouse")] pub toggle_mouse: String,
+    #[serde(default = "default_toggle_quick_actions")] pub toggle_quick_actions: String,
+    #[serde(default = "default_toggle_favorite")] pub toggle_favorite: String,
+    #[serde(default = "default_favorites_only")] pub favorites_only: String,
+    #[serde(default = "default_log_level_filter")] pub log_level: String,
+    #[serde(default = "default_wrap_logs")] pub wrap_logs: String,
+    #[serde(default = "default_search")] pub search: String,
+    #[serde(default = "default_save_chat")] pub save_chat: String,
+    #[serde(default = "default_load_chat")] pub load_chat: String,
+    #[serde(default = "default_cancel_stream")] pub cancel_stream: String,
+    #[serde(default = "default_toggle_thought")] pub toggle_thought: String,
+    #[serde(default = "default_new_chat")] pub new_chat: String,
+    #[serde(default = "default_clear_chat")] pub clear_chat: String,
+    #[serde(default = "default_close_chat")] pub close_chat: String,
+    #[serde(default = "default_next_chat")] pub next_chat: String,
+    #[serde(default = "default_prev_chat")] pub prev_chat: String,
+    #[serde(default = "default_pull_model")] pub pull_model: String,
+    #[serde(default = "default_delete_model")] pub delete_model: String,
+    #[serde(default = "default_edit_params")] pub edit_params: String,
+    #[serde(default = "default_copy")] pub copy: String,
+    #[serde(default = "default_command_palette")] pub command_palette: String,
     #[serde(default)]
     pub bedrock: BedrockKeyBindings,
 }
@@ -361,16 +598,28 @@ pub struct BedrockKeyBindings {
     pub filter: String,
     #[serde(default = "default_bedrock_sort")]
     pub sort: String,
+    #[serde(default = "default_bedrock_access")]
+    pub access: String,
+    #[serde(default = "default_bedrock_region_key")]
+    pub region: String,
+    #[serde(default = "default_bedrock_profile_key")]
+    pub profile: String,
 }
 
 fn default_bedrock_filter() -> String { "f".to_string() }
 fn default_bedrock_sort() -> String { "s".to_string() }
+fn default_bedrock_access() -> String { "a".to_string() }
+fn default_bedrock_region_key() -> String { "R".to_string() }
+fn default_bedrock_profile_key() -> String { "P".to_string() }
 
 impl Default for BedrockKeyBindings {
     fn default() -> Self {
         Self {
             filter: default_bedrock_filter(),
             sort: default_bedrock_sort(),
+            access: default_bedrock_access(),
+            region: default_bedrock_region_key(),
+            profile: default_bedrock_profile_key(),
         }
     }
 }
@@ -382,12 +631,40 @@ fn default_next_tab() -> String { "Tab".to_string() }
 fn default_prev_tab() -> String { "BackTab".to_string() }
 fn default_new_vm() -> String { "n".to_string() }
 fn default_destroy_vm() -> String { "d".to_string() }
+fn default_mark_vm() -> String { "Space".to_string() }
+fn default_refresh() -> String { "r".to_string() }
+fn default_shutdown_vm() -> String { "p".to_string() }
+fn default_suspend_vm() -> String { "z".to_string() }
+fn default_ssh_vm() -> String { "a".to_string() }
+fn default_console_vm() -> String { "c".to_string() }
+fn default_clone_vm() -> String { "C".to_string() }
 fn default_edit() -> String { "e".to_string() }
 fn default_enter() -> String { "Enter".to_string() }
 fn default_up() -> String { "Up".to_string() }
 fn default_down() -> String { "Down".to_string() }
 fn default_filter() -> String { "F".to_string() }
 fn default_sort() -> String { "S".to_string() }
+fn default_toggle_mouse() -> String { "m".to_string() }
+fn default_toggle_quick_actions() -> String { "!".to_string() }
+fn default_toggle_favorite() -> String { "*".to_string() }
+fn default_favorites_only() -> String { "v".to_string() }
+fn default_log_level_filter() -> String { "l".to_string() }
+fn default_wrap_logs() -> String { "w".to_string() }
+fn default_search() -> String { "/".to_string() }
+fn default_save_chat() -> String { "Ctrl+s".to_string() }
+fn default_load_chat() -> String { "o".to_string() }
+fn default_cancel_stream() -> String { "Esc".to_string() }
+fn default_toggle_thought() -> String { "t".to_string() }
+fn default_new_chat() -> String { "Ctrl+n".to_string() }
+fn default_clear_chat() -> String { "Ctrl+l".to_string() }
+fn default_close_chat() -> String { "Ctrl+w".to_string() }
+fn default_next_chat() -> String { "]".to_string() }
+fn default_prev_chat() -> String { "[".to_string() }
+fn default_pull_model() -> String { "g".to_string() }
+fn default_delete_model() -> String { "x".to_string() }
+fn default_edit_params() -> String { "t".to_string() }
+fn default_copy() -> String { "y".to_string() }
+fn default_command_palette() -> String { ":".to_string() }
 
 impl Default for KeyBindingsConfig {
     fn default() -> Self {
@@ -399,17 +676,140 @@ impl Default for KeyBindingsConfig {
             prev_tab: default_prev_tab(),
             new_vm: default_new_vm(),
             destroy_vm: default_destroy_vm(),
+            mark_vm: default_mark_vm(),
+            refresh: default_refresh(),
+            shutdown_vm: default_shutdown_vm(),
+            suspend_vm: default_suspend_vm(),
+            ssh_vm: default_ssh_vm(),
+            console_vm: default_console_vm(),
+            clone_vm: default_clone_vm(),
             edit: default_edit(),
             enter: default_enter(),
             up: default_up(),
             down: default_down(),
             filter: default_filter(),
             sort: default_sort(),
+            toggle_mouse: default_toggle_mouse(),
+            toggle_quick_actions: default_toggle_quick_actions(),
+            toggle_favorite: default_toggle_favorite(),
+            favorites_only: default_favorites_only(),
+            log_level: default_log_level_filter(),
+            wrap_logs: default_wrap_logs(),
+            search: default_search(),
+            save_chat: default_save_chat(),
+            load_chat: default_load_chat(),
+            cancel_stream: default_cancel_stream(),
+            toggle_thought: default_toggle_thought(),
+            new_chat: default_new_chat(),
+            clear_chat: default_clear_chat(),
+            close_chat: default_close_chat(),
+            next_chat: default_next_chat(),
+            prev_chat: default_prev_chat(),
+            pull_model: default_pull_model(),
+            delete_model: default_delete_model(),
+            edit_params: default_edit_params(),
+            copy: default_copy(),
+            command_palette: default_command_palette(),
             bedrock: BedrockKeyBindings::default(),
         }
     }
 }
 
+/// Makes `target` match `new` wherever `new` differs from `old`: changed and added keys are
+/// written (keeping the existing value's comments), keys dropped from `old` are removed, and
+/// everything else in `target`, including keys neither side knows, is left as it was.
+fn apply_changed_keys(target: &mut dyn TableLike, old: &dyn TableLike, new: &dyn TableLike) {
+    for (key, new_item) in new.iter() {
+        let old_item = old.get(key);
+        if let (Some(new_table), Some(old_table)) = (new_item.as_table_like(), old_item.and_then(Item::as_table_like)) {
+            match target.get_mut(key).and_then(Item::as_table_like_mut) {
+                Some(target_table) => apply_changed_keys(target_table, old_table, new_table),
+                None if new_item.to_string() != old_item.map(Item::to_string).unwrap_or_default() => {
+                    target.insert(key, new_item.clone());
+                }
+                None => {}
+            }
+            continue;
+        }
+        if old_item.map(Item::to_string) == Some(new_item.to_string()) {
+            continue;
+        }
+        match target.get_mut(key) {
+            Some(existing) => {
+                let decor = existing.as_value().map(|value| value.decor().clone());
+                *existing = new_item.clone();
+                if let (Some(decor), Some(value)) = (decor, existing.as_value_mut()) {
+                    *value.decor_mut() = decor;
+                }
+            }
+            None => {
+                target.insert(key, new_item.clone());
+            }
+        }
+    }
+    for (key, _) in old.iter() {
+        if !new.contains_key(key) {
+            target.remove(key);
+        }
+    }
+}
+
+impl KeyBindingsConfig {
+    /// The binding of an action, by its id in the TUI's keybinding map (the Bedrock keys are
+    /// "bedrock_filter", "bedrock_sort", "bedrock_access", "bedrock_region" and "bedrock_profile").
+    pub fn binding_mut(&mut self, action: &str) -> Option<&mut String> {
+        Some(match action {
+            "quit" => &mut self.quit,
+            "help" => &mut self.help,
+            "menu" => &mut self.menu,
+            "next_tab" => &mut self.next_tab,
+            "prev_tab" => &mut self.prev_tab,
+            "new_vm" => &mut self.new_vm,
+            "destroy_vm" => &mut self.destroy_vm,
+            "mark_vm" => &mut self.mark_vm,
+            "refresh" => &mut self.refresh,
+            "shutdown_vm" => &mut self.shutdown_vm,
+            "suspend_vm" => &mut self.suspend_vm,
+            "ssh_vm" => &mut self.ssh_vm,
+            "console_vm" => &mut self.console_vm,
+            "clone_vm" => &mut self.clone_vm,
+            "edit" => &mut self.edit,
+            "enter" => &mut self.enter,
+            "up" => &mut self.up,
+            "down" => &mut self.down,
+            "filter" => &mut self.filter,
+            "sort" => &mut self.sort,
+            "toggle_mouse" => &mut self.toggle_mouse,
+            "toggle_quick_actions" => &mut self.toggle_quick_actions,
+            "toggle_favorite" => &mut self.toggle_favorite,
+            "favorites_only" => &mut self.favorites_only,
+            "log_level" => &mut self.log_level,
+            "wrap_logs" => &mut self.wrap_logs,
+            "search" => &mut self.search,
+            "save_chat" => &mut self.save_chat,
+            "load_chat" => &mut self.load_chat,
+            "cancel_stream" => &mut self.cancel_stream,
+            "toggle_thought" => &mut self.toggle_thought,
+            "new_chat" => &mut self.new_chat,
+            "clear_chat" => &mut self.clear_chat,
+            "close_chat" => &mut self.close_chat,
+            "next_chat" => &mut self.next_chat,
+            "prev_chat" => &mut self.prev_chat,
+            "pull_model" => &mut self.pull_model,
+            "delete_model" => &mut self.delete_model,
+            "edit_params" => &mut self.edit_params,
+            "copy" => &mut self.copy,
+            "command_palette" => &mut self.command_palette,
+            "bedrock_filter" => &mut self.bedrock.filter,
+            "bedrock_sort" => &mut self.bedrock.sort,
+            "bedrock_access" => &mut self.bedrock.access,
+            "bedrock_region" => &mut self.bedrock.region,
+            "bedrock_profile" => &mut self.bedrock.profile,
+            _ => return None,
+        })
+    }
+}
+
 fn default_global_system_prompt() -> Option<String> {
     Some("You are a helpful AI assistant.".to_string())
 }
@@ -422,54 +822,310 @@ impl Default for Config {
             providers: ProvidersConfig::default(),
             logging: LoggingConfig::default(),
             tmux: TmuxConfig::default(),
+            libvirt: LibvirtConfig::default(),
+            metrics: MetricsConfig::default(),
             mcp: McpConfig::default(),
             keybindings: KeyBindingsConfig::default(),
             policy_file_path: None,
             ssh_config_file_path: None,
+            theme_file_path: None,
+            themes_dir: None,
+            config_file_path: None,
+            profile: None,
+            ollama_url: None,
             ollama_host: None,
             ollama_port: None,
             default_system_prompt: default_global_system_prompt(),
+            favorite_models: Vec::new(),
+        }
+    }
+}
+
+/// The profile named by `HYDRAVISOR_PROFILE`, for when `--profile` isn't given.
+pub fn profile_from_env() -> Option<String> {
+    std::env::var(PROFILE_ENV_VAR).ok().filter(|profile| !profile.trim().is_empty())
+}
+
+/// Resolves the base directory that `config.toml`, `policy.toml`, `ssh.toml`, and `theme.toml` are
+/// loaded from, before any profile.
+///
+/// Precedence: `--config-dir` CLI flag > `HYDRAVISOR_CONFIG_DIR` env var > XDG (`None`).
+fn resolve_base_config_dir(cli_override: Option<&Path>) -> Option<PathBuf> {
+    match cli_override {
+        Some(dir) => Some(dir.to_path_buf()),
+        None => std::env::var(CONFIG_DIR_ENV_VAR)
+            .ok()
+            .filter(|dir| !dir.trim().is_empty())
+            .map(|dir| PathBuf::from(shellexpand::tilde(&dir).into_owned())),
+    }
+}
+
+/// Resolves the directory config files are written to: the base directory above, or with a
+/// profile, `<base>/profiles/<name>/`, where base falls back to the XDG config dir.
+pub fn resolve_config_dir(cli_override: Option<&Path>, profile: Option<&str>) -> Result<Option<PathBuf>> {
+    let base_dir = resolve_base_config_dir(cli_override);
+    match profile {
+        Some(name) => {
+            validate_profile_name(name)?;
+            let base_dir = match base_dir {
+                Some(dir) => dir,
+                None => BaseDirectories::with_prefix(APP_NAME)?.get_config_home(),
+            };
+            Ok(Some(base_dir.join(PROFILES_DIR_NAME).join(name)))
         }
+        None => Ok(base_dir),
+    }
+}
+
+/// Finds a config file in the profile's directory, falling back to the base directory, or the
+/// XDG config dirs without one. Files missing from the base directory still resolve into it.
+fn find_config_file(xdg_dirs: &BaseDirectories, base_dir: Option<&Path>, profile_dir: Option<&Path>, file_name: &str) -> Option<PathBuf> {
+    if let Some(path) = profile_dir.map(|dir| dir.join(file_name)).filter(|path| path.exists()) {
+        return Some(path);
+    }
+    match base_dir {
+        Some(dir) => Some(dir.join(file_name)),
+        None => xdg_dirs.find_config_file(file_name),
     }
 }
 
+/// Rejects profile names that aren't a single directory name, since they become a path component.
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        anyhow::bail!("Invalid profile name '{}': must be a single directory name", name);
+    }
+    Ok(())
+}
+
 impl Config {
-    pub fn load(config_path_override: Option<&Path>) -> Result<Self> {
+    pub fn load(config_path_override: Option<&Path>, config_dir_override: Option<&Path>, profile: Option<&str>) -> Result<Self> {
         let xdg_dirs = BaseDirectories::with_prefix(APP_NAME)?;
-        let config_path = match config_path_override {
-            Some(path) => {
+        let base_dir = resolve_base_config_dir(config_dir_override);
+        let profile_dir = match profile {
+            Some(_) => resolve_config_dir(config_dir_override, profile)?,
+            None => None,
+        };
+        let find = |file_name: &str| find_config_file(&xdg_dirs, base_dir.as_deref(), profile_dir.as_deref(), file_name);
+        // Where the profile's or the overridden dir's config.toml goes, even if there isn't one yet
+        let explicit_dir = profile_dir.as_ref().or(base_dir.as_ref());
+
+        let config_path = match (config_path_override, find(DEFAULT_CONFIG_FILENAME), explicit_dir) {
+            (Some(path), _, _) => {
                 debug!("Using provided config path override: {}", path.display());
                 path.to_path_buf()
             }
-            None => xdg_dirs
-                .find_config_file(DEFAULT_CONFIG_FILENAME)
-                .with_context(|| {
-                    format!(
-                        "Could not find default config file '{}'",
-                        DEFAULT_CONFIG_FILENAME
-                    )
-                })?,
+            (None, Some(path), _) => path,
+            (None, None, Some(dir)) => dir.join(DEFAULT_CONFIG_FILENAME),
+            (None, None, None) => anyhow::bail!("Could not find default config file '{}'", DEFAULT_CONFIG_FILENAME),
         };
 
-        info!("Loading configuration from {}", config_path.display());
-        let config_str = std::fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config file at {}", config_path.display()))?;
+        let mut config: Config = if config_path_override.is_none() && explicit_dir.is_some() && !config_path.exists() {
+            // An explicit config dir or profile without a config.toml still isolates policy/ssh/save paths
+            info!("No {} in {}, using default configuration", DEFAULT_CONFIG_FILENAME, config_path.display());
+            Config::default()
+        } else {
+            info!("Loading configuration from {}", config_path.display());
+            let config_str = std::fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read config file at {}", config_path.display()))?;
+            toml::from_str(&config_str)
+                .map_err(|e| HydraError::ConfigInvalid(format!("{}: {}", config_path.display(), e)))?
+        };
 
-        let mut config: Config = toml::from_str(&config_str)
-            .with_context(|| "Failed to parse TOML configuration")?;
+        // Each file comes from the profile if it has one, then the base dir; an overridden base dir
+        // is authoritative, so files from XDG are never mixed in. Otherwise, files missing from XDG
+        // are expected beside the main config file.
+        let config_file_dir = config_path.parent().unwrap_or_else(|| Path::new(".")).to_path_buf();
+        let resolve = |file_name: &str| find(file_name).or_else(|| Some(config_file_dir.join(file_name)));
+        config.policy_file_path = resolve(DEFAULT_POLICY_FILENAME);
+        config.ssh_config_file_path = resolve(DEFAULT_SSH_CONFIG_FILENAME);
+        config.theme_file_path = resolve(DEFAULT_THEME_FILENAME);
+        config.themes_dir = resolve(DEFAULT_THEMES_DIRNAME);
 
-        // Resolve paths for other config files relative to the main config file's directory
-        let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-        config.policy_file_path = xdg_dirs
-            .find_config_file(DEFAULT_POLICY_FILENAME)
-            .or_else(|| Some(config_dir.join(DEFAULT_POLICY_FILENAME)));
-        config.ssh_config_file_path = xdg_dirs
-            .find_config_file(DEFAULT_SSH_CONFIG_FILENAME)
-            .or_else(|| Some(config_dir.join(DEFAULT_SSH_CONFIG_FILENAME)));
+        config.config_file_path = match &profile_dir {
+            // Settings changed under a profile are saved to it, never to the base config it fell back to
+            Some(dir) if config_path_override.is_none() => Some(dir.join(DEFAULT_CONFIG_FILENAME)),
+            _ => Some(config_path),
+        };
+        config.profile = profile.map(str::to_string);
 
         Ok(config)
     }
 
+    /// The config.toml that `load` would read for these overrides. Without any, this is the
+    /// XDG config file if one exists, and otherwise where `config init` would create it.
+    pub fn resolve_path(config_path_override: Option<&Path>, config_dir_override: Option<&Path>, profile: Option<&str>) -> Result<PathBuf> {
+        if let Some(path) = config_path_override {
+            return Ok(path.to_path_buf());
+        }
+        let xdg_dirs = BaseDirectories::with_prefix(APP_NAME)?;
+        let base_dir = resolve_base_config_dir(config_dir_override);
+        let profile_dir = match profile {
+            Some(_) => resolve_config_dir(config_dir_override, profile)?,
+            None => None,
+        };
+        Ok(match find_config_file(&xdg_dirs, base_dir.as_deref(), profile_dir.as_deref(), DEFAULT_CONFIG_FILENAME) {
+            Some(path) => path,
+            None => profile_dir
+                .or(base_dir)
+                .unwrap_or_else(|| xdg_dirs.get_config_home())
+                .join(DEFAULT_CONFIG_FILENAME),
+        })
+    }
+
+    /// Reads and deserializes a config file without resolving any of the paths `load` fills in.
+    /// Parse errors carry the line, column, and offending key.
+    pub fn parse_file(path: &Path) -> Result<Self> {
+        let config_str = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file at {}", path.display()))?;
+        toml::from_str(&config_str).map_err(|e| HydraError::ConfigInvalid(format!("{}: {}", path.display(), e)).into())
+    }
+
+    /// Ollama's scheme and host (the form the ollama-rs client takes) and port. `ollama_url` wins
+    /// when set; otherwise `ollama_host`/`ollama_port`, each defaulting to http://localhost:11434.
+    /// A URL without a port uses its scheme's default (80 or 443).
+    pub fn ollama_endpoint(&self) -> Result<(String, u16)> {
+        let Some(raw_url) = &self.ollama_url else {
+            return Ok((
+                self.ollama_host.clone().unwrap_or_else(|| DEFAULT_OLLAMA_HOST.to_string()),
+                self.ollama_port.unwrap_or(DEFAULT_OLLAMA_PORT),
+            ));
+        };
+        let url = url::Url::parse(raw_url).with_context(|| format!("ollama_url \"{}\" is not a valid URL", raw_url))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("ollama_url must use http:// or https://, not \"{}\"", raw_url);
+        }
+        let host = url.host_str().with_context(|| format!("ollama_url \"{}\" has no host", raw_url))?;
+        // ollama-rs appends "/api/..." straight after the port, so there is nowhere to put a path prefix
+        if url.path() != "/" {
+            anyhow::bail!(
+                "ollama_url \"{}\" has the path \"{}\", but the Ollama client (ollama-rs) always sends requests to \
+                 {}://{}:{}/api/... and can't add a path prefix. Serve Ollama at the root of its own host or port \
+                 (e.g. a separate proxy port) and point ollama_url there",
+                raw_url,
+                url.path(),
+                url.scheme(),
+                host,
+                url.port_or_known_default().unwrap_or(DEFAULT_OLLAMA_PORT)
+            );
+        }
+        if url.query().is_some() || url.fragment().is_some() {
+            anyhow::bail!("ollama_url \"{}\" can't include a query or fragment; only scheme, host and port are used", raw_url);
+        }
+        if !url.username().is_empty() || url.password().is_some() {
+            anyhow::bail!("ollama_url \"{}\" can't include credentials", raw_url);
+        }
+        let port = url.port_or_known_default().unwrap_or(DEFAULT_OLLAMA_PORT);
+        Ok((format!("{}://{}", url.scheme(), host), port))
+    }
+
+    /// Checks values that deserialize fine but that Hydravisor can't use. Returns one message per problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if !["session", "modal"].contains(&self.interface.mode.as_str()) {
+            issues.push(format!("interface.mode must be \"session\" or \"modal\", not \"{}\"", self.interface.mode));
+        }
+        if self.interface.refresh_interval_ms == 0 || self.interface.data_refresh_interval_ms == 0 {
+            issues.push("interface.refresh_interval_ms and interface.data_refresh_interval_ms must be greater than 0".to_string());
+        }
+        if self.interface.about_modal_readme_lines == 0 {
+            issues.push("interface.about_modal_readme_lines must be at least 1".to_string());
+        }
+        if !["json", "text"].contains(&self.logging.format.as_str()) {
+            issues.push(format!("logging.format must be \"json\" or \"text\", not \"{}\"", self.logging.format));
+        }
+        if self.defaults.default_cpu == 0 {
+            issues.push("defaults.default_cpu must be at least 1".to_string());
+        }
+        if !["trace", "debug", "info", "warn", "error"].contains(&self.logging.level.to_lowercase().as_str()) {
+            issues.push(format!("logging.level must be one of trace, debug, info, warn, error, not \"{}\"", self.logging.level));
+        }
+        if !["cast", "ansi", "jsonl"].contains(&self.tmux.record_format.as_str()) {
+            issues.push(format!("tmux.record_format must be \"cast\", \"ansi\", or \"jsonl\", not \"{}\"", self.tmux.record_format));
+        }
+        if self.metrics.bind_addr.parse::<std::net::SocketAddr>().is_err() {
+            issues.push(format!("metrics.bind_addr must be an address and port like \"127.0.0.1:9187\", not \"{}\"", self.metrics.bind_addr));
+        }
+        if self.libvirt.uri.trim().is_empty() {
+            issues.push("libvirt.uri must not be empty".to_string());
+        }
+        if self.libvirt.storage_pool.trim().is_empty() {
+            issues.push("libvirt.storage_pool must not be empty".to_string());
+        }
+        let default_filter = &self.providers.bedrock.filters.default;
+        if !["available_to_use", "available_to_request_access"].contains(&default_filter.as_str()) {
+            issues.push(format!(
+                "providers.bedrock.filters.default must be \"available_to_use\" or \"available_to_request_access\", not \"{}\"",
+                default_filter
+            ));
+        }
+        if let Err(e) = self.ollama_endpoint() {
+            issues.push(format!("{:#}", e));
+        }
+        let base_url = &self.providers.openai_compat.base_url;
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+            issues.push(format!("providers.openai_compat.base_url must start with http:// or https://, not \"{}\"", base_url));
+        }
+        issues
+    }
+
+    /// Where the application's own tracing logs are written, beside the instance and audit logs.
+    pub fn system_log_dir(&self) -> PathBuf {
+        self.log_dir().join("system")
+    }
+
+    /// The expanded log directory, namespaced by profile so profiles never share logs.
+    pub fn log_dir(&self) -> PathBuf {
+        let log_dir = PathBuf::from(shellexpand::tilde(&self.logging.log_dir).into_owned());
+        match &self.profile {
+            Some(profile) => log_dir.join(PROFILES_DIR_NAME).join(profile),
+            None => log_dir,
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let config_path = match &self.config_file_path {
+            Some(path) => path.clone(),
+            None => BaseDirectories::with_prefix(APP_NAME)?
+                .place_config_file(DEFAULT_CONFIG_FILENAME)
+                .with_context(|| format!("Could not create config directory for '{}'", DEFAULT_CONFIG_FILENAME))?,
+        };
+
+        let config_str = toml::to_string_pretty(self)
+            .with_context(|| "Failed to serialize configuration to TOML")?;
+        let config_str = if config_path.exists() {
+            // Edit the existing file in place so the user's comments, layout, and keys this
+            // version doesn't know about survive
+            let existing_str = std::fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read config file at {}", config_path.display()))?;
+            let mut document: DocumentMut = existing_str.parse()
+                .with_context(|| format!("Not saving: {} is not valid TOML", config_path.display()))?;
+            // What the file currently says, as this version reads it; only keys that differ from it are written
+            let baseline = toml::from_str::<Config>(&existing_str).unwrap_or_else(|e| {
+                warn!("{} has settings this version can't read ({}); comparing against the defaults instead", config_path.display(), e);
+                Config::default()
+            });
+            let old: DocumentMut = toml::to_string_pretty(&baseline)?.parse()?;
+            let new: DocumentMut = config_str.parse()?;
+            apply_changed_keys(document.as_table_mut(), old.as_table(), new.as_table());
+            document.to_string()
+        } else {
+            config_str
+        };
+        std::fs::write(&config_path, config_str)
+            .with_context(|| format!("Failed to write config file at {}", config_path.display()))?;
+
+        info!("Saved configuration to {}", config_path.display());
+        Ok(())
+    }
+
+    pub fn is_favorite_model(&self, model_name: &str) -> bool {
+        self.favorite_models.iter().any(|m| m == model_name)
+    }
+
+    pub fn get_model_params(&self, model_name: &str) -> ModelParams {
+        self.providers.ollama.model_params.get(model_name).cloned().unwrap_or_default()
+    }
+
     pub fn get_system_prompt_for_model(&self, model_name: &str) -> Option<String> {
         self.providers
             .ollama
@@ -485,5 +1141,8 @@ impl Config {
 // 2. Config file exists with partial overrides -> defaults + overrides.
 // 3. Config file exists with all values specified.
 // 4. Config file path override from CLI.
+// 4a. --config-dir flag vs HYDRAVISOR_CONFIG_DIR precedence.
+// 4b. A profile's files win over the base dir's, each falling back on its own; saves go to the profile.
 // 5. Malformed config file -> error.
-// 6. Correct resolution of policy_file_path and ssh_config_file_path. 
\ No newline at end of file
+// 6. Correct resolution of policy_file_path, ssh_config_file_path, and theme_file_path. 
+// 7. save() on an existing file rewrites only changed keys, keeping comments and unknown sections.
\ No newline at end of file
@@ -3,11 +3,28 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum HydraError {
-    // These variants are not constructed, so they are removed.
-    // #[error("Configuration error: {0}")]
-    // ConfigError(String),
-    // #[error("Policy error: {0}")]
-    // PolicyError(String),
+    // Failures callers tell apart: the TUI picks the toast and hint by these, and the MCP
+    // server the error code. Wrapped in anyhow like any other error; see `HydraError::find`.
+    #[error("Libvirt connection not available")]
+    LibvirtUnavailable,
+    #[error("No VM named or with UUID '{0}'. Run `hydravisor vm list` to see known VMs.")]
+    VmNotFound(String),
+    #[error("Denied by policy: {0}")]
+    PolicyDenied(String),
+    #[error("Ollama is unreachable: {0}")]
+    OllamaUnavailable(String),
+    #[error("Model '{0}' not found")]
+    ModelNotFound(String),
+    #[error("Ollama rejected the request: {0}")]
+    OllamaRequestFailed(String),
+    #[error("{0}")]
+    StreamError(String), // A reply stream that started but broke off
+    #[cfg(feature = "bedrock_integration")]
+    #[error("{message}")]
+    ModelAccessDenied { requestable: bool, message: String }, // A Bedrock model not enabled for the account; the message names it
+    #[error("Invalid configuration: {0}")]
+    ConfigInvalid(String),
+
     #[error("Initialization error: {component}: {message}")]
     ComponentInitError {
         component: String,
@@ -52,6 +69,45 @@ pub enum HydraError {
     Unknown(String),
 }
 
+impl HydraError {
+    /// The HydraError behind `err`, if it or anything in its context chain is one.
+    pub fn find(err: &anyhow::Error) -> Option<&HydraError> {
+        err.chain().find_map(|cause| cause.downcast_ref::<HydraError>())
+    }
+
+    /// What to check, for failures where the message alone doesn't say.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            HydraError::LibvirtUnavailable => Some("is libvirtd running, and is [libvirt] uri right?"),
+            HydraError::OllamaUnavailable(_) => Some("is `ollama serve` running, and is ollama_url right?"),
+            HydraError::ModelNotFound(_) => Some("pull it from the Ollama view first"),
+            _ => None,
+        }
+    }
+
+    /// The MCP error code, on the HTTP status scale the rest of the protocol uses.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            HydraError::VmNotFound(_) | HydraError::ModelNotFound(_) => 404,
+            HydraError::PolicyDenied(_) => 403,
+            #[cfg(feature = "bedrock_integration")]
+            HydraError::ModelAccessDenied { .. } => 403,
+            HydraError::OllamaRequestFailed(_) => 400,
+            HydraError::LibvirtUnavailable | HydraError::OllamaUnavailable(_) => 503,
+            HydraError::StreamError(_) => 502,
+            _ => 500,
+        }
+    }
+}
+
+/// `err` as shown to the user: its message, and a hint when it's a HydraError that has one.
+pub fn user_message(err: &anyhow::Error) -> String {
+    match HydraError::find(err).and_then(HydraError::hint) {
+        Some(hint) => format!("{} ({})", err, hint),
+        None => err.to_string(),
+    }
+}
+
 // Helper for converting anyhow::Error to HydraError if needed, or just use anyhow directly.
 // For now, main uses anyhow::Result, so this might be less critical immediately.
 impl From<anyhow::Error> for HydraError {
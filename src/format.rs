@@ -0,0 +1,14 @@
+// src/format.rs
+// Human-readable sizes, shared by the CLI and the TUI.
+
+/// Formats a byte count as GiB (one decimal) from 1 GiB up, otherwise as whole MiB.
+pub fn format_bytes(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    let b = bytes as f64;
+    if b >= GIB {
+        format!("{:.1} GiB", b / GIB)
+    } else {
+        format!("{:.0} MiB", b / MIB)
+    }
+}
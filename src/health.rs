@@ -0,0 +1,65 @@
+// src/health.rs
+// Retry scheduling for backend health checks. A healthy backend is checked on every data
+// refresh; one that is down is retried after a delay that doubles with each failure, so a
+// dead endpoint isn't polled (and logged) every few seconds.
+
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+// Wait before the first retry of a backend that just went down
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(5);
+// Longest wait between retries
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+pub struct HealthBackoff {
+    consecutive_failures: u32,
+    next_check: Instant,
+}
+
+impl HealthBackoff {
+    /// A schedule whose first check is due straight away.
+    pub fn new() -> Self {
+        HealthBackoff { consecutive_failures: 0, next_check: Instant::now() }
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        now >= self.next_check
+    }
+
+    /// Records the outcome of a check of `backend` and schedules the next one. Losing or
+    /// regaining the backend is logged at warn/info; further failures only at debug.
+    pub fn record_check(&mut self, backend: &str, was_healthy: bool, result: &anyhow::Result<()>, now: Instant) {
+        let error = match result {
+            Ok(()) => {
+                if !was_healthy {
+                    info!("Connected to {}.", backend);
+                }
+                self.consecutive_failures = 0;
+                self.next_check = now;
+                return;
+            }
+            Err(e) => e,
+        };
+        let delay = INITIAL_RETRY_DELAY
+            .saturating_mul(1 << self.consecutive_failures.min(16))
+            .min(MAX_RETRY_DELAY);
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.next_check = now + delay;
+        if was_healthy || self.consecutive_failures == 1 {
+            warn!("{} is unreachable ({:#}); retrying in {}s.", backend, error, delay.as_secs());
+        } else {
+            debug!("{} still unreachable after {} checks ({:#}); retrying in {}s.", backend, self.consecutive_failures, error, delay.as_secs());
+        }
+    }
+}
+
+impl Default for HealthBackoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// TODO: Add tests for HealthBackoff:
+// - Delays double from INITIAL_RETRY_DELAY and stop at MAX_RETRY_DELAY.
+// - A success resets the failure count and makes the next check due immediately.
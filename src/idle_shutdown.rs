@@ -0,0 +1,119 @@
+// src/idle_shutdown.rs
+// Stops VMs nobody is using, per `[libvirt] idle_shutdown_minutes`. A running VM counts as idle
+// while it has no attached session and its CPU stays near zero; once it has been idle for the
+// configured time it gets an ACPI shutdown, recorded in the audit ledger.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::audit::{AuditEvent, AuditEventType, RiskLevel};
+use crate::libvirt_manager::{VmState, VmStatus};
+use crate::session_manager::SessionManager;
+
+// How often running VMs are sampled
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// CPU use, in percent of all vCPUs, below which a VM is considered idle
+const IDLE_CPU_PERCENT: f64 = 2.0;
+// Labels that exempt a VM from idle shutdown, unless set to "false"
+pub const EXEMPT_LABELS: [&str; 2] = ["pinned", "no-autostop"];
+
+pub fn is_exempt(vm: &VmStatus) -> bool {
+    EXEMPT_LABELS.iter().any(|label| vm.labels.get(*label).is_some_and(|value| !value.eq_ignore_ascii_case("false")))
+}
+
+// What is known about one running VM between samples
+struct VmActivity {
+    cpu_time_ns: u64,
+    sampled_at: Instant,
+    idle_since: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct IdleTracker {
+    vms: HashMap<String, VmActivity>, // By instance id
+}
+
+impl IdleTracker {
+    /// Records a sample of every VM and returns the running ones idle for at least `threshold`.
+    /// A VM's first sample only sets a baseline, so nothing is stopped before it has been
+    /// watched for a full `threshold`.
+    pub fn observe(&mut self, vms: &[VmStatus], with_sessions: &HashSet<String>, threshold: Duration, now: Instant) -> Vec<VmStatus> {
+        let mut idle = Vec::new();
+        let mut seen = HashMap::new();
+        for vm in vms {
+            let (VmState::Running, Some(cpu_time_ns)) = (&vm.state, vm.cpu_time_ns) else { continue };
+            if is_exempt(vm) {
+                continue;
+            }
+            let previous = self.vms.remove(&vm.instance_id);
+            let busy = with_sessions.contains(&vm.name) || match &previous {
+                // A restarted domain resets its counter; treat that as activity
+                Some(prev) if cpu_time_ns >= prev.cpu_time_ns => {
+                    let elapsed_ns = now.duration_since(prev.sampled_at).as_nanos() as f64;
+                    let vcpus = vm.cpu_cores_used.unwrap_or(1).max(1) as f64;
+                    elapsed_ns > 0.0 && (cpu_time_ns - prev.cpu_time_ns) as f64 / (elapsed_ns * vcpus) * 100.0 >= IDLE_CPU_PERCENT
+                }
+                _ => true,
+            };
+            let idle_since = if busy { None } else { previous.and_then(|prev| prev.idle_since).or(Some(now)) };
+            if idle_since.is_some_and(|since| now.duration_since(since) >= threshold) {
+                idle.push(vm.clone());
+            }
+            seen.insert(vm.instance_id.clone(), VmActivity { cpu_time_ns, sampled_at: now, idle_since });
+        }
+        // VMs that stopped or vanished start over if they come back
+        self.vms = seen;
+        idle
+    }
+
+    /// Restarts a VM's idle clock, e.g. after asking it to shut down, so a guest that takes a
+    /// while to power off isn't asked again every check.
+    pub fn forget(&mut self, instance_id: &str) {
+        self.vms.remove(instance_id);
+    }
+}
+
+/// Starts the idle shutdown task, if `[libvirt] idle_shutdown_minutes` is set.
+pub fn spawn(session_manager: Arc<SessionManager>) -> Option<tokio::task::JoinHandle<()>> {
+    let idle_minutes = session_manager.app_config().libvirt.idle_shutdown_minutes;
+    if idle_minutes == 0 {
+        return None;
+    }
+    info!("Shutting down VMs idle for {} minutes (exempt with a label: {}).", idle_minutes, EXEMPT_LABELS.join(", "));
+    let threshold = Duration::from_secs(idle_minutes * 60);
+    Some(tokio::spawn(async move {
+        let mut tracker = IdleTracker::default();
+        let mut ticks = tokio::time::interval(IDLE_CHECK_INTERVAL);
+        loop {
+            ticks.tick().await;
+            let vms = match session_manager.list_vms().await {
+                Ok(vms) => vms,
+                Err(e) => {
+                    debug!("Idle shutdown check skipped; failed to list VMs: {:#}", e);
+                    continue;
+                }
+            };
+            let with_sessions = session_manager.vms_with_sessions().await;
+            for vm in tracker.observe(&vms, &with_sessions, threshold, Instant::now()) {
+                tracker.forget(&vm.instance_id);
+                match session_manager.shutdown_vm(&vm.name).await {
+                    Ok(()) => {
+                        info!("Shut down VM '{}' after {} idle minutes.", vm.name, idle_minutes);
+                        session_manager.record_audit_event(AuditEvent::new(
+                            AuditEventType::IdleShutdown { instance_id: vm.name.clone(), idle_minutes },
+                            Some(RiskLevel::Low),
+                        ));
+                    }
+                    Err(e) => warn!("Failed to shut down idle VM '{}': {:#}", vm.name, e),
+                }
+            }
+        }
+    }))
+}
+
+// TODO: Add tests for idle shutdown:
+// - A VM is reported only after staying under IDLE_CPU_PERCENT with no session for the threshold.
+// - A session, a CPU burst, or a reset CPU counter restarts the idle clock.
+// - is_exempt honours EXEMPT_LABELS and ignores labels set to "false".
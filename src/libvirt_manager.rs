@@ -4,17 +4,34 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
 
+use crate::audit::{AuditEngine, AuditEvent, AuditEventType, RiskLevel};
 use crate::config::Config;
-// use crate::errors::HydraError; // Not used yet, keep for later if specific errors are needed
+use crate::policy::PolicyEngine;
+use crate::vm_registry::ImportedVm;
+#[cfg(feature = "libvirt_integration")]
+use crate::vm_registry::VmRegistry;
+#[cfg(feature = "libvirt_integration")]
+use crate::policy::{check_network_rule, NetworkRule, VmNetwork};
+use crate::errors::HydraError;
 
 #[cfg(feature = "libvirt_integration")]
 use virt::connect::Connect;
 #[cfg(feature = "libvirt_integration")]
 use virt::domain::{Domain, DomainInfo};
 #[cfg(feature = "libvirt_integration")]
+use virt::nwfilter::NWFilter;
+#[cfg(feature = "libvirt_integration")]
+use virt::storage_pool::StoragePool;
+#[cfg(feature = "libvirt_integration")]
 use virt::sys; // Import the sys module for C constants
 
+// Where Hydravisor creates VM disk images, and the only place it will delete them from,
+// when the configured storage pool's directory can't be read
+pub const DEFAULT_IMAGES_DIR: &str = "/var/lib/libvirt/images";
+
 // Configuration for creating a new VM
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VmConfig {
@@ -24,11 +41,205 @@ pub struct VmConfig {
     pub cpu_cores: u32,
     pub memory_mb: u64,
     pub disk_gb: Option<u64>,
+    pub disk_path: Option<String>, // Defaults to `<images dir>/<instance_id>.qcow2`
     pub network_policy: String,    // Reference to a network policy name/ID
     pub security_policy: String,   // Reference to a security policy name/ID
     pub custom_script: Option<String>, // Optional bootstrap script content or path
-    pub template_name: Option<String>, // Name of the template used, if any
-    pub labels: Option<HashMap<String, String>>, // For tagging/metadata
+    pub template_name: Option<String>, // VM this one was cloned from, if any; recorded in the domain description
+    pub labels: Option<HashMap<String, String>>, // Stored in the domain's <metadata>, e.g. "pinned" to exempt it from idle shutdown
+    #[serde(default = "default_persistent")]
+    pub persistent: bool, // Defined domains survive host reboots; transient ones vanish when stopped
+    #[serde(default)]
+    pub cloud_init: Option<CloudInitConfig>, // Generates a NoCloud seed ISO attached as a CD-ROM
+    #[serde(default)]
+    pub existing_disk: ExistingDisk, // What to do if the disk image already exists
+}
+
+// How `create_vm` treats a disk image that is already at the VM's disk path
+#[derive(Serialize, Deserialize, clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExistingDisk {
+    #[default]
+    Refuse,    // Fail without touching the file
+    Reuse,     // Attach the image as it is
+    Overwrite, // Delete it and create a fresh one
+}
+
+// What `create_vm` would do with a `VmConfig`, worked out without changing anything
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VmCreationPreview {
+    pub disk_path: String,
+    pub disk_exists: bool,
+    pub disk_used_by: Vec<String>, // Other domains using the existing disk, directly or as a backing image
+    pub domain_xml: String,
+}
+
+// Provisioning applied on first boot by cloud-init; the VM name is used as the hostname
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CloudInitConfig {
+    #[serde(default)]
+    pub ssh_authorized_keys: Vec<String>,
+    #[serde(default)]
+    pub packages: Vec<String>,
+    #[serde(default)]
+    pub runcmd: Vec<String>,
+}
+
+// The `#cloud-config` user-data document written to the seed ISO
+#[derive(Serialize)]
+struct CloudInitUserData<'a> {
+    hostname: &'a str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    ssh_authorized_keys: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    packages: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    runcmd: &'a [String],
+}
+
+fn default_persistent() -> bool {
+    true
+}
+
+// Checks on user-entered VM settings, shared by the new-VM wizard and `hydravisor vm create`
+
+/// Rejects names libvirt won't take as a domain name (or that are awkward in a shell).
+pub fn check_vm_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(anyhow!("VM name is required"));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(anyhow!("'{}' is not a valid domain name; use letters, digits, '-', '_' or '.', starting with a letter or digit", name));
+    }
+    Ok(())
+}
+
+/// Checks that an ISO or source image path (`what`) exists. `~` is expanded.
+pub fn check_vm_file(what: &str, path: &str) -> Result<()> {
+    if !std::path::Path::new(shellexpand::tilde(path).as_ref()).exists() {
+        return Err(anyhow!("{} '{}' does not exist", what, path));
+    }
+    Ok(())
+}
+
+pub fn parse_vm_cpus(text: &str) -> Result<u32> {
+    text.parse::<u32>()
+        .ok()
+        .filter(|&n| n >= 1)
+        .ok_or_else(|| anyhow!("invalid vCPU count '{}'; expected a whole number of at least 1", text))
+}
+
+// Helper for parsing RAM string like "4GB" or "2048MB"
+pub fn parse_ram_str(ram_str: &str) -> Result<u64> {
+    let s = ram_str.trim().to_uppercase();
+    if let Some(num_str) = s.strip_suffix("GB") {
+        num_str
+            .trim()
+            .parse::<u64>()
+            .map(|num| num * 1024)
+            .map_err(anyhow::Error::from)
+    } else if let Some(num_str) = s.strip_suffix("MB") {
+        num_str.trim().parse::<u64>().map_err(anyhow::Error::from)
+    } else {
+        ram_str
+            .trim()
+            .parse::<u64>()
+            .map_err(anyhow::Error::from)
+    }
+}
+
+/// Parses a RAM size such as 4096, 4096MB or 4GB into megabytes.
+pub fn parse_vm_memory_mb(text: &str) -> Result<u64> {
+    parse_ram_str(text)
+        .ok()
+        .filter(|&mb| mb >= 1)
+        .ok_or_else(|| anyhow!("invalid RAM size '{}'; expected e.g. 4096, 4096MB or 4GB", text))
+}
+
+/// Parses a `key=value` label. Keys use the characters allowed in VM names.
+pub fn parse_vm_label(text: &str) -> Result<(String, String)> {
+    let (key, value) = text.split_once('=').unwrap_or((text, ""));
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+        return Err(anyhow!("invalid label '{}'; expected key=value with a key of letters, digits, '-', '_' or '.'", text));
+    }
+    Ok((key.to_string(), value.trim().to_string()))
+}
+
+pub fn parse_vm_disk_gb(text: &str) -> Result<u64> {
+    text.parse::<u64>()
+        .ok()
+        .filter(|&n| n >= 1)
+        .ok_or_else(|| anyhow!("invalid disk size '{}'; expected a whole number of GB, at least 1", text))
+}
+
+// New resources for an existing VM, from `vm resize` or the VM list's edit key; None keeps
+// a resource as it is
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct VmResourceChange {
+    pub cpus: Option<u32>,
+    pub memory_mb: Option<u64>,
+    pub disk_gb: Option<u64>,
+}
+
+impl VmResourceChange {
+    /// What the edit prompt starts with: every key, none set.
+    pub const EDIT_TEMPLATE: &'static str = "cpu= ram= disk_gb=";
+
+    /// Parses whitespace-separated `key=value` pairs; an empty value leaves the resource unchanged.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut change = VmResourceChange::default();
+        for pair in input.split_whitespace() {
+            let (key, value) = pair.split_once('=').ok_or_else(|| anyhow!("Expected key=value, got '{}'", pair))?;
+            if value.is_empty() {
+                continue;
+            }
+            match key {
+                "cpu" => change.cpus = Some(parse_vm_cpus(value)?),
+                "ram" => change.memory_mb = Some(parse_vm_memory_mb(value)?),
+                "disk_gb" => change.disk_gb = Some(parse_vm_disk_gb(value)?),
+                _ => return Err(anyhow!("Unknown resource '{}'; expected cpu, ram or disk_gb", key)),
+            }
+        }
+        Ok(change)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == VmResourceChange::default()
+    }
+
+    /// "4 vCPUs, memory 8192 MB, disk 40 GB", listing only what changes.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(cpus) = self.cpus {
+            parts.push(format!("{} vCPUs", cpus));
+        }
+        if let Some(mb) = self.memory_mb {
+            parts.push(format!("memory {} MB", mb));
+        }
+        if let Some(gb) = self.disk_gb {
+            parts.push(format!("disk {} GB", gb));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Cloud-init for VMs built from a source image: injects `defaults.ssh_public_key_path`, if set.
+pub fn default_cloud_init(config: &Config) -> Option<CloudInitConfig> {
+    let key_path = config.defaults.ssh_public_key_path.as_ref()?;
+    let key_path = shellexpand::tilde(key_path).into_owned();
+    match std::fs::read_to_string(&key_path) {
+        Ok(key) => Some(CloudInitConfig {
+            ssh_authorized_keys: vec![key.trim().to_string()],
+            ..Default::default()
+        }),
+        Err(e) => {
+            tracing::error!("Failed to read SSH public key '{}': {}", key_path, e);
+            None
+        }
+    }
 }
 
 // Represents the runtime state of a VM
@@ -37,7 +248,7 @@ pub enum VmState {
     Provisioning,
     Booting,
     Running,
-    Suspended,
+    Suspended, // Paused: vCPUs frozen, RAM still held
     Terminated,
     Stopped, // Cleanly shut down, can be restarted
     Error(String),
@@ -45,6 +256,18 @@ pub enum VmState {
     Unknown,
 }
 
+impl VmState {
+    /// The state as shown to users; paused and shut-off VMs are spelled out so they aren't confused.
+    pub fn label(&self) -> String {
+        match self {
+            VmState::Suspended => "Suspended (paused)".to_string(),
+            VmState::Stopped => "Stopped (shut off)".to_string(),
+            VmState::Error(reason) => format!("Error: {}", reason),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
 // Detailed status of a running or managed VM
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct VmStatus {
@@ -59,70 +282,542 @@ pub struct VmStatus {
     pub cpu_cores_used: Option<u32>, // Current vCPUs (from libvirt DomainInfo)
     pub memory_max_kb: Option<u64>,   // Max memory allocated (from libvirt DomainInfo)
     pub memory_used_kb: Option<u64>, // Current memory usage (from libvirt DomainInfo)
+    pub cpu_time_ns: Option<u64>, // Cumulative CPU time across all vCPUs (from libvirt DomainInfo)
+    pub disk_virtual_gb: Option<f64>,   // Size the guest sees, summed over its writable disks
+    pub disk_allocated_gb: Option<f64>, // Host storage those disks actually occupy (thin overlays use far less)
     pub error_details: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>, // From the domain's Hydravisor metadata, or its import record
+    #[serde(default)]
+    pub managed: bool, // Created by Hydravisor (see `is_hydravisor_domain`), or adopted with `vm import`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>, // Given at import. Sets the VM's resize limits and, for sessions whose agent has no role, their recording
+}
+
+// Everything `vm info` reports about one domain, from libvirt's runtime info and its XML definition
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VmDetails {
+    pub instance_id: String, // libvirt UUID
+    pub name: String,
+    pub state: VmState,
+    pub persistent: bool,
+    pub autostart: bool,
+    pub vcpus: u32,
+    pub memory_kb: u64,     // Current memory allocation
+    pub memory_max_kb: u64, // Maximum memory allocation
+    pub disks: Vec<VmDisk>,
+    pub interfaces: Vec<VmInterface>,
+    pub consoles: Vec<VmConsole>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VmDisk {
+    pub target: String,         // Guest device name, e.g. "vda"
+    pub device: String,         // "disk", "cdrom", ...
+    pub source: Option<String>, // Host path of the image, if file-backed
+    pub backing_file: Option<String>, // Backing image of a copy-on-write overlay
+    pub readonly: bool,
+    pub capacity_bytes: Option<u64>,   // Virtual size seen by the guest
+    pub allocation_bytes: Option<u64>, // Host storage occupied by the image, like `du`
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VmInterface {
+    pub mac: String,
+    pub source: Option<String>, // Network or bridge name
+    pub model: Option<String>,
+    pub ip_addresses: Vec<String>, // Leased or guest-agent reported addresses
 }
 
+// A way to reach the guest's display or console: VNC/SPICE graphics or a serial port
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VmConsole {
+    pub kind: String,              // "vnc", "spice", "serial"
+    pub listen: Option<String>,    // Graphics listen address
+    pub port: Option<String>,      // Graphics port, or "-1" when autoport hasn't allocated one
+    pub pty_path: Option<String>,  // Host pty for serial consoles
+}
+
+// Where to attach to a running VM: its VNC display and/or serial console pty
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ConsoleInfo {
+    pub vnc_listen: Option<String>,
+    pub vnc_port: Option<u16>, // None until autoport has allocated one (i.e. the VM is running)
+    pub serial_pty: Option<String>,
+}
+
+impl ConsoleInfo {
+    pub fn from_consoles(consoles: &[VmConsole]) -> Self {
+        let vnc = consoles.iter().find(|console| console.kind == "vnc");
+        ConsoleInfo {
+            vnc_listen: vnc.and_then(|console| console.listen.clone()),
+            vnc_port: vnc.and_then(|console| console.port.as_deref()?.parse().ok()),
+            serial_pty: consoles.iter()
+                .filter(|console| console.kind == "serial")
+                .find_map(|console| console.pty_path.clone()),
+        }
+    }
+
+    /// The `vnc://host:port` address of the display, once a port has been allocated.
+    pub fn vnc_url(&self) -> Option<String> {
+        let port = self.vnc_port?;
+        let host = match self.vnc_listen.as_deref() {
+            None | Some("0.0.0.0") | Some("::") => "127.0.0.1",
+            Some(listen) => listen,
+        };
+        Some(format!("vnc://{}:{}", host, port))
+    }
+}
+
+// How long a looked-up guest IP address is reused before libvirt is asked again
+#[cfg(feature = "libvirt_integration")]
+const IP_ADDRESS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(15);
+
+// Wait between reconnection attempts, doubling after each failure up to the maximum
+#[cfg(feature = "libvirt_integration")]
+const RECONNECT_BACKOFF_MIN: std::time::Duration = std::time::Duration::from_secs(2);
+#[cfg(feature = "libvirt_integration")]
+const RECONNECT_BACKOFF_MAX: std::time::Duration = std::time::Duration::from_secs(60);
+
 pub struct LibvirtManager {
+    uri: String, // From `[libvirt] uri`; also passed to `virsh` when it is run on the user's behalf
+    #[cfg(feature = "libvirt_integration")]
+    storage_pool: String, // From `[libvirt] storage_pool`
     #[cfg(feature = "libvirt_integration")]
     libvirt_conn: Option<Connect>,
     #[cfg(feature = "libvirt_integration")]
     pub libvirt_connected: bool,
+    // Guest IP addresses by domain UUID, with the time they were looked up
+    #[cfg(feature = "libvirt_integration")]
+    ip_address_cache: std::sync::Mutex<HashMap<String, (Option<String>, std::time::Instant)>>,
+    #[cfg(feature = "libvirt_integration")]
+    reconnect_delay: std::time::Duration,
+    #[cfg(feature = "libvirt_integration")]
+    next_reconnect_at: Option<std::time::Instant>, // Reconnection is skipped until then
+    // Sidecar records of domains adopted with `vm import`; None if the data dir is unusable
+    #[cfg(feature = "libvirt_integration")]
+    vm_registry: Option<VmRegistry>,
+    audit_engine: Arc<AuditEngine>,
+    policy_engine: Arc<PolicyEngine>, // Resolves each VM's `network_policy`
 }
 
 impl LibvirtManager {
-    pub fn new(_app_config: &Config) -> Result<Self> {
+    pub fn new(app_config: &Config, audit_engine: Arc<AuditEngine>, policy_engine: Arc<PolicyEngine>) -> Result<Self> {
+        let uri = app_config.libvirt.uri.clone();
         #[cfg(feature = "libvirt_integration")]
-        let (libvirt_conn, libvirt_connected) = match Connect::open(Some("qemu:///system")) {
+        let (libvirt_conn, libvirt_connected) = match Connect::open(Some(&uri)) {
             Ok(conn) => (Some(conn), true),
-            Err(_e) => (None, false),
+            Err(e) => {
+                tracing::warn!("Could not connect to libvirt at '{}': {}", uri, e);
+                (None, false)
+            }
         };
         #[cfg(not(feature = "libvirt_integration"))]
         let (_libvirt_conn, libvirt_connected): (Option<()>, bool) = (None, false);
+        #[cfg(feature = "libvirt_integration")]
+        let vm_registry = VmRegistry::new(app_config.profile.as_deref())
+            .map_err(|e| tracing::warn!("Imported VM records are unavailable: {}", e))
+            .ok();
 
         Ok(LibvirtManager {
+            uri,
+            #[cfg(feature = "libvirt_integration")]
+            storage_pool: app_config.libvirt.storage_pool.clone(),
             #[cfg(feature = "libvirt_integration")]
             libvirt_conn,
             #[cfg(feature = "libvirt_integration")]
             libvirt_connected,
+            #[cfg(feature = "libvirt_integration")]
+            ip_address_cache: std::sync::Mutex::new(HashMap::new()),
+            #[cfg(feature = "libvirt_integration")]
+            reconnect_delay: RECONNECT_BACKOFF_MIN,
+            #[cfg(feature = "libvirt_integration")]
+            next_reconnect_at: None,
+            #[cfg(feature = "libvirt_integration")]
+            vm_registry,
+            audit_engine,
+            policy_engine,
         })
     }
 
+    // Lifecycle changes must not fail because the ledger couldn't be written, so errors are only logged
+    fn record_audit_event(&self, event_type: AuditEventType, risk_level: RiskLevel) {
+        if let Err(e) = self.audit_engine.record_event(AuditEvent::new(event_type, Some(risk_level))) {
+            tracing::error!("Failed to record VM lifecycle event in the audit ledger: {}", e);
+        }
+    }
+
     #[cfg(feature = "libvirt_integration")]
     pub fn is_libvirt_connected(&self) -> bool {
         self.libvirt_connected
     }
 
+    /// Opens a new connection when there is none or the current one has died (e.g. libvirtd
+    /// restarted or the SSH tunnel to a remote host dropped), updating `libvirt_connected`.
+    /// Failed attempts back off exponentially so a down daemon isn't retried every refresh.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn reconnect_if_needed(&mut self) {
+        if let Some(conn) = &mut self.libvirt_conn {
+            if conn.is_alive().unwrap_or(false) {
+                self.libvirt_connected = true;
+                return;
+            }
+            tracing::warn!("Lost connection to libvirt at '{}'.", self.uri);
+            let _ = conn.close();
+            self.libvirt_conn = None;
+            self.libvirt_connected = false;
+        }
+        let now = std::time::Instant::now();
+        if self.next_reconnect_at.map_or(false, |at| now < at) {
+            return;
+        }
+        match Connect::open(Some(&self.uri)) {
+            Ok(conn) => {
+                tracing::info!("Connected to libvirt at '{}'.", self.uri);
+                self.libvirt_conn = Some(conn);
+                self.libvirt_connected = true;
+                self.reconnect_delay = RECONNECT_BACKOFF_MIN;
+                self.next_reconnect_at = None;
+            }
+            Err(e) => {
+                tracing::debug!(
+                    "Reconnecting to libvirt at '{}' failed: {}. Retrying in {:?}.",
+                    self.uri, e, self.reconnect_delay
+                );
+                self.libvirt_connected = false;
+                self.next_reconnect_at = Some(now + self.reconnect_delay);
+                self.reconnect_delay = (self.reconnect_delay * 2).min(RECONNECT_BACKOFF_MAX);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn reconnect_if_needed(&mut self) {}
+
+    /// The directory disk images are created in and the only one they are deleted from: the
+    /// target path of the configured storage pool, or `DEFAULT_IMAGES_DIR` if it can't be read.
+    #[cfg(feature = "libvirt_integration")]
+    fn images_dir(&self) -> String {
+        let Some(conn) = &self.libvirt_conn else {
+            return DEFAULT_IMAGES_DIR.to_string();
+        };
+        let target_path = StoragePool::lookup_by_name(conn, &self.storage_pool)
+            .and_then(|pool| pool.get_xml_desc(0))
+            .map(|xml| storage_pool_target_path(&xml));
+        match target_path {
+            Ok(Some(path)) => path,
+            Ok(None) => {
+                tracing::warn!("Storage pool '{}' has no target path; using {}.", self.storage_pool, DEFAULT_IMAGES_DIR);
+                DEFAULT_IMAGES_DIR.to_string()
+            }
+            Err(e) => {
+                tracing::warn!("Could not read storage pool '{}': {}. Using {}.", self.storage_pool, e, DEFAULT_IMAGES_DIR);
+                DEFAULT_IMAGES_DIR.to_string()
+            }
+        }
+    }
+
+    /// Asks libvirt to rescan the storage pool so volumes created or deleted behind its back
+    /// show up in `virsh vol-list`. Failures are only logged.
+    #[cfg(feature = "libvirt_integration")]
+    fn refresh_storage_pool(&self) {
+        let Some(conn) = &self.libvirt_conn else { return };
+        let result = StoragePool::lookup_by_name(conn, &self.storage_pool)
+            .and_then(|pool| if pool.is_active()? { pool.refresh(0).map(|_| ()) } else { Ok(()) });
+        if let Err(e) = result {
+            tracing::debug!("Could not refresh storage pool '{}': {}", self.storage_pool, e);
+        }
+    }
+
     #[cfg(not(feature = "libvirt_integration"))]
     pub fn is_libvirt_connected(&self) -> bool {
         false
     }
 
-    pub fn destroy_vm(&self, instance_id: &str) -> Result<()> {
+    /// Forces a VM off and removes it. With `delete_disks`, the writable disk images it used
+    /// under the images directory are deleted as well; CD-ROMs and read-only disks are left alone.
+    pub fn destroy_vm(&self, instance_id: &str, delete_disks: bool) -> Result<()> {
         #[cfg(feature = "libvirt_integration")]
         {
             if let Some(conn) = &self.libvirt_conn {
                 if let Ok(domain) = Domain::lookup_by_name(conn, instance_id) {
+                    // Read the disk list before the domain (and its XML) goes away
+                    let disk_paths = if delete_disks {
+                        let xml = domain.get_xml_desc(0)
+                            .map_err(|e| anyhow!("Failed to read definition of VM '{}': {}", instance_id, e))?;
+                        writable_disk_sources(&xml)
+                    } else {
+                        Vec::new()
+                    };
+
+                    let uuid = domain.get_uuid_string().ok();
+
                     if domain.is_active()? {
                         domain.destroy()?;
                     }
-                    domain.undefine()?;
-                    // TODO: Delete the associated disk image from /var/lib/libvirt/images/
+                    // Transient domains disappear once destroyed and cannot be undefined
+                    if domain.is_persistent()? {
+                        domain.undefine()?;
+                    }
+                    if let (Some(registry), Some(uuid)) = (&self.vm_registry, uuid) {
+                        if let Err(e) = registry.remove(&uuid) {
+                            tracing::warn!("VM '{}' was destroyed but its import record remains: {}", instance_id, e);
+                        }
+                    }
+
+                    let images_dir = self.images_dir();
+                    for path in disk_paths {
+                        // Linked clones read from their source's disk; deleting it would break them
+                        match self.domains_using_disk(&path) {
+                            Ok(users) if users.is_empty() => remove_disk_image(instance_id, &path, &images_dir),
+                            Ok(users) => tracing::warn!(
+                                "Keeping disk image '{}' of VM '{}': VM '{}' still uses it.",
+                                path, instance_id, users.join("', '")
+                            ),
+                            Err(e) => tracing::warn!("Keeping disk image '{}' of VM '{}': cannot tell whether other VMs use it: {}", path, instance_id, e),
+                        }
+                    }
+                    // The cloud-init seed is a CD-ROM, but one Hydravisor generated for this VM
+                    let seed_iso_path = cloud_init_seed_path(&images_dir, instance_id);
+                    if delete_disks && std::path::Path::new(&seed_iso_path).exists() {
+                        remove_disk_image(instance_id, &seed_iso_path, &images_dir);
+                    }
+                    if delete_disks {
+                        self.refresh_storage_pool();
+                    }
+                    self.record_audit_event(
+                        AuditEventType::InstanceDeleted { instance_id: instance_id.to_string() },
+                        RiskLevel::Medium,
+                    );
                     return Ok(());
                 } else {
-                    return Err(anyhow!("VM with instance_id '{}' not found.", instance_id));
+                    return Err(HydraError::VmNotFound(instance_id.to_string()).into());
                 }
             }
         }
-        Err(anyhow!(
-            "Libvirt not available. Cannot destroy VM."
-        ))
+        #[cfg(not(feature = "libvirt_integration"))]
+        let _ = (instance_id, delete_disks);
+        Err(HydraError::LibvirtUnavailable.into())
     }
 
-    pub fn resume_vm(&self, _instance_id: &str) -> Result<()> {
-        todo!("Implement VM resuming.");
+    /// Brings a domain defined outside Hydravisor under its management, recording `role` and
+    /// `labels` in a sidecar keyed by UUID. Importing again replaces both.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn import_vm(&self, id: &str, role: Option<&str>, labels: HashMap<String, String>) -> Result<ImportedVm> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let registry = self.vm_registry.as_ref()
+            .ok_or_else(|| anyhow!("Cannot import VM '{}': the data directory is unavailable.", id))?;
+        let domain = Domain::lookup_by_name(conn, id)
+            .or_else(|_| Domain::lookup_by_uuid_string(conn, id))
+            .map_err(|_| HydraError::VmNotFound(id.to_string()))?;
+        let xml = domain.get_xml_desc(0)
+            .map_err(|e| anyhow!("Failed to read definition of VM '{}': {}", id, e))?;
+        if has_hydravisor_metadata(&xml) {
+            return Err(anyhow!("VM '{}' was created by Hydravisor and is already managed.", id));
+        }
+        if let Some(role) = role {
+            if !self.policy_engine.config.roles.contains_key(role) {
+                return Err(anyhow!("Unknown role '{}'. Roles are defined under [roles] in policy.toml.", role));
+            }
+        }
+
+        let imported = ImportedVm {
+            uuid: domain.get_uuid_string().map_err(|e| anyhow!("Failed to read UUID of VM '{}': {}", id, e))?,
+            name: domain.get_name().unwrap_or_else(|_| id.to_string()),
+            imported_at: chrono::Utc::now().to_rfc3339(),
+            role: role.map(str::to_string),
+            labels,
+        };
+        registry.save(&imported)?;
+        self.record_audit_event(
+            AuditEventType::InstanceImported {
+                instance_id: imported.uuid.clone(),
+                name: imported.name.clone(),
+                role: imported.role.clone(),
+            },
+            RiskLevel::Medium,
+        );
+        Ok(imported)
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn import_vm(&self, _id: &str, _role: Option<&str>, _labels: HashMap<String, String>) -> Result<ImportedVm> {
+        Err(anyhow!("Cannot import VM: libvirt_integration feature is disabled."))
+    }
+
+    /// Stops tracking an imported domain without touching the domain itself. `id` may also be
+    /// the UUID of a domain that has since been undefined outside Hydravisor.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn forget_vm(&self, id: &str) -> Result<()> {
+        let registry = self.vm_registry.as_ref()
+            .ok_or_else(|| anyhow!("Cannot forget VM '{}': the data directory is unavailable.", id))?;
+        let uuid = self.libvirt_conn.as_ref()
+            .and_then(|conn| Domain::lookup_by_name(conn, id).or_else(|_| Domain::lookup_by_uuid_string(conn, id)).ok())
+            .and_then(|domain| domain.get_uuid_string().ok())
+            .unwrap_or_else(|| id.to_string());
+        if !registry.remove(&uuid)? {
+            return Err(anyhow!("VM '{}' was not imported, so there is nothing to forget.", id));
+        }
+        self.record_audit_event(AuditEventType::InstanceForgotten { instance_id: uuid }, RiskLevel::Low);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn forget_vm(&self, _id: &str) -> Result<()> {
+        Err(anyhow!("Cannot forget VM: libvirt_integration feature is disabled."))
+    }
+
+    /// Starts a VM: resumes it if paused, or boots it if shut off. Returns true if it was resumed.
+    pub fn resume_vm(&self, instance_id: &str) -> Result<bool> {
+        #[cfg(feature = "libvirt_integration")]
+        {
+            if let Some(conn) = &self.libvirt_conn {
+                let domain = Domain::lookup_by_name(conn, instance_id)
+                    .map_err(|_| HydraError::VmNotFound(instance_id.to_string()))?;
+                let (state, _reason) = domain.get_state()
+                    .map_err(|e| anyhow!("Failed to read state of VM '{}': {}", instance_id, e))?;
+                let resumed = match state {
+                    sys::VIR_DOMAIN_PAUSED | sys::VIR_DOMAIN_PMSUSPENDED => {
+                        domain.resume()
+                            .map_err(|e| anyhow!("Failed to resume VM '{}': {}", instance_id, e))?;
+                        true
+                    }
+                    sys::VIR_DOMAIN_SHUTOFF | sys::VIR_DOMAIN_CRASHED => {
+                        domain.create()
+                            .map_err(|e| anyhow!("Failed to start VM '{}': {}", instance_id, e))?;
+                        false
+                    }
+                    sys::VIR_DOMAIN_RUNNING | sys::VIR_DOMAIN_BLOCKED => {
+                        return Err(anyhow!("VM '{}' is already running.", instance_id));
+                    }
+                    _ => {
+                        return Err(anyhow!(
+                            "VM '{}' is in state '{:?}' and cannot be started.",
+                            instance_id,
+                            self.map_libvirt_state_to_vm_state(state)
+                        ));
+                    }
+                };
+                self.record_audit_event(
+                    AuditEventType::GenericMessage { message: format!("VM '{}' {}", instance_id, if resumed { "resumed" } else { "started" }), level: RiskLevel::Info },
+                    RiskLevel::Info,
+                );
+                return Ok(resumed);
+            }
+        }
+        #[cfg(not(feature = "libvirt_integration"))]
+        let _ = instance_id;
+        Err(HydraError::LibvirtUnavailable.into())
+    }
+
+    /// Pauses a running VM. Its memory stays allocated, so `resume_vm` picks up exactly where it left off.
+    pub fn suspend_vm(&self, instance_id: &str) -> Result<()> {
+        #[cfg(feature = "libvirt_integration")]
+        {
+            if let Some(conn) = &self.libvirt_conn {
+                let domain = Domain::lookup_by_name(conn, instance_id)
+                    .map_err(|_| HydraError::VmNotFound(instance_id.to_string()))?;
+                let (state, _reason) = domain.get_state()
+                    .map_err(|e| anyhow!("Failed to read state of VM '{}': {}", instance_id, e))?;
+                match state {
+                    sys::VIR_DOMAIN_RUNNING | sys::VIR_DOMAIN_BLOCKED => {}
+                    sys::VIR_DOMAIN_PAUSED => {
+                        return Err(anyhow!("VM '{}' is already suspended.", instance_id));
+                    }
+                    _ => return Err(anyhow!("VM '{}' is not running.", instance_id)),
+                }
+                domain.suspend()
+                    .map_err(|e| anyhow!("Failed to suspend VM '{}': {}", instance_id, e))?;
+                self.record_audit_event(
+                    AuditEventType::GenericMessage { message: format!("VM '{}' suspended", instance_id), level: RiskLevel::Info },
+                    RiskLevel::Info,
+                );
+                return Ok(());
+            }
+        }
+        #[cfg(not(feature = "libvirt_integration"))]
+        let _ = instance_id;
+        Err(HydraError::LibvirtUnavailable.into())
     }
     
-    // TODO: Add other lifecycle methods like stop, start, restart as needed.
+    /// Asks the guest to power off via ACPI and returns without waiting for it to stop.
+    pub fn shutdown_vm(&self, instance_id: &str) -> Result<()> {
+        #[cfg(feature = "libvirt_integration")]
+        {
+            if let Some(conn) = &self.libvirt_conn {
+                let domain = Domain::lookup_by_name(conn, instance_id)
+                    .map_err(|_| HydraError::VmNotFound(instance_id.to_string()))?;
+                if !domain.is_active()? {
+                    return Err(anyhow!("VM '{}' is not running.", instance_id));
+                }
+                domain.shutdown()
+                    .map_err(|e| anyhow!("Failed to shut down VM '{}': {}", instance_id, e))?;
+                self.record_audit_event(
+                    AuditEventType::GenericMessage { message: format!("VM '{}' shutdown requested", instance_id), level: RiskLevel::Info },
+                    RiskLevel::Info,
+                );
+                return Ok(());
+            }
+        }
+        #[cfg(not(feature = "libvirt_integration"))]
+        let _ = instance_id;
+        Err(HydraError::LibvirtUnavailable.into())
+    }
+
+    /// Requests an ACPI shutdown and waits up to `timeout` for the VM to power off,
+    /// forcing it off with `destroy()` if it is still running afterwards.
+    /// Blocks the calling thread while polling.
+    #[allow(dead_code)] // The TUI uses the non-blocking `shutdown_vm`
+    pub fn shutdown_timeout_vm(&self, instance_id: &str, timeout: std::time::Duration) -> Result<()> {
+        self.shutdown_vm(instance_id)?;
+        #[cfg(feature = "libvirt_integration")]
+        {
+            if let Some(conn) = &self.libvirt_conn {
+                let domain = Domain::lookup_by_name(conn, instance_id)
+                    .map_err(|_| HydraError::VmNotFound(instance_id.to_string()))?;
+                let deadline = std::time::Instant::now() + timeout;
+                while std::time::Instant::now() < deadline {
+                    if !domain.is_active()? {
+                        return Ok(());
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                }
+                if domain.is_active()? {
+                    tracing::warn!("VM '{}' did not shut down within {:?}; forcing power off.", instance_id, timeout);
+                    domain.destroy()
+                        .map_err(|e| anyhow!("Failed to force off VM '{}': {}", instance_id, e))?;
+                }
+                return Ok(());
+            }
+        }
+        Err(HydraError::LibvirtUnavailable.into())
+    }
+
+    // TODO: Add other lifecycle methods like restart as needed.
+
+    /// Runs `list_vms` on the blocking thread pool so per-domain libvirt RPCs never stall the
+    /// async executor. A dead connection is reopened first, and whether libvirt is connected
+    /// is returned alongside the listing.
+    pub async fn list_vms_async(manager: Arc<Mutex<LibvirtManager>>) -> Result<(bool, Vec<VmStatus>)> {
+        tokio::task::spawn_blocking(move || {
+            let mut manager = manager.blocking_lock();
+            manager.reconnect_if_needed();
+            if !manager.is_libvirt_connected() {
+                return Ok((false, Vec::new()));
+            }
+            match manager.list_vms() {
+                Ok(vms) => Ok((true, vms)),
+                Err(e) => {
+                    // A failed RPC is often the first sign the connection died; check again
+                    // so the next refresh starts reconnecting instead of failing the same way
+                    manager.reconnect_if_needed();
+                    if manager.is_libvirt_connected() { Err(e) } else { Ok((false, Vec::new())) }
+                }
+            }
+        })
+        .await?
+    }
 
     pub fn list_vms(&self) -> Result<Vec<VmStatus>> {
         #[cfg(feature = "libvirt_integration")]
@@ -130,12 +825,12 @@ impl LibvirtManager {
             if let Some(conn) = &self.libvirt_conn {
                 let mut vms = Vec::new();
                 let mut domain_names = Vec::new();
-                if let Ok(active_domain_ids) = conn.list_domains() {
-                    for id in active_domain_ids {
-                        if let Ok(domain) = Domain::lookup_by_id(&conn, id) {
-                            if let Ok(name) = domain.get_name() {
-                                domain_names.push(name);
-                            }
+                let active_domain_ids = conn.list_domains()
+                    .map_err(|e| anyhow!("Failed to list running domains: {}", e))?;
+                for id in active_domain_ids {
+                    if let Ok(domain) = Domain::lookup_by_id(&conn, id) {
+                        if let Ok(name) = domain.get_name() {
+                            domain_names.push(name);
                         }
                     }
                 }
@@ -144,23 +839,51 @@ impl LibvirtManager {
                 }
                 domain_names.sort_unstable();
                 domain_names.dedup();
+                let imported = self.vm_registry.as_ref().map(VmRegistry::all).unwrap_or_default();
 
                 for name in domain_names {
                     if let Ok(domain) = Domain::lookup_by_name(&conn, &name) {
                         let state_info: DomainInfo = domain.get_info()?;
                         let hydra_state = self.map_libvirt_state_to_vm_state(state_info.state);
+                        let instance_id = domain.get_uuid_string().unwrap_or_else(|_| "N/A-UUID".to_string());
+                        let ip_address = if state_info.state == sys::VIR_DOMAIN_RUNNING {
+                            self.cached_ip_address(&domain, &instance_id)
+                        } else {
+                            None
+                        };
+                        let xml = domain.get_xml_desc(0).unwrap_or_default();
+                        let mut disks = parse_disks(&xml);
+                        read_disk_sizes(&domain, &mut disks);
+                        let (disk_virtual_gb, disk_allocated_gb) = disk_totals_gb(&disks);
+                        let import = imported.get(&instance_id);
+                        let mut labels = parse_labels(&xml);
+                        if let Some(import) = import {
+                            labels.extend(import.labels.clone());
+                        }
                         let status = VmStatus {
-                            instance_id: domain.get_uuid_string().unwrap_or_else(|_| "N/A-UUID".to_string()),
+                            instance_id,
                             name: name.clone(),
                             state: hydra_state,
+                            ip_address,
                             memory_max_kb: Some(state_info.max_mem as u64),
                             memory_used_kb: Some(state_info.memory as u64),
                             cpu_cores_used: Some(state_info.nr_virt_cpu as u32),
+                            cpu_time_ns: Some(state_info.cpu_time),
+                            disk_virtual_gb,
+                            disk_allocated_gb,
+                            labels,
+                            managed: is_hydravisor_domain(&xml, &name) || import.is_some(),
+                            role: import.and_then(|import| import.role.clone()),
                             ..Default::default()
                         };
                         vms.push(status);
                     }
                 }
+                // Forget addresses of domains that no longer exist
+                self.ip_address_cache
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .retain(|id, _| vms.iter().any(|vm| &vm.instance_id == id));
                 return Ok(vms);
             } else {
                 #[cfg(feature = "dummy_env_data")]
@@ -187,6 +910,84 @@ impl LibvirtManager {
         }
     }
     
+    /// Looks a domain up by name or UUID and collects its runtime info, disks, network
+    /// interfaces, and consoles.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn get_vm_details(&self, id: &str) -> Result<VmDetails> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let domain = Domain::lookup_by_name(conn, id)
+            .or_else(|_| Domain::lookup_by_uuid_string(conn, id))
+            .map_err(|_| HydraError::VmNotFound(id.to_string()))?;
+
+        let info = domain.get_info()
+            .map_err(|e| anyhow!("Failed to read info for VM '{}': {}", id, e))?;
+        let xml = domain.get_xml_desc(0)
+            .map_err(|e| anyhow!("Failed to read definition of VM '{}': {}", id, e))?;
+
+        let mut interfaces = parse_interfaces(&xml);
+        if info.state == sys::VIR_DOMAIN_RUNNING {
+            for source in [sys::VIR_DOMAIN_INTERFACE_ADDRESSES_SRC_LEASE, sys::VIR_DOMAIN_INTERFACE_ADDRESSES_SRC_AGENT] {
+                let Ok(addresses) = domain.interface_addresses(source, 0) else { continue };
+                for address in addresses {
+                    let Some(iface) = interfaces.iter_mut().find(|i| i.mac.eq_ignore_ascii_case(&address.hwaddr)) else { continue };
+                    for ip in address.addrs {
+                        if !iface.ip_addresses.contains(&ip.addr) {
+                            iface.ip_addresses.push(ip.addr);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut disks = parse_disks(&xml);
+        read_disk_sizes(&domain, &mut disks);
+
+        Ok(VmDetails {
+            instance_id: domain.get_uuid_string()?,
+            name: domain.get_name()?,
+            state: self.map_libvirt_state_to_vm_state(info.state),
+            persistent: domain.is_persistent().unwrap_or(false),
+            autostart: domain.get_autostart().unwrap_or(false),
+            vcpus: info.nr_virt_cpu,
+            memory_kb: info.memory,
+            memory_max_kb: info.max_mem,
+            disks,
+            interfaces,
+            consoles: parse_consoles(&xml),
+        })
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn get_vm_details(&self, _id: &str) -> Result<VmDetails> {
+        Err(anyhow!("Cannot show VM details: libvirt_integration feature is disabled."))
+    }
+
+    /// Reads the VNC port and serial pty that libvirt allocated to a running VM from its live XML.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn get_console_info(&self, id: &str) -> Result<ConsoleInfo> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let domain = Domain::lookup_by_name(conn, id)
+            .or_else(|_| Domain::lookup_by_uuid_string(conn, id))
+            .map_err(|_| HydraError::VmNotFound(id.to_string()))?;
+        if !domain.is_active()? {
+            return Err(anyhow!("VM '{}' is not running; consoles are only allocated while it runs.", id));
+        }
+        let xml = domain.get_xml_desc(0)
+            .map_err(|e| anyhow!("Failed to read definition of VM '{}': {}", id, e))?;
+        let info = ConsoleInfo::from_consoles(&parse_consoles(&xml));
+        if info.vnc_port.is_none() && info.serial_pty.is_none() {
+            return Err(anyhow!("VM '{}' has no VNC display or serial console.", id));
+        }
+        Ok(info)
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn get_console_info(&self, _id: &str) -> Result<ConsoleInfo> {
+        Err(anyhow!("Cannot open VM console: libvirt_integration feature is disabled."))
+    }
+
     fn list_vms_placeholder(&self) -> Result<Vec<VmStatus>> {
         Ok(vec![
             VmStatus {
@@ -210,11 +1011,120 @@ impl LibvirtManager {
         ])
     }
     
+    /// Works out the disk path and domain XML `create_vm` would use for `vm_config`, and whether
+    /// that disk already exists (and which other VMs use it), without creating anything.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn preview_vm(&self, vm_config: &VmConfig) -> Result<VmCreationPreview> {
+        if self.libvirt_conn.is_none() {
+            return Err(HydraError::LibvirtUnavailable.into());
+        }
+        let images_dir = self.images_dir();
+        let disk_path = vm_disk_path(vm_config, &images_dir);
+        let seed_iso_path = vm_config.cloud_init.as_ref().map(|_| cloud_init_seed_path(&images_dir, &vm_config.instance_id));
+        let disk_exists = std::path::Path::new(&disk_path).exists();
+        let network = self.policy_engine.vm_network(&vm_config.network_policy)?;
+        if let VmNetwork::Filtered { policy, rules } = &network {
+            network_filter_xml(policy, rules)?; // Surface bad rules now rather than at creation
+        }
+        Ok(VmCreationPreview {
+            disk_used_by: if disk_exists { self.domains_using_disk(&disk_path)? } else { Vec::new() },
+            domain_xml: self.create_vm_xml(
+                &vm_config.instance_id,
+                vm_config.cpu_cores,
+                vm_config.memory_mb,
+                &disk_path,
+                vm_config.boot_iso.as_deref(),
+                seed_iso_path.as_deref(),
+                vm_config.template_name.as_deref(),
+                vm_config.labels.as_ref(),
+                &network,
+            ),
+            disk_path,
+            disk_exists,
+        })
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn preview_vm(&self, _vm_config: &VmConfig) -> Result<VmCreationPreview> {
+        Err(anyhow!("Cannot preview VM: libvirt_integration feature is disabled."))
+    }
+
+    /// Names of the defined or running domains that use `disk_path`: as one of their writable
+    /// disks, or anywhere in the backing chain of one (a linked clone's source disk, say).
+    #[cfg(feature = "libvirt_integration")]
+    fn domains_using_disk(&self, disk_path: &str) -> Result<Vec<String>> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let domains = conn.list_all_domains(0)
+            .map_err(|e| anyhow!("Failed to list domains: {}", e))?;
+        Ok(domains
+            .iter()
+            .filter(|domain| {
+                domain.get_xml_desc(0).map_or(false, |xml| disk_references(&xml).iter().any(|path| path == disk_path))
+            })
+            .filter_map(|domain| domain.get_name().ok())
+            .collect())
+    }
+
+    /// Creates the VM's disk (unless an existing one is reused, per `existing_disk`) and boots it.
     #[cfg(feature = "libvirt_integration")]
     pub fn create_vm(&self, vm_config: &VmConfig) -> Result<VmStatus> {
         if let Some(conn) = &self.libvirt_conn {
             let vm_name = vm_config.instance_id.clone();
-            let disk_path = format!("/var/lib/libvirt/images/{}.qcow2", vm_name);
+            let images_dir = self.images_dir();
+            let disk_path = vm_disk_path(vm_config, &images_dir);
+
+            // The network is settled first, so a bad policy fails before any disk is touched
+            let network = self.policy_engine.vm_network(&vm_config.network_policy)?;
+            if let VmNetwork::Filtered { policy, rules } = &network {
+                let filter_xml = network_filter_xml(policy, rules)?;
+                NWFilter::define_xml(conn, &filter_xml)
+                    .map_err(|e| anyhow!("Failed to define network filter for policy '{}': {}", policy, e))?;
+            }
+
+            // Only a disk made here is cleaned up if the VM can't be created
+            let created_disk = if std::path::Path::new(&disk_path).exists() {
+                if vm_config.existing_disk != ExistingDisk::Refuse {
+                    let users = self.domains_using_disk(&disk_path)?;
+                    if !users.is_empty() {
+                        return Err(anyhow!("Disk image '{}' is used by VM '{}'; refusing to share or overwrite it.", disk_path, users.join("', '")));
+                    }
+                }
+                match vm_config.existing_disk {
+                    ExistingDisk::Refuse => {
+                        return Err(anyhow!("Disk image '{}' already exists. Choose another disk path, or reuse or overwrite it.", disk_path));
+                    }
+                    ExistingDisk::Reuse => {
+                        tracing::info!("Reusing existing disk image '{}' for VM '{}'.", disk_path, vm_name);
+                        false
+                    }
+                    ExistingDisk::Overwrite => {
+                        std::fs::remove_file(&disk_path)
+                            .map_err(|e| anyhow!("Failed to remove existing disk image '{}': {}", disk_path, e))?;
+                        tracing::warn!("Deleted existing disk image '{}' to overwrite it for VM '{}'.", disk_path, vm_name);
+                        create_disk_image(&disk_path, &vm_config.base_image, vm_config.disk_gb)?;
+                        true
+                    }
+                }
+            } else {
+                create_disk_image(&disk_path, &vm_config.base_image, vm_config.disk_gb)?;
+                true
+            };
+            let created_disk_path = Some(&disk_path).filter(|_| created_disk);
+
+            let seed_iso_path = match &vm_config.cloud_init {
+                Some(cloud_init) => {
+                    let seed_iso_path = cloud_init_seed_path(&images_dir, &vm_name);
+                    if let Err(e) = create_cloud_init_seed(&seed_iso_path, &vm_name, cloud_init) {
+                        if let Some(path) = created_disk_path {
+                            let _ = std::fs::remove_file(path);
+                        }
+                        return Err(e);
+                    }
+                    Some(seed_iso_path)
+                }
+                None => None,
+            };
 
             let xml = self.create_vm_xml(
                 &vm_name,
@@ -222,18 +1132,51 @@ impl LibvirtManager {
                 vm_config.memory_mb,
                 &disk_path,
                 vm_config.boot_iso.as_deref(),
+                seed_iso_path.as_deref(),
+                vm_config.template_name.as_deref(),
+                vm_config.labels.as_ref(),
+                &network,
             );
             
-            let domain = Domain::create_xml(conn, &xml, 0)?;
+            let domain_result = if vm_config.persistent {
+                Domain::define_xml(conn, &xml).and_then(|domain| {
+                    if let Err(e) = domain.create() {
+                        // Don't keep a definition for a VM that failed to boot
+                        let _ = domain.undefine();
+                        return Err(e);
+                    }
+                    Ok(domain)
+                })
+            } else {
+                Domain::create_xml(conn, &xml, 0)
+            };
+            let domain = match domain_result {
+                Ok(domain) => domain,
+                Err(e) => {
+                    // Don't leave an orphaned disk behind for a VM that never existed
+                    for path in created_disk_path.into_iter().chain(seed_iso_path.as_ref()) {
+                        if let Err(remove_err) = std::fs::remove_file(path) {
+                            tracing::warn!("Failed to clean up disk image '{}': {}", path, remove_err);
+                        }
+                    }
+                    return Err(anyhow!("Failed to create VM '{}': {}", vm_name, e));
+                }
+            };
             
-            Ok(VmStatus {
+            self.refresh_storage_pool();
+            let status = VmStatus {
                 instance_id: domain.get_uuid_string()?,
                 name: domain.get_name()?,
                 state: VmState::Provisioning,
                 ..Default::default()
-            })
+            };
+            self.record_audit_event(
+                AuditEventType::InstanceCreated { instance_id: status.name.clone(), instance_type: "VM".to_string() },
+                RiskLevel::Low,
+            );
+            Ok(status)
         } else {
-            Err(anyhow!("Libvirt connection not available"))
+            Err(HydraError::LibvirtUnavailable.into())
         }
     }
 
@@ -242,6 +1185,234 @@ impl LibvirtManager {
         Err(anyhow!("Cannot create VM: libvirt_integration feature is disabled."))
     }
 
+    /// Creates and boots `new_name` as a linked clone of the shut-off VM `source_name`: its disk
+    /// is a qcow2 overlay backed by the source's disk, so nothing is copied. The new domain gets
+    /// the source's vCPUs and memory, and libvirt assigns it a fresh UUID and MAC address.
+    ///
+    /// The source becomes a template. Booting it again changes the disk its clones read from;
+    /// destroying it keeps that disk for as long as a clone is defined.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn clone_vm(&self, source_name: &str, new_name: &str) -> Result<VmStatus> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let source = Domain::lookup_by_name(conn, source_name)
+            .map_err(|_| HydraError::VmNotFound(source_name.to_string()))?;
+        if source.is_active()? {
+            return Err(anyhow!("Shut down VM '{}' before cloning it; its disk must not change under the clone.", source_name));
+        }
+        let xml = source.get_xml_desc(0)
+            .map_err(|e| anyhow!("Failed to read definition of VM '{}': {}", source_name, e))?;
+        let source_disk = match writable_disk_sources(&xml).as_slice() {
+            [disk] => disk.clone(),
+            [] => return Err(anyhow!("VM '{}' has no writable file-backed disk to clone.", source_name)),
+            disks => return Err(anyhow!("VM '{}' has {} writable disks; only single-disk VMs can be cloned.", source_name, disks.len())),
+        };
+        let info = source.get_info()
+            .map_err(|e| anyhow!("Failed to read info for VM '{}': {}", source_name, e))?;
+
+        let vm_config = VmConfig {
+            instance_id: new_name.to_string(),
+            base_image: source_disk,
+            boot_iso: None,
+            cpu_cores: info.nr_virt_cpu,
+            memory_mb: info.max_mem / 1024,
+            disk_gb: None, // The overlay inherits the source disk's size
+            disk_path: None,
+            network_policy: "default".to_string(),
+            security_policy: "default".to_string(),
+            custom_script: None,
+            template_name: Some(source_name.to_string()),
+            labels: Some(parse_labels(&xml)).filter(|labels| !labels.is_empty()),
+            persistent: true,
+            cloud_init: None,
+            existing_disk: ExistingDisk::Refuse,
+        };
+        self.create_vm(&vm_config)
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn clone_vm(&self, _source_name: &str, _new_name: &str) -> Result<VmStatus> {
+        Err(anyhow!("Cannot clone VM: libvirt_integration feature is disabled."))
+    }
+
+    /// Applies `change` to a VM: vCPUs, then memory, then disk. Each step is checked and applied on its own, so
+    /// a disk that can't be resized still leaves an applied memory change in place.
+    pub fn resize_vm(&self, id: &str, change: &VmResourceChange) -> Result<()> {
+        if change.is_empty() {
+            return Err(anyhow!("Nothing to change; give a new vCPU count, memory size or disk size."));
+        }
+        if let Some(cpus) = change.cpus {
+            self.set_vm_vcpus(id, cpus)?;
+        }
+        if let Some(mem_mb) = change.memory_mb {
+            self.set_vm_memory(id, mem_mb)?;
+        }
+        if let Some(gb) = change.disk_gb {
+            self.resize_vm_disk(id, gb)?;
+        }
+        Ok(())
+    }
+
+    /// Sets a VM's vCPU count, within its role's `max_cpus`. A running VM has vCPUs hot-plugged
+    /// (or unplugged, if the guest lets them go) up to the maximum it booted with; a stopped VM's
+    /// definition is changed, raising its maximum to the role's `max_cpus` if it needs more.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn set_vm_vcpus(&self, id: &str, cpus: u32) -> Result<()> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let domain = Domain::lookup_by_name(conn, id)
+            .or_else(|_| Domain::lookup_by_uuid_string(conn, id))
+            .map_err(|_| HydraError::VmNotFound(id.to_string()))?;
+        let role = self.domain_role(&domain);
+        self.policy_engine.check_vm_limits(role.as_deref(), Some(cpus), None)?;
+        let result = if domain.is_active()? {
+            let max_cpus = domain.get_max_vcpus()
+                .map_err(|e| anyhow!("Failed to read maximum vCPUs of VM '{}': {}", id, e))?;
+            if cpus as u64 > max_cpus {
+                return Err(anyhow!("VM '{}' booted with at most {} vCPUs; shut it down to give it more.", id, max_cpus));
+            }
+            let mut flags = sys::VIR_DOMAIN_VCPU_LIVE;
+            if domain.is_persistent()? {
+                flags |= sys::VIR_DOMAIN_VCPU_CONFIG;
+            }
+            domain.set_vcpus_flags(cpus, flags)
+        } else {
+            domain.get_vcpus_flags(sys::VIR_DOMAIN_VCPU_CONFIG | sys::VIR_DOMAIN_VCPU_MAXIMUM)
+                .and_then(|max_cpus| if cpus > max_cpus {
+                    let headroom = cpus.max(self.policy_engine.vm_limits(role.as_deref()).max_cpus);
+                    domain.set_vcpus_flags(headroom, sys::VIR_DOMAIN_VCPU_CONFIG | sys::VIR_DOMAIN_VCPU_MAXIMUM)
+                } else {
+                    Ok(false)
+                })
+                .and_then(|_| domain.set_vcpus_flags(cpus, sys::VIR_DOMAIN_VCPU_CONFIG))
+        };
+        self.record_audit_event(
+            AuditEventType::ResourceAllocation {
+                instance_id: id.to_string(),
+                resource: "vcpus".to_string(),
+                value: cpus.to_string(),
+                success: result.is_ok(),
+            },
+            RiskLevel::Low,
+        );
+        result.map_err(|e| anyhow!("Failed to set vCPUs of VM '{}': {}", id, e))?;
+        tracing::info!("Set vCPUs of VM '{}' to {}.", id, cpus);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn set_vm_vcpus(&self, _id: &str, _cpus: u32) -> Result<()> {
+        Err(anyhow!("Cannot resize VM: libvirt_integration feature is disabled."))
+    }
+
+    /// Sets a VM's memory, within its role's `max_mem_mb`. A running VM is ballooned live (and
+    /// its definition updated, if it has one), which can't go past the maximum it booted with;
+    /// a stopped VM's definition gets the new size as both its current and maximum memory.
+    #[cfg(feature = "libvirt_integration")]
+    pub fn set_vm_memory(&self, id: &str, mem_mb: u64) -> Result<()> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let domain = Domain::lookup_by_name(conn, id)
+            .or_else(|_| Domain::lookup_by_uuid_string(conn, id))
+            .map_err(|_| HydraError::VmNotFound(id.to_string()))?;
+        self.policy_engine.check_vm_limits(self.domain_role(&domain).as_deref(), None, Some(mem_mb))?;
+        let memory_kb = mem_mb * 1024;
+        let result = if domain.is_active()? {
+            let max_kb = domain.get_max_memory()
+                .map_err(|e| anyhow!("Failed to read maximum memory of VM '{}': {}", id, e))?;
+            if memory_kb > max_kb {
+                return Err(anyhow!("VM '{}' booted with at most {} MB; shut it down to give it more.", id, max_kb / 1024));
+            }
+            let mut flags = sys::VIR_DOMAIN_MEM_LIVE;
+            if domain.is_persistent()? {
+                flags |= sys::VIR_DOMAIN_MEM_CONFIG;
+            }
+            domain.set_memory_flags(memory_kb, flags)
+        } else {
+            domain.set_memory_flags(memory_kb, sys::VIR_DOMAIN_MEM_CONFIG | sys::VIR_DOMAIN_MEM_MAXIMUM)
+                .and_then(|_| domain.set_memory_flags(memory_kb, sys::VIR_DOMAIN_MEM_CONFIG))
+        };
+        self.record_audit_event(
+            AuditEventType::ResourceAllocation {
+                instance_id: id.to_string(),
+                resource: "memory_mb".to_string(),
+                value: mem_mb.to_string(),
+                success: result.is_ok(),
+            },
+            RiskLevel::Low,
+        );
+        result.map_err(|e| anyhow!("Failed to set memory of VM '{}': {}", id, e))?;
+        tracing::info!("Set memory of VM '{}' to {} MB.", id, mem_mb);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn set_vm_memory(&self, _id: &str, _mem_mb: u64) -> Result<()> {
+        Err(anyhow!("Cannot resize VM: libvirt_integration feature is disabled."))
+    }
+
+    /// Grows a shut-off VM's disk image to `new_gb` with `qemu-img resize`. Shrinking isn't
+    /// offered, since it would cut off the guest's filesystem; the guest still has to grow its
+    /// partition (cloud images do this on boot).
+    #[cfg(feature = "libvirt_integration")]
+    pub fn resize_vm_disk(&self, id: &str, new_gb: u64) -> Result<()> {
+        let conn = self.libvirt_conn.as_ref()
+            .ok_or(HydraError::LibvirtUnavailable)?;
+        let domain = Domain::lookup_by_name(conn, id)
+            .or_else(|_| Domain::lookup_by_uuid_string(conn, id))
+            .map_err(|_| HydraError::VmNotFound(id.to_string()))?;
+        if domain.is_active()? {
+            return Err(anyhow!("Shut down VM '{}' before resizing its disk.", id));
+        }
+        let xml = domain.get_xml_desc(0)
+            .map_err(|e| anyhow!("Failed to read definition of VM '{}': {}", id, e))?;
+        let disk_path = match writable_disk_sources(&xml).as_slice() {
+            [disk] => disk.clone(),
+            [] => return Err(anyhow!("VM '{}' has no writable file-backed disk to resize.", id)),
+            disks => return Err(anyhow!("VM '{}' has {} writable disks; only single-disk VMs can be resized.", id, disks.len())),
+        };
+        let new_bytes = new_gb * 1024 * 1024 * 1024;
+        let capacity = domain.get_block_info(&disk_path, 0)
+            .map_err(|e| anyhow!("Failed to read size of disk '{}': {}", disk_path, e))?
+            .capacity;
+        if new_bytes < capacity {
+            return Err(anyhow!("Disk of VM '{}' is already {:.1} GB; disks can only grow.", id, capacity as f64 / (1024.0 * 1024.0 * 1024.0)));
+        }
+        if new_bytes == capacity {
+            return Ok(());
+        }
+
+        let output = std::process::Command::new("qemu-img")
+            .arg("resize")
+            .arg(&disk_path)
+            .arg(format!("{}G", new_gb))
+            .output()
+            .map_err(|e| anyhow!("Failed to run qemu-img (is it installed?): {}", e))?;
+        let result = if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!("qemu-img failed to resize '{}': {}", disk_path, String::from_utf8_lossy(&output.stderr).trim()))
+        };
+        self.record_audit_event(
+            AuditEventType::ResourceAllocation {
+                instance_id: id.to_string(),
+                resource: "disk_gb".to_string(),
+                value: new_gb.to_string(),
+                success: result.is_ok(),
+            },
+            RiskLevel::Low,
+        );
+        result?;
+        self.refresh_storage_pool();
+        tracing::info!("Resized disk '{}' of VM '{}' to {} GB.", disk_path, id, new_gb);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "libvirt_integration"))]
+    pub fn resize_vm_disk(&self, _id: &str, _new_gb: u64) -> Result<()> {
+        Err(anyhow!("Cannot resize VM disk: libvirt_integration feature is disabled."))
+    }
+
     #[cfg(feature = "libvirt_integration")]
     fn create_vm_xml(
         &self,
@@ -250,8 +1421,34 @@ impl LibvirtManager {
         memory_mb: u64,
         disk_path: &str,
         boot_iso: Option<&str>,
+        cloud_init_seed: Option<&str>,
+        template_name: Option<&str>,
+        labels: Option<&HashMap<String, String>>,
+        network: &VmNetwork,
     ) -> String {
         let memory_kb = memory_mb * 1024;
+        // Boot with the policy's max_cpus as the ceiling, so `vm resize --cpu` can hot-plug up to it
+        let max_vcpu = vcpu.max(self.policy_engine.config.defaults.vm.max_cpus);
+        let interface = match network {
+            VmNetwork::Nat => r#"<interface type='network'>
+                      <source network='default'/>
+                      <model type='virtio'/>
+                    </interface>"#.to_string(),
+            VmNetwork::Filtered { policy, .. } => format!(
+                r#"<interface type='network'>
+                      <source network='default'/>
+                      <model type='virtio'/>
+                      <filterref filter='{}'/>
+                    </interface>"#,
+                xml_escape(&network_filter_name(policy))
+            ),
+            VmNetwork::Isolated => String::new(),
+        };
+        let description = template_name
+            .map(|template| format!("<description>Linked clone of {}</description>", xml_escape(template)))
+            .unwrap_or_default();
+        // Written even without labels, since it is also what marks the domain as Hydravisor's own
+        let metadata = labels_metadata_xml(labels.unwrap_or(&HashMap::new()));
         let mut iso_disk = "".to_string();
         if let Some(iso_path) = boot_iso {
             iso_disk = format!(
@@ -264,12 +1461,25 @@ impl LibvirtManager {
                 iso_path
             );
         }
+        if let Some(seed_path) = cloud_init_seed {
+            iso_disk.push_str(&format!(
+                r#"<disk type='file' device='cdrom'>
+                      <driver name='qemu' type='raw'/>
+                      <target dev='hdb' bus='sata'/>
+                      <source file='{}'/>
+                      <readonly/>
+                   </disk>"#,
+                seed_path
+            ));
+        }
 
         format!(
             r#"<domain type='kvm'>
                   <name>{}</name>
+                  {}
+                  {}
                   <memory unit='KiB'>{}</memory>
-                  <vcpu>{}</vcpu>
+                  <vcpu placement='static' current='{}'>{}</vcpu>
                   <os>
                     <type arch='x86_64' machine='q35'>hvm</type>
                     <boot dev='hd'/>
@@ -282,10 +1492,13 @@ impl LibvirtManager {
                       <target dev='vda' bus='virtio'/>
                     </disk>
                     {}
-                    <interface type='network'>
-                      <source network='default'/>
-                      <model type='virtio'/>
-                    </interface>
+                    {}
+                    <serial type='pty'>
+                      <target port='0'/>
+                    </serial>
+                    <console type='pty'>
+                      <target type='serial' port='0'/>
+                    </console>
                     <graphics type='vnc' port='-1' autoport='yes' listen='127.0.0.1'>
                       <listen type='address' address='127.0.0.1'/>
                     </graphics>
@@ -294,16 +1507,38 @@ impl LibvirtManager {
                     </video>
                   </devices>
                 </domain>"#,
-            name, memory_kb, vcpu, if boot_iso.is_some() { "<boot dev='cdrom'/>" } else { "" }, disk_path, iso_disk
+            name, description, metadata, memory_kb, vcpu, max_vcpu, if boot_iso.is_some() { "<boot dev='cdrom'/>" } else { "" }, disk_path, iso_disk, interface
         )
     }
 
+    /// The role recorded for a domain when it was imported, if any.
+    #[cfg(feature = "libvirt_integration")]
+    fn domain_role(&self, domain: &Domain) -> Option<String> {
+        let uuid = domain.get_uuid_string().ok()?;
+        self.vm_registry.as_ref()?.get(&uuid)?.role
+    }
+
+    /// Returns the guest's IPv4 address, reusing a recent lookup when there is one.
+    #[cfg(feature = "libvirt_integration")]
+    fn cached_ip_address(&self, domain: &Domain, instance_id: &str) -> Option<String> {
+        let mut cache = self.ip_address_cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((ip_address, looked_up_at)) = cache.get(instance_id) {
+            if looked_up_at.elapsed() < IP_ADDRESS_CACHE_TTL {
+                return ip_address.clone();
+            }
+        }
+        let ip_address = lookup_ip_address(domain);
+        cache.insert(instance_id.to_string(), (ip_address.clone(), std::time::Instant::now()));
+        ip_address
+    }
+
     #[cfg(feature = "libvirt_integration")]
     fn map_libvirt_state_to_vm_state(&self, state_code: u32) -> VmState {
         match state_code {
             sys::VIR_DOMAIN_NOSTATE => VmState::Unknown,
             sys::VIR_DOMAIN_RUNNING => VmState::Running,
-            sys::VIR_DOMAIN_BLOCKED => VmState::Suspended,
+            // Blocked means a vCPU is waiting on I/O; the guest is still running
+            sys::VIR_DOMAIN_BLOCKED => VmState::Running,
             sys::VIR_DOMAIN_PAUSED => VmState::Suspended,
             sys::VIR_DOMAIN_SHUTDOWN => VmState::Terminated,
             sys::VIR_DOMAIN_SHUTOFF => VmState::Stopped,
@@ -313,4 +1548,544 @@ impl LibvirtManager {
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Extracts the source paths of writable file-backed disks from a domain's XML,
+/// skipping CD-ROMs, floppies, and anything marked `<readonly/>`.
+#[cfg(feature = "libvirt_integration")]
+fn writable_disk_sources(domain_xml: &str) -> Vec<String> {
+    let mut sources = Vec::new();
+    let mut rest = domain_xml;
+    while let Some(start) = rest.find("<disk ") {
+        let after_start = &rest[start..];
+        let Some(end) = after_start.find("</disk>") else { break };
+        let disk = &after_start[..end];
+        rest = &after_start[end..];
+
+        let opening_tag = &disk[..disk.find('>').unwrap_or(disk.len())];
+        let is_disk_device = xml_attr(opening_tag, "device").map_or(true, |d| d == "disk");
+        if !is_disk_device || disk.contains("<readonly/>") {
+            continue;
+        }
+        if let Some(source_start) = disk.find("<source ") {
+            let source_tag = &disk[source_start..];
+            let source_tag = &source_tag[..source_tag.find('>').unwrap_or(source_tag.len())];
+            if let Some(file) = xml_attr(source_tag, "file") {
+                sources.push(file.to_string());
+            }
+        }
+    }
+    sources
+}
+
+/// Every image file a domain's disks read or write: each writable disk, the `<backingStore>`
+/// sources libvirt reports for it, and the backing chain recorded in the image itself (libvirt
+/// leaves `<backingStore>` out of inactive definitions).
+#[cfg(feature = "libvirt_integration")]
+fn disk_references(domain_xml: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    for disk in xml_elements(domain_xml, "disk") {
+        let Some(backing) = disk.find("<backingStore") else { continue };
+        for source in xml_elements(&disk[backing..], "source") {
+            if let Some(file) = xml_attr(source, "file") {
+                paths.push(file.to_string());
+            }
+        }
+    }
+    for source in writable_disk_sources(domain_xml) {
+        paths.extend(image_backing_chain(&source));
+        paths.push(source);
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// The backing files beneath an image, nearest first, from `qemu-img info --backing-chain`.
+/// An image qemu-img can't read is reported as having none.
+#[cfg(feature = "libvirt_integration")]
+fn image_backing_chain(image_path: &str) -> Vec<String> {
+    // -U reads images a running VM holds locked
+    let output = std::process::Command::new("qemu-img")
+        .args(["info", "-U", "--backing-chain", "--output=json"])
+        .arg(image_path)
+        .output();
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            tracing::debug!("qemu-img could not inspect '{}': {}", image_path, String::from_utf8_lossy(&output.stderr).trim());
+            return Vec::new();
+        }
+        Err(e) => {
+            tracing::debug!("Failed to run qemu-img on '{}': {}", image_path, e);
+            return Vec::new();
+        }
+    };
+    let Ok(serde_json::Value::Array(images)) = serde_json::from_slice(&output.stdout) else {
+        return Vec::new();
+    };
+    images.iter()
+        .filter_map(|image| image.get("full-backing-filename").or_else(|| image.get("backing-filename")))
+        .filter_map(|path| path.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Returns every `<tag ...>...</tag>` (or self-closing `<tag .../>`) element in `xml`, in order.
+/// Elements of the same name must not nest, which holds for the domain XML sections read here.
+#[cfg(feature = "libvirt_integration")]
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut elements = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after_start = &rest[start..];
+        // Skip longer tag names that share the prefix, e.g. `<disks` when looking for `<disk`
+        if !after_start[open.len()..].starts_with([' ', '>', '/']) {
+            rest = &after_start[open.len()..];
+            continue;
+        }
+        let Some(tag_end) = after_start.find('>') else { break };
+        let len = if after_start[..tag_end].ends_with('/') {
+            tag_end + 1
+        } else {
+            after_start.find(&close).map_or(tag_end + 1, |end| end + close.len())
+        };
+        elements.push(&after_start[..len]);
+        rest = &after_start[len..];
+    }
+    elements
+}
+
+/// The `<target><path>` of a storage pool's XML: the directory its volumes live in.
+#[cfg(feature = "libvirt_integration")]
+fn storage_pool_target_path(pool_xml: &str) -> Option<String> {
+    let target = xml_elements(pool_xml, "target").into_iter().next()?;
+    let path = xml_elements(target, "path").into_iter().next()?;
+    let text = path.strip_prefix("<path>")?.strip_suffix("</path>")?.trim();
+    Some(text.to_string()).filter(|text| !text.is_empty())
+}
+
+/// The opening tag of the first `<tag>` element in `xml`, for reading its attributes.
+#[cfg(feature = "libvirt_integration")]
+fn xml_first_tag<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    xml_elements(xml, tag).into_iter().next().map(|element| &element[..element.find('>').unwrap_or(element.len())])
+}
+
+#[cfg(feature = "libvirt_integration")]
+fn parse_disks(domain_xml: &str) -> Vec<VmDisk> {
+    xml_elements(domain_xml, "disk")
+        .into_iter()
+        .map(|disk| {
+            let opening_tag = &disk[..disk.find('>').unwrap_or(disk.len())];
+            // The disk's own source precedes its backing chain, which has sources (but no target) of its own
+            let (own, backing) = disk.split_at(disk.find("<backingStore").unwrap_or(disk.len()));
+            let source_of = |xml: &str| {
+                xml_first_tag(xml, "source").and_then(|tag| {
+                    xml_attr(tag, "file").or_else(|| xml_attr(tag, "dev")).map(str::to_string)
+                })
+            };
+            VmDisk {
+                target: xml_first_tag(disk, "target").and_then(|tag| xml_attr(tag, "dev")).unwrap_or("?").to_string(),
+                device: xml_attr(opening_tag, "device").unwrap_or("disk").to_string(),
+                source: source_of(own),
+                backing_file: source_of(backing),
+                readonly: disk.contains("<readonly/>"),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Fills in each file-backed disk's virtual size and host allocation. Disks libvirt can't
+/// size, such as an empty CD-ROM drive, are left unset.
+#[cfg(feature = "libvirt_integration")]
+fn read_disk_sizes(domain: &Domain, disks: &mut [VmDisk]) {
+    for disk in disks {
+        // libvirt accepts the image path for inactive domains too, unlike the target name
+        let Some(source) = &disk.source else { continue };
+        match domain.get_block_info(source, 0) {
+            Ok(info) => {
+                disk.capacity_bytes = Some(info.capacity);
+                disk.allocation_bytes = Some(info.allocation);
+            }
+            Err(e) => tracing::debug!("Failed to read size of disk '{}': {}", source, e),
+        }
+    }
+}
+
+/// Sums the virtual and allocated sizes of the writable disks, in GB. None if none could be sized.
+#[cfg(feature = "libvirt_integration")]
+fn disk_totals_gb(disks: &[VmDisk]) -> (Option<f64>, Option<f64>) {
+    const BYTES_PER_GB: f64 = (1u64 << 30) as f64;
+    let sized: Vec<&VmDisk> = disks.iter()
+        .filter(|disk| disk.device == "disk" && !disk.readonly && disk.capacity_bytes.is_some())
+        .collect();
+    if sized.is_empty() {
+        return (None, None);
+    }
+    let total = |bytes: fn(&VmDisk) -> Option<u64>| sized.iter().filter_map(|disk| bytes(disk)).sum::<u64>() as f64 / BYTES_PER_GB;
+    (Some(total(|disk| disk.capacity_bytes)), Some(total(|disk| disk.allocation_bytes)))
+}
+
+#[cfg(feature = "libvirt_integration")]
+fn parse_interfaces(domain_xml: &str) -> Vec<VmInterface> {
+    xml_elements(domain_xml, "interface")
+        .into_iter()
+        .map(|iface| VmInterface {
+            mac: xml_first_tag(iface, "mac").and_then(|tag| xml_attr(tag, "address")).unwrap_or_default().to_string(),
+            source: xml_first_tag(iface, "source")
+                .and_then(|tag| xml_attr(tag, "network").or_else(|| xml_attr(tag, "bridge")).or_else(|| xml_attr(tag, "dev")))
+                .map(str::to_string),
+            model: xml_first_tag(iface, "model").and_then(|tag| xml_attr(tag, "type")).map(str::to_string),
+            ip_addresses: Vec::new(),
+        })
+        .collect()
+}
+
+#[cfg(feature = "libvirt_integration")]
+fn parse_consoles(domain_xml: &str) -> Vec<VmConsole> {
+    let graphics = xml_elements(domain_xml, "graphics").into_iter().map(|graphics| {
+        let opening_tag = &graphics[..graphics.find('>').unwrap_or(graphics.len())];
+        VmConsole {
+            kind: xml_attr(opening_tag, "type").unwrap_or("graphics").to_string(),
+            listen: xml_attr(opening_tag, "listen").map(str::to_string),
+            port: xml_attr(opening_tag, "port").map(str::to_string),
+            pty_path: None,
+        }
+    });
+    let serials = xml_elements(domain_xml, "serial").into_iter().map(|serial| VmConsole {
+        kind: "serial".to_string(),
+        listen: None,
+        port: xml_first_tag(serial, "target").and_then(|tag| xml_attr(tag, "port")).map(str::to_string),
+        pty_path: xml_first_tag(serial, "source").and_then(|tag| xml_attr(tag, "path")).map(str::to_string),
+    });
+    graphics.chain(serials).collect()
+}
+
+/// Returns the value of `name='...'` or `name="..."` within a single XML tag.
+#[cfg(feature = "libvirt_integration")]
+fn xml_attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    for quote in ['\'', '"'] {
+        let needle = format!(" {}={}", name, quote);
+        if let Some(pos) = tag.find(&needle) {
+            let value = &tag[pos + needle.len()..];
+            return value.find(quote).map(|end| &value[..end]);
+        }
+    }
+    None
+}
+
+// The nwfilter enforcing a filtered network policy
+#[cfg(feature = "libvirt_integration")]
+fn network_filter_name(policy: &str) -> String {
+    let policy: String = policy.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+    format!("hydravisor-{}", policy)
+}
+
+/// Builds an nwfilter that lets a VM reach only what `rules` allow, in order. DHCP is always
+/// allowed so the guest gets an address; everything else (DNS included) must be listed.
+/// libvirt tracks connection state, so replies to allowed traffic get back in.
+#[cfg(feature = "libvirt_integration")]
+fn network_filter_xml(policy: &str, rules: &[NetworkRule]) -> Result<String> {
+    let mut rule_xml = String::new();
+    for (i, rule) in rules.iter().enumerate() {
+        check_network_rule(rule).map_err(|e| anyhow!("Network policy '{}', rule {}: {}", policy, i + 1, e))?;
+        let (address, prefix) = match rule.host.as_deref().map(|host| host.split_once('/').unwrap_or((host, ""))) {
+            Some((address, prefix)) => (Some(address), Some(prefix).filter(|prefix| !prefix.is_empty())),
+            None => (None, None),
+        };
+        let ipv6 = address.map_or(false, |address| address.contains(':'));
+        let protocol = match (rule.protocol.as_deref().unwrap_or("all"), ipv6) {
+            ("icmp", true) => "icmpv6".to_string(),
+            (protocol, true) => format!("{}-ipv6", protocol),
+            (protocol, false) => protocol.to_string(),
+        };
+        let mut attributes = String::new();
+        if let Some(address) = address {
+            attributes.push_str(&format!(" dstipaddr='{}'", xml_escape(address)));
+        }
+        if let Some(prefix) = prefix {
+            attributes.push_str(&format!(" dstipmask='{}'", xml_escape(prefix)));
+        }
+        if let Some(port) = rule.port {
+            attributes.push_str(&format!(" dstportstart='{}'", port));
+        }
+        let action = if rule.allow.unwrap_or(true) { "accept" } else { "drop" };
+        // Earlier rules win, as they read in policy.toml
+        rule_xml.push_str(&format!(
+            "
+  <rule action='{}' direction='out' priority='{}'>
+    <{}{}/>
+  </rule>",
+            action, (200 + i).min(900), protocol, attributes
+        ));
+    }
+    Ok(format!(
+        r#"<filter name='{}' chain='root'>
+  <filterref filter='clean-traffic'/>
+  <rule action='accept' direction='out' priority='100'>
+    <udp srcportstart='68' dstportstart='67'/>
+  </rule>
+  <rule action='accept' direction='in' priority='100'>
+    <udp srcportstart='67' dstportstart='68'/>
+  </rule>{}
+  <rule action='drop' direction='inout' priority='1000'>
+    <all/>
+  </rule>
+  <rule action='drop' direction='inout' priority='1000'>
+    <all-ipv6/>
+  </rule>
+</filter>"#,
+        xml_escape(&network_filter_name(policy)),
+        rule_xml
+    ))
+}
+
+// Namespace of the labels Hydravisor keeps in a domain's <metadata>
+#[cfg(feature = "libvirt_integration")]
+const LABELS_NAMESPACE: &str = "https://github.com/TrippingKelsea/Hydravisor/labels";
+
+/// A `<metadata>` element holding `labels`, sorted by key so the XML is stable.
+#[cfg(feature = "libvirt_integration")]
+fn labels_metadata_xml(labels: &HashMap<String, String>) -> String {
+    let mut sorted: Vec<_> = labels.iter().collect();
+    sorted.sort();
+    let entries: String = sorted
+        .into_iter()
+        .map(|(key, value)| format!("<hydravisor:label key='{}' value='{}'/>", xml_escape(key), xml_escape(value)))
+        .collect();
+    format!("<metadata><hydravisor:labels xmlns:hydravisor='{}'>{}</hydravisor:labels></metadata>", LABELS_NAMESPACE, entries)
+}
+
+/// Whether the domain was created by Hydravisor, which always writes its labels metadata.
+#[cfg(feature = "libvirt_integration")]
+fn has_hydravisor_metadata(domain_xml: &str) -> bool {
+    domain_xml.contains(LABELS_NAMESPACE)
+}
+
+/// Whether the domain looks like one Hydravisor created. Older builds only wrote the metadata
+/// when a VM had labels, so those VMs are recognized by what Hydravisor names after them
+/// instead: an `mcp-<uuid>` name, a `<name>-seed.iso` cloud-init seed or a `hydravisor-*` nwfilter.
+#[cfg(feature = "libvirt_integration")]
+fn is_hydravisor_domain(domain_xml: &str, name: &str) -> bool {
+    let mcp_name = name
+        .strip_prefix("mcp-")
+        .is_some_and(|id| id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()));
+    has_hydravisor_metadata(domain_xml)
+        || mcp_name
+        || domain_xml.contains(&format!("/{}-seed.iso", name))
+        || xml_elements(domain_xml, "filterref")
+            .into_iter()
+            .any(|filter| xml_attr(filter, "filter").is_some_and(|filter| filter.starts_with("hydravisor-")))
+}
+
+/// Labels from the domain's Hydravisor metadata; empty for domains created elsewhere.
+#[cfg(feature = "libvirt_integration")]
+fn parse_labels(domain_xml: &str) -> HashMap<String, String> {
+    xml_elements(domain_xml, "hydravisor:label")
+        .into_iter()
+        .filter_map(|label| {
+            let key = xml_attr(label, "key")?;
+            Some((xml_unescape(key), xml_unescape(xml_attr(label, "value").unwrap_or(""))))
+        })
+        .collect()
+}
+
+/// Escapes text for use in XML element content or attribute values.
+#[cfg(feature = "libvirt_integration")]
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&apos;")
+        .replace('"', "&quot;")
+}
+
+/// Reverses `xml_escape` (and libvirt's own escaping of attribute values).
+#[cfg(feature = "libvirt_integration")]
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&apos;", "'")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+/// Deletes a destroyed VM's disk image if it lives under the images directory.
+/// Failures are logged rather than returned, since the VM itself is already gone.
+#[cfg(feature = "libvirt_integration")]
+fn remove_disk_image(instance_id: &str, path: &str, images_dir: &str) {
+    let disk_path = std::path::Path::new(path);
+    if !disk_path.starts_with(images_dir) || disk_path.components().any(|c| c == std::path::Component::ParentDir) {
+        tracing::warn!("Not deleting disk '{}' of VM '{}': it is outside {}.", path, instance_id, images_dir);
+        return;
+    }
+    match std::fs::remove_file(disk_path) {
+        Ok(()) => tracing::info!("Deleted disk image '{}' of VM '{}'.", path, instance_id),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tracing::warn!("Disk image '{}' of VM '{}' was already gone.", path, instance_id);
+        }
+        Err(e) => tracing::warn!("Failed to delete disk image '{}' of VM '{}': {}", path, instance_id, e),
+    }
+}
+
+/// Creates a qcow2 disk with `qemu-img`. If `base_image` is an existing image file the new disk
+/// is a copy-on-write overlay backed by it; otherwise a blank disk of `disk_gb` is created.
+#[cfg(feature = "libvirt_integration")]
+fn create_disk_image(disk_path: &str, base_image: &str, disk_gb: Option<u64>) -> Result<()> {
+    let path = std::path::Path::new(disk_path);
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+    ensure_dir_writable(dir)?;
+    if path.exists() {
+        return Err(anyhow!("Disk image '{}' already exists; refusing to overwrite it.", disk_path));
+    }
+
+    let mut command = std::process::Command::new("qemu-img");
+    command.args(["create", "-f", "qcow2"]);
+    let base_path = std::path::Path::new(base_image);
+    if !base_image.is_empty() && base_path.is_file() {
+        let backing_format = image_format(base_image)?;
+        command.arg("-b").arg(base_path).arg("-F").arg(backing_format);
+        command.arg(disk_path);
+        // Without a size the overlay inherits the backing image's virtual size
+        if let Some(gb) = disk_gb {
+            command.arg(format!("{}G", gb));
+        }
+    } else {
+        let gb = disk_gb.ok_or_else(|| anyhow!("A disk size is required when no source image is given."))?;
+        command.arg(disk_path).arg(format!("{}G", gb));
+    }
+
+    let output = command.output()
+        .map_err(|e| anyhow!("Failed to run qemu-img (is it installed?): {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "qemu-img failed to create '{}': {}",
+            disk_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    tracing::info!("Created disk image '{}'.", disk_path);
+    Ok(())
+}
+
+/// Reports an image's format (e.g. `qcow2`, `raw`) using `qemu-img info`.
+#[cfg(feature = "libvirt_integration")]
+fn image_format(image_path: &str) -> Result<String> {
+    let output = std::process::Command::new("qemu-img")
+        .args(["info", "--output=json"])
+        .arg(image_path)
+        .output()
+        .map_err(|e| anyhow!("Failed to run qemu-img (is it installed?): {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "qemu-img could not inspect '{}': {}",
+            image_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    info.get("format")
+        .and_then(|f| f.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("qemu-img did not report a format for '{}'.", image_path))
+}
+
+/// Checks that files can be created in `dir` by creating and removing a probe file.
+#[cfg(feature = "libvirt_integration")]
+fn ensure_dir_writable(dir: &std::path::Path) -> Result<()> {
+    let probe = dir.join(format!(".hydravisor-write-check-{}", std::process::id()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| anyhow!("Cannot create disk images in '{}': {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// The VM's disk image path: the configured one, or `<images dir>/<instance_id>.qcow2`.
+#[cfg(feature = "libvirt_integration")]
+fn vm_disk_path(vm_config: &VmConfig, images_dir: &str) -> String {
+    vm_config.disk_path.clone()
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| format!("{}/{}.qcow2", images_dir, vm_config.instance_id))
+}
+
+/// Where the cloud-init seed ISO for a VM is written.
+#[cfg(feature = "libvirt_integration")]
+fn cloud_init_seed_path(images_dir: &str, vm_name: &str) -> String {
+    format!("{}/{}-seed.iso", images_dir, vm_name)
+}
+
+/// Writes a cloud-init NoCloud seed ISO (volume label `cidata`) containing `user-data` and `meta-data`.
+#[cfg(feature = "libvirt_integration")]
+fn create_cloud_init_seed(seed_iso_path: &str, vm_name: &str, cloud_init: &CloudInitConfig) -> Result<()> {
+    let user_data = CloudInitUserData {
+        hostname: vm_name,
+        ssh_authorized_keys: &cloud_init.ssh_authorized_keys,
+        packages: &cloud_init.packages,
+        runcmd: &cloud_init.runcmd,
+    };
+    let user_data = format!("#cloud-config\n{}", serde_yaml::to_string(&user_data)?);
+    let meta_data = format!("instance-id: {}\nlocal-hostname: {}\n", vm_name, vm_name);
+
+    let staging_dir = std::env::temp_dir().join(format!("hydravisor-cloud-init-{}-{}", vm_name, std::process::id()));
+    std::fs::create_dir_all(&staging_dir)?;
+    let result = (|| {
+        std::fs::write(staging_dir.join("user-data"), user_data)?;
+        std::fs::write(staging_dir.join("meta-data"), meta_data)?;
+
+        // genisoimage, mkisofs, and xorrisofs all accept the same arguments for this
+        let mut last_error = None;
+        for tool in ["genisoimage", "mkisofs", "xorrisofs"] {
+            let output = std::process::Command::new(tool)
+                .args(["-output", seed_iso_path, "-volid", "cidata", "-joliet", "-rock"])
+                .arg(staging_dir.join("user-data"))
+                .arg(staging_dir.join("meta-data"))
+                .output();
+            match output {
+                Ok(output) if output.status.success() => {
+                    tracing::info!("Created cloud-init seed '{}' with {}.", seed_iso_path, tool);
+                    return Ok(());
+                }
+                Ok(output) => {
+                    return Err(anyhow!(
+                        "{} failed to create cloud-init seed '{}': {}",
+                        tool,
+                        seed_iso_path,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(anyhow!(
+            "No ISO tool found to build the cloud-init seed (tried genisoimage, mkisofs, xorrisofs): {}",
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    })();
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    result
+}
+
+/// Finds a guest's IPv4 address from the network's DHCP leases (matched by the domain's MACs),
+/// falling back to the qemu guest agent for guests on bridged or static networks.
+#[cfg(feature = "libvirt_integration")]
+fn lookup_ip_address(domain: &Domain) -> Option<String> {
+    for source in [sys::VIR_DOMAIN_INTERFACE_ADDRESSES_SRC_LEASE, sys::VIR_DOMAIN_INTERFACE_ADDRESSES_SRC_AGENT] {
+        let Ok(interfaces) = domain.interface_addresses(source, 0) else {
+            continue; // e.g. no guest agent installed
+        };
+        let ipv4 = interfaces
+            .iter()
+            .filter(|iface| iface.name != "lo")
+            .flat_map(|iface| iface.addrs.iter())
+            .find(|addr| addr.typed == sys::VIR_IP_ADDR_TYPE_IPV4 as i64 && !addr.addr.starts_with("127."));
+        if let Some(addr) = ipv4 {
+            return Some(addr.addr.clone());
+        }
+    }
+    None
+}
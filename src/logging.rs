@@ -1,3 +1,131 @@
 // src/logging.rs
-// This file exists to satisfy the module system.
-// Logging is configured directly in main.rs using the `tracing` crate. 
\ No newline at end of file
+// The file side of Hydravisor's tracing setup, driven by `[logging]`: where the system log is
+// written, how it rotates, and how each line is formatted. main.rs assembles the subscriber.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveDate};
+use std::fs::{self, create_dir_all};
+use std::path::Path;
+use std::time::SystemTime;
+use tracing::{warn, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, fmt::MakeWriter, registry::LookupSpan, Layer};
+
+use crate::config::{Config, APP_NAME, PROFILES_DIR_NAME};
+
+// Subdirectories of the log dir that retention never touches: the audit ledger is the
+// tamper-evident record, and other profiles apply their own `retain_days`
+const RETENTION_EXEMPT_DIRS: [&str; 2] = ["audit", PROFILES_DIR_NAME];
+
+/// The system log's file name. Daily rotation appends the date, e.g. `hydravisor.log.2025-06-01`.
+pub fn system_log_file_name() -> String {
+    format!("{}.log", APP_NAME)
+}
+
+/// Opens the system log in `Config::system_log_dir`. With `rotate_daily` a new file is started
+/// each day and only the newest `retain_days` are kept (all of them if `retain_days` is 0);
+/// otherwise everything goes to one file.
+pub fn system_log_appender(config: &Config) -> Result<RollingFileAppender> {
+    let log_dir = config.system_log_dir();
+    create_dir_all(&log_dir).with_context(|| format!("Failed to create log directory {:?}", log_dir))?;
+    let mut builder = RollingFileAppender::builder().filename_prefix(system_log_file_name());
+    if config.logging.rotate_daily {
+        builder = builder.rotation(Rotation::DAILY);
+        if config.logging.retain_days > 0 {
+            builder = builder.max_log_files(config.logging.retain_days as usize);
+        }
+    } else {
+        builder = builder.rotation(Rotation::NEVER);
+    }
+    builder
+        .build(&log_dir)
+        .with_context(|| format!("Failed to open the system log in {:?}", log_dir))
+}
+
+/// A layer writing uncolored lines to `writer`, as JSON objects or plain text per `logging.format`.
+pub fn file_layer<S, W>(config: &Config, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let layer = fmt::layer().with_writer(writer).with_ansi(false);
+    match config.logging.format.as_str() {
+        "text" => layer.boxed(),
+        // `config validate` flags anything else; JSON is the default
+        _ => layer.json().boxed(),
+    }
+}
+
+/// Deletes log files under `Config::log_dir` older than `retain_days`, except the audit ledger.
+/// A rotated file's age comes from its date suffix (`.YYYY-MM-DD`), anything else's from its
+/// modification time. The undated system log is skipped: in the TUI it is already open for this
+/// run. Returns how many files were removed; `retain_days = 0` keeps everything.
+pub fn prune_old_logs(config: &Config) -> Result<usize> {
+    let retain_days = config.logging.retain_days;
+    if retain_days == 0 {
+        return Ok(0);
+    }
+    let log_dir = config.log_dir();
+    if !log_dir.is_dir() {
+        return Ok(0);
+    }
+    let cutoff_date = Local::now().date_naive() - Duration::days(retain_days as i64);
+    let cutoff_time = SystemTime::now() - std::time::Duration::from_secs(retain_days as u64 * 24 * 60 * 60);
+    let active_log = config.system_log_dir().join(system_log_file_name());
+    let mut pruned = 0;
+    for entry in fs::read_dir(&log_dir).with_context(|| format!("Failed to read log directory {:?}", log_dir))? {
+        let entry = entry?;
+        if RETENTION_EXEMPT_DIRS.iter().any(|dir| entry.file_name() == *dir) {
+            continue;
+        }
+        prune_path(&entry.path(), &active_log, cutoff_date, cutoff_time, &mut pruned);
+    }
+    Ok(pruned)
+}
+
+// Failures are logged and skipped so one unreadable file doesn't stop the rest being pruned
+fn prune_path(path: &Path, active_log: &Path, cutoff_date: NaiveDate, cutoff_time: SystemTime, pruned: &mut usize) {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            warn!("Log retention: failed to read {:?}: {}", path, e);
+            return;
+        }
+    };
+    if metadata.is_dir() {
+        match fs::read_dir(path) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    prune_path(&entry.path(), active_log, cutoff_date, cutoff_time, pruned);
+                }
+            }
+            Err(e) => warn!("Log retention: failed to read {:?}: {}", path, e),
+        }
+        return;
+    }
+    if !metadata.is_file() || path == active_log {
+        return;
+    }
+    let expired = match rotation_date(path) {
+        Some(date) => date < cutoff_date,
+        None => metadata.modified().map_or(false, |modified| modified < cutoff_time),
+    };
+    if expired {
+        match fs::remove_file(path) {
+            Ok(()) => *pruned += 1,
+            Err(e) => warn!("Log retention: failed to delete {:?}: {}", path, e),
+        }
+    }
+}
+
+// The date a daily-rotated file was written on, from a name like `hydravisor.log.2025-06-01`
+fn rotation_date(path: &Path) -> Option<NaiveDate> {
+    let name = path.file_name()?.to_str()?;
+    let (_, suffix) = name.rsplit_once('.')?;
+    NaiveDate::parse_from_str(suffix, "%Y-%m-%d").ok()
+}
+
+// TODO: Add tests for logging:
+// - system_log_appender rotates daily or never per `rotate_daily`, in `<log_dir>/system`.
+// - file_layer writes JSON lines by default and plain text for `format = "text"`.
+// - prune_old_logs honors date suffixes and mtimes, skips audit/, profiles/ and the open system log, and keeps all with retain_days = 0.
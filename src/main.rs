@@ -7,27 +7,35 @@ mod cli;
 mod config;
 mod libvirt_manager;
 mod errors;
+mod format;
+mod health;
+mod idle_shutdown;
 mod logging;
+mod mcp;
+mod metrics;
 mod tui;
 // Placeholders for other modules based on design
 mod policy;
 mod session_manager;
+mod shutdown;
 mod ssh_manager;
+mod vm_registry;
 mod ollama_manager;
 #[cfg(feature = "bedrock_integration")]
 mod bedrock_manager;
+#[cfg(feature = "openai_compat")]
+mod openai_compat_manager;
 
 use anyhow::Result;
 use clap::Parser;
 use std::sync::Arc;
 use tokio::sync::Mutex; // Use tokio's Mutex
-use std::fs::create_dir_all; // For creating log directory
 
 use cli::Cli;
-use config::{Config, APP_NAME}; // Import APP_NAME
+use config::Config;
 use policy::PolicyEngine;
 use ssh_manager::SshManager;
-use audit::AuditEngine;
+use audit::{AuditEngine, AuditEvent, AuditEventType, RiskLevel};
 use libvirt_manager::LibvirtManager;
 use session_manager::SessionManager;
 use ollama_manager::OllamaManager;
@@ -43,8 +51,6 @@ use tracing_subscriber::{
     Registry, // Explicitly using Registry as the base
 };
 use tracing_appender::non_blocking::WorkerGuard; // Specific import for WorkerGuard
-use tracing_appender::rolling; // For file logging
-use xdg::BaseDirectories; // For log path
 
 // Import for the custom TUI tracing layer and its message type
 use crate::tui::tracing_layer::TuiLogCollectorLayer;
@@ -54,15 +60,25 @@ use tokio::sync::mpsc; // For the channel
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse CLI arguments first to decide logging strategy
-    let cli_args = Cli::parse();
+    let mut cli_args = Cli::parse();
+    if cli_args.profile.is_none() {
+        cli_args.profile = config::profile_from_env(); // `--profile` wins over HYDRAVISOR_PROFILE
+    }
 
     // Determine if TUI is likely to run
-    let tui_mode = cli_args.command.is_none() && !cli_args.headless;
-
-    // Setup XDG directories for log path if needed
-    let xdg_dirs = BaseDirectories::with_prefix(APP_NAME)?;
-    let log_path = xdg_dirs.get_cache_home(); // Use cache home for logs
-    create_dir_all(&log_path)?; // Ensure log directory exists
+    let tui_mode = cli_args.is_tui();
+
+    // `[logging]` decides where and how file logs are written, so the config is read before
+    // logging starts. A failure to load it is reported once the subscriber is up.
+    let loaded_config = Config::load(cli_args.config.as_deref(), cli_args.config_dir.as_deref(), cli_args.profile.as_deref());
+    let log_config = match &loaded_config {
+        Ok(cfg) => cfg.clone(),
+        // The profile names the log directory, so one that failed validation is left out
+        Err(_) => Config {
+            profile: cli_args.profile.clone().filter(|profile| config::validate_profile_name(profile).is_ok()),
+            ..Config::default()
+        },
+    };
 
     // Configure tracing subscriber
     let log_level_str = cli_args.log_level.to_string();
@@ -82,14 +98,11 @@ async fn main() -> Result<()> {
         tui_log_rx = Some(rx); // Store receiver for TUI
 
         // File logging layer
-        let file_appender = rolling::daily(&log_path, format!("{}.log", APP_NAME));
+        let file_appender = logging::system_log_appender(&log_config)?;
         let (non_blocking_writer, guard) = tracing_appender::non_blocking(file_appender);
         _file_worker_guard = Some(guard); // Store the guard
 
-        let file_layer = fmt::layer()
-            .with_writer(non_blocking_writer)
-            .with_ansi(false) // No ANSI colors in file logs
-            .json(); // Use JSON format for file logs (requires 'json' feature on tracing-subscriber)
+        let file_layer = logging::file_layer(&log_config, non_blocking_writer);
 
         // Custom TUI log collector layer
         let tui_collector_layer = TuiLogCollectorLayer::new(tx);
@@ -103,7 +116,7 @@ async fn main() -> Result<()> {
         // ... existing code ...
 
         // Original info log about TUI mode and log file path
-        info!("TUI mode detected. Logging to file and TUI. Log file: {:?}", log_path.join(format!("{}.log", APP_NAME)));
+        info!("TUI mode detected. Logging to file and TUI. Log file: {:?}", log_config.system_log_dir().join(logging::system_log_file_name()));
     } else {
         // Standard FmtSubscriber for console output
         tui_log_rx = None; // No receiver in non-TUI mode
@@ -118,12 +131,22 @@ async fn main() -> Result<()> {
 
     info!("Hydravisor initializing...");
 
+    // `config` subcommands work on the file itself, so they run even when it can't be loaded
+    if let Some(cli::Commands::Config(config_cmd)) = &cli_args.command {
+        return cli::handle_config_command(
+            config_cmd,
+            cli_args.config.as_deref(),
+            cli_args.config_dir.as_deref(),
+            cli_args.profile.as_deref(),
+        );
+    }
+
     // Load configuration
-    let config = match Config::load(cli_args.config.as_deref()) {
+    let config = match loaded_config {
         Ok(cfg) => Arc::new(cfg), // Wrap in Arc for sharing
         Err(e) => {
-            error!("Failed to load configuration: {}", e);
-            if cli_args.config.is_some() {
+            error!("Failed to load configuration: {:#}", e);
+            if cli_args.config.is_some() || cli_args.config_dir.is_some() || cli_args.profile.is_some() {
                 return Err(e.into());
             }
             warn!("Proceeding with default configuration due to error: {}", e);
@@ -134,6 +157,15 @@ async fn main() -> Result<()> {
     info!("Configuration loaded. Effective log level controlled by RUST_LOG, CLI (--log-level), or default.");
     debug!("Loaded app config: {:?}", config);
 
+    // Long-running modes enforce `retain_days`; one-off commands leave the logs alone
+    if cli_args.is_long_running() {
+        match logging::prune_old_logs(&config) {
+            Ok(0) => {}
+            Ok(pruned) => info!("Pruned {} log file(s) older than {} days.", pruned, config.logging.retain_days),
+            Err(e) => warn!("Failed to prune old logs: {:#}", e),
+        }
+    }
+
     // Initialize core components (Order might matter due to dependencies)
     let policy_engine = match PolicyEngine::load(&config) {
         Ok(engine) => Arc::new(engine),
@@ -159,8 +191,11 @@ async fn main() -> Result<()> {
     // AuditEngine might depend on config.logging.log_dir for its paths
     let audit_engine = Arc::new(AuditEngine::new(&config)?);
     info!("Audit Engine initialized.");
+    if let Err(e) = audit_engine.record_event(AuditEvent::new(AuditEventType::SystemStart, Some(RiskLevel::Info))) {
+        error!("Failed to record startup in the audit ledger: {}", e);
+    }
 
-    let libvirt_manager = match LibvirtManager::new(&config) {
+    let libvirt_manager = match LibvirtManager::new(&config, Arc::clone(&audit_engine), Arc::clone(&policy_engine)) {
         Ok(manager) => Arc::new(Mutex::new(manager)),
         Err(e) => {
             error!("Failed to initialize Libvirt Manager: {}", e);
@@ -193,7 +228,8 @@ async fn main() -> Result<()> {
     #[cfg(feature = "bedrock_integration")]
     let bedrock_manager = {
         let aws_region = config.providers.bedrock.region.clone();
-        match BedrockManager::new(Some(aws_region)).await {
+        let aws_profile = config.providers.bedrock.profile.clone();
+        match BedrockManager::new(Some(aws_region), Some(aws_profile)).await {
             Ok(manager) => {
                 info!("Bedrock Manager initialized.");
                 Arc::new(Mutex::new(manager))
@@ -206,7 +242,7 @@ async fn main() -> Result<()> {
                 // A more robust solution might involve a dummy manager.
                 // For simplicity, we'll let the app proceed.
                 // In a real-world scenario, you might want to handle this more gracefully.
-                Arc::new(Mutex::new(BedrockManager::new(None).await?)) // Simplified for now
+                Arc::new(Mutex::new(BedrockManager::new(None, None).await?)) // Simplified for now
             }
         }
     };
@@ -220,12 +256,47 @@ async fn main() -> Result<()> {
     };
     info!("Session Manager initialized.");
 
-    // McpServer will be initialized and started on demand via CLI or TUI action.
+    // McpServer is started on demand with `hydravisor mcp serve`.
+
+    // Only long-running modes serve metrics; a one-off command would exit before anyone scraped it
+    if config.metrics.enabled && cli_args.is_long_running() {
+        #[cfg(feature = "metrics")]
+        {
+            let sources = metrics::MetricsSources {
+                libvirt_manager: Arc::clone(&libvirt_manager),
+                ollama_manager: Arc::clone(&ollama_manager),
+                #[cfg(feature = "bedrock_integration")]
+                bedrock_manager: Arc::clone(&bedrock_manager),
+                audit_engine: Arc::clone(&audit_engine),
+            };
+            // A metrics endpoint that can't bind shouldn't stop Hydravisor itself
+            match metrics::serve(&config.metrics.bind_addr, sources).await {
+                Ok(addr) => info!("Serving metrics at http://{}/metrics", addr),
+                Err(e) => error!("Metrics endpoint disabled: {:#}", e),
+            }
+        }
+        #[cfg(not(feature = "metrics"))]
+        warn!("metrics.enabled is set, but this build lacks the `metrics` feature; no metrics endpoint will be served.");
+    }
+
+    // Long-running modes stop idle VMs, if `[libvirt] idle_shutdown_minutes` is set
+    let _idle_shutdown = if cli_args.is_long_running() && !cli_args.read_only { idle_shutdown::spawn(Arc::clone(&session_manager)) } else { None };
 
     // Dispatch based on CLI arguments
-    if let Some(command) = cli_args.command {
+    let output_format = cli_args.output_format();
+    // `attach` takes its VMs from a running daemon rather than this process's libvirt connection
+    let attach_socket = match &cli_args.command {
+        Some(cli::Commands::Attach { socket }) => Some(
+            socket
+                .clone()
+                .unwrap_or_else(|| std::path::PathBuf::from(shellexpand::tilde(&config.mcp.socket_path).into_owned())),
+        ),
+        _ => None,
+    };
+    if let Some(command) = cli_args.command.filter(|_| !tui_mode) {
         cli::handle_command(
             command, // CliCommand enum variant
+            output_format,
             Arc::clone(&config),
             Arc::clone(&policy_engine),
             // SshManager is not currently taken by handle_command, will add later if needed by subcommands
@@ -237,9 +308,17 @@ async fn main() -> Result<()> {
             // Arc::clone(&ollama_manager),
         )
         .await?;
-    } else if !cli_args.headless { // Use cli_args.headless
-        // Launch TUI if no subcommand and not headless
-        info!("No subcommand provided and not headless, launching TUI.");
+    } else if tui_mode {
+        // The daemon owns the VMs, so an attached TUI only watches them
+        let daemon = match attach_socket {
+            Some(socket_path) => {
+                let daemon = tui::daemon::DaemonLink::connect(socket_path, std::time::Duration::from_millis(config.mcp.timeout_ms)).await?;
+                info!("Attached to the Hydravisor daemon at {:?}.", daemon.socket_path());
+                Some(daemon)
+            }
+            None => None,
+        };
+        info!("Launching TUI.");
         crate::tui::run_tui(
             // No longer passing the handle
             Arc::clone(&config),
@@ -247,16 +326,21 @@ async fn main() -> Result<()> {
             Arc::clone(&policy_engine),
             Arc::clone(&libvirt_manager),
             Arc::clone(&audit_engine),
+            Arc::clone(&ssh_manager),
             Arc::clone(&ollama_manager),
             #[cfg(feature = "bedrock_integration")]
             Arc::clone(&bedrock_manager),
+            cli_args.read_only || daemon.is_some(),
+            daemon,
             tui_log_rx.expect("Log receiver should exist in TUI mode"), // Pass receiver
         )
         .await?; // run_tui is now async
+        if let Err(e) = audit_engine.record_event(AuditEvent::new(AuditEventType::SystemShutdown, Some(RiskLevel::Info))) {
+            error!("Failed to record shutdown in the audit ledger: {}", e);
+        }
+        audit_engine.flush()?;
     } else {
-        info!("No subcommand provided and running in headless mode. Exiting.");
-        // Optionally, print help here using clap if no command is given
-        // cli::Cli::command().print_help()?;
+        cli::run_daemon(Arc::clone(&config), Arc::clone(&session_manager), Arc::clone(&audit_engine)).await?;
     }
 
     info!("Hydravisor shutting down.");
@@ -0,0 +1,406 @@
+// src/mcp.rs
+// MCP server: accepts line-delimited JSON requests from agents over a unix socket and
+// dispatches them to the session manager. Also a client for talking to it.
+
+use anyhow::{anyhow, Result};
+use serde_json::json;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::api::{McpMessage, McpMeta};
+use crate::audit::{AuditEvent, AuditEventType, RiskLevel};
+use crate::config::Config;
+use crate::errors::HydraError;
+use crate::libvirt_manager::{parse_ram_str, ExistingDisk, VmConfig};
+use crate::policy::{AuthDecision, PolicyAction};
+use crate::session_manager::SessionManager;
+
+// Name the server uses for itself in audit records
+const SERVER_ENTITY: &str = "hydravisor";
+
+pub struct McpServer {
+    socket_path: PathBuf,
+    accept_task: JoinHandle<()>,
+}
+
+impl McpServer {
+    /// Binds the configured socket and starts accepting connections in the background.
+    /// A leftover socket from a previous run is replaced; a live one is an error.
+    pub async fn start(config: Arc<Config>, session_manager: Arc<SessionManager>) -> Result<Self> {
+        let socket_path = PathBuf::from(shellexpand::tilde(&config.mcp.socket_path).into_owned());
+        if socket_path.exists() {
+            if UnixStream::connect(&socket_path).await.is_ok() {
+                return Err(anyhow!("Another MCP server is already listening on {:?}", socket_path));
+            }
+            std::fs::remove_file(&socket_path)
+                .map_err(|e| anyhow!("Failed to remove stale MCP socket {:?}: {}", socket_path, e))?;
+        }
+        let listener = UnixListener::bind(&socket_path)
+            .map_err(|e| anyhow!("Failed to bind MCP socket {:?}: {}", socket_path, e))?;
+        // Only the owning user may drive the sandbox
+        std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600))?;
+        info!("MCP server listening on {:?}", socket_path);
+
+        let accept_task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _addr)) => {
+                        let config = Arc::clone(&config);
+                        let session_manager = Arc::clone(&session_manager);
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(stream, &config, &session_manager).await {
+                                warn!("MCP connection closed with error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        error!("Failed to accept MCP connection: {}", e);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        });
+
+        Ok(McpServer { socket_path, accept_task })
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Stops accepting connections and removes the socket file.
+    pub fn shutdown(self) {
+        self.accept_task.abort();
+        if let Err(e) = std::fs::remove_file(&self.socket_path) {
+            warn!("Failed to remove MCP socket {:?}: {}", self.socket_path, e);
+        }
+        info!("MCP server stopped.");
+    }
+}
+
+/// Serves one client: answers each request line in order and sends a heartbeat every
+/// `heartbeat_interval` seconds until the client disconnects.
+async fn handle_connection(stream: UnixStream, config: &Config, session_manager: &SessionManager) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let write_timeout = Duration::from_millis(config.mcp.timeout_ms);
+    let mut heartbeat = tokio::time::interval(Duration::from_secs(config.mcp.heartbeat_interval.max(1)));
+    heartbeat.tick().await; // The first tick completes immediately
+
+    debug!("MCP client connected.");
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line? else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let (response, src) = handle_request(&line, session_manager).await;
+                send_message(&mut writer, &response, write_timeout).await?;
+                record_outbound(session_manager, &response, src);
+            }
+            _ = heartbeat.tick() => {
+                // Keepalives are not audited; they carry no request
+                send_message(&mut writer, &McpMessage::heartbeat(), write_timeout).await?;
+            }
+        }
+    }
+    debug!("MCP client disconnected.");
+    Ok(())
+}
+
+/// Writes one message as a JSON line, giving up after `timeout` so a stalled client can't wedge the server.
+async fn send_message(writer: &mut OwnedWriteHalf, message: &McpMessage, timeout: Duration) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    tokio::time::timeout(timeout, writer.write_all(line.as_bytes()))
+        .await
+        .map_err(|_| anyhow!("Timed out writing to MCP client"))??;
+    Ok(())
+}
+
+/// Parses and dispatches one request line. Returns the response and the requesting agent, if known.
+async fn handle_request(line: &str, session_manager: &SessionManager) -> (McpMessage, Option<String>) {
+    let request: McpMessage = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = format!("Malformed MCP message: {}", e);
+            record_inbound(session_manager, None, "unknown", false, Some(error.clone()));
+            return (McpMessage::error(None, 400, error), None);
+        }
+    };
+    record_inbound(session_manager, request.src.clone(), &request.r#type, true, None);
+
+    let response = match dispatch(&request, session_manager).await {
+        Ok(response) => response,
+        Err((code, message)) => McpMessage::error(request.id.clone(), code, message),
+    };
+    (response, request.src)
+}
+
+type McpResult = std::result::Result<McpMessage, (u16, String)>;
+
+async fn dispatch(request: &McpMessage, session_manager: &SessionManager) -> McpResult {
+    if request.r#type == "mcp/heartbeat" {
+        return Ok(McpMessage::response(request, json!({})));
+    }
+    // Deny by default: every other request must say which agent is asking
+    let agent_id = request.src.as_deref().ok_or((400, "Missing 'src' (agent id)".to_string()))?;
+    let message_type = canonical_message_type(&request.r#type);
+
+    let response = match message_type {
+        "agent/list" => {
+            // Other agents' roles and sessions are only for agents the policy knows
+            if session_manager.policy_engine().agent_role(agent_id).is_none() {
+                return Err((403, format!("Access denied: agent '{}' has no entry in [permissions]", agent_id)));
+            }
+            McpMessage::response(request, json!(session_manager.agent_summaries().await))
+        }
+        "vm/list" => {
+            let vms = session_manager.list_vms().await.map_err(internal_error)?;
+            McpMessage::response(request, json!(vms))
+        }
+        "vm/create" => {
+            let vm_config = vm_config_from_request(request, agent_id, session_manager)?;
+            authorize(session_manager, agent_id, PolicyAction::Create, &vm_config.instance_id)?;
+            let status = session_manager.create_vm(vm_config).await.map_err(internal_error)?;
+            McpMessage::response(request, json!(status))
+        }
+        "vm/delete" => {
+            let instance_id = required_instance_id(request)?;
+            authorize(session_manager, agent_id, PolicyAction::Destroy, instance_id)?;
+            session_manager.destroy_vm(instance_id).await.map_err(internal_error)?;
+            McpMessage::response(request, json!({ "instance_id": instance_id }))
+        }
+        "vm/attach-terminal" => {
+            let instance_id = required_instance_id(request)?;
+            authorize(session_manager, agent_id, PolicyAction::AttachTerminal, instance_id)?;
+            let (session, vm) = session_manager.attach_session(agent_id, instance_id).await.map_err(internal_error)?;
+            McpMessage::response(request, json!({ "session": session, "ip_address": vm.ip_address }))
+        }
+        other => return Err((400, format!("Unsupported MCP message type '{}'", other))),
+    };
+    // Only requests that got through count as activity; asking about agents doesn't count at all
+    if !message_type.starts_with("agent/") {
+        session_manager.record_agent_activity(agent_id, message_type).await;
+    }
+    Ok(response)
+}
+
+// The `vm/*` name of a request type, accepting the method names agents may know them by
+pub fn canonical_message_type(message_type: &str) -> &str {
+    match message_type {
+        "list_vms" => "vm/list",
+        "create_vm" => "vm/create",
+        "destroy_vm" => "vm/delete",
+        "attach_session" => "vm/attach-terminal",
+        other => other,
+    }
+}
+
+fn authorize(session_manager: &SessionManager, agent_id: &str, action: PolicyAction, resource: &str) -> std::result::Result<(), (u16, String)> {
+    match session_manager.authorize(agent_id, action, Some(resource)) {
+        AuthDecision::Allowed => Ok(()),
+        AuthDecision::Denied { reason } => Err((403, format!("Access denied: {}", reason))),
+    }
+}
+
+fn required_instance_id(request: &McpMessage) -> std::result::Result<&str, (u16, String)> {
+    request.instance_id.as_deref().ok_or((400, "Missing 'instance_id'".to_string()))
+}
+
+// A failed manager call: its HydraError's code when it has one (404 for a missing VM, 503 when
+// libvirt is down, ...), otherwise 500
+fn internal_error(e: anyhow::Error) -> (u16, String) {
+    (HydraError::find(&e).map_or(500, HydraError::status_code), e.to_string())
+}
+
+/// Builds a VM config from a `vm/create` request, filling gaps from the config defaults and
+/// checking CPU and memory against the requesting agent's role limits.
+fn vm_config_from_request(request: &McpMessage, agent_id: &str, session_manager: &SessionManager) -> std::result::Result<VmConfig, (u16, String)> {
+    let defaults = &session_manager.app_config().defaults;
+    let memory_mb = request
+        .ram
+        .as_deref()
+        .map(parse_ram_str)
+        .transpose()
+        .map_err(|e| (400, format!("Invalid 'ram': {}", e)))?;
+    let (cpu_cores, memory_mb) = session_manager
+        .policy_engine()
+        .resolve_vm_resources(session_manager.policy_engine().agent_role(agent_id), request.cpu, memory_mb)
+        .map_err(internal_error)?;
+    let instance_id = request
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.name.clone())
+        .unwrap_or_else(|| format!("mcp-{}", uuid::Uuid::new_v4().simple()));
+
+    Ok(VmConfig {
+        instance_id,
+        base_image: request.os.clone().or_else(|| defaults.default_source_image.clone()).unwrap_or_default(),
+        boot_iso: None,
+        cpu_cores,
+        memory_mb,
+        disk_gb: Some(defaults.default_disk_gb),
+        disk_path: None,
+        network_policy: "default".to_string(),
+        security_policy: "default".to_string(),
+        custom_script: None,
+        template_name: None,
+        labels: None,
+        persistent: true,
+        cloud_init: None,
+        existing_disk: ExistingDisk::Refuse,
+    })
+}
+
+fn record_inbound(session_manager: &SessionManager, src: Option<String>, message_type: &str, success: bool, error: Option<String>) {
+    let mut event = AuditEvent::new(
+        AuditEventType::McpMessageInbound {
+            source: src.clone().unwrap_or_else(|| "unknown".to_string()),
+            dest: SERVER_ENTITY.to_string(),
+            message_type: message_type.to_string(),
+            success,
+            error,
+        },
+        Some(if success { RiskLevel::Info } else { RiskLevel::Low }),
+    );
+    event.agent_id = src;
+    session_manager.record_audit_event(event);
+}
+
+fn record_outbound(session_manager: &SessionManager, response: &McpMessage, dest: Option<String>) {
+    let success = response.ok.unwrap_or(false);
+    let mut event = AuditEvent::new(
+        AuditEventType::McpMessageOutbound {
+            source: SERVER_ENTITY.to_string(),
+            dest: dest.clone().unwrap_or_else(|| "unknown".to_string()),
+            message_type: response.r#type.clone(),
+            success,
+            error: response.message.clone(),
+        },
+        Some(if success { RiskLevel::Info } else { RiskLevel::Low }),
+    );
+    event.agent_id = dest;
+    session_manager.record_audit_event(event);
+}
+
+pub struct McpClient {
+    lines: tokio::io::Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    agent_id: String,
+    timeout: Duration,
+    next_id: u64,
+}
+
+impl McpClient {
+    /// Connects to an MCP server, identifying as `agent_id`. Connecting and every call
+    /// give up after `timeout`.
+    pub async fn connect(socket_path: &Path, agent_id: impl Into<String>, timeout: Duration) -> Result<Self> {
+        let stream = tokio::time::timeout(timeout, UnixStream::connect(socket_path))
+            .await
+            .map_err(|_| anyhow!("Timed out connecting to MCP server at {:?}", socket_path))?
+            .map_err(|e| anyhow!("Failed to connect to MCP server at {:?}: {}", socket_path, e))?;
+        let (reader, writer) = stream.into_split();
+        Ok(McpClient {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            agent_id: agent_id.into(),
+            timeout,
+            next_id: 1,
+        })
+    }
+
+    /// Sends a request and waits for its response, skipping heartbeats. The client fills in
+    /// `id` and, if unset, `src`. An `mcp/error` response is returned as an error.
+    pub async fn call(&mut self, mut request: McpMessage) -> Result<McpMessage> {
+        let id = json!(self.next_id);
+        self.next_id += 1;
+        request.id = Some(id.clone());
+        if request.src.is_none() {
+            request.src = Some(self.agent_id.clone());
+        }
+        send_message(&mut self.writer, &request, self.timeout).await?;
+
+        let response = tokio::time::timeout(self.timeout, async {
+            loop {
+                let line = self.lines.next_line().await?
+                    .ok_or_else(|| anyhow!("MCP server closed the connection"))?;
+                let message: McpMessage = serde_json::from_str(&line)
+                    .map_err(|e| anyhow!("Malformed message from MCP server: {}", e))?;
+                // Heartbeats and replies to other requests are not ours
+                if message.id.as_ref() == Some(&id) || (message.id.is_none() && message.r#type == "mcp/error") {
+                    return Ok::<_, anyhow::Error>(message);
+                }
+            }
+        })
+        .await
+        .map_err(|_| anyhow!("Timed out waiting for MCP response to '{}'", request.r#type))??;
+
+        if response.r#type == "mcp/error" {
+            return Err(anyhow!(
+                "MCP error {}: {}",
+                response.code.unwrap_or(0),
+                response.message.as_deref().unwrap_or("unknown error")
+            ));
+        }
+        Ok(response)
+    }
+}
+
+// Typed wrappers mirroring the server's dispatch table
+impl McpClient {
+    async fn call_type(&mut self, message_type: &str, request: McpMessage) -> Result<serde_json::Value> {
+        let response = self.call(McpMessage { r#type: message_type.to_string(), ..request }).await?;
+        Ok(response.payload.unwrap_or(serde_json::Value::Null))
+    }
+
+    pub async fn heartbeat(&mut self) -> Result<()> {
+        self.call_type("mcp/heartbeat", McpMessage::default()).await.map(|_| ())
+    }
+
+    pub async fn list_agents(&mut self) -> Result<Vec<crate::session_manager::AgentSummary>> {
+        let payload = self.call_type("agent/list", McpMessage::default()).await?;
+        Ok(serde_json::from_value(payload)?)
+    }
+
+    pub async fn list_vms(&mut self) -> Result<Vec<crate::libvirt_manager::VmStatus>> {
+        let payload = self.call_type("vm/list", McpMessage::default()).await?;
+        Ok(serde_json::from_value(payload)?)
+    }
+
+    pub async fn create_vm(&mut self, name: Option<String>, os: Option<String>, cpu: Option<u32>, ram: Option<String>) -> Result<crate::libvirt_manager::VmStatus> {
+        let request = McpMessage {
+            os,
+            cpu,
+            ram,
+            meta: name.map(|name| McpMeta { name: Some(name), ..Default::default() }),
+            ..Default::default()
+        };
+        let payload = self.call_type("vm/create", request).await?;
+        Ok(serde_json::from_value(payload)?)
+    }
+
+    pub async fn destroy_vm(&mut self, instance_id: &str) -> Result<()> {
+        let request = McpMessage { instance_id: Some(instance_id.to_string()), ..Default::default() };
+        self.call_type("vm/delete", request).await.map(|_| ())
+    }
+
+    /// Returns the new session and the VM's IP address, if known.
+    pub async fn attach_session(&mut self, instance_id: &str) -> Result<serde_json::Value> {
+        let request = McpMessage { instance_id: Some(instance_id.to_string()), ..Default::default() };
+        self.call_type("vm/attach-terminal", request).await
+    }
+}
+
+// TODO: Add tests for McpServer:
+// - Request parsing and 400 responses for malformed or incomplete messages.
+// - Policy denials surfacing as 403 `mcp/error` responses.
+// - Heartbeats on idle connections.
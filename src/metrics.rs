@@ -0,0 +1,171 @@
+// src/metrics.rs
+// Prometheus text-format metrics for monitoring a long-running Hydravisor. With the `metrics`
+// feature and `[metrics] enabled = true`, they are served at `http://<bind_addr>/metrics`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Chat responses currently streaming. The TUI owns the streams, so they are counted here
+// rather than read from a manager.
+static ACTIVE_CHAT_STREAMS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a chat stream as active for as long as the guard is alive, including when its
+/// task is aborted.
+pub struct ChatStreamGuard(());
+
+impl ChatStreamGuard {
+    pub fn new() -> Self {
+        ACTIVE_CHAT_STREAMS.fetch_add(1, Ordering::Relaxed);
+        ChatStreamGuard(())
+    }
+}
+
+impl Drop for ChatStreamGuard {
+    fn drop(&mut self) {
+        ACTIVE_CHAT_STREAMS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "metrics")]
+pub use server::{serve, MetricsSources};
+
+#[cfg(feature = "metrics")]
+mod server {
+    use anyhow::{Context, Result};
+    use http_body_util::Full;
+    use hyper::body::Bytes;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper::{Method, Request, Response, StatusCode};
+    use hyper_util::rt::TokioIo;
+    use std::collections::BTreeMap;
+    use std::fmt::Write;
+    use std::net::SocketAddr;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+    use tracing::{debug, warn};
+
+    use super::ACTIVE_CHAT_STREAMS;
+    use crate::audit::AuditEngine;
+    use crate::libvirt_manager::{LibvirtManager, VmState};
+    use crate::ollama_manager::OllamaManager;
+    #[cfg(feature = "bedrock_integration")]
+    use crate::bedrock_manager::BedrockManager;
+
+    /// The managers whose state is exported. Everything is read fresh on each scrape.
+    pub struct MetricsSources {
+        pub libvirt_manager: Arc<Mutex<LibvirtManager>>,
+        pub ollama_manager: Arc<Mutex<OllamaManager>>,
+        #[cfg(feature = "bedrock_integration")]
+        pub bedrock_manager: Arc<Mutex<BedrockManager>>,
+        pub audit_engine: Arc<AuditEngine>,
+    }
+
+    impl MetricsSources {
+        async fn gather(&self) -> String {
+            let (libvirt_connected, vms) = match LibvirtManager::list_vms_async(Arc::clone(&self.libvirt_manager)).await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!("Metrics: failed to list VMs: {}", e);
+                    (false, Vec::new())
+                }
+            };
+            // Every state is reported, so a state dropping to zero doesn't look like a missing series
+            let mut vms_by_state: BTreeMap<&str, usize> = [
+                "provisioning", "booting", "running", "suspended", "terminated", "stopped", "error", "unknown",
+            ]
+            .into_iter()
+            .map(|state| (state, 0))
+            .collect();
+            for vm in &vms {
+                *vms_by_state.entry(state_label(&vm.state)).or_default() += 1;
+            }
+            let ollama_connected = self.ollama_manager.lock().await.is_ollama_connected();
+            #[cfg(feature = "bedrock_integration")]
+            let bedrock_connected = Some(self.bedrock_manager.lock().await.is_bedrock_connected());
+            #[cfg(not(feature = "bedrock_integration"))]
+            let bedrock_connected: Option<bool> = None;
+
+            let mut out = String::new();
+            let _ = writeln!(out, "# HELP hydravisor_vms Number of VMs known to libvirt, by state.");
+            let _ = writeln!(out, "# TYPE hydravisor_vms gauge");
+            for (state, count) in &vms_by_state {
+                let _ = writeln!(out, "hydravisor_vms{{state=\"{}\"}} {}", state, count);
+            }
+            let _ = writeln!(out, "# HELP hydravisor_provider_connected Whether a backend is connected (1) or not (0).");
+            let _ = writeln!(out, "# TYPE hydravisor_provider_connected gauge");
+            let _ = writeln!(out, "hydravisor_provider_connected{{provider=\"libvirt\"}} {}", u8::from(libvirt_connected));
+            let _ = writeln!(out, "hydravisor_provider_connected{{provider=\"ollama\"}} {}", u8::from(ollama_connected));
+            if let Some(bedrock_connected) = bedrock_connected {
+                let _ = writeln!(out, "hydravisor_provider_connected{{provider=\"bedrock\"}} {}", u8::from(bedrock_connected));
+            }
+            let _ = writeln!(out, "# HELP hydravisor_audit_events_total Audit events written to the ledger since startup.");
+            let _ = writeln!(out, "# TYPE hydravisor_audit_events_total counter");
+            let _ = writeln!(out, "hydravisor_audit_events_total {}", self.audit_engine.events_written());
+            let _ = writeln!(out, "# HELP hydravisor_chat_streams_active Chat responses currently streaming.");
+            let _ = writeln!(out, "# TYPE hydravisor_chat_streams_active gauge");
+            let _ = writeln!(out, "hydravisor_chat_streams_active {}", ACTIVE_CHAT_STREAMS.load(Ordering::Relaxed));
+            out
+        }
+    }
+
+    fn state_label(state: &VmState) -> &'static str {
+        match state {
+            VmState::Provisioning => "provisioning",
+            VmState::Booting => "booting",
+            VmState::Running => "running",
+            VmState::Suspended => "suspended",
+            VmState::Terminated => "terminated",
+            VmState::Stopped => "stopped",
+            VmState::Error(_) => "error",
+            VmState::Unknown => "unknown",
+        }
+    }
+
+    async fn handle_request(request: Request<hyper::body::Incoming>, sources: Arc<MetricsSources>) -> Result<Response<Full<Bytes>>, std::convert::Infallible> {
+        let response = if request.method() == Method::GET && request.uri().path() == "/metrics" {
+            Response::builder()
+                .header("Content-Type", "text/plain; version=0.0.4")
+                .body(Full::new(Bytes::from(sources.gather().await)))
+        } else {
+            Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Full::new(Bytes::from_static(b"Not found. Metrics are served at /metrics.\n")))
+        };
+        Ok(response.expect("static response parts are valid"))
+    }
+
+    /// Binds `bind_addr` and serves `/metrics` in the background until the process exits.
+    /// Returns the bound address.
+    pub async fn serve(bind_addr: &str, sources: MetricsSources) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(bind_addr)
+            .await
+            .with_context(|| format!("Failed to bind metrics endpoint on {}", bind_addr))?;
+        let local_addr = listener.local_addr()?;
+        let sources = Arc::new(sources);
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        warn!("Metrics endpoint failed to accept a connection: {}", e);
+                        continue;
+                    }
+                };
+                let sources = Arc::clone(&sources);
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| handle_request(request, Arc::clone(&sources)));
+                    if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                        debug!("Metrics connection from {} ended with an error: {}", peer, e);
+                    }
+                });
+            }
+        });
+        Ok(local_addr)
+    }
+}
+
+// TODO: Add tests for metrics:
+// - ChatStreamGuard increments on creation and decrements on drop.
+// - The exposition output lists every VM state and parses as Prometheus text format.
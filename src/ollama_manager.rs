@@ -2,7 +2,9 @@
 // Manages interactions with the Ollama API
 
 use anyhow::Result;
-use crate::config::Config;
+use serde::Deserialize;
+use crate::config::{Config, ModelParams};
+use crate::errors::HydraError;
 use crate::tui::app::ChatMessage as TuiChatMessage;
 use tracing::{info, error, debug, warn}; // Added tracing macros
 
@@ -12,16 +14,58 @@ use ollama_rs::{
     models::LocalModel,
     generation::chat::{ChatMessage, ChatMessageResponse, MessageRole}, // Import MessageRole
     generation::chat::request::ChatMessageRequest,
-    // ollama_rs::error::OllamaError is no longer used directly here
+    generation::options::GenerationOptions,
+    error::OllamaError,
 };
 
 #[cfg(feature = "ollama_integration")]
 use futures::stream::StreamExt;
 
+#[cfg(feature = "ollama_integration")]
+const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+// A model currently loaded into memory, as reported by Ollama's `/api/ps` endpoint
+#[derive(Deserialize, Debug, Clone)]
+pub struct RunningModel {
+    pub name: String,
+    #[serde(default)]
+    pub size: u64, // Total bytes in memory
+    #[serde(default)]
+    pub size_vram: u64, // Bytes resident in VRAM
+    #[serde(default)]
+    pub expires_at: Option<String>, // ISO 8601 timestamp when Ollama will unload it
+}
+
+// One status update from a model download (`/api/pull`)
+#[derive(Debug, Clone)]
+pub struct PullProgress {
+    pub status: String,
+    pub completed: Option<u64>, // Bytes of the current layer downloaded
+    pub total: Option<u64>,     // Size of the current layer
+}
+
+impl PullProgress {
+    pub fn percent(&self) -> Option<u16> {
+        match (self.completed, self.total) {
+            (Some(completed), Some(total)) if total > 0 => Some((completed.min(total) * 100 / total) as u16),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "ollama_integration")]
+#[derive(Deserialize, Debug)]
+struct RunningModelsResponse {
+    #[serde(default)]
+    models: Vec<RunningModel>,
+}
+
 pub struct OllamaManager {
     #[cfg(feature = "ollama_integration")]
     client: Option<Ollama>,
     #[cfg(feature = "ollama_integration")]
+    http_client: reqwest::Client, // For endpoints ollama-rs doesn't wrap (e.g. /api/ps)
+    #[cfg(feature = "ollama_integration")]
     pub ollama_connected: bool,
     // We need a way to signal that ollama is not available even if the feature is compiled
     // if the client fails to initialize.
@@ -34,7 +78,7 @@ impl Default for OllamaManager {
         #[cfg(feature = "ollama_integration")]
         {
             warn!("Creating default (non-functional) OllamaManager due to earlier initialization issue or feature configuration.");
-            OllamaManager { client: None, ollama_connected: false }
+            OllamaManager { client: None, http_client: reqwest::Client::new(), ollama_connected: false }
         }
         #[cfg(not(feature = "ollama_integration"))]
         {
@@ -46,17 +90,58 @@ impl Default for OllamaManager {
 }
 
 #[cfg(feature = "ollama_integration")] // Helper function also needs this cfg
-fn map_stream_item_error(_err: ()) -> String { // Return String instead of OllamaError
-    "Error processing stream item from Ollama".to_string()
+fn map_stream_item_error(_err: ()) -> HydraError { // ollama-rs doesn't say what went wrong mid-stream
+    HydraError::StreamError("Error processing stream item from Ollama".to_string())
+}
+
+/// Sorts an ollama-rs error into daemon down, unknown model, or a request Ollama refused.
+/// ollama-rs flattens transport and API errors into one message, so this goes by its text.
+#[cfg(feature = "ollama_integration")]
+fn classify_ollama_error(model_name: Option<&str>, err: OllamaError) -> HydraError {
+    let message = err.to_string();
+    let message = message.strip_prefix("An error occurred with ollama-rs: ").unwrap_or(&message).to_string();
+    match model_name {
+        _ if message.contains("error sending request") || message.contains("onnection refused") => HydraError::OllamaUnavailable(message),
+        Some(model_name) if message.contains("not found") => HydraError::ModelNotFound(model_name.to_string()),
+        _ => HydraError::OllamaRequestFailed(message),
+    }
+}
+
+#[cfg(feature = "ollama_integration")]
+fn client_unavailable() -> HydraError {
+    HydraError::OllamaUnavailable("no connection was made at startup".to_string())
+}
+
+#[cfg(feature = "ollama_integration")]
+fn generation_options(params: &ModelParams) -> GenerationOptions {
+    let mut options = GenerationOptions::default();
+    if let Some(temperature) = params.temperature {
+        options = options.temperature(temperature);
+    }
+    if let Some(top_p) = params.top_p {
+        options = options.top_p(top_p);
+    }
+    if let Some(num_ctx) = params.num_ctx {
+        options = options.num_ctx(num_ctx);
+    }
+    if let Some(num_predict) = params.num_predict {
+        options = options.num_predict(num_predict);
+    }
+    if let Some(seed) = params.seed {
+        options = options.seed(seed);
+    }
+    options
 }
 
 impl OllamaManager {
     pub async fn new(app_config: &Config) -> Result<Self> {
         #[cfg(feature = "ollama_integration")]
         {
-            let ollama_host = app_config.ollama_host.clone().unwrap_or_else(|| "http://localhost".to_string());
-            let ollama_port = app_config.ollama_port.unwrap_or(11434);
-            
+            let (ollama_host, ollama_port) = app_config.ollama_endpoint()?;
+            if app_config.ollama_url.is_some() && (app_config.ollama_host.is_some() || app_config.ollama_port.is_some()) {
+                warn!("ollama_url is set, so ollama_host and ollama_port are ignored.");
+            }
+
             info!("Attempting to connect to Ollama at {}:{}", ollama_host, ollama_port);
             let client = Ollama::new(ollama_host, ollama_port);
 
@@ -72,7 +157,7 @@ impl OllamaManager {
             }
 
             info!("OllamaManager initialized. Ollama integration enabled.");
-            Ok(Self { client: Some(client), ollama_connected })
+            Ok(Self { client: Some(client), http_client: reqwest::Client::new(), ollama_connected })
         }
         
         #[cfg(not(feature = "ollama_integration"))]
@@ -92,6 +177,28 @@ impl OllamaManager {
         false
     }
 
+    /// Asks the daemon for its version, the cheapest call it answers, and updates the connected
+    /// flag from the result. The short timeout keeps an unreachable host from stalling the TUI.
+    #[cfg(feature = "ollama_integration")]
+    pub async fn check_health(&mut self) -> Result<()> {
+        let Some(client) = &self.client else {
+            return Err(client_unavailable().into());
+        };
+        let url = format!("{}/api/version", client.uri());
+        let result = self.http_client.get(&url).timeout(HEALTH_CHECK_TIMEOUT).send().await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|e| HydraError::OllamaUnavailable(format!("health check at {} failed: {}", url, e)).into());
+        self.ollama_connected = result.is_ok();
+        result
+    }
+
+    #[cfg(not(feature = "ollama_integration"))]
+    #[allow(clippy::unused_async)]
+    pub async fn check_health(&mut self) -> Result<()> {
+        Err(anyhow::anyhow!("Ollama integration not enabled"))
+    }
+
     #[cfg(feature = "ollama_integration")]
     pub async fn list_local_models(&self) -> Result<Vec<LocalModel>> {
         if let Some(client) = &self.client {
@@ -103,7 +210,7 @@ impl OllamaManager {
                 }
                 Err(e) => {
                     error!("Failed to list Ollama models: {}", e);
-                    Err(anyhow::anyhow!("Failed to list Ollama models: {}", e))
+                    Err(classify_ollama_error(None, e).into())
                 }
             }
         } else {
@@ -123,6 +230,67 @@ impl OllamaManager {
         Ok(Vec::new())
     }
 
+    #[cfg(feature = "ollama_integration")]
+    pub async fn list_running_models(&self) -> Result<Vec<RunningModel>> {
+        if let Some(client) = &self.client {
+            let url = format!("{}/api/ps", client.uri());
+            debug!("Listing running Ollama models from {}", url);
+            let response = self.http_client.get(&url).send().await
+                .map_err(|e| HydraError::OllamaUnavailable(e.to_string()))?;
+            if !response.status().is_success() {
+                // Older Ollama versions don't expose /api/ps; treat as "nothing known to be loaded"
+                return Err(anyhow::anyhow!("Ollama /api/ps returned status {}", response.status()));
+            }
+            let running: RunningModelsResponse = response.json().await
+                .map_err(|e| anyhow::anyhow!("Failed to parse running Ollama models: {}", e))?;
+            debug!("{} Ollama models currently loaded.", running.models.len());
+            Ok(running.models)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    #[cfg(not(feature = "ollama_integration"))]
+    #[allow(clippy::unused_async)]
+    pub async fn list_running_models(&self) -> Result<Vec<RunningModel>> {
+        Ok(Vec::new())
+    }
+
+    /// Starts downloading a model, streaming Ollama's status updates as it goes.
+    #[cfg(feature = "ollama_integration")]
+    pub async fn pull_model(&self, model_name: &str) -> Result<impl StreamExt<Item = Result<PullProgress, HydraError>>> {
+        let client = self.client.as_ref().ok_or_else(client_unavailable)?;
+        info!("Pulling Ollama model '{}'", model_name);
+        let stream = client.pull_model_stream(model_name.to_string(), false).await
+            .map_err(|e| classify_ollama_error(Some(model_name), e))?;
+        let model_name = model_name.to_string();
+        Ok(stream.map(move |item| {
+            item.map(|status| PullProgress { status: status.message, completed: status.completed, total: status.total })
+                .map_err(|e| classify_ollama_error(Some(&model_name), e))
+        }))
+    }
+
+    #[cfg(not(feature = "ollama_integration"))]
+    #[allow(clippy::unused_async)]
+    pub async fn pull_model(&self, model_name: &str) -> Result<futures::stream::Empty<Result<PullProgress, HydraError>>> {
+        Err(anyhow::anyhow!("Ollama integration not enabled; cannot pull model '{}'", model_name))
+    }
+
+    /// Removes a local model, freeing its disk space.
+    #[cfg(feature = "ollama_integration")]
+    pub async fn delete_model(&self, model_name: &str) -> Result<()> {
+        let client = self.client.as_ref().ok_or_else(client_unavailable)?;
+        info!("Deleting Ollama model '{}'", model_name);
+        client.delete_model(model_name.to_string()).await
+            .map_err(|e| classify_ollama_error(Some(model_name), e).into())
+    }
+
+    #[cfg(not(feature = "ollama_integration"))]
+    #[allow(clippy::unused_async)]
+    pub async fn delete_model(&self, model_name: &str) -> Result<()> {
+        Err(anyhow::anyhow!("Ollama integration not enabled; cannot delete model '{}'", model_name))
+    }
+
     // Placeholder for generate_response method
     #[cfg(feature = "ollama_integration")]
     pub async fn generate_response_stream(
@@ -130,7 +298,8 @@ impl OllamaManager {
         model_name_param: String,
         history: Vec<TuiChatMessage>, 
         system_prompt_override: Option<String>,
-    ) -> Result<impl StreamExt<Item = Result<String, String>>> { // Item error type changed to String
+        params: ModelParams,
+    ) -> Result<impl StreamExt<Item = Result<String, HydraError>>> {
         if let Some(client) = &self.client {
             let mut ollama_messages: Vec<ChatMessage> = Vec::new();
 
@@ -174,14 +343,18 @@ impl OllamaManager {
                 ollama_messages.last().map_or("N/A", |m| m.content.as_str())
             );
             
-            let chat_request = ChatMessageRequest::new(model_name_param.clone(), ollama_messages);
+            let mut chat_request = ChatMessageRequest::new(model_name_param.clone(), ollama_messages);
+            if !params.is_empty() {
+                debug!("Using generation params for {}: {}", model_name_param, params.to_edit_string());
+                chat_request = chat_request.options(generation_options(&params));
+            }
 
             match client.send_chat_messages_stream(chat_request).await {
                 Ok(ollama_stream) => {
                     debug!("Successfully started chat messages stream for model: {}", model_name_param);
                     Ok(ollama_stream.map(|result_chat_message_response: Result<ChatMessageResponse, ()>| {
                         result_chat_message_response
-                            .map_err(map_stream_item_error) // This now returns String
+                            .map_err(map_stream_item_error)
                             .map(|chat_message_response| {
                                 chat_message_response.message.map_or_else(String::new, |chat_msg| chat_msg.content)
                         })
@@ -189,12 +362,12 @@ impl OllamaManager {
                 },
                 Err(e) => {
                     error!("Failed to start chat messages stream for model {}: {}", model_name_param, e);
-                    Err(anyhow::anyhow!("Failed to start chat messages stream for model {}: {}", model_name_param, e))
+                    Err(classify_ollama_error(Some(&model_name_param), e).into())
                 }
             }
         } else {
             warn!("Ollama client not available for generation.");
-             Err(anyhow::anyhow!("Ollama client not available"))
+             Err(client_unavailable().into())
         }
     }
 
@@ -205,7 +378,8 @@ impl OllamaManager {
         model_name: String,
         history: Vec<TuiChatMessage>,
         system_prompt_override: Option<String>,
-    ) -> Result<futures::stream::Empty<Result<String, String>>> { // Changed OllamaError to String for cfg-disabled case
+        _params: ModelParams,
+    ) -> Result<futures::stream::Empty<Result<String, HydraError>>> {
         let last_prompt = history.last().map_or("N/A", |m| m.content.as_str());
         warn!(
             "Ollama integration not enabled. Cannot generate stream for model: {}, system_prompt: {:?}, last user prompt: {}.", 
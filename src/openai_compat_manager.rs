@@ -0,0 +1,289 @@
+// src/openai_compat_manager.rs
+// Manages interactions with servers that speak the OpenAI chat completions API (vLLM, LM Studio, ...)
+
+#![cfg(feature = "openai_compat")]
+
+use anyhow::Result;
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use tracing::{info, error, debug};
+
+use crate::config::{ModelParams, OpenAiCompatConfig};
+use crate::errors::HydraError;
+use crate::tui::app::ChatMessage as TuiChatMessage;
+
+// Environment variable read when `providers.openai_compat.api_key` is unset
+pub const API_KEY_ENV_VAR: &str = "OPENAI_API_KEY";
+
+// Model listing is awaited by the TUI's event loop, so an unreachable server must fail fast.
+// Chat streams only get the connect timeout: a long reply can take minutes.
+const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+const LIST_MODELS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+// A model the server offers, from `GET /models` or the configured `models` list
+#[derive(Deserialize, Debug, Clone)]
+pub struct OpenAiModel {
+    pub id: String,
+    #[serde(default)]
+    pub owned_by: Option<String>,
+    #[serde(default)]
+    pub created: Option<i64>, // Unix timestamp
+    #[serde(skip)]
+    pub from_config: bool, // Listed in config.toml but not reported by the server
+}
+
+#[derive(Deserialize, Debug)]
+struct ModelsResponse {
+    #[serde(default)]
+    data: Vec<OpenAiModel>,
+}
+
+#[derive(Serialize, Debug)]
+struct RequestMessage {
+    role: &'static str,
+    content: String,
+}
+
+// Body of `POST /chat/completions`. Unset params are left out so the server's defaults apply.
+#[derive(Serialize, Debug)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<RequestMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<i32>,
+}
+
+// One server-sent event of a streamed completion
+#[derive(Deserialize, Debug)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    #[serde(default)]
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChunkChoice {
+    #[serde(default)]
+    delta: ChunkDelta,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ChunkDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ApiError {
+    message: String,
+}
+
+// What one line of the event stream means for the reply
+enum SseLine {
+    Text(String),
+    Error(String),
+    Done,
+    Skip, // Comments, blank keep-alives, role-only deltas
+}
+
+fn parse_sse_line(line: &str) -> SseLine {
+    let Some(payload) = line.strip_prefix("data:").map(str::trim) else {
+        return SseLine::Skip;
+    };
+    if payload == "[DONE]" {
+        return SseLine::Done;
+    }
+    match serde_json::from_str::<ChatCompletionChunk>(payload) {
+        Ok(ChatCompletionChunk { error: Some(e), .. }) => SseLine::Error(e.message),
+        Ok(chunk) => {
+            let text: String = chunk.choices.into_iter().filter_map(|choice| choice.delta.content).collect();
+            if text.is_empty() { SseLine::Skip } else { SseLine::Text(text) }
+        }
+        Err(e) => SseLine::Error(format!("Unreadable stream event: {}", e)),
+    }
+}
+
+// Reads the response body and splits it into events; dropped after the first error
+struct EventStreamState {
+    response: Option<reqwest::Response>,
+    buffer: Vec<u8>,
+}
+
+pub struct OpenAiCompatManager {
+    http_client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    configured_models: Vec<String>,
+    pub enabled: bool,
+    pub openai_connected: bool,
+}
+
+impl OpenAiCompatManager {
+    /// Builds a client for the configured server. Nothing is sent until models are listed.
+    pub fn new(provider_config: &OpenAiCompatConfig) -> Self {
+        let api_key = provider_config.api_key.clone()
+            .or_else(|| std::env::var(API_KEY_ENV_VAR).ok())
+            .filter(|key| !key.is_empty());
+        info!(
+            "OpenAiCompatManager initialized for {} ({}).",
+            provider_config.base_url,
+            if provider_config.enabled { "enabled" } else { "disabled in config" }
+        );
+        let http_client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .unwrap_or_else(|e| {
+                error!("Failed to build the OpenAI-compatible HTTP client, using defaults: {}", e);
+                reqwest::Client::new()
+            });
+        Self {
+            http_client,
+            base_url: provider_config.base_url.trim_end_matches('/').to_string(),
+            api_key,
+            configured_models: provider_config.models.clone(),
+            enabled: provider_config.enabled,
+            openai_connected: false,
+        }
+    }
+
+    pub fn is_openai_connected(&self) -> bool {
+        self.openai_connected
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self.http_client.request(method, format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// Lists the server's models plus configured ones it didn't report. The connection state
+    /// follows whether `/models` answered.
+    pub async fn list_local_models(&mut self) -> Result<Vec<OpenAiModel>> {
+        if !self.enabled {
+            debug!("OpenAI-compatible provider disabled in config; not listing models.");
+            return Ok(Vec::new());
+        }
+        debug!("Listing models from {}/models", self.base_url);
+        let listed = async {
+            let response = self.request(reqwest::Method::GET, "/models").timeout(LIST_MODELS_TIMEOUT).send().await?.error_for_status()?;
+            response.json::<ModelsResponse>().await
+        }
+        .await;
+        let mut models = match listed {
+            Ok(response) => {
+                self.openai_connected = true;
+                debug!("Successfully listed {} models from the OpenAI-compatible server.", response.data.len());
+                response.data
+            }
+            Err(e) => {
+                self.openai_connected = false;
+                if self.configured_models.is_empty() {
+                    error!("Failed to list models from {}: {}", self.base_url, e);
+                    return Err(anyhow::anyhow!("Failed to list models from {}: {}", self.base_url, e));
+                }
+                error!("Failed to list models from {}; showing only the configured ones: {}", self.base_url, e);
+                Vec::new()
+            }
+        };
+        for model_id in &self.configured_models {
+            if !models.iter().any(|m| &m.id == model_id) {
+                models.push(OpenAiModel { id: model_id.clone(), owned_by: None, created: None, from_config: true });
+            }
+        }
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(models)
+    }
+
+    /// Streams a reply through `/chat/completions`.
+    /// Items match `OllamaManager::generate_response_stream`: text chunks, or an error message.
+    pub async fn generate_response_stream(
+        &self,
+        model_name: String,
+        history: Vec<TuiChatMessage>,
+        system_prompt_override: Option<String>,
+        params: ModelParams,
+    ) -> Result<impl Stream<Item = Result<String, HydraError>>> {
+        let mut messages = Vec::new();
+        if let Some(sp) = system_prompt_override.filter(|sp| !sp.is_empty()) {
+            messages.push(RequestMessage { role: "system", content: sp });
+        }
+        for tui_msg in history.iter() {
+            if tui_msg.sender != "user" && tui_msg.content.is_empty() {
+                debug!("Skipping empty placeholder assistant message from history for model: {}", tui_msg.sender);
+                continue;
+            }
+            let role = if tui_msg.sender == "user" { "user" } else { "assistant" };
+            messages.push(RequestMessage { role, content: tui_msg.content.clone() });
+        }
+        if messages.last().map_or(true, |msg| msg.role != "user") {
+            error!("The last message in the history sent to the OpenAI-compatible server must be from the user. Model: {}", model_name);
+            return Err(anyhow::anyhow!("Last message to the OpenAI-compatible server was not from User."));
+        }
+
+        debug!("Sending {} messages to OpenAI-compatible model: {}", messages.len(), model_name);
+        // num_ctx is a server launch option here, not a request field
+        let body = ChatCompletionRequest {
+            model: model_name.clone(),
+            messages,
+            stream: true,
+            temperature: params.temperature,
+            top_p: params.top_p,
+            max_tokens: params.num_predict.filter(|&n| n > 0),
+            seed: params.seed,
+        };
+        let response = self.request(reqwest::Method::POST, "/chat/completions").json(&body).send().await.map_err(|e| {
+            error!("Failed to start chat stream for model '{}': {}", model_name, e);
+            anyhow::anyhow!("Failed to reach {}: {}", self.base_url, e)
+        })?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let detail = response.text().await.unwrap_or_default();
+            error!("Chat request for model '{}' failed with {}: {}", model_name, status, detail);
+            return Err(anyhow::anyhow!("Server returned {}: {}", status, detail.trim()));
+        }
+        debug!("Successfully started chat completion stream for model: {}", model_name);
+
+        let state = EventStreamState { response: Some(response), buffer: Vec::new() };
+        Ok(stream::unfold(state, |mut state| async move {
+            loop {
+                while let Some(end) = state.buffer.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = state.buffer.drain(..=end).collect();
+                    match parse_sse_line(String::from_utf8_lossy(&line).trim()) {
+                        SseLine::Text(text) => return Some((Ok(text), state)),
+                        SseLine::Error(e) => {
+                            state.response = None;
+                            state.buffer.clear();
+                            return Some((Err(HydraError::StreamError(e)), state));
+                        }
+                        SseLine::Done => return None,
+                        SseLine::Skip => {}
+                    }
+                }
+                match state.response.as_mut()?.chunk().await {
+                    Ok(Some(bytes)) => state.buffer.extend_from_slice(&bytes),
+                    Ok(None) => return None,
+                    Err(e) => {
+                        state.response = None;
+                        state.buffer.clear();
+                        return Some((Err(HydraError::StreamError(e.to_string())), state));
+                    }
+                }
+            }
+        }))
+    }
+}
+
+// TODO: Add tests for the OpenAI-compatible provider:
+// - parse_sse_line handles content deltas, role-only deltas, [DONE], comments, and error events.
+// - Events split across body chunks are reassembled before parsing.
+// - list_local_models merges configured models the server doesn't report.
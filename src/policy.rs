@@ -8,6 +8,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use crate::config::Config;
+use crate::errors::HydraError;
 use serde_yaml;
 
 // Main structure for the parsed policy.toml file
@@ -71,6 +72,8 @@ pub struct RoleDefinition {
     pub audited: bool,
     pub session_recording: Option<bool>,
     pub allowed_commands: Option<Vec<String>>,
+    #[serde(default)]
+    pub vm: Option<VmResourceLimits>, // This role's VM limits, instead of `[defaults.vm]`
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -123,12 +126,59 @@ pub struct SessionTypePolicy {
     pub network_access: Option<Vec<NetworkRule>>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct NetworkRule {
-    pub allow: Option<bool>,
-    pub host: Option<String>,
-    pub port: Option<u16>,
-    pub protocol: Option<String>,
+    pub allow: Option<bool>,       // Defaults to true
+    pub host: Option<String>,      // IP address or CIDR range; any host if unset
+    pub port: Option<u16>,         // Destination port; needs protocol "tcp" or "udp"
+    pub protocol: Option<String>,  // "tcp", "udp", "icmp", or "all" (the default)
+}
+
+// The `network_policy` name that always means no network at all, whatever policy.toml says
+pub const NO_NETWORK_POLICY: &str = "no-network";
+
+// How a VM is attached to the network, resolved from its `network_policy`
+#[derive(Debug, Clone, PartialEq)]
+pub enum VmNetwork {
+    Nat,                                                  // libvirt's `default` network, unrestricted
+    Filtered { policy: String, rules: Vec<NetworkRule> }, // `default` network behind an nwfilter allowing only `rules`
+    Isolated,                                             // No network interface at all
+}
+
+// Actions an agent can request, each gated by a flag on its role
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyAction {
+    #[value(alias = "create_vm")]
+    Create,
+    #[value(alias = "destroy_vm")]
+    Destroy,
+    AttachTerminal,
+}
+
+impl PolicyAction {
+    // The role/override field that decides this action
+    pub fn permission_field(&self) -> &'static str {
+        match self {
+            PolicyAction::Create => "can_create",
+            PolicyAction::Destroy => "can_destroy",
+            PolicyAction::AttachTerminal => "can_attach_terminal",
+        }
+    }
+}
+
+// The outcome of evaluating an action, with the rule that decided it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyEvaluation {
+    pub allowed: bool,
+    pub role: Option<String>, // The agent's role, if one could be resolved
+    pub rule: String,
+}
+
+// The answer to an authorization request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthDecision {
+    Allowed,
+    Denied { reason: String },
 }
 
 pub struct PolicyEngine {
@@ -143,7 +193,9 @@ impl PolicyEngine {
             let path = Path::new(policy_path);
             if path.exists() {
                 let policy_str = std::fs::read_to_string(path)?;
-                serde_yaml::from_str(&policy_str)?
+                let mut policy_config: PolicyConfig = serde_yaml::from_str(&policy_str)?;
+                policy_config.source_path = Some(path.display().to_string());
+                policy_config
             } else {
                 warn!("Policy file path specified but not found at {:?}. Using default policy.", policy_path);
                 PolicyConfig::default()
@@ -160,24 +212,328 @@ impl PolicyEngine {
         })
     }
 
+    /// Authorizes `agent_id` to perform `action` on `resource` (a VM name or id, when there is one).
+    /// Precedence is agent override > role definition > default deny.
+    pub fn check_permission(&self, agent_id: &str, action: PolicyAction, resource: Option<&str>) -> AuthDecision {
+        let evaluation = self.evaluate_action(agent_id, action);
+        debug!("Policy decision for agent '{}', {:?} on {:?}: {:?}", agent_id, action, resource, evaluation);
+        if evaluation.allowed {
+            AuthDecision::Allowed
+        } else {
+            let reason = match resource {
+                Some(resource) => format!("{:?} on '{}' denied: {}", action, resource, evaluation.rule),
+                None => format!("{:?} denied: {}", action, evaluation.rule),
+            };
+            AuthDecision::Denied { reason }
+        }
+    }
+
+    /// The role `[permissions]` assigns to `agent_id`, if it has an entry.
+    pub fn agent_role(&self, agent_id: &str) -> Option<&str> {
+        self.config.permissions.get(agent_id).map(|permission| permission.role.as_str())
+    }
+
+    /// Whether terminal sessions for `role` should be recorded. A role's own `session_recording`
+    /// setting wins; otherwise the role must be listed in `record_for_roles`, or recording is on by default.
+    pub fn should_record_session(&self, role: &str) -> bool {
+        if let Some(record) = self.config.roles.get(role).and_then(|r| r.session_recording) {
+            return record;
+        }
+        let recording = &self.config.recording;
+        recording.record_by_default || recording.record_for_roles.iter().any(|r| r == role)
+    }
+
+    /// Resolves a VM's `network_policy`. `no-network` is always isolated. Otherwise the name
+    /// refers to a `[session_type.<name>]` section: `allow_all_network = true` gives the plain
+    /// NAT network, `network_access` rules give a filtered one, and neither means isolated.
+    /// `default` needs no section and is NAT unless `default_network_access_policy = false`.
+    pub fn vm_network(&self, network_policy: &str) -> Result<VmNetwork> {
+        if network_policy == NO_NETWORK_POLICY {
+            return Ok(VmNetwork::Isolated);
+        }
+        match self.config.session_type_policies.get(network_policy) {
+            Some(policy) if policy.allow_all_network == Some(true) => Ok(VmNetwork::Nat),
+            Some(policy) => match &policy.network_access {
+                Some(rules) if !rules.is_empty() => Ok(VmNetwork::Filtered { policy: network_policy.to_string(), rules: rules.clone() }),
+                _ => Ok(VmNetwork::Isolated),
+            },
+            None if network_policy == "default" => Ok(if self.config.default_network_access_policy == Some(false) {
+                VmNetwork::Isolated
+            } else {
+                VmNetwork::Nat
+            }),
+            None => anyhow::bail!(
+                "Unknown network policy '{}': define [session_type.{}] in policy.toml, or use \"default\" or \"{}\"",
+                network_policy, network_policy, NO_NETWORK_POLICY
+            ),
+        }
+    }
+
+    /// Cross-section checks the schema can't express. Returns one message per problem found.
+    pub fn validate_internal_consistency(config: &PolicyConfig) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        let mut agents: Vec<_> = config.permissions.iter().collect();
+        agents.sort_by(|a, b| a.0.cmp(b.0));
+        for (agent_id, permission) in agents {
+            if !config.roles.contains_key(&permission.role) {
+                issues.push(format!("permissions.\"{}\" references undefined role '{}'", agent_id, permission.role));
+            }
+        }
+        for role in &config.recording.record_for_roles {
+            if !config.roles.contains_key(role) {
+                issues.push(format!("recording.record_for_roles references undefined role '{}'", role));
+            }
+        }
+
+        let mut session_types: Vec<_> = config.session_type_policies.iter().collect();
+        session_types.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, policy) in session_types {
+            for (i, rule) in policy.network_access.iter().flatten().enumerate() {
+                if let Err(e) = check_network_rule(rule) {
+                    issues.push(format!("session_type.{}.network_access[{}]: {}", name, i, e));
+                }
+            }
+        }
+
+        let mut roles: Vec<_> = config.roles.iter().filter_map(|(name, role)| Some((format!("roles.{}.vm", name), role.vm.as_ref()?))).collect();
+        roles.sort_by(|a, b| a.0.cmp(&b.0));
+        for (section, limits) in std::iter::once(("defaults.vm".to_string(), &config.defaults.vm)).chain(roles) {
+            if limits.max_cpus < limits.default_cpus {
+                issues.push(format!("{}.max_cpus ({}) is less than default_cpus ({})", section, limits.max_cpus, limits.default_cpus));
+            }
+            if limits.max_mem_mb < limits.default_mem_mb {
+                issues.push(format!("{}.max_mem_mb ({}) is less than default_mem_mb ({})", section, limits.max_mem_mb, limits.default_mem_mb));
+            }
+        }
+        issues
+    }
+
+    /// The VM limits for `role`: its `[roles.<role>.vm]` section if it has one, otherwise
+    /// `[defaults.vm]`. No role (the operator, or an unknown one) gets the defaults too.
+    pub fn vm_limits(&self, role: Option<&str>) -> &VmResourceLimits {
+        role.and_then(|role| self.config.roles.get(role))
+            .and_then(|role| role.vm.as_ref())
+            .unwrap_or(&self.config.defaults.vm)
+    }
+
+    /// Fills in unset vCPU and memory values from `role`'s limits and rejects requests above
+    /// its maximums.
+    pub fn resolve_vm_resources(&self, role: Option<&str>, cpus: Option<u32>, mem_mb: Option<u64>) -> Result<(u32, u64)> {
+        let limits = self.vm_limits(role);
+        let cpus = cpus.unwrap_or(limits.default_cpus);
+        let mem_mb = mem_mb.unwrap_or(limits.default_mem_mb);
+        self.check_vm_limits(role, Some(cpus), Some(mem_mb))?;
+        Ok((cpus, mem_mb))
+    }
+
+    /// Rejects vCPU or memory values above `role`'s maximums; unset values pass.
+    pub fn check_vm_limits(&self, role: Option<&str>, cpus: Option<u32>, mem_mb: Option<u64>) -> Result<()> {
+        let limits = self.vm_limits(role);
+        let scope = role.filter(|role| self.config.roles.get(*role).is_some_and(|r| r.vm.is_some()))
+            .map_or_else(|| "policy".to_string(), |role| format!("role '{}'", role));
+        if let Some(cpus) = cpus.filter(|&cpus| cpus > limits.max_cpus) {
+            return Err(HydraError::PolicyDenied(format!("requested {} vCPUs exceeds {} max {}", cpus, scope, limits.max_cpus)).into());
+        }
+        if let Some(mem_mb) = mem_mb.filter(|&mem_mb| mem_mb > limits.max_mem_mb) {
+            return Err(HydraError::PolicyDenied(format!("requested {} MB of memory exceeds {} max {} MB", mem_mb, scope, limits.max_mem_mb)).into());
+        }
+        Ok(())
+    }
+
+    /// Decides whether `agent_id` may perform `action`. The agent's entry in `[permissions]`
+    /// names its role; an `override` value for the action wins over the role's flag.
+    /// Agents without an entry, or whose role is undefined, are denied.
+    pub fn evaluate_action(&self, agent_id: &str, action: PolicyAction) -> PolicyEvaluation {
+        let field = action.permission_field();
+        let Some(permission) = self.config.permissions.get(agent_id) else {
+            return PolicyEvaluation {
+                allowed: false,
+                role: None,
+                rule: format!("agent '{}' has no entry in [permissions]; actions are denied by default", agent_id),
+            };
+        };
+        let Some(role) = self.config.roles.get(&permission.role) else {
+            return PolicyEvaluation {
+                allowed: false,
+                role: Some(permission.role.clone()),
+                rule: format!("agent '{}' has role '{}', which is not defined in [roles]", agent_id, permission.role),
+            };
+        };
+
+        let override_value = permission.override_settings.as_ref().and_then(|o| match action {
+            PolicyAction::Create => o.can_create,
+            PolicyAction::AttachTerminal => o.can_attach_terminal,
+            PolicyAction::Destroy => None,
+        });
+        let (allowed, rule) = match override_value {
+            Some(allowed) => (allowed, format!("permissions.\"{}\".override.{} = {}", agent_id, field, allowed)),
+            None => {
+                let allowed = match action {
+                    PolicyAction::Create => role.can_create,
+                    PolicyAction::Destroy => role.can_destroy,
+                    PolicyAction::AttachTerminal => role.can_attach_terminal,
+                };
+                (allowed, format!("roles.{}.{} = {}", permission.role, field, allowed))
+            }
+        };
+        PolicyEvaluation { allowed, role: Some(permission.role.clone()), rule }
+    }
+
     // The following methods are not used and will be removed.
 
-    // pub fn check_permission(&self, request: &AuthRequest) -> Result<AuthDecision> { ... }
     // fn get_role_definition_and_overrides(...) -> Result<(...)> { ... }
     // fn determine_effective_role_and_settings(...) -> Result<(...)> { ... }
-    // pub fn get_default_vm_limits(&self) -> &VmResourceLimits { ... }
-    // pub fn should_record_session(&self, effective_role: &str) -> bool { ... }
     // pub fn get_session_recording_config(&self) -> &SessionRecordingPolicy { ... }
     // pub fn get_audit_settings(&self) -> &AuditPolicySettings { ... }
     // fn evaluate_network_policy(...) -> Result<()> { ... }
 }
 
+/// Rejects rules an nwfilter can't express: hosts that aren't an IP address or CIDR range,
+/// unknown protocols, and ports without "tcp" or "udp".
+pub fn check_network_rule(rule: &NetworkRule) -> Result<()> {
+    let protocol = rule.protocol.as_deref().unwrap_or("all");
+    if !["tcp", "udp", "icmp", "all"].contains(&protocol) {
+        anyhow::bail!("protocol must be \"tcp\", \"udp\", \"icmp\" or \"all\", not \"{}\"", protocol);
+    }
+    if rule.port.is_some() && !["tcp", "udp"].contains(&protocol) {
+        anyhow::bail!("a port needs protocol \"tcp\" or \"udp\"");
+    }
+    if let Some(host) = &rule.host {
+        let (address, prefix) = match host.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (host.as_str(), None),
+        };
+        let Ok(address) = address.parse::<std::net::IpAddr>() else {
+            anyhow::bail!("host must be an IP address or CIDR range, not \"{}\"", host);
+        };
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        if prefix.is_some_and(|prefix| prefix.parse::<u8>().map_or(true, |prefix| prefix > max_prefix)) {
+            anyhow::bail!("\"{}\" has an invalid prefix length", host);
+        }
+    }
+    Ok(())
+}
+
 // TODO: Add tests for PolicyEngine:
 // - Loading policy.toml (valid, missing, malformed).
 // - Default values being applied correctly.
-// - `check_permission` for various ActionTypes and agent roles/overrides.
-//   - Test trusted, sandboxed, audited roles.
-//   - Test agent-specific overrides.
-//   - Test interaction with VmPolicyContext and precedence rules.
-// - `determine_effective_role_and_settings` logic.
-// - `get_default_vm_limits` and `should_record_session`.
+// - Interaction with VmPolicyContext.
+// - `get_default_vm_limits`.
+// - `vm_network` for no-network, allow_all_network, rule lists, "default" and unknown names.
+// - `check_network_rule` rejections (hostnames, bad prefixes, ports on icmp).
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(can_create: bool, can_destroy: bool, can_attach_terminal: bool, audited: bool, session_recording: Option<bool>) -> RoleDefinition {
+        RoleDefinition { can_create, can_destroy, can_attach_terminal, audited, session_recording, allowed_commands: None, vm: None }
+    }
+
+    fn agent(role: &str, override_settings: Option<OverrideSettings>) -> PermissionOverride {
+        PermissionOverride { role: role.to_string(), override_settings }
+    }
+
+    fn engine() -> PolicyEngine {
+        let mut config = PolicyConfig::default();
+        config.roles.insert("trusted".to_string(), role(true, true, true, false, None));
+        config.roles.insert("sandboxed".to_string(), role(false, false, true, false, Some(false)));
+        config.roles.insert("audited".to_string(), role(true, false, true, true, Some(true)));
+        config.permissions.insert("ops-agent".to_string(), agent("trusted", None));
+        config.permissions.insert("sandbox-bot".to_string(), agent("sandboxed", None));
+        config.permissions.insert("reviewer".to_string(), agent("audited", None));
+        config.permissions.insert("builder".to_string(), agent("sandboxed", Some(OverrideSettings {
+            can_create: Some(true),
+            can_attach_terminal: Some(false),
+            audited: None,
+        })));
+        config.permissions.insert("orphan".to_string(), agent("missing", None));
+        PolicyEngine { config }
+    }
+
+    #[test]
+    fn trusted_role_allows_every_action() {
+        let engine = engine();
+        for action in [PolicyAction::Create, PolicyAction::Destroy, PolicyAction::AttachTerminal] {
+            assert_eq!(engine.check_permission("ops-agent", action, Some("vm1")), AuthDecision::Allowed);
+        }
+    }
+
+    #[test]
+    fn sandboxed_role_denies_create_and_destroy() {
+        let engine = engine();
+        let decision = engine.check_permission("sandbox-bot", PolicyAction::Create, Some("vm1"));
+        assert_eq!(decision, AuthDecision::Denied {
+            reason: "Create on 'vm1' denied: roles.sandboxed.can_create = false".to_string(),
+        });
+        assert!(matches!(engine.check_permission("sandbox-bot", PolicyAction::Destroy, None), AuthDecision::Denied { .. }));
+        assert_eq!(engine.check_permission("sandbox-bot", PolicyAction::AttachTerminal, None), AuthDecision::Allowed);
+    }
+
+    #[test]
+    fn audited_role_follows_its_flags() {
+        let engine = engine();
+        assert_eq!(engine.check_permission("reviewer", PolicyAction::Create, None), AuthDecision::Allowed);
+        assert!(matches!(engine.check_permission("reviewer", PolicyAction::Destroy, None), AuthDecision::Denied { .. }));
+        assert_eq!(engine.agent_role("reviewer"), Some("audited"));
+    }
+
+    #[test]
+    fn agent_override_wins_over_role() {
+        let engine = engine();
+        let create = engine.evaluate_action("builder", PolicyAction::Create);
+        assert!(create.allowed);
+        assert_eq!(create.rule, "permissions.\"builder\".override.can_create = true");
+        let attach = engine.evaluate_action("builder", PolicyAction::AttachTerminal);
+        assert!(!attach.allowed);
+        // No override for destroy, so the role decides
+        let destroy = engine.evaluate_action("builder", PolicyAction::Destroy);
+        assert!(!destroy.allowed);
+        assert_eq!(destroy.rule, "roles.sandboxed.can_destroy = false");
+    }
+
+    #[test]
+    fn unknown_agents_and_undefined_roles_are_denied() {
+        let engine = engine();
+        let unknown = engine.evaluate_action("nobody", PolicyAction::AttachTerminal);
+        assert!(!unknown.allowed);
+        assert_eq!(unknown.role, None);
+        let orphan = engine.evaluate_action("orphan", PolicyAction::AttachTerminal);
+        assert!(!orphan.allowed);
+        assert_eq!(orphan.role.as_deref(), Some("missing"));
+    }
+
+    #[test]
+    fn session_recording_follows_role_then_recording_policy() {
+        let mut engine = engine();
+        assert!(engine.should_record_session("audited"));
+        assert!(!engine.should_record_session("sandboxed"));
+        assert!(!engine.should_record_session("trusted"));
+        engine.config.recording.record_for_roles.push("trusted".to_string());
+        assert!(engine.should_record_session("trusted"));
+        engine.config.recording.record_by_default = true;
+        assert!(engine.should_record_session("other"));
+        // A role's own setting still wins over record_by_default
+        assert!(!engine.should_record_session("sandboxed"));
+    }
+
+    #[test]
+    fn vm_resources_use_the_roles_limits() {
+        let mut engine = engine();
+        engine.config.roles.get_mut("sandboxed").unwrap().vm = Some(VmResourceLimits {
+            default_cpus: 1,
+            max_cpus: 2,
+            default_mem_mb: 1024,
+            max_mem_mb: 2048,
+        });
+        assert_eq!(engine.resolve_vm_resources(Some("sandboxed"), None, None).unwrap(), (1, 1024));
+        assert_eq!(engine.resolve_vm_resources(None, None, None).unwrap(), (1, 2048));
+        let err = engine.resolve_vm_resources(Some("sandboxed"), Some(4), None).unwrap_err();
+        assert_eq!(err.to_string(), "Denied by policy: requested 4 vCPUs exceeds role 'sandboxed' max 2");
+        // Roles without their own section fall back to [defaults.vm]
+        assert_eq!(engine.resolve_vm_resources(Some("trusted"), Some(4), Some(8192)).unwrap(), (4, 8192));
+        let err = engine.resolve_vm_resources(Some("trusted"), Some(16), None).unwrap_err();
+        assert_eq!(err.to_string(), "Denied by policy: requested 16 vCPUs exceeds policy max 4");
+    }
+}
@@ -1,17 +1,23 @@
 // src/session_manager.rs
 // Manages agent workspaces, environment lifecycles, and tmux sessions.
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
 use std::sync::{Arc};
+use std::time::Instant;
 use tokio::sync::Mutex;
 
 use crate::config::Config as AppConfig;
-use crate::libvirt_manager::LibvirtManager;
-use crate::policy::PolicyEngine;
+use crate::libvirt_manager::{LibvirtManager, VmConfig, VmState, VmStatus};
+use crate::policy::{AuthDecision, PolicyAction, PolicyEngine};
 use crate::ssh_manager::SshManager;
-use crate::audit::AuditEngine;
-// use crate::errors::HydraError; // Commented out as it's unused
+use crate::audit::{AuditEngine, AuditEvent, AuditEventType, LogFormat, LogType, RiskLevel};
+use crate::errors::HydraError;
 
 // Represents an active Hydravisor session (agent workspace)
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -20,6 +26,8 @@ pub struct Session {
     pub environment_instance_id: String, // ID of the VM this session is tied to
     pub agent_id: Option<String>,    // ID of the AI agent attached (if any)
     pub model_id: Option<String>,    // ID of the model used by the agent (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,        // The agent's role from `[permissions]`, else the VM's recorded role
     pub tmux_session_name: Option<String>, // Name of the tmux session, e.g., "hydravisor-session_id"
     pub created_at: String,          // ISO 8601 timestamp
     pub status: SessionStatus,
@@ -36,39 +44,348 @@ pub enum SessionStatus {
     Error(String),
 }
 
+// Activity of an agent this process has heard from, via MCP or an attached session
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AgentActivity {
+    pub first_seen: String,    // RFC 3339
+    pub last_activity: String, // RFC 3339
+    pub last_action: String,   // e.g. an MCP message type such as "vm/create"
+}
+
+// An agent as shown by `hydravisor agent list` and `agent info`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AgentSummary {
+    pub agent_id: String,
+    pub role: Option<String>,              // From `[permissions]` in policy.toml; None means denied by default
+    pub activity: Option<AgentActivity>,   // None if the agent hasn't been seen since startup
+    pub sessions: Vec<Session>,            // Its active sessions
+}
+
 pub struct SessionManager {
-    // These fields are not read and will be removed.
-    // app_config: Arc<Config>,
-    // libvirt_manager: Arc<Mutex<LibvirtManager>>,
-    // policy_engine: Arc<PolicyEngine>,
-    // ssh_manager: Arc<SshManager>,
-    // audit_engine: Arc<AuditEngine>,
-    // active_sessions: tokio::sync::Mutex<HashMap<String, Session>>,
+    app_config: Arc<AppConfig>,
+    libvirt_manager: Arc<Mutex<LibvirtManager>>,
+    policy_engine: Arc<PolicyEngine>,
+    // ssh_manager: Arc<SshManager>, // Not read yet
+    audit_engine: Arc<AuditEngine>,
+    active_sessions: Mutex<HashMap<String, Session>>,
+    agents: Mutex<HashMap<String, AgentActivity>>, // By agent id
 }
 
 impl SessionManager {
     pub fn new(
-        _app_config: Arc<AppConfig>,
-        _libvirt_manager: Arc<Mutex<LibvirtManager>>,
-        _policy_engine: Arc<PolicyEngine>,
+        app_config: Arc<AppConfig>,
+        libvirt_manager: Arc<Mutex<LibvirtManager>>,
+        policy_engine: Arc<PolicyEngine>,
         _ssh_manager: Arc<SshManager>,
-        _audit_engine: Arc<AuditEngine>,
+        audit_engine: Arc<AuditEngine>,
     ) -> Result<Self> {
         Ok(SessionManager {
-            // All fields were unused.
+            app_config,
+            libvirt_manager,
+            policy_engine,
+            audit_engine,
+            active_sessions: Mutex::new(HashMap::new()),
+            agents: Mutex::new(HashMap::new()),
         })
     }
 
-    // The following methods are not used and will be removed.
-    // pub async fn create_session(...) -> Result<CreateSessionResponse> { ... }
-    // pub async fn attach_agent_to_session(...) -> Result<()> { ... }
-    // pub async fn terminate_session(...) -> Result<()> { ... }
-    // pub async fn get_session(...) -> Result<Option<Session>> { ... }
-    // pub async fn list_sessions(...) -> Result<Vec<Session>> { ... }
+    pub fn app_config(&self) -> &Arc<AppConfig> {
+        &self.app_config
+    }
+
+    pub fn policy_engine(&self) -> &Arc<PolicyEngine> {
+        &self.policy_engine
+    }
+
+    /// Records an audit event, logging rather than returning a failure to write it.
+    pub fn record_audit_event(&self, event: AuditEvent) {
+        if let Err(e) = self.audit_engine.record_event(event) {
+            tracing::error!("Failed to record event in the audit ledger: {}", e);
+        }
+    }
+
+    /// Checks an agent's request against the policy and records the decision in the audit ledger.
+    pub fn authorize(&self, agent_id: &str, action: PolicyAction, resource: Option<&str>) -> AuthDecision {
+        let decision = self.policy_engine.check_permission(agent_id, action, resource);
+        let (allowed, reason, risk_level) = match &decision {
+            AuthDecision::Allowed => (true, None, RiskLevel::Info),
+            AuthDecision::Denied { reason } => (false, Some(reason.clone()), RiskLevel::Medium),
+        };
+        let mut event = AuditEvent::new(
+            AuditEventType::PolicyDecision {
+                agent_id: Some(agent_id.to_string()),
+                action: action.permission_field().to_string(),
+                resource: resource.map(str::to_string),
+                allowed,
+                reason,
+            },
+            Some(risk_level),
+        );
+        event.agent_id = Some(agent_id.to_string());
+        self.record_audit_event(event);
+        decision
+    }
+
+    /// Notes that `agent_id` just did `action`, adding it to the agent registry if it is new.
+    /// Agents without a `[permissions]` entry aren't tracked, so made-up ids can't grow the registry.
+    pub async fn record_agent_activity(&self, agent_id: &str, action: &str) {
+        if self.policy_engine.agent_role(agent_id).is_none() {
+            return;
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        self.agents
+            .lock()
+            .await
+            .entry(agent_id.to_string())
+            .and_modify(|activity| {
+                activity.last_activity = now.clone();
+                activity.last_action = action.to_string();
+            })
+            .or_insert_with(|| AgentActivity { first_seen: now.clone(), last_activity: now, last_action: action.to_string() });
+    }
+
+    /// Every agent that is configured in `[permissions]`, has been active, or holds a session,
+    /// sorted by id, with the role the policy gives it.
+    pub async fn agent_summaries(&self) -> Vec<AgentSummary> {
+        let agents = self.agents.lock().await;
+        let sessions = self.active_sessions.lock().await;
+        let mut agent_ids: Vec<&String> = self.policy_engine.config.permissions.keys()
+            .chain(agents.keys())
+            .chain(sessions.values().filter_map(|session| session.agent_id.as_ref()))
+            .collect();
+        agent_ids.sort();
+        agent_ids.dedup();
+        agent_ids
+            .into_iter()
+            .map(|agent_id| AgentSummary {
+                agent_id: agent_id.clone(),
+                role: self.policy_engine.agent_role(agent_id).map(str::to_string),
+                activity: agents.get(agent_id).cloned(),
+                sessions: sessions.values().filter(|session| session.agent_id.as_ref() == Some(agent_id)).cloned().collect(),
+            })
+            .collect()
+    }
+
+    pub async fn list_vms(&self) -> Result<Vec<VmStatus>> {
+        let (_connected, vms) = LibvirtManager::list_vms_async(Arc::clone(&self.libvirt_manager)).await?;
+        Ok(vms)
+    }
+
+    /// Creates a VM on the blocking pool, since disk and seed image creation shell out.
+    pub async fn create_vm(&self, vm_config: VmConfig) -> Result<VmStatus> {
+        let libvirt_manager = Arc::clone(&self.libvirt_manager);
+        tokio::task::spawn_blocking(move || libvirt_manager.blocking_lock().create_vm(&vm_config)).await?
+    }
+
+    /// Asks a running VM to power off, on the blocking pool.
+    pub async fn shutdown_vm(&self, instance_id: &str) -> Result<()> {
+        let libvirt_manager = Arc::clone(&self.libvirt_manager);
+        let id = instance_id.to_string();
+        tokio::task::spawn_blocking(move || libvirt_manager.blocking_lock().shutdown_vm(&id)).await?
+    }
+
+    /// Names of the VMs that have a session attached.
+    pub async fn vms_with_sessions(&self) -> HashSet<String> {
+        self.active_sessions
+            .lock()
+            .await
+            .values()
+            .map(|session| session.environment_instance_id.clone())
+            .collect()
+    }
+
+    /// Destroys a VM and its disks, ending any sessions attached to it.
+    pub async fn destroy_vm(&self, instance_id: &str) -> Result<()> {
+        let libvirt_manager = Arc::clone(&self.libvirt_manager);
+        let id = instance_id.to_string();
+        tokio::task::spawn_blocking(move || libvirt_manager.blocking_lock().destroy_vm(&id, true)).await??;
+
+        let mut sessions = self.active_sessions.lock().await;
+        let ended: Vec<String> = sessions
+            .values()
+            .filter(|session| session.environment_instance_id == instance_id)
+            .map(|session| session.session_id.clone())
+            .collect();
+        for session_id in ended {
+            sessions.remove(&session_id);
+            self.record_audit_event(AuditEvent::new(AuditEventType::SessionEnd { session_id }, Some(RiskLevel::Info)));
+        }
+        Ok(())
+    }
+
+    /// Opens a session binding `agent_id` to a running VM, looked up by name or UUID.
+    pub async fn attach_session(&self, agent_id: &str, instance_id: &str) -> Result<(Session, VmStatus)> {
+        let vm = self
+            .list_vms()
+            .await?
+            .into_iter()
+            .find(|vm| vm.name == instance_id || vm.instance_id == instance_id)
+            .ok_or_else(|| HydraError::VmNotFound(instance_id.to_string()))?;
+        if !matches!(vm.state, VmState::Running) {
+            return Err(anyhow!("VM '{}' is not running (state: {:?}).", vm.name, vm.state));
+        }
+
+        let session = Session {
+            session_id: uuid::Uuid::new_v4().to_string(),
+            environment_instance_id: vm.name.clone(),
+            agent_id: Some(agent_id.to_string()),
+            model_id: None,
+            role: self.policy_engine.agent_role(agent_id).map(str::to_string).or_else(|| vm.role.clone()),
+            tmux_session_name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: SessionStatus::AgentAttached,
+        };
+        let mut event = AuditEvent::new(
+            AuditEventType::SessionStart { session_id: session.session_id.clone() },
+            Some(RiskLevel::Info),
+        );
+        event.session_id = Some(session.session_id.clone());
+        event.agent_id = Some(agent_id.to_string());
+        self.record_audit_event(event);
+
+        self.active_sessions.lock().await.insert(session.session_id.clone(), session.clone());
+        self.record_agent_activity(agent_id, &format!("attached to {}", vm.name)).await;
+        Ok((session, vm))
+    }
+
+    /// Closes a session opened with `attach_session`.
+    pub async fn end_session(&self, session_id: &str) {
+        if self.active_sessions.lock().await.remove(session_id).is_some() {
+            let mut event = AuditEvent::new(
+                AuditEventType::SessionEnd { session_id: session_id.to_string() },
+                Some(RiskLevel::Info),
+            );
+            event.session_id = Some(session_id.to_string());
+            self.record_audit_event(event);
+        }
+    }
+
+    /// Whether a session's terminal output should be recorded: always with
+    /// `tmux.record_all_sessions`, otherwise as the policy decides for the session's role.
+    pub async fn should_record_session(&self, session_id: &str) -> bool {
+        if self.app_config.tmux.record_all_sessions {
+            return true;
+        }
+        let sessions = self.active_sessions.lock().await;
+        let role = sessions.get(session_id).and_then(|session| session.role.as_deref());
+        role.is_some_and(|role| self.policy_engine.should_record_session(role))
+    }
+
+    /// Starts recording a session's terminal output to `<log_dir>/instances/<vm>/<session_id>.<ext>`,
+    /// in the format named by `tmux.record_format`.
+    pub async fn start_recording(&self, session_id: &str) -> Result<RecordingHandle> {
+        let instance_id = self
+            .active_sessions
+            .lock()
+            .await
+            .get(session_id)
+            .map(|session| session.environment_instance_id.clone())
+            .ok_or_else(|| anyhow!("No active session '{}'.", session_id))?;
+        let format = LogFormat::from_str(&self.app_config.tmux.record_format, true)
+            .map_err(|_| anyhow!("Unknown tmux.record_format '{}'", self.app_config.tmux.record_format))?;
+
+        let dir = self.audit_engine.log_type_dir(LogType::Vm).join(&instance_id);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create recording directory {:?}", dir))?;
+        let path = dir.join(format!("{}.{}", session_id, format.extension()));
+        let file = fs::File::create(&path)
+            .with_context(|| format!("Failed to create recording file {:?}", path))?;
+
+        let mut handle = RecordingHandle {
+            session_id: session_id.to_string(),
+            path,
+            format,
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+            audit_engine: Arc::clone(&self.audit_engine),
+            pending: Vec::new(),
+        };
+        handle.write_header()?;
+        Ok(handle)
+    }
+}
+
+/// An open terminal recording. Output is appended with `write_output`; `finish` flushes the
+/// file and records `TerminalSessionRecorded`.
+pub struct RecordingHandle {
+    session_id: String,
+    path: PathBuf,
+    format: LogFormat,
+    writer: BufWriter<fs::File>,
+    started: Instant,
+    audit_engine: Arc<AuditEngine>,
+    // Trailing bytes of a UTF-8 sequence split across output chunks
+    pending: Vec<u8>,
+}
+
+impl RecordingHandle {
+    fn write_header(&mut self) -> Result<()> {
+        if self.format != LogFormat::Cast {
+            return Ok(());
+        }
+        let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+        let header = serde_json::json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "env": { "TERM": std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string()) },
+        });
+        writeln!(self.writer, "{}", header)?;
+        Ok(())
+    }
+
+    /// Appends a chunk of terminal output, timestamped relative to the start of the recording.
+    pub fn write_output(&mut self, data: &[u8]) -> Result<()> {
+        if self.format == LogFormat::Ansi {
+            self.writer.write_all(data)?;
+            return Ok(());
+        }
+
+        // JSON events need text, so hold back an incomplete multi-byte character for the next chunk
+        self.pending.extend_from_slice(data);
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(), // Genuinely invalid bytes are written lossily
+        };
+        if complete == 0 {
+            return Ok(());
+        }
+        let chunk: Vec<u8> = self.pending.drain(..complete).collect();
+        let text = String::from_utf8_lossy(&chunk);
+        let time = self.started.elapsed().as_secs_f64();
+        let event = if self.format == LogFormat::Cast {
+            serde_json::json!([time, "o", text])
+        } else {
+            serde_json::json!({ "time": time, "data": text })
+        };
+        writeln!(self.writer, "{}", event)?;
+        Ok(())
+    }
+
+    /// Flushes the recording and records it in the audit ledger.
+    pub fn finish(mut self) -> Result<PathBuf> {
+        self.writer.flush()
+            .with_context(|| format!("Failed to flush recording {:?}", self.path))?;
+        let mut event = AuditEvent::new(
+            AuditEventType::TerminalSessionRecorded {
+                session_id: self.session_id.clone(),
+                recording_path: self.path.clone(),
+                format: self.format.extension().to_string(),
+            },
+            Some(RiskLevel::Info),
+        );
+        event.session_id = Some(self.session_id.clone());
+        self.audit_engine.record_event(event)?;
+        Ok(self.path)
+    }
 }
 
 // TODO: Add tests for SessionManager:
 // - Session creation and termination lifecycle (mocking dependent managers).
 // - Agent attachment logic.
+// - agent_summaries merges policy, activity and session agents without duplicates.
 // - Correct interaction with PolicyEngine for authorization.
-// - Correct interaction with TmuxHandler (mocked). 
\ No newline at end of file
+// - Correct interaction with TmuxHandler (mocked).
+// - Recording headers and events for each `record_format`. 
\ No newline at end of file
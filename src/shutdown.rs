@@ -0,0 +1,17 @@
+// src/shutdown.rs
+// Waits for the signals that should stop Hydravisor cleanly, in both TUI and headless modes.
+
+use anyhow::Result;
+use tokio::signal::unix::{signal, SignalKind};
+
+/// Resolves on SIGINT (Ctrl+C) or SIGTERM, returning the signal's name for logging.
+pub async fn wait_for_shutdown_signal() -> Result<&'static str> {
+    let mut terminate = signal(SignalKind::terminate())?;
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => {
+            result?;
+            Ok("SIGINT")
+        }
+        _ = terminate.recv() => Ok("SIGTERM"),
+    }
+}
@@ -1,11 +1,13 @@
 // src/ssh_manager.rs
 // Manages SSH key generation, distribution, and configuration (ssh.toml)
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use crate::config::Config;
+use crate::libvirt_manager::VmStatus;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::process::Command;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
@@ -60,6 +62,55 @@ impl SshManager {
             config: ssh_config,
         })
     }
+
+    /// Builds the `ssh` invocation for an interactive session on `target`.
+    ///
+    /// A `[hosts.<vm-name>]` entry in ssh.toml supplies the user, key, port, and
+    /// connection options; its `address` is used when libvirt reports no IP.
+    /// Without an entry, `ssh` connects to the reported IP with its own defaults.
+    pub fn ssh_command(&self, target: &VmStatus) -> Result<Command> {
+        self.build_ssh_command(target, false)
+    }
+
+    /// Like `ssh_command`, but forces a remote PTY (`-tt`) so output can be piped through
+    /// a session recorder while the session stays interactive.
+    pub fn recorded_ssh_command(&self, target: &VmStatus) -> Result<Command> {
+        self.build_ssh_command(target, true)
+    }
+
+    fn build_ssh_command(&self, target: &VmStatus, force_tty: bool) -> Result<Command> {
+        let host_entry = self.config.hosts.get(&target.name);
+        let address = target.ip_address.clone()
+            .or_else(|| host_entry.map(|entry| entry.address.clone()))
+            .ok_or_else(|| anyhow!("VM '{}' has no known IP address", target.name))?;
+
+        let mut command = Command::new("ssh");
+        if force_tty {
+            command.arg("-tt");
+        }
+        match host_entry {
+            Some(entry) => {
+                let identity_file = shellexpand::full(&entry.identity_file)
+                    .with_context(|| format!("Failed to expand identity_file for host '{}'", target.name))?;
+                command.arg("-i").arg(identity_file.as_ref());
+                command.arg("-p").arg(entry.port.to_string());
+                if !entry.host_key_check {
+                    command.args(["-o", "StrictHostKeyChecking=no", "-o", "UserKnownHostsFile=/dev/null"]);
+                }
+                if entry.forward_agent {
+                    command.arg("-A");
+                }
+                if let Some(timeout) = entry.connect_timeout {
+                    command.arg("-o").arg(format!("ConnectTimeout={}", timeout));
+                }
+                command.arg(format!("{}@{}", entry.username, address));
+            }
+            None => {
+                command.arg(address);
+            }
+        }
+        Ok(command)
+    }
 }
 
 // TODO: Add tests for SshManager:
@@ -67,4 +118,5 @@ impl SshManager {
 // - Default values for SshHostConfigEntry.
 // - `provision_vm_keys` (mock filesystem or use temp dirs, verify key file creation).
 // - `get_ssh_connection_info` (path expansion, correct defaults).
-// - `get_vm_ssh_details`. 
\ No newline at end of file
+// - `get_vm_ssh_details`.
+// - `ssh_command` (host entry vs. bare IP, missing IP, option flags). 
\ No newline at end of file
@@ -2,15 +2,20 @@
 
 use anyhow::Result;
 use ratatui::{
+    layout::Rect,
     widgets::{ListState},
 };
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
-use tracing::{Level, error};
+use tracing::{Level, debug, error, info, warn};
 use tokio::sync::mpsc;
 use uuid::Uuid;
-use crossterm::event::{KeyCode, KeyModifiers};
-use std::collections::HashMap;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{HashMap, HashSet};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ollama_integration")]
 use ollama_rs::models::LocalModel;
@@ -18,58 +23,88 @@ use ollama_rs::models::LocalModel;
 #[cfg(feature = "bedrock_integration")]
 use aws_sdk_bedrock::types::FoundationModelSummary;
 
-use crate::config::Config;
+use crate::config::{Config, ModelParams};
 use crate::session_manager::SessionManager;
 use crate::policy::PolicyEngine;
-use crate::libvirt_manager::{LibvirtManager, VmStatus};
-use crate::audit::AuditEngine;
-use crate::ollama_manager::OllamaManager;
+use crate::libvirt_manager::{
+    check_vm_file, check_vm_name, default_cloud_init, parse_ram_str, parse_vm_cpus, parse_vm_disk_gb, parse_vm_memory_mb,
+    ExistingDisk, LibvirtManager, VmConfig, VmCreationPreview, VmDetails, VmResourceChange, VmState, VmStatus,
+};
+use crate::errors::user_message;
+use crate::audit::{AuditEngine, AuditEvent, AuditEventType, RiskLevel};
+use crate::health::HealthBackoff;
+use crate::ollama_manager::{OllamaManager, PullProgress, RunningModel};
+use crate::ssh_manager::SshManager;
 #[cfg(feature = "bedrock_integration")]
-use crate::bedrock_manager::BedrockManager;
+use crate::bedrock_manager::{BedrockManager, ModelAccess};
 #[cfg(feature = "bedrock_integration")]
 use crate::tui::view_mode::list::ListViewMode;
+#[cfg(feature = "openai_compat")]
+use crate::openai_compat_manager::{OpenAiCompatManager, OpenAiModel};
 
-use super::theme::AppTheme;
+use super::chat_store::{ChatStore, SavedChat};
+use super::daemon::DaemonLink;
+use super::ui_state::{LastChat, UiState};
+use super::command_palette::{format_keybinding, fuzzy_score, help_action_rows, help_section_line, key_actions, KeyAction};
+use super::text_input::TextInput;
+use super::theme::{available_themes, AppTheme, NamedTheme};
 
 // Define different views for the TUI
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AppView {
     VmList,
     OllamaModelList,
     #[cfg(feature = "bedrock_integration")]
     BedrockModelList,
+    #[cfg(feature = "openai_compat")]
+    OpenAiModelList,
     Chat,
     Logs,
 }
 
 impl AppView {
+    /// Every view in this build, in tab order.
+    pub fn all() -> Vec<AppView> {
+        #[allow(unused_mut)]
+        let mut views = vec![Self::VmList, Self::OllamaModelList];
+        #[cfg(feature = "bedrock_integration")]
+        views.push(Self::BedrockModelList);
+        #[cfg(feature = "openai_compat")]
+        views.push(Self::OpenAiModelList);
+        views.extend([Self::Chat, Self::Logs]);
+        views
+    }
+
     pub fn next(&self) -> Self {
-        match self {
-            Self::VmList => Self::OllamaModelList,
-            #[cfg(not(feature = "bedrock_integration"))]
-            Self::OllamaModelList => Self::Chat,
-            #[cfg(feature = "bedrock_integration")]
-            Self::OllamaModelList => Self::BedrockModelList,
-            #[cfg(feature = "bedrock_integration")]
-            Self::BedrockModelList => Self::Chat,
-            Self::Chat => Self::Logs,
-            Self::Logs => Self::VmList,
-        }
+        let views = Self::all();
+        let idx = views.iter().position(|view| view == self).unwrap_or(0);
+        views[(idx + 1) % views.len()]
     }
 
     pub fn previous(&self) -> Self {
+        let views = Self::all();
+        let idx = views.iter().position(|view| view == self).unwrap_or(0);
+        views[(idx + views.len() - 1) % views.len()]
+    }
+
+    /// A stable name for the view, as saved in the UI state file.
+    pub fn id(&self) -> &'static str {
         match self {
-            Self::VmList => Self::Logs,
-            Self::OllamaModelList => Self::VmList,
-            #[cfg(feature = "bedrock_integration")]
-            Self::BedrockModelList => Self::OllamaModelList,
-            #[cfg(not(feature = "bedrock_integration"))]
-            Self::Chat => Self::OllamaModelList,
+            Self::VmList => "vms",
+            Self::OllamaModelList => "ollama",
             #[cfg(feature = "bedrock_integration")]
-            Self::Chat => Self::BedrockModelList,
-            Self::Logs => Self::Chat,
+            Self::BedrockModelList => "bedrock",
+            #[cfg(feature = "openai_compat")]
+            Self::OpenAiModelList => "openai_compat",
+            Self::Chat => "chat",
+            Self::Logs => "logs",
         }
     }
+
+    /// The view named `id`, if this build has it.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::all().into_iter().find(|view| view.id() == id)
+    }
 }
 
 // Define input modes
@@ -78,24 +113,298 @@ pub enum InputMode {
     Normal,
     Editing,
     VmWizard,
-    ConfirmingDestroy,
+    ConfirmingVmAction,
+    ConfirmingModelDelete,
+}
+
+// VM operations that require confirmation before they are sent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VmAction {
+    Destroy,  // Force off, undefine, and remove
+    Shutdown, // ACPI power off; the VM stays defined
+}
+
+// State filter for the VM list, cycled with the filter key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VmStateFilter {
+    All,
+    Running,
+    Suspended,
+    Stopped,
+    Error,
+}
+
+impl VmStateFilter {
+    pub fn next(self) -> Self {
+        match self {
+            VmStateFilter::All => VmStateFilter::Running,
+            VmStateFilter::Running => VmStateFilter::Suspended,
+            VmStateFilter::Suspended => VmStateFilter::Stopped,
+            VmStateFilter::Stopped => VmStateFilter::Error,
+            VmStateFilter::Error => VmStateFilter::All,
+        }
+    }
+
+    pub fn matches(self, state: &VmState) -> bool {
+        match self {
+            VmStateFilter::All => true,
+            VmStateFilter::Running => matches!(state, VmState::Running),
+            VmStateFilter::Suspended => matches!(state, VmState::Suspended),
+            // Terminated domains are shut off too, as far as the user is concerned
+            VmStateFilter::Stopped => matches!(state, VmState::Stopped | VmState::Terminated),
+            VmStateFilter::Error => matches!(state, VmState::Error(_)),
+        }
+    }
+}
+
+// New-VM wizard fields, in the order NewVmPopupWidget renders them and Tab visits them
+pub mod new_vm_field {
+    pub const NAME: usize = 0;
+    pub const SOURCE_IMAGE: usize = 1;
+    pub const DISK_PATH: usize = 2;
+    pub const CPU: usize = 3;
+    pub const RAM: usize = 4;
+    pub const DISK_SIZE: usize = 5;
+    pub const USE_ISO: usize = 6;
+    pub const ISO_PATH: usize = 7;
+    pub const PERSISTENT: usize = 8;
+    pub const COUNT: usize = 9;
 }
 
 // Represents a chat message
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub sender: String,
     pub content: String,
     pub timestamp: String,
     pub thought: Option<String>,
+    // Whether the thought is shown in full rather than as a one-line summary; not saved
+    #[serde(skip)]
+    pub thought_expanded: bool,
+}
+
+// The backend a chat session streams replies from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatProvider {
+    #[default]
+    Ollama,
+    Bedrock,
+    #[serde(rename = "openai_compat")]
+    OpenAiCompat,
 }
 
 // Represents an active chat session
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatSession {
+    // Model name plus start time; names the file the conversation is saved to
+    #[serde(default)]
+    pub id: String,
+    // Chats saved before Bedrock support are Ollama chats
+    #[serde(default)]
+    pub provider: ChatProvider,
     pub model_name: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip)]
     pub is_streaming: bool,
+    // Timing of the latest reply; not saved with the chat
+    #[serde(skip)]
+    pub stream_stats: Option<StreamStats>,
+    // Routes the latest reply's chunks into its thought or content while it streams
+    #[serde(skip)]
+    pub think_splitter: ThinkSplitter,
+}
+
+// Where the active view's list was last drawn, so a mouse click can be mapped to one of its items
+#[derive(Debug, Clone)]
+pub struct ListHitArea {
+    pub view: AppView,
+    pub area: Rect,   // Inside the list's borders
+    pub offset: usize, // First item drawn, as the render left it
+    pub item_count: usize,
+    pub item_heights: Vec<u16>, // Lines per item, from the offset on; items past the end are one line
+}
+
+impl ListHitArea {
+    /// Records a list whose items are one line each.
+    pub fn single_line(view: AppView, area: Rect, state: &ListState, item_count: usize) -> Self {
+        ListHitArea { view, area, offset: state.offset(), item_count, item_heights: Vec::new() }
+    }
+
+    /// The index of the item drawn at a terminal cell, if the cell is inside the list.
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inside = column >= self.area.x && column < self.area.right() && row >= self.area.y && row < self.area.bottom();
+        if !inside {
+            return None;
+        }
+        let mut line = row - self.area.y;
+        let mut idx = self.offset;
+        while idx < self.item_count {
+            let height = self.item_heights.get(idx - self.offset).copied().unwrap_or(1).max(1);
+            if line < height {
+                return Some(idx);
+            }
+            line -= height;
+            idx += 1;
+        }
+        None
+    }
+}
+
+// How many async tasks (chat streams, model pulls, VM operations, fetches) are in flight, for the
+// status bar. A task holds the guard from `start` until it ends, including when it is aborted.
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundTasks(Arc<AtomicUsize>);
+
+impl BackgroundTasks {
+    pub fn start(&self) -> BackgroundTaskGuard {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        BackgroundTaskGuard(Arc::clone(&self.0))
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+pub struct BackgroundTaskGuard(Arc<AtomicUsize>);
+
+impl Drop for BackgroundTaskGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Timing of one streamed reply, for the Chat Info pane
+#[derive(Debug, Clone, Copy)]
+pub struct StreamStats {
+    pub started: Instant,
+    pub first_chunk: Option<Instant>,
+    pub finished: Option<Instant>,
+    // Whitespace-separated words received, standing in for tokens since providers report
+    // usage differently (or not at all) in their streams
+    pub tokens: usize,
+}
+
+impl StreamStats {
+    pub(super) fn new() -> Self {
+        StreamStats { started: Instant::now(), first_chunk: None, finished: None, tokens: 0 }
+    }
+
+    /// Time since the message was sent, up to the end of the reply.
+    pub fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(Instant::now).duration_since(self.started)
+    }
+
+    /// Time the model took to start replying.
+    pub fn time_to_first_token(&self) -> Option<Duration> {
+        self.first_chunk.map(|first| first.duration_since(self.started))
+    }
+
+    /// Approximate tokens per second, measured from the first chunk so prompt processing
+    /// doesn't drag the rate down. None until there is something to measure.
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        let generating = self.finished.unwrap_or_else(Instant::now).duration_since(self.first_chunk?).as_secs_f64();
+        (self.tokens > 1 && generating > 0.0).then(|| self.tokens as f64 / generating)
+    }
+}
+
+const THINK_OPEN: &str = "<think>";
+const THINK_CLOSE: &str = "</think>";
+
+// Splits a streamed reply into thought and content as chunks arrive: text inside
+// `<think>...</think>` goes to the thought. An opening tag only counts at the start of the
+// reply's content, so models that never think stream straight into the content.
+// The tail of a chunk that could be the start of a tag is held back until the next chunk
+// completes or rules it out.
+#[derive(Debug, Clone, Default)]
+pub struct ThinkSplitter {
+    in_thought: bool,
+    pending: String,
+}
+
+impl ThinkSplitter {
+    /// Adds `chunk` to `message`'s thought or content.
+    pub fn push(&mut self, chunk: &str, message: &mut ChatMessage) {
+        let mut text = std::mem::take(&mut self.pending) + chunk;
+        loop {
+            if !self.in_thought {
+                let rest = text.trim_start();
+                let may_open = message.content.is_empty() && (rest.starts_with(THINK_OPEN) || THINK_OPEN.starts_with(rest));
+                if !may_open {
+                    self.emit(&text, message);
+                    return;
+                }
+            }
+            let tag = if self.in_thought { THINK_CLOSE } else { THINK_OPEN };
+            if let Some(pos) = text.find(tag) {
+                self.emit(&text[..pos], message);
+                self.in_thought = !self.in_thought;
+                text.drain(..pos + tag.len());
+                continue;
+            }
+            // Longest tail of `text` that the tag starts with
+            let held = (1..tag.len().min(text.len() + 1))
+                .rev()
+                .find(|&len| text.is_char_boundary(text.len() - len) && tag.starts_with(&text[text.len() - len..]))
+                .unwrap_or(0);
+            self.pending = text.split_off(text.len() - held);
+            self.emit(&text, message);
+            return;
+        }
+    }
+
+    /// Flushes held-back text at the end of the reply. An unclosed thought stays a thought.
+    pub fn finish(&mut self, message: &mut ChatMessage) {
+        let pending = std::mem::take(&mut self.pending);
+        self.emit(&pending, message);
+        if let Some(thought) = message.thought.as_mut() {
+            thought.truncate(thought.trim_end().len());
+        }
+        if message.thought.as_deref() == Some("") {
+            message.thought = None;
+        }
+        self.in_thought = false;
+    }
+
+    // Leading whitespace is dropped, so the newlines around the tags don't show
+    fn emit(&self, text: &str, message: &mut ChatMessage) {
+        let target = if self.in_thought {
+            message.thought.get_or_insert_with(String::new)
+        } else {
+            &mut message.content
+        };
+        if target.is_empty() {
+            target.push_str(text.trim_start());
+        } else {
+            target.push_str(text);
+        }
+    }
+}
+
+impl ChatSession {
+    /// Starts a conversation whose first entry is the display-only system prompt.
+    pub fn new(provider: ChatProvider, model_name: &str, system_prompt: String) -> Self {
+        let safe_model_name: String = model_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '-' })
+            .collect();
+        ChatSession {
+            id: format!("{}-{}", safe_model_name, chrono::Local::now().format("%Y%m%d-%H%M%S")),
+            provider,
+            model_name: model_name.to_string(),
+            messages: vec![ChatMessage {
+                sender: "System".to_string(),
+                content: system_prompt,
+                timestamp: "".to_string(),
+                thought: None,
+                thought_expanded: false,
+            }],
+            is_streaming: false,
+            stream_stats: None,
+            think_splitter: ThinkSplitter::default(),
+        }
+    }
 }
 
 // New struct for TUI log entries
@@ -105,7 +414,19 @@ pub struct UILogEntry {
     pub level: Level,
     pub target: String,
     pub message: String,
-    // Consider adding: pub file: Option<String>, pub line: Option<u32>,
+    pub file: Option<String>, // Source file of the log call, from the event's metadata
+    pub line: Option<u32>,
+}
+
+impl UILogEntry {
+    /// "file:line" of the log call, or just the file if the line is unknown.
+    pub fn location(&self) -> Option<String> {
+        let file = self.file.as_deref()?;
+        Some(match self.line {
+            Some(line) => format!("{}:{}", file, line),
+            None => file.to_string(),
+        })
+    }
 }
 
 // New enum for TUI chat stream events
@@ -114,17 +435,65 @@ pub enum ChatStreamEvent {
     Chunk(String),      // A piece of the response
     Error(String),      // An error occurred during streaming - changed from ollama_rs::error::OllamaError
     Completed,          // Streaming finished successfully
+    Cancelled,          // The user aborted the stream
+}
+
+// Severity of a toast notification; picks its colour
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+// A transient notification shown in the corner until it expires
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub text: String,
+    pub expires_at: std::time::Instant,
 }
 
+/// How long toasts stay up; errors stay twice as long.
+pub const TOAST_TTL: std::time::Duration = std::time::Duration::from_secs(4);
+const MAX_TOASTS: usize = 5;
+
+// Menu entries, in display order
+pub const MAIN_MENU_ITEMS: [&str; 3] = ["About", "Preferences", "Quit"];
+pub const PREFERENCES_MENU_ITEMS: [&str; 3] = ["Key Bindings", "Theme", "Back"];
+
 // New enum for app-level events to handle async operations
 #[derive(Clone)]
 pub enum AppEvent {
     FetchVms,
+    VmsFetched { connected: bool, result: Result<Vec<VmStatus>, String> },
     FetchOllamaModels,
     #[cfg(feature = "bedrock_integration")]
     FetchBedrockModels,
+    #[cfg(feature = "bedrock_integration")]
+    BedrockModelAccessChecked { model_id: String, result: Result<ModelAccess, String>, access_url: String },
+    #[cfg(feature = "bedrock_integration")]
+    BedrockModelAccessKnown { model_id: String, access: ModelAccess }, // Learned from a chat attempt
+    #[cfg(feature = "bedrock_integration")]
+    BedrockClientSwitched { generation: usize, region: String, profile: String, error: Option<String> },
+    #[cfg(feature = "openai_compat")]
+    FetchOpenAiModels,
+    // Dry-runs the wizard's VM; with `create`, it is created straight away unless its disk already exists
+    PreviewVm { vm_config: VmConfig, create: bool },
+    VmPreviewed { vm_config: VmConfig, create: bool, result: Result<VmCreationPreview, String> },
+    VmDetailsFetched { vm_name: String, result: Result<VmDetails, String> },
+    CreateVm(VmConfig),
+    CloneVm { source_name: String, new_name: String },
+    ResizeVm { vm_name: String, change: VmResourceChange },
     DestroyVm(String),
+    ShutdownVm(String),
+    SuspendVm(String),
     ResumeVm(String),
+    ModelPullProgress(PullProgress),
+    ModelPullFinished { model_name: String, error: Option<String> },
+    DeleteModel(String),
+    Notify(ToastLevel, String), // Lets background tasks raise a toast
 }
 
 
@@ -134,17 +503,51 @@ pub struct App {
     pub menu_state: ListState,
     pub show_about_modal: bool,
     pub readme_content: String,
+    pub about_scroll: usize, // First README line shown; clamped to the content when rendered
     
     #[cfg(feature = "ollama_integration")]
     pub ollama_models: Vec<LocalModel>,
     #[cfg(not(feature = "ollama_integration"))]
     pub ollama_models: Vec<String>,
 
+    // Models Ollama currently holds in memory (from /api/ps)
+    pub ollama_running_models: Vec<RunningModel>,
+
     #[cfg(feature = "bedrock_integration")]
     pub bedrock_models: Vec<FoundationModelSummary>,
+    // Access to Bedrock models by ID, as learned from access checks and chat attempts
+    #[cfg(feature = "bedrock_integration")]
+    pub bedrock_model_access: HashMap<String, ModelAccess>,
+    // A model whose chat opens once an access re-check says it's granted
+    #[cfg(feature = "bedrock_integration")]
+    pub pending_bedrock_chat: Option<String>,
+    // The region and AWS profile the Bedrock client was last built for; switchable at runtime
+    #[cfg(feature = "bedrock_integration")]
+    pub bedrock_region: String,
+    #[cfg(feature = "bedrock_integration")]
+    pub bedrock_profile: String,
+    // Bumped on every switch, so a slow client build can't replace a newer one
+    #[cfg(feature = "bedrock_integration")]
+    pub bedrock_client_generation: Arc<AtomicUsize>,
+    // While a switch connects, refreshes would list the old client's models under the new title
+    #[cfg(feature = "bedrock_integration")]
+    pub bedrock_switch_pending: bool,
+
+    #[cfg(feature = "openai_compat")]
+    pub openai_models: Vec<OpenAiModel>,
 
     pub vms: Vec<VmStatus>,
     pub vm_list_state: ListState,
+    pub vm_fetch_in_flight: bool,
+    pub background_tasks: BackgroundTasks,
+    vm_cpu_samples: HashMap<String, (u64, std::time::Instant)>, // instance_id -> (cpu_time_ns, sampled at)
+    pub vm_cpu_usage: HashMap<String, f64>, // instance_id -> CPU utilization in percent of all vCPUs
+    pub marked_vms: HashSet<String>, // Names of VMs marked for a bulk destroy
+    pub vm_detail: Option<VmDetails>, // VM shown in the detail popup
+    pub vm_detail_scroll: usize,
+    // VM list filters; like the Logs filters, they select what is shown without touching `vms`
+    pub vm_state_filter: VmStateFilter,
+    pub vm_search_input: TextInput,
     
     #[cfg(feature = "ollama_integration")]
     pub ollama_model_list_state: ListState,
@@ -152,30 +555,65 @@ pub struct App {
     #[cfg(feature = "bedrock_integration")]
     pub bedrock_model_list_state: ListState,
 
+    #[cfg(feature = "openai_compat")]
+    pub openai_model_list_state: ListState,
+
     pub config: Arc<Config>,
+    pub session_manager: Arc<SessionManager>,
     pub libvirt_manager: Arc<Mutex<LibvirtManager>>,
+    pub audit_engine: Arc<AuditEngine>,
+    pub policy_engine: Arc<PolicyEngine>,
+    pub ssh_manager: Arc<SshManager>,
     pub ollama_manager: Arc<Mutex<OllamaManager>>,
     #[cfg(feature = "bedrock_integration")]
     pub bedrock_manager: Arc<Mutex<BedrockManager>>,
+    // Built from `[providers.openai_compat]`; it holds no connection, so nothing is set up at startup
+    #[cfg(feature = "openai_compat")]
+    pub openai_manager: Arc<Mutex<OpenAiCompatManager>>,
 
     pub active_view: AppView,
     pub input_mode: InputMode,
-    pub current_input: String,
-    pub active_chat: Option<ChatSession>,
+    // Open conversations in the order they were opened; `active_chat_idx` is the one shown
+    pub chats: Vec<ChatSession>,
+    pub active_chat_idx: usize,
+    pub chat_store: Option<ChatStore>,
+    // For the "load conversation" picker
+    pub show_chat_picker: bool,
+    // Command palette: the actions it offers, the typed filter, and the highlighted match
+    pub key_actions: Vec<KeyAction>,
+    pub show_command_palette: bool,
+    pub command_palette_query: String,
+    pub command_palette_state: ListState,
+    pub saved_chats: Vec<SavedChat>,
+    pub chat_picker_state: ListState,
     pub log_entries: Vec<UILogEntry>,
     pub log_list_state: ListState,
+    // Logs view filters; they select what is shown without touching `log_entries`
+    pub log_min_level: Level,
+    pub log_search_input: TextInput,
+    // Long lines are wrapped, or truncated and scrolled sideways by `log_hscroll` characters
+    pub log_wrap: bool,
+    pub log_hscroll: usize,
+    pub log_detail: Option<UILogEntry>, // Entry shown in the detail popup, copied so it outlives log rotation
+    pub log_detail_scroll: usize,
     pub log_receiver: Option<mpsc::UnboundedReceiver<UILogEntry>>,
 
-    // For Ollama chat streaming
-    pub chat_stream_sender: mpsc::UnboundedSender<ChatStreamEvent>,
-    pub chat_stream_receiver: Option<mpsc::UnboundedReceiver<ChatStreamEvent>>,
+    // For chat streaming; events are tagged with the id of the chat they belong to
+    pub chat_stream_sender: mpsc::UnboundedSender<(String, ChatStreamEvent)>,
+    pub chat_stream_receiver: Option<mpsc::UnboundedReceiver<(String, ChatStreamEvent)>>,
+    // The tasks feeding in-flight streams, by chat id, kept so they can be aborted
+    pub chat_stream_tasks: HashMap<String, tokio::task::JoinHandle<()>>,
     pub chat_list_state: ListState,
     pub theme: Arc<AppTheme>, // Add theme field
+    // Opened on first copy and kept, since X11 clipboards only serve contents while the owner lives
+    #[cfg(feature = "clipboard")]
+    pub clipboard: Option<arboard::Clipboard>,
 
     // For the New VM Popup
     pub show_new_vm_popup: bool,
     pub new_vm_name: String,
     pub new_vm_use_iso: bool,
+    pub new_vm_persistent: bool,
     pub new_vm_iso_path: String,
     pub new_vm_source_image_path: String,
     pub new_vm_disk_path: String,
@@ -183,27 +621,61 @@ pub struct App {
     pub new_vm_ram_mb: String,
     pub new_vm_disk_gb: String,
     pub active_new_vm_input_idx: usize,
+    // Why the last submit was rejected, and the wizard field to highlight (if the problem is in one field)
+    pub new_vm_error: Option<String>,
+    pub new_vm_error_field: Option<usize>,
+    // Dry run of the wizard's VM, shown over the wizard until it is created or dismissed
+    pub new_vm_preview: Option<(VmConfig, VmCreationPreview)>,
+    pub new_vm_preview_scroll: u16,
+
+    // For VM destroy/shutdown confirmation: the action and the names of the VMs it applies to
+    pub pending_vm_action: Option<(VmAction, Vec<String>)>,
+    // For model delete confirmation: the model name and its size on disk
+    pub pending_model_delete: Option<(String, u64)>,
 
-    // For VM Destruction confirmation
-    pub vm_to_destroy: Option<String>,
+    // For pulling a model: whether the input bar holds the model name, and the download in progress
+    pub editing_pull_model_name: bool,
+    pub pull_model_input: TextInput,
+    pub model_pull: Option<(String, PullProgress)>,
+
+    // For editing generation params: the model whose params the input bar holds
+    pub editing_params_for_model: Option<String>,
+    pub model_params_input: TextInput,
+
+    // For resizing a VM: the VM whose new memory/disk sizes the input bar holds
+    pub editing_resources_for_vm: Option<String>,
+    pub vm_resources_input: TextInput,
 
     // For editing system prompts
     pub editing_system_prompt_for_model: Option<String>, // Name of the model whose system prompt is being edited
     // This map will hold live edits to system prompts before saving to config
     // It's initialized from app.config and is the source for OllamaModelListWidget display
     pub editable_ollama_model_prompts: std::collections::HashMap<String, String>,
-    pub input_bar_scroll: u16, // Scroll offset for the input bar
-    pub input_bar_last_wrapped_line_count: usize, // For clamping scroll
     pub input_bar_visible_height: u16,          // For scroll calculation
-    pub input_bar_cursor_needs_to_be_visible: bool, // Flag for auto-scroll logic
-    pub input_cursor_char_idx: usize, // Character index of the cursor in current_input
     pub last_input_text_area_width: u16, // Cache for Up/Down arrow navigation
 
+    // One input buffer per editing context, so drafts never bleed between them
+    pub chat_input: TextInput,
+    pub system_prompt_input: TextInput,
+
     // State for status bar
     pub libvirt_connected: bool,
+    libvirt_connection_lost: bool, // Set when a connection drops, so its return can be announced
     pub ollama_connected: bool,
+    // Error shown in the status bar until the next key press
+    pub status_message: Option<String>,
+    // Set when `status_message` is a confirmation rather than an error
+    pub status_message_is_info: bool,
+    // Notifications stacked in the top-right corner, oldest first
+    pub toasts: Vec<Toast>,
     #[cfg(feature = "bedrock_integration")]
     pub bedrock_connected: bool,
+    #[cfg(feature = "openai_compat")]
+    pub openai_connected: bool,
+    // When the backends are next health-checked; checks back off while a backend is down
+    pub ollama_health: HealthBackoff,
+    #[cfg(feature = "bedrock_integration")]
+    pub bedrock_health: HealthBackoff,
 
     // Channel for sending async commands from sync event handlers
     pub event_sender: mpsc::UnboundedSender<AppEvent>,
@@ -213,11 +685,40 @@ pub struct App {
     pub bedrock_model_view_mode: ListViewMode<FoundationModelSummary>,
 
     pub show_keybindings_modal: bool,
+    pub keybindings_scroll: usize, // First modal line shown; clamped to the content when rendered
+    pub keybindings_selected: usize, // Index into help_action_rows()
+    pub keybinding_capture: bool, // Waiting for the new key of the selected action
 
-    pub menu_level: u8, // 0 = main, 1 = preferences
+    pub menu_level: u8, // 0 = main, 1 = preferences, 2 = theme picker
     pub menu_sub_state: ListState,
+    pub theme_choices: Vec<NamedTheme>, // Listed by the theme picker; re-scanned each time it opens
+    pub theme_picker_state: ListState,
 
     pub keybinding_map: HashMap<String, (KeyCode, KeyModifiers)>,
+    pub keybinding_issues: HashMap<String, String>, // Action -> why its binding is unparseable or conflicting
+
+    // Whether the TUI captures mouse events; the event loop applies changes to the terminal
+    pub mouse_capture_enabled: bool,
+    // Whether VM destroy/shutdown skip their confirmation popup
+    pub quick_actions: bool,
+    // Set by --read-only: keys that would create, change or destroy anything are refused
+    pub read_only: bool,
+    // Set by `hydravisor attach`: the VM list comes from a running daemon instead of libvirt
+    pub daemon: Option<Arc<DaemonLink>>,
+    // What is left to restore from the last run's UI state, applied as each list first loads
+    pub restored_selections: HashMap<AppView, String>,
+    pub restored_chat: Option<LastChat>,
+    // For mouse clicks: the list drawn last frame, and the last click, to spot double-clicks
+    pub list_hit_area: Option<ListHitArea>,
+    pub last_list_click: Option<(AppView, usize, Instant)>,
+
+    // VM to open an SSH session on; the event loop suspends the TUI to run it
+    pub pending_ssh_target: Option<VmStatus>,
+    // VM whose serial console or VNC address to open; handled by the event loop like SSH
+    pub pending_console_target: Option<VmStatus>,
+
+    // When set, model lists only show entries from `config.favorite_models`
+    pub show_favorites_only: bool,
 
     #[cfg(feature = "bedrock_integration")]
     pub current_bedrock_filter: String,
@@ -228,16 +729,19 @@ pub struct App {
 impl App {
     pub fn new(
         config: Arc<Config>,
-        _session_manager: Arc<SessionManager>,
-        _policy_engine: Arc<PolicyEngine>,
+        session_manager: Arc<SessionManager>,
+        policy_engine: Arc<PolicyEngine>,
         libvirt_manager: Arc<Mutex<LibvirtManager>>,
-        _audit_engine: Arc<AuditEngine>,
+        audit_engine: Arc<AuditEngine>,
+        ssh_manager: Arc<SshManager>,
         ollama_manager: Arc<Mutex<OllamaManager>>,
         #[cfg(feature = "bedrock_integration")] bedrock_manager: Arc<Mutex<BedrockManager>>,
+        read_only: bool,
+        daemon: Option<DaemonLink>,
         log_receiver: mpsc::UnboundedReceiver<UILogEntry>,
     ) -> Self {
         // Create channel for chat stream events
-        let (chat_tx, chat_rx) = mpsc::unbounded_channel::<ChatStreamEvent>();
+        let (chat_tx, chat_rx) = mpsc::unbounded_channel::<(String, ChatStreamEvent)>();
         let (event_tx, event_rx) = mpsc::unbounded_channel::<AppEvent>();
 
         // Initialize editable_ollama_model_prompts from config
@@ -256,36 +760,96 @@ impl App {
             menu_state: ListState::default(),
             show_about_modal: false,
             readme_content: String::new(),
+            about_scroll: 0,
             ollama_models: Vec::new(),
+            ollama_running_models: Vec::new(),
             #[cfg(feature = "bedrock_integration")]
             bedrock_models: Vec::new(),
+            #[cfg(feature = "bedrock_integration")]
+            bedrock_model_access: HashMap::new(),
+            #[cfg(feature = "bedrock_integration")]
+            pending_bedrock_chat: None,
+            #[cfg(feature = "bedrock_integration")]
+            bedrock_region: config.providers.bedrock.region.clone(),
+            #[cfg(feature = "bedrock_integration")]
+            bedrock_profile: config.providers.bedrock.profile.clone(),
+            #[cfg(feature = "bedrock_integration")]
+            bedrock_client_generation: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "bedrock_integration")]
+            bedrock_switch_pending: false,
+            #[cfg(feature = "openai_compat")]
+            openai_models: Vec::new(),
             vms: Vec::new(),
             vm_list_state: ListState::default(),
+            vm_fetch_in_flight: false,
+            background_tasks: BackgroundTasks::default(),
+            vm_cpu_samples: HashMap::new(),
+            vm_cpu_usage: HashMap::new(),
+            marked_vms: HashSet::new(),
+            vm_detail: None,
+            vm_detail_scroll: 0,
+            vm_state_filter: VmStateFilter::All,
+            vm_search_input: TextInput::new(),
             #[cfg(feature = "ollama_integration")]
             ollama_model_list_state: ListState::default(),
             #[cfg(feature = "bedrock_integration")]
             bedrock_model_list_state: ListState::default(),
+            #[cfg(feature = "openai_compat")]
+            openai_model_list_state: ListState::default(),
             config: Arc::clone(&config),
+            session_manager,
             libvirt_manager,
+            audit_engine,
+            policy_engine,
+            ssh_manager,
             ollama_manager,
             #[cfg(feature = "bedrock_integration")]
             bedrock_manager,
             #[cfg(feature = "bedrock_integration")]
             bedrock_model_view_mode,
+            #[cfg(feature = "openai_compat")]
+            openai_manager: Arc::new(Mutex::new(OpenAiCompatManager::new(&config.providers.openai_compat))),
             active_view: AppView::VmList,
             input_mode: InputMode::Normal,
-            current_input: String::new(),
-            active_chat: None,
+            chats: Vec::new(),
+            active_chat_idx: 0,
+            chat_store: ChatStore::new(config.profile.as_deref())
+                .map_err(|e| error!("Chat history is unavailable: {}", e))
+                .ok(),
+            show_chat_picker: false,
+            key_actions: key_actions(),
+            show_command_palette: false,
+            command_palette_query: String::new(),
+            command_palette_state: ListState::default(),
+            saved_chats: Vec::new(),
+            chat_picker_state: ListState::default(),
             log_entries: Vec::new(),
             log_list_state: ListState::default(),
+            log_min_level: Level::TRACE,
+            log_search_input: TextInput::new(),
+            log_wrap: false,
+            log_hscroll: 0,
+            log_detail: None,
+            log_detail_scroll: 0,
             log_receiver: Some(log_receiver),
             chat_stream_sender: chat_tx,
             chat_stream_receiver: Some(chat_rx),
+            chat_stream_tasks: HashMap::new(),
             chat_list_state: ListState::default(),
-            theme: Arc::new(AppTheme::default()), // Initialize theme
+            theme: Arc::new(AppTheme::load_configured(
+                config.interface.theme.as_deref(),
+                config.themes_dir.as_deref(),
+                config.theme_file_path.as_deref(),
+            ).unwrap_or_else(|e| {
+                error!("{:#}. Using the default theme.", e);
+                AppTheme::default()
+            })),
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
             show_new_vm_popup: false,
             new_vm_name: format!("{}-{}", &config.defaults.default_vm_image, vm_uuid.simple()),
             new_vm_use_iso: true,
+            new_vm_persistent: true,
             new_vm_iso_path: config.defaults.default_vm_iso.clone(),
             new_vm_source_image_path: config.defaults.default_source_image.clone().unwrap_or_default(),
             new_vm_disk_path: String::new(),
@@ -293,25 +857,61 @@ impl App {
             new_vm_ram_mb: config.defaults.default_ram.clone(),
             new_vm_disk_gb: config.defaults.default_disk_gb.to_string(),
             active_new_vm_input_idx: 0,
-            vm_to_destroy: None,
+            new_vm_error: None,
+            new_vm_error_field: None,
+            new_vm_preview: None,
+            new_vm_preview_scroll: 0,
+            pending_vm_action: None,
+            pending_model_delete: None,
+            editing_pull_model_name: false,
+            pull_model_input: TextInput::new(),
+            model_pull: None,
+            editing_params_for_model: None,
+            model_params_input: TextInput::new(),
+            editing_resources_for_vm: None,
+            vm_resources_input: TextInput::new(),
             editing_system_prompt_for_model: None,
             editable_ollama_model_prompts: initial_editable_prompts,
-            input_bar_scroll: 0, // Initialize scroll offset
-            input_bar_last_wrapped_line_count: 0,
             input_bar_visible_height: 1, // Default to 1, will be updated by render
-            input_bar_cursor_needs_to_be_visible: true,
-            input_cursor_char_idx: 0, // Initialize cursor position
             last_input_text_area_width: 1, // Default, will be updated by render
+            chat_input: TextInput::new(),
+            system_prompt_input: TextInput::new(),
             libvirt_connected: false, // Initial state
+            libvirt_connection_lost: false,
             ollama_connected: false, // Initial state
+            status_message: None,
+            status_message_is_info: false,
+            toasts: Vec::new(),
             #[cfg(feature = "bedrock_integration")]
             bedrock_connected: false, // Initial state
+            #[cfg(feature = "openai_compat")]
+            openai_connected: false,
+            ollama_health: HealthBackoff::new(),
+            #[cfg(feature = "bedrock_integration")]
+            bedrock_health: HealthBackoff::new(),
             event_sender: event_tx,
             event_receiver: Some(event_rx),
             show_keybindings_modal: false,
+            keybindings_scroll: 0,
+            keybindings_selected: 0,
+            keybinding_capture: false,
             menu_level: 0,
             menu_sub_state: ListState::default(),
+            theme_choices: Vec::new(),
+            theme_picker_state: ListState::default(),
             keybinding_map: HashMap::new(),
+            keybinding_issues: HashMap::new(),
+            mouse_capture_enabled: config.interface.mouse_capture,
+            quick_actions: config.interface.quick_actions && !read_only,
+            read_only,
+            daemon: daemon.map(Arc::new),
+            restored_selections: HashMap::new(),
+            restored_chat: None,
+            list_hit_area: None,
+            last_list_click: None,
+            pending_ssh_target: None,
+            pending_console_target: None,
+            show_favorites_only: false,
             #[cfg(feature = "bedrock_integration")]
             current_bedrock_filter: config.providers.bedrock.filters.default.clone(),
             #[cfg(feature = "bedrock_integration")]
@@ -320,29 +920,302 @@ impl App {
         
         // Read README.md for the about modal
         let readme_path = "README.md";
-        let readme_lines = app.config.interface.about_modal_readme_lines;
         match std::fs::read_to_string(readme_path) {
             Ok(content) => {
-                app.readme_content = content.lines().take(readme_lines).collect::<Vec<&str>>().join("\n");
+                app.readme_content = content;
             }
             Err(e) => {
                 app.readme_content = format!("Could not read README.md: {}", e);
             }
         }
 
-        let keybinding_map = parse_keybindings(&app.config.keybindings);
+        let (keybinding_map, keybinding_issues) = parse_keybindings(&app.config.keybindings);
+        let mut sorted_issues: Vec<_> = keybinding_issues.iter().collect();
+        sorted_issues.sort();
+        for (action, issue) in sorted_issues {
+            warn!("Keybinding '{}': {}", action, issue);
+        }
         app.keybinding_map = keybinding_map;
+        app.keybinding_issues = keybinding_issues;
+
+        let ui_state = UiState::load(app.config.profile.as_deref());
+        app.restore_ui_state(ui_state);
 
         app
     }
 
+    /// Returns to the view left last run. Selections and the chat are restored later, by
+    /// `apply_restored_ui_state`, once the lists they point into have loaded.
+    fn restore_ui_state(&mut self, state: UiState) {
+        self.restored_selections = state.selected_items.into_iter()
+            .filter_map(|(id, name)| AppView::from_id(&id).map(|view| (view, name)))
+            .collect();
+        self.restored_chat = state.last_chat;
+        match state.active_view.as_deref().and_then(AppView::from_id) {
+            // With no chat to bring back, the Chat view would open empty
+            Some(AppView::Chat) if self.restored_chat.is_none() => {}
+            Some(view) => self.active_view = view,
+            None => {}
+        }
+    }
+
+    /// Names (or ids) of the items a list view shows, in the order shown; None for views
+    /// that aren't one of the item lists.
+    fn list_item_names(&self, view: AppView) -> Option<Vec<String>> {
+        match view {
+            AppView::VmList => Some(self.visible_vms().iter().map(|vm| vm.name.clone()).collect()),
+            AppView::OllamaModelList => Some(self.visible_ollama_models().iter().map(|m| m.name.clone()).collect()),
+            #[cfg(feature = "bedrock_integration")]
+            AppView::BedrockModelList => Some(
+                self.bedrock_model_view_mode.apply(&self.bedrock_models).iter().map(|m| m.model_id().to_string()).collect(),
+            ),
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => Some(self.visible_openai_models().iter().map(|m| m.id.clone()).collect()),
+            AppView::Chat | AppView::Logs => None,
+        }
+    }
+
+    fn list_state_mut(&mut self, view: AppView) -> Option<&mut ListState> {
+        match view {
+            AppView::VmList => Some(&mut self.vm_list_state),
+            AppView::OllamaModelList => Some(&mut self.ollama_model_list_state),
+            #[cfg(feature = "bedrock_integration")]
+            AppView::BedrockModelList => Some(&mut self.bedrock_model_list_state),
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => Some(&mut self.openai_model_list_state),
+            AppView::Chat | AppView::Logs => None,
+        }
+    }
+
+    /// Reselects the items selected last run in lists that now have items, and reopens the
+    /// last chat once its provider's models are listed. An item or model that is no longer
+    /// listed (or filtered out) is dropped and the default kept.
+    fn apply_restored_ui_state(&mut self) {
+        let views: Vec<AppView> = self.restored_selections.keys().copied().collect();
+        for view in views {
+            let Some(names) = self.list_item_names(view).filter(|names| !names.is_empty()) else {
+                continue; // Not loaded yet
+            };
+            let Some(name) = self.restored_selections.remove(&view) else { continue };
+            let Some(idx) = names.iter().position(|item| *item == name) else { continue };
+            if let Some(list_state) = self.list_state_mut(view) {
+                list_state.select(Some(idx));
+            }
+        }
+
+        let Some(last_chat) = self.restored_chat.clone() else { return };
+        #[allow(unused_mut)]
+        let mut listed: Option<Vec<&str>> = match last_chat.provider {
+            ChatProvider::Ollama => Some(self.ollama_models.iter().map(|m| m.name.as_str()).collect()),
+            _ => None,
+        };
+        #[cfg(feature = "bedrock_integration")]
+        if last_chat.provider == ChatProvider::Bedrock {
+            listed = Some(self.bedrock_models.iter().map(|m| m.model_id()).collect());
+        }
+        #[cfg(feature = "openai_compat")]
+        if last_chat.provider == ChatProvider::OpenAiCompat {
+            listed = Some(self.openai_models.iter().map(|m| m.id.as_str()).collect());
+        }
+        let available = match listed {
+            Some(models) if models.is_empty() => return, // Not loaded yet
+            Some(models) => models.contains(&last_chat.model_name.as_str()),
+            None => false, // Provider not in this build
+        };
+        self.restored_chat = None;
+        if available {
+            // Opening a chat switches to it; stay wherever the user is now
+            let view = self.active_view;
+            self.open_chat_with_model(last_chat.provider, &last_chat.model_name);
+            self.active_view = view;
+        } else if self.active_view == AppView::Chat && self.chats.is_empty() {
+            self.active_view = AppView::VmList;
+        }
+    }
+
+    /// Saves the active view, list selections and chat model for the next run. Read-only
+    /// sessions leave the saved state alone.
+    pub fn save_ui_state(&self) {
+        if self.read_only {
+            return;
+        }
+        #[allow(unused_mut)]
+        let mut lists = vec![
+            (AppView::VmList, &self.vm_list_state),
+            (AppView::OllamaModelList, &self.ollama_model_list_state),
+        ];
+        #[cfg(feature = "bedrock_integration")]
+        lists.push((AppView::BedrockModelList, &self.bedrock_model_list_state));
+        #[cfg(feature = "openai_compat")]
+        lists.push((AppView::OpenAiModelList, &self.openai_model_list_state));
+        let state = UiState {
+            active_view: Some(self.active_view.id().to_string()),
+            selected_items: lists.into_iter()
+                .filter_map(|(view, list_state)| {
+                    let name = self.list_item_names(view)?.into_iter().nth(list_state.selected()?)?;
+                    Some((view.id().to_string(), name))
+                })
+                .collect(),
+            last_chat: self.active_chat().map(|chat| LastChat { provider: chat.provider, model_name: chat.model_name.clone() }),
+        };
+        if let Err(e) = state.save(self.config.profile.as_deref()) {
+            warn!("Failed to save UI state: {}", e);
+        }
+    }
+
     pub fn tick(&mut self) {
         // This method can be used for periodic updates, e.g., animations
+        let now = std::time::Instant::now();
+        self.toasts.retain(|toast| toast.expires_at > now);
+    }
+
+    /// Brings size-dependent state back in range after the terminal is resized. The input bar
+    /// re-wraps on the next draw, so each input is asked to scroll its cursor back into view.
+    pub fn handle_resize(&mut self) {
+        for input in [
+            &mut self.chat_input,
+            &mut self.system_prompt_input,
+            &mut self.log_search_input,
+            &mut self.vm_search_input,
+            &mut self.pull_model_input,
+            &mut self.model_params_input,
+            &mut self.vm_resources_input,
+        ] {
+            input.scroll = input.scroll.min(input.last_wrapped_line_count.saturating_sub(1) as u16);
+            input.cursor_needs_to_be_visible = true;
+        }
+
+        let vm_count = self.visible_vms().len();
+        clamp_selection(&mut self.vm_list_state, vm_count);
+        #[cfg(feature = "ollama_integration")]
+        {
+            let model_count = self.visible_ollama_models().len();
+            clamp_selection(&mut self.ollama_model_list_state, model_count);
+        }
+        #[cfg(feature = "bedrock_integration")]
+        clamp_selection(&mut self.bedrock_model_list_state, self.bedrock_model_view_mode.apply(&self.bedrock_models).len());
+        #[cfg(feature = "openai_compat")]
+        {
+            let model_count = self.visible_openai_models().len();
+            clamp_selection(&mut self.openai_model_list_state, model_count);
+        }
+        let message_count = self.active_chat().map_or(0, |c| c.messages.len());
+        clamp_selection(&mut self.chat_list_state, message_count);
+        let log_count = self.visible_log_entries().len();
+        clamp_selection(&mut self.log_list_state, log_count);
+        clamp_selection(&mut self.chat_picker_state, self.saved_chats.len());
+    }
+
+    /// Shows a toast for `ttl`. Only the newest few are kept.
+    pub fn notify(&mut self, level: ToastLevel, text: impl Into<String>, ttl: std::time::Duration) {
+        self.toasts.push(Toast {
+            level,
+            text: text.into(),
+            expires_at: std::time::Instant::now() + ttl,
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Shows an error toast for twice the usual time.
+    pub fn notify_error(&mut self, text: impl Into<String>) {
+        self.notify(ToastLevel::Error, text, TOAST_TTL * 2);
+    }
+
+    /// Where the VM list comes from, as named in toasts and the status bar.
+    pub fn vm_source_name(&self) -> &'static str {
+        if self.daemon.is_some() { "the daemon" } else { "libvirt" }
+    }
+
+    /// When attached to a daemon, says that `what` needs this host's libvirt, which an attached
+    /// TUI doesn't use, and returns true so the caller stops.
+    pub fn refuse_when_attached(&mut self, what: &str) -> bool {
+        if self.daemon.is_some() {
+            self.notify(ToastLevel::Warning, format!("Attached to a daemon: {} is not available", what), TOAST_TTL);
+        }
+        self.daemon.is_some()
+    }
+
+    /// In read-only mode, says that `what` is disabled and returns true so the caller stops.
+    pub fn refuse_in_read_only(&mut self, what: &str) -> bool {
+        if self.read_only {
+            self.notify(ToastLevel::Warning, format!("Read-only mode: {} is disabled", what), TOAST_TTL);
+        }
+        self.read_only
+    }
+
+    /// Records an informational audit event, logging rather than returning a failure to write it.
+    pub fn record_audit_event(&self, event_type: AuditEventType) {
+        if let Err(e) = self.audit_engine.record_event(AuditEvent::new(event_type, Some(RiskLevel::Info))) {
+            error!("Failed to record audit event: {}", e);
+        }
+    }
+
+    /// Records the user's answer to a VM action confirmation in the audit ledger.
+    pub fn record_vm_action_decision(&self, action: VmAction, vm_name: &str, allowed: bool) {
+        let action_name = match action {
+            VmAction::Destroy => "destroy_vm",
+            VmAction::Shutdown => "shutdown_vm",
+        };
+        let event_type = AuditEventType::PolicyDecision {
+            agent_id: None,
+            action: action_name.to_string(),
+            resource: Some(vm_name.to_string()),
+            allowed,
+            reason: Some(if allowed { "Confirmed by user in TUI" } else { "Cancelled by user in TUI" }.to_string()),
+        };
+        if let Err(e) = self.audit_engine.record_event(AuditEvent::new(event_type, Some(RiskLevel::Info))) {
+            error!("Failed to record VM action decision in the audit ledger: {}", e);
+        }
     }
 
+    /// Asks the event loop to re-fetch the VM and model lists.
+    pub fn request_data_refresh(&self) {
+        let _ = self.event_sender.send(AppEvent::FetchVms);
+        #[cfg(feature = "ollama_integration")]
+        let _ = self.event_sender.send(AppEvent::FetchOllamaModels);
+        #[cfg(feature = "bedrock_integration")]
+        let _ = self.event_sender.send(AppEvent::FetchBedrockModels);
+        #[cfg(feature = "openai_compat")]
+        let _ = self.event_sender.send(AppEvent::FetchOpenAiModels);
+    }
+
+    #[cfg(feature = "openai_compat")]
+    pub async fn fetch_openai_models(&mut self) {
+        let mut manager = self.openai_manager.lock().await;
+        match manager.list_local_models().await {
+            Ok(models) => {
+                self.openai_models = models;
+                let visible_count = self.visible_openai_models().len();
+                if visible_count == 0 {
+                    self.openai_model_list_state.select(None);
+                } else if self.openai_model_list_state.selected().is_none() {
+                    self.openai_model_list_state.select(Some(0));
+                }
+            }
+            Err(e) => {
+                error!("Failed to fetch OpenAI-compatible models: {}", e);
+            }
+        }
+        self.openai_connected = manager.is_openai_connected();
+        drop(manager);
+        self.apply_restored_ui_state();
+    }
+
+    /// Lists Bedrock models. Listing is Bedrock's health check too: while it is down, the
+    /// connection is retried with backoff instead of on every refresh.
     #[cfg(feature = "bedrock_integration")]
     pub async fn fetch_bedrock_models(&mut self) {
-        let bm = self.bedrock_manager.lock().await;
+        if self.bedrock_switch_pending {
+            return; // apply_bedrock_client_switch fetches once the new client is in place
+        }
+        let mut bm = self.bedrock_manager.lock().await;
+        let now = std::time::Instant::now();
+        if !bm.is_bedrock_connected() && self.bedrock_health.is_due(now) {
+            let result = bm.check_health().await;
+            self.bedrock_health.record_check("AWS Bedrock", false, &result, now);
+        }
         self.bedrock_connected = bm.is_bedrock_connected();
         if self.bedrock_connected {
             match bm.list_foundation_models().await {
@@ -356,15 +1229,169 @@ impl App {
                 }
                 Err(e) => {
                     error!("Failed to fetch Bedrock models: {}", e);
+                    bm.mark_disconnected();
+                    self.bedrock_connected = false;
+                    self.bedrock_health.record_check("AWS Bedrock", true, &Err(e), now);
+                }
+            }
+        }
+        drop(bm);
+        self.apply_restored_ui_state();
+    }
+
+    /// Rebuilds the Bedrock client for another region or AWS profile. Models and access results
+    /// from the old client are dropped; the new list is fetched once `BedrockClientSwitched` arrives.
+    #[cfg(feature = "bedrock_integration")]
+    pub fn switch_bedrock_client(&mut self, region: String, profile: String) {
+        self.bedrock_region = region.clone();
+        self.bedrock_profile = profile.clone();
+        self.bedrock_models.clear();
+        self.bedrock_model_access.clear();
+        self.pending_bedrock_chat = None;
+        self.bedrock_model_list_state.select(None);
+        self.bedrock_connected = false;
+        self.bedrock_switch_pending = true;
+        self.notify(ToastLevel::Info, format!("Connecting to Bedrock in {} (profile '{}')…", region, profile), TOAST_TTL);
+
+        let generation = self.bedrock_client_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let current_generation = Arc::clone(&self.bedrock_client_generation);
+        let bedrock_manager = Arc::clone(&self.bedrock_manager);
+        let sender = self.event_sender.clone();
+        let task = self.background_tasks.start();
+        tokio::spawn(async move {
+            let _task = task;
+            let error = match BedrockManager::new(Some(region.clone()), Some(profile.clone())).await {
+                Ok(manager) => {
+                    let error = manager.connection_error.clone();
+                    let mut guard = bedrock_manager.lock().await;
+                    // Checked under the lock: a later switch may have started while this one connected
+                    if current_generation.load(Ordering::SeqCst) != generation {
+                        return;
+                    }
+                    *guard = manager;
+                    error
+                }
+                Err(e) => Some(format!("bedrock: {}", e)),
+            };
+            let _ = sender.send(AppEvent::BedrockClientSwitched { generation, region, profile, error });
+        });
+    }
+
+    /// Moves the Bedrock client to the next region in `providers.bedrock.regions`.
+    #[cfg(feature = "bedrock_integration")]
+    pub fn cycle_bedrock_region(&mut self) {
+        let regions = self.config.providers.bedrock.regions.clone();
+        let Some(next) = next_in_cycle(&regions, &self.bedrock_region) else {
+            self.notify(ToastLevel::Warning, "No regions in providers.bedrock.regions", TOAST_TTL);
+            return;
+        };
+        self.switch_bedrock_client(next, self.bedrock_profile.clone());
+    }
+
+    /// Moves the Bedrock client to the next profile in the shared AWS config files.
+    #[cfg(feature = "bedrock_integration")]
+    pub fn cycle_bedrock_profile(&mut self) {
+        let profiles = BedrockManager::aws_profile_names();
+        let Some(next) = next_in_cycle(&profiles, &self.bedrock_profile) else {
+            self.notify(ToastLevel::Warning, "No AWS profiles found in ~/.aws/config or ~/.aws/credentials", TOAST_TTL);
+            return;
+        };
+        self.switch_bedrock_client(self.bedrock_region.clone(), next);
+    }
+
+    #[cfg(feature = "bedrock_integration")]
+    pub fn apply_bedrock_client_switch(&mut self, generation: usize, region: String, profile: String, error: Option<String>) {
+        // A newer switch superseded this one; its own event follows
+        if generation != self.bedrock_client_generation.load(Ordering::SeqCst) {
+            return;
+        }
+        self.bedrock_switch_pending = false;
+        let now = std::time::Instant::now();
+        self.bedrock_health = HealthBackoff::new();
+        match error {
+            None => {
+                self.bedrock_health.record_check("AWS Bedrock", false, &Ok(()), now);
+                self.notify(ToastLevel::Success, format!("Bedrock: {} (profile '{}')", region, profile), TOAST_TTL);
+            }
+            Some(e) => {
+                self.bedrock_health.record_check("AWS Bedrock", false, &Err(anyhow::anyhow!(e.clone())), now);
+                self.notify_error(e);
+            }
+        }
+        let _ = self.event_sender.send(AppEvent::FetchBedrockModels);
+    }
+
+    /// Starts an access check of a Bedrock model; the result arrives as `BedrockModelAccessChecked`.
+    #[cfg(feature = "bedrock_integration")]
+    pub fn check_bedrock_model_access(&mut self, model_id: String) {
+        let bedrock_manager = Arc::clone(&self.bedrock_manager);
+        let sender = self.event_sender.clone();
+        let task = self.background_tasks.start();
+        tokio::spawn(async move {
+            let _task = task;
+            let bedrock_manager = bedrock_manager.lock().await;
+            let result = bedrock_manager.model_access_status(&model_id).await.map_err(|e| e.to_string());
+            let access_url = bedrock_manager.model_access_url();
+            let _ = sender.send(AppEvent::BedrockModelAccessChecked { model_id, result, access_url });
+        });
+    }
+
+    /// Opens a chat with a Bedrock model. A model last seen without access is checked again
+    /// first, and its chat opens only if access has since been granted.
+    #[cfg(feature = "bedrock_integration")]
+    pub fn open_bedrock_model(&mut self, model_id: &str) {
+        match self.bedrock_model_access.get(model_id) {
+            Some(ModelAccess::Requestable | ModelAccess::Denied) => {
+                self.notify(ToastLevel::Info, format!("Checking access to '{}' again…", model_id), TOAST_TTL);
+                self.pending_bedrock_chat = Some(model_id.to_string());
+                self.check_bedrock_model_access(model_id.to_string());
+            }
+            _ => self.open_chat_with_model(ChatProvider::Bedrock, model_id),
+        }
+    }
+
+    #[cfg(feature = "bedrock_integration")]
+    pub fn apply_bedrock_model_access(&mut self, model_id: String, result: Result<ModelAccess, String>, access_url: &str) {
+        let opening = self.pending_bedrock_chat.as_deref() == Some(model_id.as_str());
+        if opening {
+            self.pending_bedrock_chat = None;
+        }
+        match result {
+            Ok(ModelAccess::Granted) if opening => {
+                self.bedrock_model_access.insert(model_id.clone(), ModelAccess::Granted);
+                self.open_chat_with_model(ChatProvider::Bedrock, &model_id);
+            }
+            Ok(access) => {
+                self.bedrock_model_access.insert(model_id.clone(), access);
+                match access {
+                    ModelAccess::Granted => self.notify(ToastLevel::Success, format!("Access to '{}' is granted", model_id), TOAST_TTL),
+                    ModelAccess::Requestable => self.notify(
+                        ToastLevel::Warning,
+                        format!("No access to '{}' yet. Request it at {}", model_id, access_url),
+                        TOAST_TTL * 3,
+                    ),
+                    ModelAccess::Denied => self.notify(
+                        ToastLevel::Warning,
+                        format!("Access to '{}' is denied by your IAM policy", model_id),
+                        TOAST_TTL * 2,
+                    ),
                 }
             }
+            Err(e) => self.notify_error(e),
         }
     }
 
+    /// Health-checks Ollama when due, then lists its models if it is up.
     pub async fn fetch_ollama_models(&mut self) {
         #[cfg(feature = "ollama_integration")]
         {
-            let om = self.ollama_manager.lock().await;
+            let mut om = self.ollama_manager.lock().await;
+            let now = std::time::Instant::now();
+            if self.ollama_health.is_due(now) {
+                let was_connected = om.is_ollama_connected();
+                let result = om.check_health().await;
+                self.ollama_health.record_check("Ollama", was_connected, &result, now);
+            }
             self.ollama_connected = om.is_ollama_connected();
             if self.ollama_connected {
                 match om.list_local_models().await {
@@ -381,36 +1408,114 @@ impl App {
                         self.ollama_models.clear(); // Clear models on failure
                     }
                 }
-            }
-        }
-    }
-
-    pub async fn fetch_vms(&mut self) {
-        let lm = self.libvirt_manager.lock().await;
-        self.libvirt_connected = lm.is_libvirt_connected();
-        if self.libvirt_connected {
-            match lm.list_vms() {
-                Ok(vms) => {
-                    self.vms = vms;
-                    if self.vms.is_empty() {
-                        self.vm_list_state.select(None);
-                    } else if self.vm_list_state.selected().is_none() {
-                        self.vm_list_state.select(Some(0));
+                match om.list_running_models().await {
+                    Ok(running) => self.ollama_running_models = running,
+                    Err(e) => {
+                        // Not fatal: the loaded indicator is informational only
+                        debug!("Could not fetch loaded Ollama models: {}", e);
+                        self.ollama_running_models.clear();
                     }
                 }
-                Err(e) => {
-                    error!("Failed to fetch VMs: {}", e);
-                    self.vms.clear();
-                    self.vm_list_state.select(None);
-                }
             }
-        } else {
-            // If not connected, clear the list
-            self.vms.clear();
-            self.vm_list_state.select(None);
+            drop(om);
+            self.apply_restored_ui_state();
         }
     }
 
+    /// Starts a background VM listing, from the daemon when attached to one; the result arrives
+    /// as `AppEvent::VmsFetched`.
+    pub fn fetch_vms(&mut self) {
+        if self.vm_fetch_in_flight {
+            return; // A slow libvirt shouldn't pile up overlapping listings
+        }
+        self.vm_fetch_in_flight = true;
+        let event_sender = self.event_sender.clone();
+        let task = self.background_tasks.start();
+        if let Some(daemon) = &self.daemon {
+            let daemon = Arc::clone(daemon);
+            tokio::spawn(async move {
+                let _task = task;
+                // `connected` tracks the daemon here; its own libvirt state isn't reported
+                let event = match daemon.list_vms().await {
+                    Ok(vms) => AppEvent::VmsFetched { connected: true, result: Ok(vms) },
+                    Err(e) => AppEvent::VmsFetched { connected: false, result: Err(user_message(&e)) },
+                };
+                let _ = event_sender.send(event);
+            });
+            return;
+        }
+        let libvirt_manager = Arc::clone(&self.libvirt_manager);
+        tokio::spawn(async move {
+            let _task = task;
+            let event = match LibvirtManager::list_vms_async(libvirt_manager).await {
+                Ok((connected, vms)) => AppEvent::VmsFetched { connected, result: Ok(vms) },
+                Err(e) => AppEvent::VmsFetched { connected: true, result: Err(user_message(&e)) },
+            };
+            let _ = event_sender.send(event);
+        });
+    }
+
+    pub fn apply_fetched_vms(&mut self, connected: bool, result: Result<Vec<VmStatus>, String>) {
+        self.vm_fetch_in_flight = false;
+        let source = self.vm_source_name();
+        if self.libvirt_connected && !connected {
+            self.libvirt_connection_lost = true;
+            self.notify(ToastLevel::Warning, format!("Lost connection to {}. Reconnecting in the background.", source), TOAST_TTL);
+        } else if connected && self.libvirt_connection_lost {
+            self.libvirt_connection_lost = false;
+            self.notify(ToastLevel::Success, format!("Reconnected to {}.", source), TOAST_TTL);
+        }
+        self.libvirt_connected = connected;
+        match result {
+            Ok(vms) => {
+                self.vms = vms;
+                // Marks on VMs that no longer exist would otherwise be destroyed by name later
+                let vms = &self.vms;
+                self.marked_vms.retain(|name| vms.iter().any(|vm| &vm.name == name));
+                self.update_vm_cpu_usage();
+                let visible_count = self.visible_vms().len();
+                if visible_count == 0 {
+                    self.vm_list_state.select(None);
+                } else if self.vm_list_state.selected().map_or(true, |idx| idx >= visible_count) {
+                    self.vm_list_state.select(Some(0));
+                }
+                self.apply_restored_ui_state();
+            }
+            Err(e) => {
+                error!("Failed to fetch VMs: {}", e);
+                self.vms.clear();
+                self.vm_cpu_samples.clear();
+                self.vm_cpu_usage.clear();
+                self.marked_vms.clear();
+                self.vm_list_state.select(None);
+            }
+        }
+    }
+
+    // CPU utilization is the growth in cumulative CPU time between two fetches, divided by the
+    // wall time elapsed and the vCPU count. virt 0.4 has no per-domain CPU stats call, so the
+    // DomainInfo counter is sampled instead; a VM needs two fetches before it has a reading.
+    fn update_vm_cpu_usage(&mut self) {
+        let now = std::time::Instant::now();
+        let mut samples = HashMap::new();
+        let mut usage = HashMap::new();
+        for vm in &self.vms {
+            let Some(cpu_time) = vm.cpu_time_ns else { continue };
+            if let Some(&(prev_cpu_time, prev_at)) = self.vm_cpu_samples.get(&vm.instance_id) {
+                let elapsed_ns = now.duration_since(prev_at).as_nanos() as f64;
+                let vcpus = vm.cpu_cores_used.unwrap_or(1).max(1) as f64;
+                // A restarted domain resets its counter, so a drop gives no reading this tick
+                if elapsed_ns > 0.0 && cpu_time >= prev_cpu_time {
+                    let percent = (cpu_time - prev_cpu_time) as f64 / (elapsed_ns * vcpus) * 100.0;
+                    usage.insert(vm.instance_id.clone(), percent.clamp(0.0, 100.0));
+                }
+            }
+            samples.insert(vm.instance_id.clone(), (cpu_time, now));
+        }
+        self.vm_cpu_samples = samples;
+        self.vm_cpu_usage = usage;
+    }
+
     // This gets the system prompt for a model, checking for a model-specific override
     // in our live-editing map first, then falling back to the main config.
     pub fn get_active_system_prompt(&self, model_name: &str) -> String {
@@ -424,30 +1529,306 @@ impl App {
             })
     }
 
+    /// Ollama models in display order: favorites first, optionally restricted to favorites only.
+    #[cfg(feature = "ollama_integration")]
+    pub fn visible_ollama_models(&self) -> Vec<&LocalModel> {
+        let mut models: Vec<&LocalModel> = self.ollama_models
+            .iter()
+            .filter(|m| !self.show_favorites_only || self.config.is_favorite_model(&m.name))
+            .collect();
+        models.sort_by_key(|m| !self.config.is_favorite_model(&m.name));
+        models
+    }
+
+    #[cfg(feature = "openai_compat")]
+    pub fn visible_openai_models(&self) -> Vec<&OpenAiModel> {
+        let mut models: Vec<&OpenAiModel> = self.openai_models
+            .iter()
+            .filter(|m| !self.show_favorites_only || self.config.is_favorite_model(&m.id))
+            .collect();
+        models.sort_by_key(|m| !self.config.is_favorite_model(&m.id));
+        models
+    }
+
+    #[cfg(feature = "openai_compat")]
+    pub fn selected_openai_model_id(&self) -> Option<String> {
+        let selected = self.openai_model_list_state.selected()?;
+        self.visible_openai_models().get(selected).map(|m| m.id.clone())
+    }
+
+    #[cfg(feature = "bedrock_integration")]
+    pub fn selected_bedrock_model_id(&self) -> Option<String> {
+        let selected = self.bedrock_model_list_state.selected()?;
+        self.bedrock_model_view_mode.apply(&self.bedrock_models).get(selected).map(|m| m.model_id().to_string())
+    }
+
+    pub fn running_ollama_model(&self, model_name: &str) -> Option<&RunningModel> {
+        self.ollama_running_models.iter().find(|m| m.name == model_name)
+    }
+
+    // Adds or removes a model from the favorites list and persists it to config.toml. If the
+    // save fails, the favorites are left as they were.
+    pub fn toggle_favorite_model(&mut self, model_name: &str) {
+        let mut updated_config = (*self.config).clone();
+        let removed = match updated_config.favorite_models.iter().position(|m| m == model_name) {
+            Some(pos) => {
+                updated_config.favorite_models.remove(pos);
+                true
+            }
+            None => {
+                updated_config.favorite_models.push(model_name.to_string());
+                false
+            }
+        };
+        if let Err(e) = updated_config.save() {
+            error!("Failed to save favorite models to config: {}", e);
+            self.notify_error(format!("Failed to save favorites: {}", e));
+            return;
+        }
+        if removed {
+            info!("Removed '{}' from favorite models.", model_name);
+        } else {
+            info!("Added '{}' to favorite models.", model_name);
+        }
+        self.config = Arc::new(updated_config);
+    }
+
+    /// Opens the generation params of a model for editing in the input bar.
+    pub fn begin_model_params_input(&mut self, model_name: String) {
+        let params = self.config.get_model_params(&model_name);
+        self.model_params_input.set_text(params.to_edit_string());
+        self.editing_params_for_model = Some(model_name);
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Parses the edited params and persists them to config.toml. Invalid input, or a failed
+    /// save, keeps the editor open and the old params in effect.
+    pub fn save_model_params_input(&mut self) {
+        let Some(model_name) = self.editing_params_for_model.clone() else { return; };
+        let params = match ModelParams::parse(self.model_params_input.text()) {
+            Ok(params) => params,
+            Err(e) => {
+                error!("Invalid generation params for '{}': {}", model_name, e);
+                self.notify_error(format!("Invalid params: {}", e));
+                return;
+            }
+        };
+        let mut updated_config = (*self.config).clone();
+        if params.is_empty() {
+            updated_config.providers.ollama.model_params.remove(&model_name);
+        } else {
+            updated_config.providers.ollama.model_params.insert(model_name.clone(), params);
+        }
+        if let Err(e) = updated_config.save() {
+            error!("Failed to save generation params to config: {}", e);
+            self.notify_error(format!("Failed to save params: {}", e));
+            return;
+        }
+        info!("Updated generation params for '{}'.", model_name);
+        self.notify(ToastLevel::Success, format!("Saved params for '{}'", model_name), TOAST_TTL);
+        self.config = Arc::new(updated_config);
+        self.editing_params_for_model = None;
+        self.model_params_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Opens the input bar on the selected VM's new memory and disk sizes.
+    pub fn begin_vm_resize_input(&mut self) {
+        let Some(vm_name) = self.selected_vm().map(|vm| vm.name.clone()) else { return; };
+        self.vm_resources_input.set_text(VmResourceChange::EDIT_TEMPLATE.to_string());
+        self.editing_resources_for_vm = Some(vm_name);
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Parses the edited sizes and hands them to libvirt in the background. Invalid input keeps the editor open.
+    pub fn submit_vm_resize_input(&mut self) {
+        let Some(vm_name) = self.editing_resources_for_vm.clone() else { return; };
+        let change = match VmResourceChange::parse(self.vm_resources_input.text()) {
+            Ok(change) if change.is_empty() => {
+                self.notify_error("Nothing to change; set cpu=, ram= or disk_gb=".to_string());
+                return;
+            }
+            Ok(change) => change,
+            Err(e) => {
+                self.notify_error(format!("Invalid sizes: {}", e));
+                return;
+            }
+        };
+        let role = self.vms.iter().find(|vm| vm.name == vm_name).and_then(|vm| vm.role.as_deref());
+        if let Err(e) = self.policy_engine.check_vm_limits(role, change.cpus, change.memory_mb) {
+            self.notify_error(e.to_string());
+            return;
+        }
+        self.status_message = Some(format!("Resizing VM '{}' ({})…", vm_name, change.describe()));
+        self.status_message_is_info = true;
+        self.event_sender.send(AppEvent::ResizeVm { vm_name, change }).unwrap();
+        self.editing_resources_for_vm = None;
+        self.vm_resources_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn toggle_favorites_only(&mut self) {
+        self.show_favorites_only = !self.show_favorites_only;
+        #[cfg(feature = "ollama_integration")]
+        {
+            let visible_count = self.visible_ollama_models().len();
+            self.ollama_model_list_state.select(if visible_count == 0 { None } else { Some(0) });
+        }
+        #[cfg(feature = "bedrock_integration")]
+        self.bedrock_model_list_state.select(if self.bedrock_models.is_empty() { None } else { Some(0) });
+        #[cfg(feature = "openai_compat")]
+        {
+            let visible_count = self.visible_openai_models().len();
+            self.openai_model_list_state.select(if visible_count == 0 { None } else { Some(0) });
+        }
+    }
+
+    // The list state and item count of the menu level being shown
+    fn active_menu(&mut self) -> (&mut ListState, usize) {
+        match self.menu_level {
+            1 => (&mut self.menu_sub_state, PREFERENCES_MENU_ITEMS.len()),
+            2 => (&mut self.theme_picker_state, self.theme_choices.len()),
+            _ => (&mut self.menu_state, MAIN_MENU_ITEMS.len()),
+        }
+    }
+
     pub fn menu_next(&mut self) {
-        let i = match self.menu_state.selected() {
-            Some(i) => (i + 1) % 3, // 3 items in menu
+        let (state, count) = self.active_menu();
+        if count == 0 { return; }
+        let i = match state.selected() {
+            Some(i) => (i + 1) % count,
             None => 0,
         };
-        self.menu_state.select(Some(i));
+        state.select(Some(i));
     }
 
     pub fn menu_previous(&mut self) {
-        let i = match self.menu_state.selected() {
-            Some(i) => (i + 3 - 1) % 3,
+        let (state, count) = self.active_menu();
+        if count == 0 { return; }
+        let i = match state.selected() {
+            Some(i) => (i + count - 1) % count,
             None => 0,
         };
-        self.menu_state.select(Some(i));
+        state.select(Some(i));
+    }
+
+    /// Opens Preferences → Theme with the themes found now, selecting the one in use.
+    pub fn open_theme_picker(&mut self) {
+        self.theme_choices = available_themes(self.config.themes_dir.as_deref());
+        let current = self.config.interface.theme.as_deref();
+        let selected = self.theme_choices.iter().position(|theme| Some(theme.name.as_str()) == current);
+        self.theme_picker_state.select(Some(selected.unwrap_or(0)));
+        self.menu_level = 2;
+    }
+
+    /// Swaps in the theme selected in the picker once it is remembered in config.toml. If the
+    /// theme can't be loaded or saved, the current one stays.
+    pub fn apply_selected_theme(&mut self) {
+        let Some(choice) = self.theme_picker_state.selected().and_then(|i| self.theme_choices.get(i)).cloned() else {
+            return;
+        };
+        match choice.load() {
+            Ok(theme) => {
+                let mut updated_config = (*self.config).clone();
+                updated_config.interface.theme = Some(choice.name.clone());
+                if let Err(e) = updated_config.save() {
+                    error!("Failed to save theme choice to config: {}", e);
+                    self.notify_error(format!("Theme not applied, failed to save it: {}", e));
+                    return;
+                }
+                // Widgets read the theme through this Arc on every frame, so the next draw uses it
+                self.theme = Arc::new(theme);
+                info!("Applied theme '{}'.", choice.name);
+                self.notify(ToastLevel::Success, format!("Theme '{}' applied", choice.name), TOAST_TTL);
+                self.config = Arc::new(updated_config);
+            }
+            Err(e) => {
+                error!("Failed to load theme '{}': {:#}", choice.name, e);
+                self.notify_error(format!("Failed to load theme '{}': {:#}", choice.name, e));
+            }
+        }
+    }
+
+    /// VMs matching the state filter and whose name contains the search text, in list order.
+    pub fn visible_vms(&self) -> Vec<&VmStatus> {
+        let query = self.vm_search_input.text().to_lowercase();
+        self.vms
+            .iter()
+            .filter(|vm| self.vm_state_filter.matches(&vm.state))
+            .filter(|vm| query.is_empty() || vm.name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// The highlighted VM in the filtered list.
+    pub fn selected_vm(&self) -> Option<&VmStatus> {
+        self.visible_vms().get(self.vm_list_state.selected()?).copied()
+    }
+
+    /// Fetches the highlighted VM's full details in the background; they open in the detail popup
+    /// as `AppEvent::VmDetailsFetched`.
+    pub fn open_vm_detail(&mut self) {
+        let Some(vm_name) = self.selected_vm().map(|vm| vm.name.clone()) else { return };
+        if self.refuse_when_attached(&format!("VM details (run `hydravisor vm info {}` on the daemon's host)", vm_name)) {
+            return;
+        }
+        let libvirt_manager = Arc::clone(&self.libvirt_manager);
+        let event_sender = self.event_sender.clone();
+        let task = self.background_tasks.start();
+        tokio::spawn(async move {
+            let _task = task;
+            let result = libvirt_manager.lock().await.get_vm_details(&vm_name).map_err(|e| user_message(&e));
+            let _ = event_sender.send(AppEvent::VmDetailsFetched { vm_name, result });
+        });
+    }
+
+    pub fn apply_vm_details(&mut self, vm_name: &str, result: Result<VmDetails, String>) {
+        match result {
+            Ok(details) => {
+                self.vm_detail = Some(details);
+                self.vm_detail_scroll = 0;
+            }
+            Err(e) => self.notify(ToastLevel::Error, format!("Failed to get details of VM '{}': {}", vm_name, e), TOAST_TTL),
+        }
+    }
+
+    /// The first free `<source>-clone-<n>` name for a clone of `source_name`.
+    pub fn next_clone_name(&self, source_name: &str) -> String {
+        (1..)
+            .map(|n| format!("{}-clone-{}", source_name, n))
+            .find(|name| !self.vms.iter().any(|vm| &vm.name == name))
+            .expect("an unused clone name exists")
+    }
+
+    /// Re-selects after the VM filters change: the same VM if it is still shown, else the first.
+    pub fn reselect_vm(&mut self, previous_name: Option<String>) {
+        let visible = self.visible_vms();
+        let idx = previous_name
+            .and_then(|name| visible.iter().position(|vm| vm.name == name))
+            .or(if visible.is_empty() { None } else { Some(0) });
+        self.vm_list_state.select(idx);
+    }
+
+    pub fn cycle_vm_state_filter(&mut self) {
+        let previous_name = self.selected_vm().map(|vm| vm.name.clone());
+        self.vm_state_filter = self.vm_state_filter.next();
+        self.reselect_vm(previous_name);
+    }
+
+    /// Enters search input mode for the VM list; the list narrows as the name is typed.
+    pub fn begin_vm_search(&mut self) {
+        self.vm_search_input.cursor_needs_to_be_visible = true;
+        self.input_mode = InputMode::Editing;
     }
 
     pub fn select_next_item_in_vm_list(&mut self) {
-        if self.vms.is_empty() {
+        let visible_count = self.visible_vms().len();
+        if visible_count == 0 {
             self.vm_list_state.select(None);
             return;
         }
         let i = match self.vm_list_state.selected() {
             Some(i) => {
-                if i >= self.vms.len() - 1 {
+                if i >= visible_count - 1 {
                     0
                 } else {
                     i + 1
@@ -459,14 +1840,15 @@ impl App {
     }
 
     pub fn select_previous_item_in_vm_list(&mut self) {
-        if self.vms.is_empty() {
+        let visible_count = self.visible_vms().len();
+        if visible_count == 0 {
             self.vm_list_state.select(None);
             return;
         }
         let i = match self.vm_list_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.vms.len() - 1
+                    visible_count - 1
                 } else {
                     i - 1
                 }
@@ -478,13 +1860,14 @@ impl App {
 
     #[cfg(feature = "ollama_integration")]
     pub fn select_next_item_in_ollama_list(&mut self) {
-        if self.ollama_models.is_empty() {
+        let visible_count = self.visible_ollama_models().len();
+        if visible_count == 0 {
             self.ollama_model_list_state.select(None);
             return;
         }
         let i = match self.ollama_model_list_state.selected() {
             Some(i) => {
-                if i >= self.ollama_models.len() - 1 {
+                if i >= visible_count - 1 {
                     0
                 } else {
                     i + 1
@@ -497,14 +1880,15 @@ impl App {
 
     #[cfg(feature = "ollama_integration")]
     pub fn select_previous_item_in_ollama_list(&mut self) {
-        if self.ollama_models.is_empty() {
+        let visible_count = self.visible_ollama_models().len();
+        if visible_count == 0 {
             self.ollama_model_list_state.select(None);
             return;
         }
         let i = match self.ollama_model_list_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.ollama_models.len() - 1
+                    visible_count - 1
                 } else {
                     i - 1
                 }
@@ -515,7 +1899,7 @@ impl App {
     }
 
     pub fn scroll_chat_up(&mut self) {
-        if let Some(_) = &self.active_chat {
+        if self.active_chat().is_some() {
             let current_selection = self.chat_list_state.selected().unwrap_or(0);
             if current_selection > 0 {
                 self.chat_list_state.select(Some(current_selection - 1));
@@ -524,7 +1908,7 @@ impl App {
     }
     
     pub fn scroll_chat_down(&mut self) {
-        if let Some(session) = &self.active_chat {
+        if let Some(session) = self.active_chat() {
             if session.messages.is_empty() { return; }
             let max_index = session.messages.len() - 1;
             let current_selection = self.chat_list_state.selected().unwrap_or(0);
@@ -542,17 +1926,641 @@ impl App {
     }
     
     pub fn scroll_logs_down(&mut self) {
-        if self.log_entries.is_empty() { return; }
-        let max_index = self.log_entries.len() - 1;
+        let visible_count = self.visible_log_entries().len();
+        if visible_count == 0 { return; }
+        let max_index = visible_count - 1;
         let current_selection = self.log_list_state.selected().unwrap_or(0);
         if current_selection < max_index {
             self.log_list_state.select(Some(current_selection + 1));
         }
     }
 
-    pub fn reset_cursor_position(&mut self) {
-        self.input_cursor_char_idx = self.current_input.chars().count();
-        self.input_bar_cursor_needs_to_be_visible = true;
+    /// Log entries at or above the minimum level whose message or target contains the search text.
+    pub fn visible_log_entries(&self) -> Vec<&UILogEntry> {
+        let query = self.log_search_input.text().to_lowercase();
+        self.log_entries
+            .iter()
+            .filter(|entry| entry.level <= self.log_min_level)
+            .filter(|entry| {
+                query.is_empty()
+                    || entry.message.to_lowercase().contains(&query)
+                    || entry.target.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    /// Opens the detail popup for the selected log entry.
+    pub fn open_log_detail(&mut self) {
+        let entry = self.log_list_state.selected()
+            .and_then(|idx| self.visible_log_entries().get(idx).map(|entry| (*entry).clone()));
+        if entry.is_some() {
+            self.log_detail = entry;
+            self.log_detail_scroll = 0;
+        }
+    }
+
+    /// Raises the minimum level shown in the Logs view, wrapping from ERROR back to TRACE.
+    pub fn cycle_log_level_filter(&mut self) {
+        self.log_min_level = match self.log_min_level {
+            Level::TRACE => Level::DEBUG,
+            Level::DEBUG => Level::INFO,
+            Level::INFO => Level::WARN,
+            Level::WARN => Level::ERROR,
+            _ => Level::TRACE,
+        };
+        self.log_list_state.select(self.visible_log_entries().len().checked_sub(1));
+    }
+
+    /// Text of the selected chat message (with its thought, if any) or the selected log line.
+    fn selected_copy_text(&self) -> Option<String> {
+        match self.active_view {
+            AppView::Chat => {
+                let session = self.active_chat()?;
+                let message = session.messages.get(self.chat_list_state.selected()?)?;
+                match message.thought.as_deref().filter(|t| !t.is_empty()) {
+                    Some(thought) => Some(format!("<think>\n{}\n</think>\n\n{}", thought, message.content)),
+                    None => Some(message.content.clone()),
+                }
+            }
+            AppView::Logs => {
+                let entries = self.visible_log_entries();
+                let entry = entries.get(self.log_list_state.selected()?)?;
+                let target = match entry.location() {
+                    Some(location) => format!("{} {}", entry.target, location),
+                    None => entry.target.clone(),
+                };
+                Some(format!("{} {:<5} [{}] {}", entry.timestamp, entry.level.as_str(), target, entry.message))
+            }
+            _ => None,
+        }
+    }
+
+    /// Copies the selected chat message or log line to the system clipboard.
+    pub fn copy_selection_to_clipboard(&mut self) {
+        let Some(text) = self.selected_copy_text() else {
+            self.status_message = Some("Nothing selected to copy.".to_string());
+            return;
+        };
+        #[cfg(feature = "clipboard")]
+        {
+            let result = match self.clipboard.as_mut() {
+                Some(clipboard) => clipboard.set_text(text.as_str()),
+                None => arboard::Clipboard::new().and_then(|clipboard| {
+                    self.clipboard.insert(clipboard).set_text(text.as_str())
+                }),
+            };
+            match result {
+                Ok(()) => {
+                    self.status_message = Some(format!("Copied {} chars", text.chars().count()));
+                    self.status_message_is_info = true;
+                }
+                Err(e) => {
+                    error!("Failed to copy to the clipboard: {}", e);
+                    self.status_message = Some(format!("Clipboard unavailable: {}", e));
+                }
+            }
+        }
+        #[cfg(not(feature = "clipboard"))]
+        {
+            debug!("Not copying {} chars: built without the clipboard feature.", text.chars().count());
+            self.status_message = Some("Clipboard support is not enabled in this build.".to_string());
+        }
+    }
+
+    /// Enters search input mode for the Logs view; the filter updates as the query is typed.
+    pub fn begin_log_search(&mut self) {
+        self.log_search_input.cursor_needs_to_be_visible = true;
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Opens the new-VM wizard with its fields as last left and no error shown.
+    pub fn open_new_vm_wizard(&mut self) {
+        self.show_new_vm_popup = true;
+        self.input_mode = InputMode::VmWizard;
+        self.active_new_vm_input_idx = new_vm_field::NAME;
+        self.new_vm_error = None;
+        self.new_vm_error_field = None;
+    }
+
+    /// Validates the wizard and queues the VM for creation. On failure the wizard stays open,
+    /// focused on the offending field, with the reason shown in the popup.
+    ///
+    /// The VM is dry-run first. It is created straight away if its disk path is free; otherwise,
+    /// and always when `create` is false, the dry run is shown so the user can decide.
+    pub fn submit_new_vm_wizard(&mut self, create: bool) {
+        let result = match self.validate_new_vm_wizard() {
+            Some((field, reason)) => Err((Some(field), reason)),
+            None => self.new_vm_config_from_wizard().map_err(|e| (None, e.to_string())),
+        };
+        match result {
+            Ok(vm_config) => {
+                self.new_vm_error = None;
+                self.new_vm_error_field = None;
+                self.event_sender.send(AppEvent::PreviewVm { vm_config, create }).unwrap();
+            }
+            Err((field, reason)) => {
+                info!("New VM wizard rejected: {}", reason);
+                if let Some(field) = field {
+                    self.active_new_vm_input_idx = field;
+                }
+                self.new_vm_error_field = field;
+                self.new_vm_error = Some(reason);
+            }
+        }
+    }
+
+    /// Acts on a finished dry run of the wizard's VM: creates it, or shows the preview.
+    pub fn apply_vm_preview(&mut self, vm_config: VmConfig, create: bool, result: Result<VmCreationPreview, String>) {
+        if !self.show_new_vm_popup {
+            return; // The wizard was closed while the dry run was in flight
+        }
+        match result {
+            Ok(preview) if create && !preview.disk_exists => self.create_vm_from_wizard(vm_config),
+            Ok(preview) => {
+                self.new_vm_preview = Some((vm_config, preview));
+                self.new_vm_preview_scroll = 0;
+            }
+            Err(e) => {
+                info!("New VM dry run failed: {}", e);
+                self.new_vm_error_field = None;
+                self.new_vm_error = Some(e);
+            }
+        }
+    }
+
+    /// Queues the VM for creation and closes the wizard.
+    pub fn create_vm_from_wizard(&mut self, vm_config: VmConfig) {
+        self.event_sender.send(AppEvent::CreateVm(vm_config)).unwrap();
+        self.new_vm_preview = None;
+        self.show_new_vm_popup = false;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Checks the wizard fields, returning the index of the first invalid field and why.
+    /// Blank CPU, RAM and disk size fields are valid; they take the configured defaults.
+    fn validate_new_vm_wizard(&self) -> Option<(usize, String)> {
+        let name = self.new_vm_name.trim();
+        if let Err(e) = check_vm_name(name) {
+            return Some((new_vm_field::NAME, e.to_string()));
+        }
+        if self.vms.iter().any(|vm| vm.name == name) {
+            return Some((new_vm_field::NAME, format!("a VM named '{}' already exists", name)));
+        }
+
+        // The same checks as `hydravisor vm create`; blank fields are skipped
+        let non_empty = |value: &str| Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        if self.new_vm_use_iso {
+            if let Some(Err(e)) = non_empty(&self.new_vm_iso_path).map(|path| check_vm_file("ISO", &path)) {
+                return Some((new_vm_field::ISO_PATH, e.to_string()));
+            }
+        } else if let Some(Err(e)) = non_empty(&self.new_vm_source_image_path).map(|path| check_vm_file("source image", &path)) {
+            return Some((new_vm_field::SOURCE_IMAGE, e.to_string()));
+        }
+        if let Some(Err(e)) = non_empty(&self.new_vm_cpu).map(|cpu| parse_vm_cpus(&cpu)) {
+            return Some((new_vm_field::CPU, e.to_string()));
+        }
+        if let Some(Err(e)) = non_empty(&self.new_vm_ram_mb).map(|ram| parse_vm_memory_mb(&ram)) {
+            return Some((new_vm_field::RAM, e.to_string()));
+        }
+        if let Some(Err(e)) = non_empty(&self.new_vm_disk_gb).map(|disk| parse_vm_disk_gb(&disk)) {
+            return Some((new_vm_field::DISK_SIZE, e.to_string()));
+        }
+        None
+    }
+
+    /// Builds a `VmConfig` from the new-VM wizard fields, falling back to config defaults.
+    pub fn new_vm_config_from_wizard(&self) -> Result<VmConfig> {
+        let defaults = &self.config.defaults;
+        let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        // Blank CPU and RAM fields take the policy defaults; anything above the policy maximums is rejected
+        let cpus = non_empty(&self.new_vm_cpu)
+            .map(|cpu| cpu.parse::<u32>().map_err(|_| anyhow::anyhow!("invalid vCPU count '{}'", cpu)))
+            .transpose()?;
+        let mem_mb = non_empty(&self.new_vm_ram_mb)
+            .map(|ram| parse_ram_str(&ram).map_err(|_| anyhow::anyhow!("invalid RAM size '{}'", ram)))
+            .transpose()?;
+        let (cpu_cores, memory_mb) = self.policy_engine.resolve_vm_resources(None, cpus, mem_mb)?;
+        Ok(VmConfig {
+            instance_id: self.new_vm_name.trim().to_string(),
+            base_image: self.new_vm_source_image_path.trim().to_string(),
+            boot_iso: if self.new_vm_use_iso { non_empty(&self.new_vm_iso_path) } else { None },
+            cpu_cores,
+            memory_mb,
+            disk_gb: Some(
+                non_empty(&self.new_vm_disk_gb)
+                    .map(|disk| disk.parse::<u64>().map_err(|_| anyhow::anyhow!("invalid disk size '{}'", disk)))
+                    .transpose()?
+                    .unwrap_or(defaults.default_disk_gb),
+            ),
+            disk_path: non_empty(&self.new_vm_disk_path),
+            network_policy: "default".to_string(),
+            security_policy: "default".to_string(),
+            custom_script: None,
+            template_name: None,
+            labels: None,
+            persistent: self.new_vm_persistent,
+            cloud_init: if self.new_vm_use_iso { None } else { default_cloud_init(&self.config) },
+            existing_disk: ExistingDisk::Refuse,
+        })
+    }
+
+
+    /// The input buffer the input bar is currently showing, if any.
+    pub fn active_text_input(&self) -> Option<&TextInput> {
+        if self.editing_pull_model_name {
+            Some(&self.pull_model_input)
+        } else if self.editing_params_for_model.is_some() {
+            Some(&self.model_params_input)
+        } else if self.editing_resources_for_vm.is_some() {
+            Some(&self.vm_resources_input)
+        } else if self.editing_system_prompt_for_model.is_some() {
+            Some(&self.system_prompt_input)
+        } else if self.active_view == AppView::Chat {
+            Some(&self.chat_input)
+        } else if self.active_view == AppView::Logs {
+            Some(&self.log_search_input)
+        } else if self.active_view == AppView::VmList {
+            Some(&self.vm_search_input)
+        } else {
+            None
+        }
+    }
+
+    pub fn active_text_input_mut(&mut self) -> Option<&mut TextInput> {
+        if self.editing_pull_model_name {
+            Some(&mut self.pull_model_input)
+        } else if self.editing_params_for_model.is_some() {
+            Some(&mut self.model_params_input)
+        } else if self.editing_resources_for_vm.is_some() {
+            Some(&mut self.vm_resources_input)
+        } else if self.editing_system_prompt_for_model.is_some() {
+            Some(&mut self.system_prompt_input)
+        } else if self.active_view == AppView::Chat {
+            Some(&mut self.chat_input)
+        } else if self.active_view == AppView::Logs {
+            Some(&mut self.log_search_input)
+        } else if self.active_view == AppView::VmList {
+            Some(&mut self.vm_search_input)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the input bar is currently editing a chat message (as opposed to a system prompt).
+    pub fn is_editing_chat(&self) -> bool {
+        self.input_mode == InputMode::Editing
+            && self.editing_system_prompt_for_model.is_none()
+            && !self.editing_pull_model_name
+            && self.editing_params_for_model.is_none()
+            && self.editing_resources_for_vm.is_none()
+            && self.active_view == AppView::Chat
+    }
+
+    /// Saves the active conversation if it has any messages beyond the system prompt,
+    /// returning where it was written.
+    pub fn save_active_chat(&mut self) -> Option<std::path::PathBuf> {
+        self.save_chat(self.active_chat_idx)
+    }
+
+    /// Saves every open conversation that has messages, e.g. on quit.
+    pub fn save_all_chats(&mut self) {
+        for idx in 0..self.chats.len() {
+            self.save_chat(idx);
+        }
+    }
+
+    fn save_chat(&mut self, idx: usize) -> Option<std::path::PathBuf> {
+        let (Some(store), Some(session)) = (&self.chat_store, self.chats.get(idx)) else { return None; };
+        if !session.messages.iter().any(|m| m.sender == "user") {
+            return None;
+        }
+        match store.save(session) {
+            Ok(path) => {
+                info!("Saved chat with '{}' to {}", session.model_name, path.display());
+                Some(path)
+            }
+            Err(e) => {
+                error!("Failed to save chat with '{}': {}", session.model_name, e);
+                self.notify_error(format!("Failed to save chat: {}", e));
+                None
+            }
+        }
+    }
+
+    /// Opens the keybindings modal scrolled to the current view's section, with its first action selected.
+    pub fn open_keybindings_modal(&mut self) {
+        let section_line = help_section_line(self.active_view);
+        self.keybindings_scroll = section_line;
+        self.keybindings_selected = help_action_rows().iter().position(|(line, _, _)| *line > section_line).unwrap_or(0);
+        self.keybinding_capture = false;
+        self.show_keybindings_modal = true;
+    }
+
+    /// Moves the keybindings modal's selection by `delta` rows, stopping at either end.
+    pub fn move_keybindings_selection(&mut self, delta: isize) {
+        let last = help_action_rows().len().saturating_sub(1);
+        self.keybindings_selected = self.keybindings_selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Binds the action selected in the keybindings modal to the key just pressed.
+    pub fn rebind_selected_action(&mut self, key_event: KeyEvent) {
+        self.keybinding_capture = false;
+        let mut mods = key_event.modifiers;
+        // As in key matching, the character already says whether Shift was held
+        if matches!(key_event.code, KeyCode::Char(_)) {
+            mods.remove(KeyModifiers::SHIFT);
+        }
+        let binding = format_keybinding(key_event.code, mods);
+        if parse_keybinding(&binding) != Some((key_event.code, mods)) {
+            self.notify_error(format!("{} can't be used as a keybinding", binding));
+            return;
+        }
+        self.set_selected_keybinding(binding);
+    }
+
+    /// Puts the action selected in the keybindings modal back on its default key.
+    pub fn reset_selected_keybinding(&mut self) {
+        let Some((_, action, _)) = help_action_rows().get(self.keybindings_selected).copied() else { return; };
+        let mut defaults = crate::config::KeyBindingsConfig::default();
+        if let Some(default) = defaults.binding_mut(action) {
+            let default = default.clone();
+            self.set_selected_keybinding(default);
+        }
+    }
+
+    // Saves a new binding for the selected action to config.toml and applies it, unless it
+    // conflicts or the save fails
+    fn set_selected_keybinding(&mut self, binding: String) {
+        let Some((_, action, label)) = help_action_rows().get(self.keybindings_selected).copied() else { return; };
+        let mut updated_config = (*self.config).clone();
+        let Some(slot) = updated_config.keybindings.binding_mut(action) else {
+            self.notify_error(format!("'{}' can't be rebound here", action));
+            return;
+        };
+        *slot = binding.clone();
+        let (keybinding_map, keybinding_issues) = parse_keybindings(&updated_config.keybindings);
+        if let Some(issue) = keybinding_issues.get(action) {
+            self.notify_error(format!("Not bound: {}", issue));
+            return;
+        }
+        if let Err(e) = updated_config.save() {
+            error!("Failed to save keybindings to config: {}", e);
+            self.notify_error(format!("Keybinding not applied, failed to save it: {}", e));
+            return;
+        }
+        info!("Bound '{}' to {}.", action, binding);
+        self.notify(ToastLevel::Success, format!("{}: {}", label, binding), TOAST_TTL);
+        self.keybinding_map = keybinding_map;
+        self.keybinding_issues = keybinding_issues;
+        self.config = Arc::new(updated_config);
+    }
+
+    /// Opens the command palette with an empty filter.
+    pub fn open_command_palette(&mut self) {
+        self.command_palette_query.clear();
+        self.command_palette_state.select(Some(0));
+        self.show_command_palette = true;
+    }
+
+    /// Bound actions available in the current view that match the palette filter, best first.
+    pub fn command_palette_matches(&self) -> Vec<&KeyAction> {
+        let mut matches: Vec<(i32, &KeyAction)> = self.key_actions
+            .iter()
+            .filter(|action| action.is_available_in(self.active_view) && self.keybinding_map.contains_key(action.id))
+            .filter(|action| !(self.read_only && action.is_blocked_in_read_only(self.active_view)))
+            .filter_map(|action| {
+                let score = fuzzy_score(&self.command_palette_query, action.label)
+                    .max(fuzzy_score(&self.command_palette_query, action.id))?;
+                Some((score, action))
+            })
+            .collect();
+        // Stable, so an empty query keeps the registry order
+        if !self.command_palette_query.is_empty() {
+            matches.sort_by(|a, b| b.0.cmp(&a.0));
+        }
+        matches.into_iter().map(|(_, action)| action).collect()
+    }
+
+    /// Opens the picker listing saved conversations.
+    pub fn open_chat_picker(&mut self) {
+        let Some(store) = &self.chat_store else { return; };
+        match store.list() {
+            Ok(chats) => {
+                self.chat_picker_state.select(if chats.is_empty() { None } else { Some(0) });
+                self.saved_chats = chats;
+                self.show_chat_picker = true;
+            }
+            Err(e) => error!("Failed to list saved chats: {}", e),
+        }
+    }
+
+    /// Resumes the conversation selected in the picker in a chat of its own, or switches to it
+    /// if it is already open.
+    pub fn load_selected_chat(&mut self) {
+        let Some(id) = self.chat_picker_state.selected()
+            .and_then(|idx| self.saved_chats.get(idx))
+            .map(|chat| chat.id.clone()) else { return; };
+        let Some(store) = &self.chat_store else { return; };
+        match store.load(&id) {
+            Ok(session) => {
+                match self.chats.iter().position(|c| c.id == session.id) {
+                    Some(idx) => self.switch_chat(idx),
+                    None => {
+                        info!("Resumed chat '{}' with '{}'.", session.id, session.model_name);
+                        self.open_chat(session);
+                    }
+                }
+                self.active_view = AppView::Chat;
+                self.show_chat_picker = false;
+            }
+            Err(e) => error!("Failed to load chat '{}': {}", id, e),
+        }
+    }
+
+    /// Prompts for the name of a model to pull in the input bar.
+    pub fn begin_pull_model_input(&mut self) {
+        if self.model_pull.is_some() {
+            info!("A model download is already in progress.");
+            return;
+        }
+        self.pull_model_input.clear();
+        self.pull_model_input.cursor_needs_to_be_visible = true;
+        self.editing_pull_model_name = true;
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Pulls the model named in the input bar, reporting progress through `AppEvent`s.
+    pub fn start_model_pull(&mut self) {
+        let model_name = self.pull_model_input.take().trim().to_string();
+        if model_name.is_empty() {
+            return;
+        }
+        self.model_pull = Some((model_name.clone(), PullProgress { status: "starting".to_string(), completed: None, total: None }));
+
+        let ollama_manager = Arc::clone(&self.ollama_manager);
+        let sender = self.event_sender.clone();
+        let task = self.background_tasks.start();
+        tokio::spawn(async move {
+            let _task = task;
+            let stream_result = ollama_manager.lock().await.pull_model(&model_name).await;
+            let error = match stream_result {
+                Ok(stream) => {
+                    futures::pin_mut!(stream);
+                    let mut error = None;
+                    while let Some(item) = stream.next().await {
+                        match item {
+                            Ok(progress) => {
+                                if sender.send(AppEvent::ModelPullProgress(progress)).is_err() {
+                                    return; // The TUI has shut down
+                                }
+                            }
+                            Err(e) => {
+                                error = Some(user_message(&anyhow::Error::from(e)));
+                                break;
+                            }
+                        }
+                    }
+                    error
+                }
+                Err(e) => Some(user_message(&e)),
+            };
+            let _ = sender.send(AppEvent::ModelPullFinished { model_name, error });
+        });
+    }
+
+    pub fn active_chat(&self) -> Option<&ChatSession> {
+        self.chats.get(self.active_chat_idx)
+    }
+
+    /// Expands or collapses the thought of the selected message, or of the latest message with
+    /// one when nothing is selected.
+    pub fn toggle_selected_thought(&mut self) {
+        let selected = self.chat_list_state.selected();
+        let Some(session) = self.chats.get_mut(self.active_chat_idx) else { return; };
+        let has_thought = |m: &ChatMessage| m.thought.as_deref().is_some_and(|t| !t.is_empty());
+        let message = match selected {
+            Some(idx) => session.messages.get_mut(idx).filter(|m| has_thought(m)),
+            None => session.messages.iter_mut().rev().find(|m| has_thought(m)),
+        };
+        match message {
+            Some(message) => message.thought_expanded = !message.thought_expanded,
+            None => self.notify(ToastLevel::Info, "No thought to show for this message", TOAST_TTL),
+        }
+    }
+
+    /// Shows open chat `idx`.
+    pub fn switch_chat(&mut self, idx: usize) {
+        if idx < self.chats.len() {
+            self.active_chat_idx = idx;
+            self.chat_list_state.select(None);
+        }
+    }
+
+    pub fn next_chat(&mut self) {
+        if !self.chats.is_empty() {
+            self.switch_chat((self.active_chat_idx + 1) % self.chats.len());
+        }
+    }
+
+    pub fn previous_chat(&mut self) {
+        if !self.chats.is_empty() {
+            self.switch_chat((self.active_chat_idx + self.chats.len() - 1) % self.chats.len());
+        }
+    }
+
+    /// Adds a conversation and shows it.
+    pub fn open_chat(&mut self, mut session: ChatSession) {
+        session.id = self.unique_chat_id(session.id);
+        self.chats.push(session);
+        self.switch_chat(self.chats.len() - 1);
+    }
+
+    // Ids are per-second timestamps and name the save file, so two chats opened with the same
+    // model in the same second get a suffix rather than overwriting each other
+    fn unique_chat_id(&self, base_id: String) -> String {
+        let mut id = base_id.clone();
+        let mut n = 2;
+        while self.chats.iter().any(|c| c.id == id) {
+            id = format!("{}-{}", base_id, n);
+            n += 1;
+        }
+        id
+    }
+
+    /// Shows a chat with `model_name`: the active one if it already is, else the latest open
+    /// chat with it, else a new one.
+    pub fn open_chat_with_model(&mut self, provider: ChatProvider, model_name: &str) {
+        let is_match = |c: &ChatSession| c.provider == provider && c.model_name == model_name;
+        if !self.active_chat().is_some_and(is_match) {
+            match self.chats.iter().rposition(is_match) {
+                Some(idx) => self.switch_chat(idx),
+                None => {
+                    let system_prompt = self.get_active_system_prompt(model_name);
+                    self.open_chat(ChatSession::new(provider, model_name, system_prompt));
+                }
+            }
+        }
+        self.active_view = AppView::Chat;
+        self.chat_list_state.select(None);
+    }
+
+    /// Opens an empty conversation with the active chat's model, leaving the current one open.
+    pub fn new_chat(&mut self) {
+        let Some((provider, model_name)) = self.active_chat().map(|c| (c.provider, c.model_name.clone())) else {
+            self.notify(ToastLevel::Info, "No chat open; press Enter on a model to start one", TOAST_TTL);
+            return;
+        };
+        let system_prompt = self.get_active_system_prompt(&model_name);
+        self.open_chat(ChatSession::new(provider, &model_name, system_prompt));
+    }
+
+    /// Empties the active conversation, keeping its model. What it held is saved first, and the
+    /// chat gets a new id so the next save doesn't overwrite it.
+    pub fn clear_active_chat(&mut self) {
+        let Some((provider, model_name)) = self.active_chat().map(|c| (c.provider, c.model_name.clone())) else {
+            return;
+        };
+        self.cancel_chat_stream();
+        self.save_active_chat();
+        let system_prompt = self.get_active_system_prompt(&model_name);
+        let mut session = ChatSession::new(provider, &model_name, system_prompt);
+        session.id = self.unique_chat_id(session.id);
+        self.chats[self.active_chat_idx] = session;
+        self.chat_list_state.select(None);
+    }
+
+    /// Saves and closes the active conversation, stopping its stream.
+    pub fn close_active_chat(&mut self) {
+        self.close_chat(self.active_chat_idx);
+    }
+
+    /// Saves and closes every open conversation with `model_name`, e.g. when it is deleted.
+    pub fn close_chats_with_model(&mut self, model_name: &str) {
+        while let Some(idx) = self.chats.iter().position(|c| c.model_name == model_name) {
+            self.close_chat(idx);
+        }
+    }
+
+    fn close_chat(&mut self, idx: usize) {
+        if idx >= self.chats.len() {
+            return;
+        }
+        self.cancel_chat_stream_at(idx);
+        self.save_chat(idx);
+        self.chats.remove(idx);
+        if idx < self.active_chat_idx || self.active_chat_idx >= self.chats.len() {
+            self.active_chat_idx = self.active_chat_idx.saturating_sub(1);
+        }
+        self.chat_list_state.select(None);
+    }
+
+    /// Enters chat input mode; any unsent draft is still in `chat_input`.
+    pub fn begin_chat_input(&mut self) {
+        if self.active_chat().is_none() {
+            return;
+        }
+        self.chat_input.cursor_needs_to_be_visible = true;
+        self.input_mode = InputMode::Editing;
     }
 
     #[cfg(feature = "bedrock_integration")]
@@ -592,35 +2600,77 @@ impl App {
         };
         self.bedrock_model_list_state.select(Some(i));
     }
+
+    #[cfg(feature = "openai_compat")]
+    pub fn select_next_item_in_openai_list(&mut self) {
+        let visible_count = self.visible_openai_models().len();
+        if visible_count == 0 {
+            self.openai_model_list_state.select(None);
+            return;
+        }
+        let i = match self.openai_model_list_state.selected() {
+            Some(i) if i < visible_count - 1 => i + 1,
+            _ => 0,
+        };
+        self.openai_model_list_state.select(Some(i));
+    }
+
+    #[cfg(feature = "openai_compat")]
+    pub fn select_previous_item_in_openai_list(&mut self) {
+        let visible_count = self.visible_openai_models().len();
+        if visible_count == 0 {
+            self.openai_model_list_state.select(None);
+            return;
+        }
+        let i = match self.openai_model_list_state.selected() {
+            Some(0) => visible_count - 1,
+            Some(i) => i - 1,
+            None => 0,
+        };
+        self.openai_model_list_state.select(Some(i));
+    }
 }
 
-// Helper for parsing RAM string like "4GB" or "2048MB"
-pub fn parse_ram_str(ram_str: &str) -> Result<u64> {
-    let s = ram_str.trim().to_uppercase();
-    if let Some(num_str) = s.strip_suffix("GB") {
-        num_str
-            .trim()
-            .parse::<u64>()
-            .map(|num| num * 1024)
-            .map_err(anyhow::Error::from)
-    } else if let Some(num_str) = s.strip_suffix("MB") {
-        num_str.trim().parse::<u64>().map_err(anyhow::Error::from)
-    } else {
-        ram_str
-            .trim()
-            .parse::<u64>()
-            .map_err(anyhow::Error::from)
+// Keeps a selection inside a list of `len` items and resets the scroll offset, which the
+// next render recomputes so the selection is on screen.
+fn clamp_selection(state: &mut ListState, len: usize) {
+    if let Some(selected) = state.selected() {
+        state.select(len.checked_sub(1).map(|last| selected.min(last)));
     }
+    *state.offset_mut() = 0;
+}
+
+// The item after `current` in `items`, wrapping around; the first item if `current` isn't there
+#[cfg(feature = "bedrock_integration")]
+fn next_in_cycle(items: &[String], current: &str) -> Option<String> {
+    let next = items.iter().position(|item| item == current).map_or(0, |idx| (idx + 1) % items.len());
+    items.get(next).cloned()
 }
 
-fn parse_keybindings(cfg: &crate::config::KeyBindingsConfig) -> HashMap<String, (KeyCode, KeyModifiers)> {
+/// Parses `[keybindings]` into the action -> key map. Bindings that can't be parsed are left
+/// out (or fall back to their default, for the filter/sort keys), and every unparseable or
+/// conflicting binding is reported as an action -> problem message.
+pub fn parse_keybindings(cfg: &crate::config::KeyBindingsConfig) -> (HashMap<String, (KeyCode, KeyModifiers)>, HashMap<String, String>) {
     let mut map = HashMap::new();
+    let mut issues = HashMap::new();
     macro_rules! insert {
         ($action:expr, $binding:expr) => {
-            if let Some((code, mods)) = parse_keybinding(&$binding) {
-                map.insert($action.to_string(), (code, mods));
+            match parse_keybinding(&$binding) {
+                Some((code, mods)) => {
+                    map.insert($action.to_string(), (code, mods));
+                }
+                None => {
+                    issues.insert($action.to_string(), format!("\"{}\" is not a key this action can be bound to", $binding));
+                }
             }
         };
+        ($action:expr, $binding:expr, $default:expr) => {
+            let parsed = parse_keybinding(&$binding).unwrap_or_else(|| {
+                issues.insert($action.to_string(), format!("\"{}\" is not a key; using the default", $binding));
+                $default()
+            });
+            map.insert($action.to_string(), parsed);
+        };
     }
     insert!("quit", cfg.quit);
     insert!("help", cfg.help);
@@ -629,45 +2679,120 @@ fn parse_keybindings(cfg: &crate::config::KeyBindingsConfig) -> HashMap<String,
     insert!("prev_tab", cfg.prev_tab);
     insert!("new_vm", cfg.new_vm);
     insert!("destroy_vm", cfg.destroy_vm);
+    insert!("mark_vm", cfg.mark_vm);
+    insert!("refresh", cfg.refresh);
+    insert!("shutdown_vm", cfg.shutdown_vm);
+    insert!("suspend_vm", cfg.suspend_vm);
+    insert!("ssh_vm", cfg.ssh_vm);
+    insert!("console_vm", cfg.console_vm);
+    insert!("clone_vm", cfg.clone_vm);
     insert!("edit", cfg.edit);
     insert!("enter", cfg.enter);
     insert!("up", cfg.up);
     insert!("down", cfg.down);
+    insert!("toggle_mouse", cfg.toggle_mouse);
+    insert!("toggle_quick_actions", cfg.toggle_quick_actions);
+    insert!("toggle_favorite", cfg.toggle_favorite);
+    insert!("favorites_only", cfg.favorites_only);
+    insert!("log_level", cfg.log_level);
+    insert!("wrap_logs", cfg.wrap_logs);
+    insert!("search", cfg.search);
+    insert!("save_chat", cfg.save_chat);
+    insert!("load_chat", cfg.load_chat);
+    insert!("cancel_stream", cfg.cancel_stream);
+    insert!("toggle_thought", cfg.toggle_thought);
+    insert!("new_chat", cfg.new_chat);
+    insert!("clear_chat", cfg.clear_chat);
+    insert!("close_chat", cfg.close_chat);
+    insert!("next_chat", cfg.next_chat);
+    insert!("prev_chat", cfg.prev_chat);
+    insert!("pull_model", cfg.pull_model);
+    insert!("delete_model", cfg.delete_model);
+    insert!("edit_params", cfg.edit_params);
+    insert!("copy", cfg.copy);
+    insert!("command_palette", cfg.command_palette);
+    insert!("filter", cfg.filter, default_parsed_filter);
+    insert!("sort", cfg.sort, default_parsed_sort);
+    insert!("bedrock_filter", cfg.bedrock.filter, default_parsed_bedrock_filter);
+    insert!("bedrock_sort", cfg.bedrock.sort, default_parsed_bedrock_sort);
+    insert!("bedrock_access", cfg.bedrock.access, default_parsed_bedrock_access);
+    insert!("bedrock_region", cfg.bedrock.region, default_parsed_bedrock_region);
+    insert!("bedrock_profile", cfg.bedrock.profile, default_parsed_bedrock_profile);
 
-    map.insert("filter".to_string(), parse_keybinding(&cfg.filter).unwrap_or_else(default_parsed_filter));
-    map.insert("sort".to_string(), parse_keybinding(&cfg.sort).unwrap_or_else(default_parsed_sort));
-    map.insert("bedrock_filter".to_string(), parse_keybinding(&cfg.bedrock.filter).unwrap_or_else(default_parsed_bedrock_filter));
-    map.insert("bedrock_sort".to_string(), parse_keybinding(&cfg.bedrock.sort).unwrap_or_else(default_parsed_bedrock_sort));
-
-    map
+    // Two actions conflict when they share a key in a view where both are active. Actions the
+    // palette doesn't list (navigation, the palette key itself) are active everywhere.
+    let actions = key_actions();
+    let views_of = |id: &str| actions.iter().find(|action| action.id == id).map(|action| action.views.clone()).unwrap_or_default();
+    let mut bound: Vec<(&String, &(KeyCode, KeyModifiers))> = map.iter().collect();
+    bound.sort_by(|a, b| a.0.cmp(b.0));
+    for (i, (action, binding)) in bound.iter().enumerate() {
+        for (other, other_binding) in &bound[i + 1..] {
+            if binding != other_binding {
+                continue;
+            }
+            let (views, other_views) = (views_of(action), views_of(other));
+            let overlap = views.is_empty() || other_views.is_empty() || views.iter().any(|view| other_views.contains(view));
+            if overlap {
+                let key = format_keybinding(binding.0, binding.1);
+                issues.entry(action.to_string()).or_insert_with(|| format!("{} is also bound to {}", key, other));
+                issues.entry(other.to_string()).or_insert_with(|| format!("{} is also bound to {}", key, action));
+            }
+        }
+    }
+    (map, issues)
 }
 
+/// Parses a binding such as "q", "F", "Space", "F5", or "Ctrl+Alt+Left". Modifier prefixes may
+/// come in any order. Shift is folded into the character for printable keys ("Shift+f" is "F"),
+/// since terminals report the shifted character rather than a separate modifier.
 fn parse_keybinding(s: &str) -> Option<(KeyCode, KeyModifiers)> {
-    let s = s.trim();
     let mut mods = KeyModifiers::empty();
-    let mut key = s;
-    if let Some(stripped) = s.strip_prefix("Ctrl+") {
-        mods |= KeyModifiers::CONTROL;
-        key = stripped;
-    }
-    if let Some(stripped) = key.strip_prefix("Alt+") {
-        mods |= KeyModifiers::ALT;
-        key = stripped;
+    let mut key = s.trim();
+    loop {
+        let lowered = key.to_lowercase();
+        let (modifier, prefix_len) = if lowered.starts_with("ctrl+") {
+            (KeyModifiers::CONTROL, 5)
+        } else if lowered.starts_with("alt+") {
+            (KeyModifiers::ALT, 4)
+        } else if lowered.starts_with("shift+") {
+            (KeyModifiers::SHIFT, 6)
+        } else {
+            break;
+        };
+        mods |= modifier;
+        key = &key[prefix_len..];
     }
-    if let Some(stripped) = key.strip_prefix("Shift+") {
-        mods |= KeyModifiers::SHIFT;
-        key = stripped;
+    // A single character keeps its case: "F" and "f" are different keys
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if mods.contains(KeyModifiers::SHIFT) {
+            mods.remove(KeyModifiers::SHIFT);
+            return Some((KeyCode::Char(c.to_ascii_uppercase()), mods));
+        }
+        return Some((KeyCode::Char(c), mods));
     }
-    let code = match key.to_lowercase().as_str() {
+    let lowered = key.to_lowercase();
+    let code = match lowered.as_str() {
         "tab" => KeyCode::Tab,
         "backtab" => KeyCode::BackTab,
         "enter" => KeyCode::Enter,
         "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
         "up" => KeyCode::Up,
         "down" => KeyCode::Down,
         "left" => KeyCode::Left,
         "right" => KeyCode::Right,
-        c if c.len() == 1 => KeyCode::Char(c.chars().next().unwrap()),
+        f if f.starts_with('f') => match f[1..].parse::<u8>() {
+            Ok(n @ 1..=24) => KeyCode::F(n),
+            _ => return None,
+        },
         _ => return None,
     };
     Some((code, mods))
@@ -678,4 +2803,7 @@ fn default_parsed_down() -> (KeyCode, KeyModifiers) { (KeyCode::Down, KeyModifie
 fn default_parsed_filter() -> (KeyCode, KeyModifiers) { (KeyCode::Char('F'), KeyModifiers::NONE) }
 fn default_parsed_sort() -> (KeyCode, KeyModifiers) { (KeyCode::Char('S'), KeyModifiers::NONE) }
 fn default_parsed_bedrock_filter() -> (KeyCode, KeyModifiers) { (KeyCode::Char('f'), KeyModifiers::NONE) }
-fn default_parsed_bedrock_sort() -> (KeyCode, KeyModifiers) { (KeyCode::Char('s'), KeyModifiers::NONE) } 
\ No newline at end of file
+fn default_parsed_bedrock_sort() -> (KeyCode, KeyModifiers) { (KeyCode::Char('s'), KeyModifiers::NONE) }
+fn default_parsed_bedrock_access() -> (KeyCode, KeyModifiers) { (KeyCode::Char('a'), KeyModifiers::NONE) }
+fn default_parsed_bedrock_region() -> (KeyCode, KeyModifiers) { (KeyCode::Char('R'), KeyModifiers::NONE) }
+fn default_parsed_bedrock_profile() -> (KeyCode, KeyModifiers) { (KeyCode::Char('P'), KeyModifiers::NONE) } 
\ No newline at end of file
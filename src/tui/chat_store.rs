@@ -0,0 +1,98 @@
+// src/tui/chat_store.rs
+// Saves chat conversations as JSON under the XDG data dir so they survive restarts.
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use xdg::BaseDirectories;
+
+use crate::config::{APP_NAME, PROFILES_DIR_NAME};
+use super::app::ChatSession;
+
+const CHATS_DIR_NAME: &str = "chats";
+
+// A saved conversation as shown in the load picker.
+#[derive(Serialize, Debug, Clone)]
+pub struct SavedChat {
+    pub id: String,
+    pub model_name: String,
+    pub message_count: usize,
+    pub modified: Option<String>, // RFC 3339
+}
+
+pub struct ChatStore {
+    dir: PathBuf,
+}
+
+impl ChatStore {
+    /// Opens the chat directory, `$XDG_DATA_HOME/hydravisor/chats`, namespaced by profile.
+    pub fn new(profile: Option<&str>) -> Result<Self> {
+        let data_home = BaseDirectories::with_prefix(APP_NAME)?.get_data_home();
+        let dir = match profile {
+            Some(profile) => data_home.join(PROFILES_DIR_NAME).join(profile).join(CHATS_DIR_NAME),
+            None => data_home.join(CHATS_DIR_NAME),
+        };
+        Ok(ChatStore { dir })
+    }
+
+    /// Writes the session to `<id>.json`, replacing any earlier save of the same conversation.
+    pub fn save(&self, session: &ChatSession) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create chat directory {:?}", self.dir))?;
+        let path = self.dir.join(format!("{}.json", session.id));
+        let json = serde_json::to_string_pretty(session)?;
+        fs::write(&path, json).with_context(|| format!("Failed to write chat file {:?}", path))?;
+        Ok(path)
+    }
+
+    /// Lists saved conversations, newest first. Unreadable files are skipped.
+    pub fn list(&self) -> Result<Vec<SavedChat>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut chats = Vec::new();
+        for entry in fs::read_dir(&self.dir).with_context(|| format!("Failed to read chat directory {:?}", self.dir))? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(session) = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| serde_json::from_str::<ChatSession>(&content).map_err(anyhow::Error::from))
+            else {
+                tracing::warn!("Skipping unreadable chat file {:?}", path);
+                continue;
+            };
+            let modified = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339());
+            chats.push(SavedChat {
+                id: session.id,
+                model_name: session.model_name,
+                message_count: session.messages.len(),
+                modified,
+            });
+        }
+        chats.sort_by(|a, b| b.modified.cmp(&a.modified));
+        Ok(chats)
+    }
+
+    pub fn load(&self, id: &str) -> Result<ChatSession> {
+        if id.contains(['/', '\\']) || id.starts_with('.') {
+            return Err(anyhow!("Invalid chat id '{}'", id));
+        }
+        let path = self.dir.join(format!("{}.json", id));
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read chat file {:?}", path))?;
+        let mut session: ChatSession = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse chat file {:?}", path))?;
+        session.id = id.to_string();
+        Ok(session)
+    }
+}
+
+// TODO: Add tests for ChatStore:
+// - save/load round trip, including message thoughts.
+// - list ordering and skipping of malformed files.
+// - Rejection of path-like ids in `load`.
@@ -0,0 +1,238 @@
+// src/tui/chat_stream.rs
+// Sending a chat message to its model and streaming the reply back into the session.
+
+use anyhow::Result;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::{error, info};
+
+#[cfg(feature = "bedrock_integration")]
+use crate::bedrock_manager::ModelAccess;
+use crate::errors::{user_message, HydraError};
+use crate::metrics::ChatStreamGuard;
+
+use super::app::{App, ChatMessage, ChatProvider, ChatSession, ChatStreamEvent, StreamStats, ThinkSplitter, ToastLevel, TOAST_TTL};
+#[cfg(feature = "bedrock_integration")]
+use super::app::AppEvent;
+
+impl App {
+    /// Sends the chat input to the active chat's model and streams the reply into the session.
+    pub fn send_chat_message(&mut self) {
+        if self.chat_input.text().trim().is_empty() {
+            return;
+        }
+        let Some((provider, model_name, chat_id)) = self.active_chat().map(|c| (c.provider, c.model_name.clone(), c.id.clone())) else {
+            return;
+        };
+        if self.active_chat().map_or(false, |c| c.is_streaming) {
+            info!("Still streaming a response from '{}'; message not sent.", model_name);
+            self.notify(ToastLevel::Warning, format!("Still streaming from '{}'; message not sent", model_name), TOAST_TTL);
+            return;
+        }
+        let system_prompt = self.get_active_system_prompt(&model_name);
+        let params = self.config.get_model_params(&model_name);
+        let prompt = self.chat_input.submit().trim().to_string();
+
+        let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
+        let Some(session) = self.chats.get_mut(self.active_chat_idx) else { return; };
+        session.messages.push(ChatMessage {
+            sender: "user".to_string(),
+            content: prompt,
+            timestamp: timestamp.clone(),
+            thought: None,
+            thought_expanded: false,
+        });
+        // The system prompt is passed separately, so the display-only "System" entry is not sent as history
+        let history: Vec<ChatMessage> = session.messages
+            .iter()
+            .filter(|m| m.sender != "System")
+            .cloned()
+            .collect();
+        session.messages.push(ChatMessage {
+            sender: model_name.clone(),
+            content: String::new(),
+            timestamp,
+            thought: None,
+            thought_expanded: false,
+        });
+        session.is_streaming = true;
+        session.stream_stats = Some(StreamStats::new());
+        session.think_splitter = ThinkSplitter::default();
+        self.chat_list_state.select(Some(session.messages.len() - 1));
+
+        let sender = self.chat_stream_sender.clone();
+        let stream_chat_id = chat_id.clone();
+        let background_task = self.background_tasks.start();
+        let task = match provider {
+            ChatProvider::Ollama => {
+                let ollama_manager = Arc::clone(&self.ollama_manager);
+                tokio::spawn(async move {
+                    let _task = background_task;
+                    let stream_result = ollama_manager
+                        .lock()
+                        .await
+                        .generate_response_stream(model_name.clone(), history, Some(system_prompt), params)
+                        .await;
+                    forward_chat_stream(&stream_chat_id, &model_name, stream_result, &sender).await;
+                })
+            }
+            #[cfg(feature = "bedrock_integration")]
+            ChatProvider::Bedrock => {
+                let bedrock_manager = Arc::clone(&self.bedrock_manager);
+                let event_sender = self.event_sender.clone();
+                tokio::spawn(async move {
+                    let _task = background_task;
+                    let stream_result = bedrock_manager
+                        .lock()
+                        .await
+                        .converse_stream(model_name.clone(), history, Some(system_prompt), params)
+                        .await;
+                    // Whether the stream started says whether the model can be used
+                    let access = match &stream_result {
+                        Ok(_) => Some(ModelAccess::Granted),
+                        Err(e) => match HydraError::find(e) {
+                            Some(HydraError::ModelAccessDenied { requestable: true, .. }) => Some(ModelAccess::Requestable),
+                            Some(HydraError::ModelAccessDenied { requestable: false, .. }) => Some(ModelAccess::Denied),
+                            _ => None,
+                        },
+                    };
+                    if let Some(access) = access {
+                        let _ = event_sender.send(AppEvent::BedrockModelAccessKnown { model_id: model_name.clone(), access });
+                    }
+                    forward_chat_stream(&stream_chat_id, &model_name, stream_result, &sender).await;
+                })
+            }
+            #[cfg(not(feature = "bedrock_integration"))]
+            ChatProvider::Bedrock => {
+                let _ = (history, system_prompt, params);
+                let _ = sender.send((stream_chat_id, ChatStreamEvent::Error("Bedrock integration is not enabled in this build.".to_string())));
+                return;
+            }
+            #[cfg(feature = "openai_compat")]
+            ChatProvider::OpenAiCompat => {
+                let openai_manager = Arc::clone(&self.openai_manager);
+                tokio::spawn(async move {
+                    let _task = background_task;
+                    let stream_result = openai_manager
+                        .lock()
+                        .await
+                        .generate_response_stream(model_name.clone(), history, Some(system_prompt), params)
+                        .await;
+                    forward_chat_stream(&stream_chat_id, &model_name, stream_result, &sender).await;
+                })
+            }
+            #[cfg(not(feature = "openai_compat"))]
+            ChatProvider::OpenAiCompat => {
+                let _ = (history, system_prompt, params);
+                let _ = sender.send((stream_chat_id, ChatStreamEvent::Error("OpenAI-compatible chat is not enabled in this build.".to_string())));
+                return;
+            }
+        };
+        self.chat_stream_tasks.insert(chat_id, task);
+    }
+
+    /// Aborts the active chat's in-flight stream, keeping whatever was received so far.
+    pub fn cancel_chat_stream(&mut self) {
+        self.cancel_chat_stream_at(self.active_chat_idx);
+    }
+
+    pub(super) fn cancel_chat_stream_at(&mut self, idx: usize) {
+        let Some(chat_id) = self.chats.get(idx).filter(|c| c.is_streaming).map(|c| c.id.clone()) else {
+            return;
+        };
+        if let Some(task) = self.chat_stream_tasks.remove(&chat_id) {
+            task.abort();
+        }
+        self.handle_chat_stream_event(&chat_id, ChatStreamEvent::Cancelled);
+    }
+
+    /// Applies a streamed chat event to the chat it belongs to. Events for a chat that has
+    /// since been closed are dropped.
+    pub fn handle_chat_stream_event(&mut self, chat_id: &str, event: ChatStreamEvent) {
+        let Some(session) = self.chats.iter_mut().find(|c| c.id == chat_id) else { return; };
+        match event {
+            // Chunks still queued from an aborted stream are dropped
+            ChatStreamEvent::Chunk(_) if !session.is_streaming => {}
+            ChatStreamEvent::Chunk(chunk) => {
+                if let Some(last) = session.messages.last_mut() {
+                    session.think_splitter.push(&chunk, last);
+                    if let Some(stats) = session.stream_stats.as_mut() {
+                        stats.first_chunk.get_or_insert_with(Instant::now);
+                        let thought_words = last.thought.as_deref().map_or(0, |t| t.split_whitespace().count());
+                        stats.tokens = thought_words + last.content.split_whitespace().count();
+                    }
+                }
+            }
+            ChatStreamEvent::Error(e) => {
+                error!("Chat stream error for model '{}': {}", session.model_name, e);
+                let toast = format!("{}: {}", session.model_name, e);
+                finish_reply(session);
+                if let Some(last) = session.messages.last_mut() {
+                    if !last.content.is_empty() {
+                        last.content.push('\n');
+                    }
+                    last.content.push_str(&format!("[Error: {}]", e));
+                }
+                session.is_streaming = false;
+                self.chat_stream_tasks.remove(chat_id);
+                self.notify_error(toast);
+            }
+            ChatStreamEvent::Completed => {
+                finish_reply(session);
+                session.is_streaming = false;
+                self.chat_stream_tasks.remove(chat_id);
+            }
+            ChatStreamEvent::Cancelled => {
+                info!("Cancelled chat stream from '{}'.", session.model_name);
+                finish_reply(session);
+                if let Some(last) = session.messages.last_mut() {
+                    last.content.push_str(" [cancelled]");
+                }
+                session.is_streaming = false;
+            }
+        }
+    }
+}
+
+// Flushes the thought splitter into the reply and stops its timer
+fn finish_reply(session: &mut ChatSession) {
+    if let Some(last) = session.messages.last_mut() {
+        session.think_splitter.finish(last);
+    }
+    if let Some(stats) = session.stream_stats.as_mut() {
+        stats.finished.get_or_insert_with(Instant::now);
+    }
+}
+
+// Relays a provider's reply stream to the TUI, ending with Completed or an Error.
+async fn forward_chat_stream<S>(
+    chat_id: &str,
+    model_name: &str,
+    stream_result: Result<S>,
+    sender: &mpsc::UnboundedSender<(String, ChatStreamEvent)>,
+) where
+    S: futures::Stream<Item = std::result::Result<String, HydraError>>,
+{
+    let _active_stream = ChatStreamGuard::new();
+    match stream_result {
+        Ok(stream) => {
+            futures::pin_mut!(stream);
+            while let Some(item) = stream.next().await {
+                let event = match item {
+                    Ok(chunk) => ChatStreamEvent::Chunk(chunk),
+                    Err(e) => ChatStreamEvent::Error(e.to_string()),
+                };
+                if sender.send((chat_id.to_string(), event)).is_err() {
+                    return; // The TUI has shut down
+                }
+            }
+            let _ = sender.send((chat_id.to_string(), ChatStreamEvent::Completed));
+        }
+        Err(e) => {
+            error!("Failed to start chat stream for model '{}': {}", model_name, e);
+            let _ = sender.send((chat_id.to_string(), ChatStreamEvent::Error(user_message(&e))));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// src/tui/command_palette.rs
+// Registry of keybinding actions for the command palette, plus its fuzzy matcher.
+//
+// The palette runs an action by replaying the key bound to it in `keybinding_map`, so an
+// action always does exactly what its key does, and rebinding a key updates both.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use super::app::AppView;
+
+// An action from `[keybindings]` that the palette can run
+#[derive(Clone, Debug)]
+pub struct KeyAction {
+    pub id: &'static str, // Key in `keybinding_map`
+    pub label: &'static str,
+    pub views: Vec<AppView>, // Views where the action does something; empty means all
+}
+
+impl KeyAction {
+    fn new(id: &'static str, label: &'static str, views: &[AppView]) -> Self {
+        KeyAction { id, label, views: views.to_vec() }
+    }
+
+    pub fn is_available_in(&self, view: AppView) -> bool {
+        self.views.is_empty() || self.views.contains(&view)
+    }
+
+    /// Whether `--read-only` refuses the action in `view`. Enter only reads in the Logs view,
+    /// where it opens the entry; elsewhere it starts a VM, sends a message or opens a chat.
+    pub fn is_blocked_in_read_only(&self, view: AppView) -> bool {
+        match self.id {
+            "enter" => view != AppView::Logs,
+            id => READ_ONLY_BLOCKED_ACTIONS.contains(&id),
+        }
+    }
+}
+
+// Actions that create, change or destroy something (VMs, models, saved files or config.toml),
+// refused in read-only mode
+const READ_ONLY_BLOCKED_ACTIONS: &[&str] = &[
+    "new_vm", "destroy_vm", "shutdown_vm", "suspend_vm", "clone_vm", "ssh_vm", "console_vm",
+    "edit", "toggle_favorite", "pull_model", "delete_model", "edit_params", "save_chat", "clear_chat", "close_chat",
+    "toggle_quick_actions",
+];
+
+/// Actions listed in the palette. Navigation keys (up/down) and the palette key itself are left out.
+pub fn key_actions() -> Vec<KeyAction> {
+    #[allow(unused_mut)]
+    let mut model_lists = vec![AppView::OllamaModelList];
+    #[cfg(feature = "bedrock_integration")]
+    model_lists.push(AppView::BedrockModelList);
+    #[cfg(feature = "openai_compat")]
+    model_lists.push(AppView::OpenAiModelList);
+    let mut openable = vec![AppView::VmList, AppView::Chat, AppView::Logs];
+    openable.extend(model_lists.iter().copied());
+
+    #[allow(unused_mut)]
+    let mut actions = vec![
+        KeyAction::new("next_tab", "Switch to next view", &[]),
+        KeyAction::new("prev_tab", "Switch to previous view", &[]),
+        KeyAction::new("refresh", "Refresh VMs and models", &[]),
+        KeyAction::new("new_vm", "Create new VM", &[]),
+        KeyAction::new("enter", "Open selection (start VM / chat with model / log details)", &openable),
+        KeyAction::new("destroy_vm", "Destroy selected or marked VMs", &[AppView::VmList]),
+        KeyAction::new("mark_vm", "Mark VM for bulk destroy", &[AppView::VmList]),
+        KeyAction::new("shutdown_vm", "Shut down selected VM", &[AppView::VmList]),
+        KeyAction::new("suspend_vm", "Suspend / resume selected VM", &[AppView::VmList]),
+        KeyAction::new("ssh_vm", "SSH into selected VM", &[AppView::VmList]),
+        KeyAction::new("console_vm", "Open console of selected VM", &[AppView::VmList]),
+        KeyAction::new("clone_vm", "Clone selected VM", &[AppView::VmList]),
+        KeyAction::new("edit", "Edit message / system prompt / resize VM", &[AppView::Chat, AppView::OllamaModelList, AppView::VmList]),
+        KeyAction::new("toggle_favorite", "Toggle favorite model", &model_lists),
+        KeyAction::new("favorites_only", "Show favorite models only", &model_lists),
+        KeyAction::new("pull_model", "Pull Ollama model", &[AppView::OllamaModelList]),
+        KeyAction::new("delete_model", "Delete Ollama model", &[AppView::OllamaModelList]),
+        KeyAction::new("edit_params", "Edit model generation params", &[AppView::OllamaModelList]),
+        KeyAction::new("save_chat", "Save chat", &[AppView::Chat]),
+        KeyAction::new("load_chat", "Load saved chat", &[AppView::Chat]),
+        KeyAction::new("cancel_stream", "Cancel response", &[AppView::Chat]),
+        KeyAction::new("toggle_thought", "Expand / collapse the selected message's thinking", &[AppView::Chat]),
+        KeyAction::new("new_chat", "New chat with the same model", &[AppView::Chat]),
+        KeyAction::new("clear_chat", "Clear conversation (saves it first)", &[AppView::Chat]),
+        KeyAction::new("close_chat", "Close chat (saves it first)", &[AppView::Chat]),
+        KeyAction::new("next_chat", "Switch to next open chat", &[AppView::Chat]),
+        KeyAction::new("prev_chat", "Switch to previous open chat", &[AppView::Chat]),
+        KeyAction::new("copy", "Copy selection to clipboard", &[AppView::Chat, AppView::Logs]),
+        KeyAction::new("log_level", "Cycle log level filter", &[AppView::Logs]),
+        KeyAction::new("wrap_logs", "Toggle wrapping of long log lines", &[AppView::Logs]),
+        KeyAction::new("search", "Search logs / VM names", &[AppView::Logs, AppView::VmList]),
+        KeyAction::new("filter", "Cycle VM state filter", &[AppView::VmList]),
+        KeyAction::new("toggle_mouse", "Toggle mouse capture", &[]),
+        KeyAction::new("toggle_quick_actions", "Toggle quick actions (no VM confirmations)", &[]),
+        KeyAction::new("menu", "Open menu", &[]),
+        KeyAction::new("help", "Show keybindings", &[]),
+        KeyAction::new("quit", "Quit", &[]),
+    ];
+    #[cfg(feature = "bedrock_integration")]
+    actions.extend([
+        KeyAction::new("bedrock_filter", "Cycle Bedrock filter", &[AppView::BedrockModelList]),
+        KeyAction::new("bedrock_sort", "Cycle Bedrock sort", &[AppView::BedrockModelList]),
+        KeyAction::new("bedrock_access", "Check access to Bedrock model", &[AppView::BedrockModelList]),
+        KeyAction::new("bedrock_region", "Switch Bedrock region", &[AppView::BedrockModelList]),
+        KeyAction::new("bedrock_profile", "Switch AWS profile", &[AppView::BedrockModelList]),
+    ]);
+    actions
+}
+
+// A group of actions in the keybindings modal: the global ones, or those that work in one view
+pub struct HelpSection {
+    pub title: &'static str,
+    pub view: Option<AppView>, // None for the global section
+    pub actions: Vec<(&'static str, &'static str)>, // (id in `keybinding_map`, label)
+}
+
+/// The keybindings modal's contents, grouped by where each action works. An action that works
+/// in several views is listed under each of them.
+pub fn help_sections() -> Vec<HelpSection> {
+    let actions = key_actions();
+    let mut global: Vec<(&'static str, &'static str)> = actions.iter()
+        .filter(|action| action.views.is_empty())
+        .map(|action| (action.id, action.label))
+        .collect();
+    // Left out of the palette, but still worth listing
+    global.extend([("up", "Move up"), ("down", "Move down"), ("command_palette", "Open command palette")]);
+
+    #[allow(unused_mut)]
+    let mut views = vec![(AppView::VmList, "VM List"), (AppView::OllamaModelList, "Ollama Models")];
+    #[cfg(feature = "bedrock_integration")]
+    views.push((AppView::BedrockModelList, "Bedrock Models"));
+    #[cfg(feature = "openai_compat")]
+    views.push((AppView::OpenAiModelList, "OpenAI-compatible Models"));
+    views.extend([(AppView::Chat, "Chat"), (AppView::Logs, "Logs")]);
+
+    let mut sections = vec![HelpSection { title: "Global", view: None, actions: global }];
+    sections.extend(views.into_iter().map(|(view, title)| HelpSection {
+        title,
+        view: Some(view),
+        actions: actions.iter()
+            .filter(|action| action.views.contains(&view))
+            .map(|action| (action.id, action.label))
+            .collect(),
+    }));
+    sections
+}
+
+/// The modal line `view`'s section starts on: every section is a title, its actions, and a blank line.
+pub fn help_section_line(view: AppView) -> usize {
+    help_sections()
+        .iter()
+        .take_while(|section| section.view != Some(view))
+        .map(|section| section.actions.len() + 2)
+        .sum()
+}
+
+/// Every action row of the keybindings modal, in order: (modal line, action id, label).
+pub fn help_action_rows() -> Vec<(usize, &'static str, &'static str)> {
+    let mut rows = Vec::new();
+    let mut line = 0;
+    for section in help_sections() {
+        for (i, (id, label)) in section.actions.iter().enumerate() {
+            rows.push((line + 1 + i, *id, *label));
+        }
+        line += section.actions.len() + 2;
+    }
+    rows
+}
+
+/// Scores `text` against a fuzzy `query`: every query character must appear in order
+/// (case-insensitively). Higher is better; consecutive and word-start matches score more.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next_idx = 0;
+    let mut previous_match: Option<usize> = None;
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = text[next_idx..].iter().position(|&c| c == query_char)?;
+        let idx = next_idx + offset;
+        score += 1;
+        if previous_match.map_or(false, |prev| prev + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || !text[idx - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(idx);
+        next_idx = idx + 1;
+    }
+    // Prefer shorter labels when the matches are otherwise equal
+    Some(score * 100 - text.len() as i32)
+}
+
+/// Formats a parsed binding the way it is written in config.toml, e.g. "Ctrl+s".
+pub fn format_keybinding(code: KeyCode, mods: KeyModifiers) -> String {
+    let mut text = String::new();
+    if mods.contains(KeyModifiers::CONTROL) {
+        text.push_str("Ctrl+");
+    }
+    if mods.contains(KeyModifiers::ALT) {
+        text.push_str("Alt+");
+    }
+    if mods.contains(KeyModifiers::SHIFT) {
+        text.push_str("Shift+");
+    }
+    match code {
+        KeyCode::Char(' ') => text.push_str("Space"),
+        KeyCode::Char(c) => text.push(c),
+        KeyCode::F(n) => text.push_str(&format!("F{}", n)),
+        KeyCode::PageUp => text.push_str("PageUp"),
+        KeyCode::PageDown => text.push_str("PageDown"),
+        KeyCode::Tab => text.push_str("Tab"),
+        KeyCode::BackTab => text.push_str("BackTab"),
+        KeyCode::Enter => text.push_str("Enter"),
+        KeyCode::Esc => text.push_str("Esc"),
+        KeyCode::Up => text.push_str("Up"),
+        KeyCode::Down => text.push_str("Down"),
+        KeyCode::Left => text.push_str("Left"),
+        KeyCode::Right => text.push_str("Right"),
+        other => text.push_str(&format!("{:?}", other)),
+    }
+    text
+}
+
+// TODO: Add tests for the command palette:
+// - fuzzy_score ordering (prefix and consecutive matches first) and rejection of out-of-order queries.
+// - format_keybinding round-trips what parse_keybinding accepts.
+// - Every key_actions() id has a binding in parse_keybindings' map.
+// - help_section_line points at each view's section title.
+// - help_action_rows' lines match the rows the keybindings modal draws.
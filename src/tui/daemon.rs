@@ -0,0 +1,49 @@
+// src/tui/daemon.rs
+// The daemon an attached TUI (`hydravisor attach`) takes its VM state from. It is reached over the
+// daemon's MCP socket, so the TUI never opens a libvirt connection of its own for the VM list.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::libvirt_manager::VmStatus;
+use crate::mcp::McpClient;
+
+// The `src` an attached TUI sends; `vm/list` and heartbeats need no `[permissions]` entry
+const ATTACH_AGENT_ID: &str = "hydravisor-tui";
+
+pub struct DaemonLink {
+    socket_path: PathBuf,
+    timeout: Duration,
+    client: Mutex<Option<McpClient>>, // Dropped when a call fails, and reopened on the next one
+}
+
+impl DaemonLink {
+    /// Connects to the daemon serving MCP at `socket_path`, failing if none answers a heartbeat.
+    pub async fn connect(socket_path: PathBuf, timeout: Duration) -> Result<Self> {
+        let mut client = McpClient::connect(&socket_path, ATTACH_AGENT_ID, timeout)
+            .await
+            .with_context(|| format!("No Hydravisor daemon is listening at {:?}; start one with `hydravisor serve`", socket_path))?;
+        client.heartbeat().await?;
+        Ok(DaemonLink { socket_path, timeout, client: Mutex::new(Some(client)) })
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// The daemon's VMs. After a failure the connection is reopened on the next call, so a
+    /// restarted daemon is picked up again.
+    pub async fn list_vms(&self) -> Result<Vec<VmStatus>> {
+        let mut client = self.client.lock().await;
+        if client.is_none() {
+            *client = Some(McpClient::connect(&self.socket_path, ATTACH_AGENT_ID, self.timeout).await?);
+        }
+        let result = client.as_mut().expect("the client was just connected").list_vms().await;
+        if result.is_err() {
+            *client = None;
+        }
+        result
+    }
+}
@@ -1,32 +1,52 @@
 // src/tui/events.rs
 
 use anyhow::Result;
-use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, MouseEvent, MouseEventKind, EventStream};
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind, EventStream, EnableMouseCapture, DisableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use futures::StreamExt;
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
-use std::io::Stdout;
+use std::io::{self, Read, Stdout, Write};
+use std::process::{Command, ExitStatus, Stdio};
 use std::time::{Duration, Instant};
-use tracing::{error};
+use tracing::{error, info};
 use std::sync::Arc;
 
-use super::app::{App, AppEvent, AppView, ChatMessage, ChatSession, InputMode};
+use super::app::{new_vm_field, App, AppEvent, AppView, ChatProvider, InputMode, ToastLevel, VmAction, MAIN_MENU_ITEMS, PREFERENCES_MENU_ITEMS, TOAST_TTL};
 use super::ui::ui;
+use crate::audit::AuditEventType;
+use crate::errors::{user_message, HydraError};
+use crate::libvirt_manager::{ExistingDisk, VmState, VmStatus};
+use crate::session_manager::RecordingHandle;
+#[cfg(feature = "bedrock_integration")]
+use super::widgets::bedrock_model_list::BEDROCK_SORTS;
 
 pub async fn run_app_loop(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     mut app: App,
 ) -> Result<()> {
     let mut last_tick = Instant::now();
+    // Data fetches run on their own, slower cadence than the render tick
+    let mut last_data_refresh = Instant::now();
     let mut event_receiver = app.event_receiver.take().unwrap();
+    let mut chat_stream_receiver = app.chat_stream_receiver.take().unwrap();
     let mut crossterm_events = EventStream::new();
+    // Tracks the capture state last applied to the terminal so toggles take effect immediately
+    let mut mouse_capture_applied = app.mouse_capture_enabled;
+    // SIGTERM, or SIGINT sent from outside (in raw mode Ctrl+C arrives as a key instead)
+    let shutdown_signal = crate::shutdown::wait_for_shutdown_signal();
+    tokio::pin!(shutdown_signal);
+    let mut listening_for_signals = true;
 
     // Initial data fetch
-    app.fetch_vms().await;
+    app.fetch_vms();
     #[cfg(feature = "ollama_integration")]
     app.fetch_ollama_models().await;
     #[cfg(feature = "bedrock_integration")]
     app.fetch_bedrock_models().await;
+    #[cfg(feature = "openai_compat")]
+    app.fetch_openai_models().await;
 
     loop {
         terminal.draw(|f| ui(f, &mut app))?;
@@ -43,23 +63,38 @@ pub async fn run_app_loop(
             app.log_entries.drain(0..overflow);
         }
         if app.active_view == AppView::Logs {
+            let visible_count = app.visible_log_entries().len();
             let is_scrolled_to_bottom = match app.log_list_state.selected() {
-                Some(index) => index >= app.log_entries.len().saturating_sub(1),
+                Some(index) => index >= visible_count.saturating_sub(1),
                 None => true,
             };
-            if is_scrolled_to_bottom && !app.log_entries.is_empty() {
-                    app.log_list_state.select(Some(app.log_entries.len() - 1));
+            if is_scrolled_to_bottom {
+                app.log_list_state.select(visible_count.checked_sub(1));
             }
         }
 
         let tick_duration = Duration::from_millis(app.config.interface.refresh_interval_ms);
+        let data_refresh_interval = Duration::from_millis(app.config.interface.data_refresh_interval_ms);
 
         tokio::select! {
+            result = &mut shutdown_signal, if listening_for_signals => {
+                listening_for_signals = false;
+                match result {
+                    Ok(signal) => {
+                        info!("Received {}, shutting down.", signal);
+                        app.should_quit = true;
+                    }
+                    Err(e) => error!("Failed to listen for shutdown signals: {}", e),
+                }
+            }
             // Handle app events from the channel
             Some(event) = event_receiver.recv() => {
                 match event {
                     AppEvent::FetchVms => {
-                        app.fetch_vms().await;
+                        app.fetch_vms();
+                    }
+                    AppEvent::VmsFetched { connected, result } => {
+                        app.apply_fetched_vms(connected, result);
                     }
                     #[cfg(feature = "ollama_integration")]
                     AppEvent::FetchOllamaModels => {
@@ -69,32 +104,234 @@ pub async fn run_app_loop(
                     AppEvent::FetchBedrockModels => {
                         app.fetch_bedrock_models().await;
                     }
+                    #[cfg(feature = "bedrock_integration")]
+                    AppEvent::BedrockModelAccessChecked { model_id, result, access_url } => {
+                        app.apply_bedrock_model_access(model_id, result, &access_url);
+                    }
+                    #[cfg(feature = "bedrock_integration")]
+                    AppEvent::BedrockModelAccessKnown { model_id, access } => {
+                        app.bedrock_model_access.insert(model_id, access);
+                    }
+                    #[cfg(feature = "bedrock_integration")]
+                    AppEvent::BedrockClientSwitched { generation, region, profile, error } => {
+                        app.apply_bedrock_client_switch(generation, region, profile, error);
+                    }
+                    #[cfg(feature = "openai_compat")]
+                    AppEvent::FetchOpenAiModels => {
+                        app.fetch_openai_models().await;
+                    }
+                    AppEvent::PreviewVm { vm_config, create } => {
+                        let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let result = libvirt_manager.lock().await.preview_vm(&vm_config).map_err(|e| e.to_string());
+                            let _ = event_sender.send(AppEvent::VmPreviewed { vm_config, create, result });
+                        });
+                    }
+                    AppEvent::VmPreviewed { vm_config, create, result } => {
+                        app.apply_vm_preview(vm_config, create, result);
+                    }
+                    AppEvent::VmDetailsFetched { vm_name, result } => {
+                        app.apply_vm_details(&vm_name, result);
+                    }
+                    AppEvent::CreateVm(vm_config) => {
+                        let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.create_vm(&vm_config) {
+                                Ok(status) => {
+                                    info!("Created VM '{}'.", status.name);
+                                    (ToastLevel::Success, format!("Created VM '{}'", status.name))
+                                }
+                                Err(e) => {
+                                    error!("Failed to create VM '{}': {}", &vm_config.instance_id, e);
+                                    failure_toast(format!("Failed to create VM '{}'", &vm_config.instance_id), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchVms);
+                        });
+                    }
+                    AppEvent::CloneVm { source_name, new_name } => {
+                        let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.clone_vm(&source_name, &new_name) {
+                                Ok(status) => {
+                                    info!("Cloned VM '{}' as '{}'.", &source_name, status.name);
+                                    (ToastLevel::Success, format!("Cloned VM '{}' as '{}'", &source_name, status.name))
+                                }
+                                Err(e) => {
+                                    error!("Failed to clone VM '{}': {}", &source_name, e);
+                                    failure_toast(format!("Failed to clone VM '{}'", &source_name), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchVms);
+                        });
+                    }
+                    AppEvent::ResizeVm { vm_name, change } => {
+                        let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.resize_vm(&vm_name, &change) {
+                                Ok(()) => (ToastLevel::Success, format!("Resized VM '{}': {}", &vm_name, change.describe())),
+                                Err(e) => {
+                                    error!("Failed to resize VM '{}': {}", &vm_name, e);
+                                    failure_toast(format!("Failed to resize VM '{}'", &vm_name), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchVms);
+                        });
+                    }
                     AppEvent::DestroyVm(vm_name) => {
                         let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
                         tokio::spawn(async move {
-                            if let Err(e) = libvirt_manager.lock().await.destroy_vm(&vm_name) {
-                                error!("Failed to destroy VM '{}': {}", &vm_name, e);
-                            }
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.destroy_vm(&vm_name, true) {
+                                Ok(()) => (ToastLevel::Success, format!("Destroyed VM '{}'", &vm_name)),
+                                Err(e) => {
+                                    error!("Failed to destroy VM '{}': {}", &vm_name, e);
+                                    failure_toast(format!("Failed to destroy VM '{}'", &vm_name), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
                             // Need to trigger a refresh. For now, rely on tick or user action.
                         });
                         app.event_sender.send(AppEvent::FetchVms).unwrap(); // Trigger refresh
                     }
-                    AppEvent::ResumeVm(vm_name) => {
+                    AppEvent::ShutdownVm(vm_name) => {
                         let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
                         tokio::spawn(async move {
-                            if let Err(e) = libvirt_manager.lock().await.resume_vm(&vm_name) {
-                                error!("Failed to start VM '{}': {}", &vm_name, e);
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.shutdown_vm(&vm_name) {
+                                Ok(()) => {
+                                    info!("Sent shutdown request to VM '{}'.", &vm_name);
+                                    (ToastLevel::Info, format!("Shutting down VM '{}'", &vm_name))
+                                }
+                                Err(e) => {
+                                    error!("Failed to shut down VM '{}': {}", &vm_name, e);
+                                    failure_toast(format!("Failed to shut down VM '{}'", &vm_name), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchVms);
+                        });
+                    }
+                    AppEvent::SuspendVm(vm_name) => {
+                        let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.suspend_vm(&vm_name) {
+                                Ok(()) => {
+                                    info!("Suspended VM '{}'.", &vm_name);
+                                    (ToastLevel::Success, format!("Suspended VM '{}'", &vm_name))
+                                }
+                                Err(e) => {
+                                    error!("Failed to suspend VM '{}': {}", &vm_name, e);
+                                    failure_toast(format!("Failed to suspend VM '{}'", &vm_name), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchVms);
+                        });
+                    }
+                    AppEvent::ModelPullProgress(progress) => {
+                        if let Some((_, current)) = app.model_pull.as_mut() {
+                            *current = progress;
+                        }
+                    }
+                    AppEvent::ModelPullFinished { model_name, error } => {
+                        app.model_pull = None;
+                        match error {
+                            None => {
+                                info!("Pulled Ollama model '{}'.", model_name);
+                                app.notify(ToastLevel::Success, format!("Pulled model '{}'", model_name), TOAST_TTL);
+                            }
+                            Some(e) => {
+                                error!("Failed to pull Ollama model '{}': {}", model_name, e);
+                                app.notify_error(format!("Failed to pull '{}': {}", model_name, e));
                             }
+                        }
+                        let _ = app.event_sender.send(AppEvent::FetchOllamaModels);
+                    }
+                    AppEvent::DeleteModel(model_name) => {
+                        let ollama_manager = Arc::clone(&app.ollama_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let toast = match ollama_manager.lock().await.delete_model(&model_name).await {
+                                Ok(()) => {
+                                    info!("Deleted Ollama model '{}'.", model_name);
+                                    (ToastLevel::Success, format!("Deleted model '{}'", model_name))
+                                }
+                                Err(e) => {
+                                    error!("{}", e);
+                                    (ToastLevel::Error, e.to_string())
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchOllamaModels);
+                        });
+                    }
+                    AppEvent::ResumeVm(vm_name) => {
+                        let libvirt_manager = Arc::clone(&app.libvirt_manager);
+                        let event_sender = app.event_sender.clone();
+                        let task = app.background_tasks.start();
+                        tokio::spawn(async move {
+                            let _task = task;
+                            let toast = match libvirt_manager.lock().await.resume_vm(&vm_name) {
+                                Ok(resumed) => {
+                                    let verb = if resumed { "Resumed" } else { "Started" };
+                                    info!("{} VM '{}'.", verb, &vm_name);
+                                    (ToastLevel::Success, format!("{} VM '{}'", verb, &vm_name))
+                                }
+                                Err(e) => {
+                                    error!("Failed to start VM '{}': {}", &vm_name, e);
+                                    failure_toast(format!("Failed to start VM '{}'", &vm_name), &e)
+                                }
+                            };
+                            let _ = event_sender.send(AppEvent::Notify(toast.0, toast.1));
+                            let _ = event_sender.send(AppEvent::FetchVms);
                         });
                     }
+                    AppEvent::Notify(ToastLevel::Error, text) => app.notify_error(text),
+                    AppEvent::Notify(level, text) => app.notify(level, text, TOAST_TTL),
                 }
             }
 
+            // Handle streamed chat responses
+            Some((chat_id, chat_event)) = chat_stream_receiver.recv() => {
+                app.handle_chat_stream_event(&chat_id, chat_event);
+            }
+
             // Handle terminal events
             Some(Ok(event)) = crossterm_events.next() => {
                 match event {
                     CrosstermEvent::Key(key) => on_key(&mut app, key),
                     CrosstermEvent::Mouse(mouse) => on_mouse_event(&mut app, mouse),
+                    CrosstermEvent::Resize(_, _) => {
+                        app.handle_resize();
+                        // The input bar and wrap widths are measured while drawing, so draw now
+                        // to pick up the new size; the loop's next draw then lays out with it
+                        terminal.draw(|f| ui(f, &mut app))?;
+                    }
                     _ => {}
                 }
             }
@@ -103,27 +340,269 @@ pub async fn run_app_loop(
             _ = tokio::time::sleep(tick_duration) => {
                  if last_tick.elapsed() >= tick_duration {
                     app.tick();
-                    app.event_sender.send(AppEvent::FetchVms).unwrap(); // Send event to refresh
-                    #[cfg(feature = "ollama_integration")]
-                    app.event_sender.send(AppEvent::FetchOllamaModels).unwrap(); // Send event to refresh
-                    #[cfg(feature = "bedrock_integration")]
-                    app.event_sender.send(AppEvent::FetchBedrockModels).unwrap();
+                    if last_data_refresh.elapsed() >= data_refresh_interval {
+                        app.request_data_refresh();
+                        last_data_refresh = Instant::now();
+                    }
                     last_tick = Instant::now();
                 }
             }
         }
         
+        if app.mouse_capture_enabled != mouse_capture_applied {
+            if app.mouse_capture_enabled {
+                execute!(terminal.backend_mut(), EnableMouseCapture)?;
+            } else {
+                execute!(terminal.backend_mut(), DisableMouseCapture)?;
+            }
+            mouse_capture_applied = app.mouse_capture_enabled;
+            info!("Mouse capture {}.", if mouse_capture_applied { "enabled" } else { "disabled" });
+        }
+
+        if let Some(target) = app.pending_ssh_target.take() {
+            // The event stream reads stdin on its own thread; stop it so ssh gets the keystrokes
+            drop(crossterm_events);
+            run_ssh_session(terminal, &mut app, &target).await?;
+            crossterm_events = EventStream::new();
+            mouse_capture_applied = app.mouse_capture_enabled;
+        }
+
+        if let Some(target) = app.pending_console_target.take() {
+            let console_info = app.libvirt_manager.lock().await.get_console_info(&target.name);
+            match console_info {
+                Ok(console_info) => {
+                    if let Some(vnc_url) = console_info.vnc_url() {
+                        info!("VNC display of VM '{}': {}", target.name, vnc_url);
+                        app.status_message = Some(format!("VNC display: {}", vnc_url));
+                        app.status_message_is_info = true;
+                    }
+                    // A serial console works without a VNC viewer, so it is opened when there is one
+                    if console_info.serial_pty.is_some() {
+                        drop(crossterm_events);
+                        run_console_session(terminal, &mut app, &target).await?;
+                        crossterm_events = EventStream::new();
+                        mouse_capture_applied = app.mouse_capture_enabled;
+                    }
+                }
+                Err(e) => {
+                    error!("Cannot open console of VM '{}': {}", target.name, e);
+                    app.notify_error(e.to_string());
+                }
+            }
+        }
+
         if app.should_quit {
+            app.save_all_chats();
+            app.save_ui_state();
             return Ok(());
         }
     }
 }
 
+/// The toast for a failed VM operation. A policy refusal or a VM that has gone since the list
+/// was fetched is only a warning; other failures are errors, with a hint where there is one.
+fn failure_toast(what: String, e: &anyhow::Error) -> (ToastLevel, String) {
+    match HydraError::find(e) {
+        Some(HydraError::PolicyDenied(_) | HydraError::VmNotFound(_)) => (ToastLevel::Warning, format!("{}: {}", what, e)),
+        _ => (ToastLevel::Error, format!("{}: {}", what, user_message(e))),
+    }
+}
+
+/// Suspends the TUI, runs an interactive `ssh` session on `target`, and restores the TUI afterwards.
+/// With `tmux.record_all_sessions` set, or a role the policy records, the session's output is also
+/// recorded to the VM's log directory.
+async fn run_ssh_session(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+    target: &VmStatus,
+) -> Result<()> {
+    let session_id = match app.session_manager.attach_session("user", &target.name).await {
+        Ok((session, _)) => session.session_id,
+        Err(e) => {
+            error!("Cannot open SSH session: {}", e);
+            app.status_message = Some(e.to_string());
+            return Ok(());
+        }
+    };
+    let recorder = if app.session_manager.should_record_session(&session_id).await {
+        match app.session_manager.start_recording(&session_id).await {
+            Ok(recorder) => Some(recorder),
+            Err(e) => {
+                error!("Failed to start recording session {}: {}", session_id, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let command = if recorder.is_some() {
+        app.ssh_manager.recorded_ssh_command(target)
+    } else {
+        app.ssh_manager.ssh_command(target)
+    };
+    let command = match command {
+        Ok(command) => command,
+        Err(e) => {
+            error!("Cannot open SSH session: {}", e);
+            app.status_message = Some(e.to_string());
+            app.session_manager.end_session(&session_id).await;
+            return Ok(());
+        }
+    };
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    app.record_audit_event(AuditEventType::SshSessionEstablished {
+        session_id: session_id.clone(),
+        agent_id: "user".to_string(),
+        source_ip: None,
+    });
+    info!("Opening SSH session {} to VM '{}'.", session_id, target.name);
+
+    let result = tokio::task::spawn_blocking(move || run_ssh_process(command, recorder)).await;
+
+    app.record_audit_event(AuditEventType::SshSessionTerminated {
+        session_id: session_id.clone(),
+        agent_id: "user".to_string(),
+    });
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    if app.mouse_capture_enabled {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+
+    match result {
+        Ok(Ok((exit_status, recorder))) => {
+            if let Some(recorder) = recorder {
+                match recorder.finish() {
+                    Ok(path) => info!("Recorded SSH session {} to {:?}.", session_id, path),
+                    Err(e) => error!("Failed to finish recording session {}: {}", session_id, e),
+                }
+            }
+            if exit_status.success() {
+                info!("SSH session {} to VM '{}' closed.", session_id, target.name);
+            } else {
+                error!("SSH session to VM '{}' exited with {}.", target.name, exit_status);
+                app.status_message = Some(format!("ssh exited with {}", exit_status));
+            }
+        }
+        Ok(Err(e)) => {
+            error!("Failed to launch ssh for VM '{}': {}", target.name, e);
+            app.status_message = Some(format!("Failed to launch ssh: {}", e));
+        }
+        Err(e) => error!("SSH session task failed: {}", e),
+    }
+    app.session_manager.end_session(&session_id).await;
+    Ok(())
+}
+
+/// Suspends the TUI and attaches to the VM's serial console with `virsh console` until the
+/// user detaches (Ctrl+]), then restores the TUI.
+async fn run_console_session(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+    target: &VmStatus,
+) -> Result<()> {
+    let mut command = Command::new("virsh");
+    command.args(["-c", app.config.libvirt.uri.as_str(), "console", &target.name]);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    println!("Connecting to the serial console of '{}'. Press Ctrl+] to return to Hydravisor.", target.name);
+    info!("Opening serial console of VM '{}'.", target.name);
+
+    let result = tokio::task::spawn_blocking(move || command.status()).await;
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    if app.mouse_capture_enabled {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+
+    match result {
+        Ok(Ok(exit_status)) if exit_status.success() => {
+            info!("Serial console of VM '{}' closed.", target.name);
+        }
+        Ok(Ok(exit_status)) => {
+            error!("virsh console for VM '{}' exited with {}.", target.name, exit_status);
+            app.status_message = Some(format!("virsh console exited with {}", exit_status));
+            app.status_message_is_info = false;
+        }
+        Ok(Err(e)) => {
+            error!("Failed to launch virsh for VM '{}': {}", target.name, e);
+            app.status_message = Some(format!("Failed to launch virsh: {}", e));
+            app.status_message_is_info = false;
+        }
+        Err(e) => error!("Console session task failed: {}", e),
+    }
+    Ok(())
+}
+
+/// Runs `ssh` to completion. When recording, its output is relayed to the terminal and the recorder.
+fn run_ssh_process(
+    mut command: Command,
+    recorder: Option<RecordingHandle>,
+) -> io::Result<(ExitStatus, Option<RecordingHandle>)> {
+    let Some(mut recorder) = recorder else {
+        return command.status().map(|status| (status, None));
+    };
+
+    let mut child = command.stdout(Stdio::piped()).spawn()?;
+    let mut child_stdout = child.stdout.take().expect("ssh stdout is piped");
+    let mut stdout = io::stdout();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = match child_stdout.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+        stdout.write_all(&buf[..n])?;
+        stdout.flush()?;
+        if let Err(e) = recorder.write_output(&buf[..n]) {
+            error!("Failed to write session recording: {}", e);
+        }
+    }
+    let status = child.wait()?;
+    Ok((status, Some(recorder)))
+}
+
 pub fn on_tick(_app: &mut App) {
     // This is now handled in app.tick()
 }
 
 pub fn on_mouse_event(app: &mut App, mouse_event: MouseEvent) {
+    if app.show_keybindings_modal {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => app.move_keybindings_selection(-1),
+            MouseEventKind::ScrollDown => app.move_keybindings_selection(1),
+            _ => {}
+        }
+        return;
+    }
+    if app.log_detail.is_some() {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => app.log_detail_scroll = app.log_detail_scroll.saturating_sub(1),
+            MouseEventKind::ScrollDown => app.log_detail_scroll = app.log_detail_scroll.saturating_add(1),
+            _ => {}
+        }
+        return;
+    }
+    if app.show_about_modal {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => app.about_scroll = app.about_scroll.saturating_sub(1),
+            MouseEventKind::ScrollDown => app.about_scroll = app.about_scroll.saturating_add(1),
+            _ => {}
+        }
+        return;
+    }
     match mouse_event.kind {
         MouseEventKind::ScrollUp => {
             match app.active_view {
@@ -131,6 +610,8 @@ pub fn on_mouse_event(app: &mut App, mouse_event: MouseEvent) {
                 AppView::OllamaModelList => app.select_previous_item_in_ollama_list(),
                 #[cfg(feature = "bedrock_integration")]
                 AppView::BedrockModelList => app.select_previous_item_in_bedrock_list(),
+                #[cfg(feature = "openai_compat")]
+                AppView::OpenAiModelList => app.select_previous_item_in_openai_list(),
                 AppView::Chat => app.scroll_chat_up(),
                 AppView::Logs => app.scroll_logs_up(),
             }
@@ -141,32 +622,187 @@ pub fn on_mouse_event(app: &mut App, mouse_event: MouseEvent) {
                 AppView::OllamaModelList => app.select_next_item_in_ollama_list(),
                 #[cfg(feature = "bedrock_integration")]
                 AppView::BedrockModelList => app.select_next_item_in_bedrock_list(),
+                #[cfg(feature = "openai_compat")]
+                AppView::OpenAiModelList => app.select_next_item_in_openai_list(),
                 AppView::Chat => app.scroll_chat_down(),
                 AppView::Logs => app.scroll_logs_down(),
             }
         }
+        MouseEventKind::Down(MouseButton::Left) => handle_list_click(app, mouse_event.column, mouse_event.row),
         _ => {}
     }
 }
 
+/// Selects the list item under a left click. Clicking it again soon after activates it as Enter
+/// would (opening a chat or a log entry), except in the VM list, where Enter starts the VM, so a
+/// double-click opens the VM's details instead.
+fn handle_list_click(app: &mut App, column: u16, row: u16) {
+    const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+    let popup_open = app.input_mode != InputMode::Normal
+        || app.show_menu
+        || app.show_command_palette
+        || app.show_chat_picker
+        || app.show_new_vm_popup
+        || app.show_keybindings_modal
+        || app.show_about_modal
+        || app.log_detail.is_some()
+        || app.vm_detail.is_some();
+    if popup_open {
+        return;
+    }
+    let Some(hit_area) = app.list_hit_area.as_ref().filter(|hit_area| hit_area.view == app.active_view) else { return; };
+    let Some(idx) = hit_area.item_at(column, row) else { return; };
+    let view = hit_area.view;
+    let list_state = match view {
+        AppView::VmList => &mut app.vm_list_state,
+        AppView::OllamaModelList => &mut app.ollama_model_list_state,
+        #[cfg(feature = "bedrock_integration")]
+        AppView::BedrockModelList => &mut app.bedrock_model_list_state,
+        #[cfg(feature = "openai_compat")]
+        AppView::OpenAiModelList => &mut app.openai_model_list_state,
+        AppView::Logs => &mut app.log_list_state,
+        AppView::Chat => return,
+    };
+    list_state.select(Some(idx));
+
+    let double_click = app.last_list_click
+        .is_some_and(|(last_view, last_idx, at)| last_view == view && last_idx == idx && at.elapsed() < DOUBLE_CLICK_INTERVAL);
+    app.last_list_click = if double_click { None } else { Some((view, idx, Instant::now())) };
+    if double_click && view == AppView::VmList {
+        app.open_vm_detail();
+    } else if double_click {
+        // Replaying the bound key keeps double-click and Enter in lockstep
+        if let Some(&(code, mods)) = app.keybinding_map.get("enter") {
+            handle_normal_mode_key(app, KeyEvent::new(code, mods));
+        }
+    }
+}
+
 fn key_matches(app: &App, action: &str, key_event: &KeyEvent) -> bool {
-    if let Some((code, mods)) = app.keybinding_map.get(action) {
-        key_event.code == *code && key_event.modifiers == *mods
-    } else {
-        false
+    let Some((code, mods)) = app.keybinding_map.get(action) else {
+        return false;
+    };
+    // Terminals report Shift alongside shifted characters ('F' arrives as Shift+F), but the
+    // character already says it, so Shift is ignored for character keys
+    let mut event_mods = key_event.modifiers;
+    if matches!(key_event.code, KeyCode::Char(_)) {
+        event_mods.remove(KeyModifiers::SHIFT);
     }
+    key_event.code == *code && event_mods == *mods
 }
 
 pub fn on_key(app: &mut App, key_event: KeyEvent) {
+    if key_event.code == KeyCode::Char('c') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        app.should_quit = true;
+        return;
+    }
+    if app.show_command_palette {
+        handle_command_palette_key(app, key_event);
+        return;
+    }
     if app.show_keybindings_modal {
-        if key_matches(app, "help", &key_event) || key_event.code == KeyCode::Esc {
+        const PAGE: isize = 10;
+        if app.keybinding_capture {
+            // Esc can't be captured, since it cancels
+            if key_event.code == KeyCode::Esc {
+                app.keybinding_capture = false;
+            } else {
+                app.rebind_selected_action(key_event);
+            }
+        } else if key_matches(app, "help", &key_event) || key_event.code == KeyCode::Esc {
             app.show_keybindings_modal = false;
+        } else if key_matches(app, "down", &key_event) || key_event.code == KeyCode::Char('j') {
+            app.move_keybindings_selection(1);
+        } else if key_matches(app, "up", &key_event) || key_event.code == KeyCode::Char('k') {
+            app.move_keybindings_selection(-1);
+        } else if key_event.code == KeyCode::PageDown {
+            app.move_keybindings_selection(PAGE);
+        } else if key_event.code == KeyCode::PageUp {
+            app.move_keybindings_selection(-PAGE);
+        } else if key_event.code == KeyCode::Home {
+            app.keybindings_selected = 0;
+            app.keybindings_scroll = 0;
+        } else if key_event.code == KeyCode::End {
+            app.move_keybindings_selection(isize::MAX);
+        } else if key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Enter {
+            if !app.refuse_in_read_only("rebinding keys") {
+                app.keybinding_capture = true;
+            }
+        } else if matches!(key_event.code, KeyCode::Delete | KeyCode::Backspace) {
+            if !app.refuse_in_read_only("rebinding keys") {
+                app.reset_selected_keybinding();
+            }
+        }
+        return;
+    }
+    if app.log_detail.is_some() {
+        const PAGE: usize = 10;
+        if key_event.code == KeyCode::Esc || key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Char('q') {
+            app.log_detail = None;
+        } else if key_matches(app, "down", &key_event) || key_event.code == KeyCode::Char('j') {
+            app.log_detail_scroll = app.log_detail_scroll.saturating_add(1);
+        } else if key_matches(app, "up", &key_event) || key_event.code == KeyCode::Char('k') {
+            app.log_detail_scroll = app.log_detail_scroll.saturating_sub(1);
+        } else if key_event.code == KeyCode::PageDown {
+            app.log_detail_scroll = app.log_detail_scroll.saturating_add(PAGE);
+        } else if key_event.code == KeyCode::PageUp {
+            app.log_detail_scroll = app.log_detail_scroll.saturating_sub(PAGE);
+        } else if key_matches(app, "copy", &key_event) {
+            app.copy_selection_to_clipboard();
+        }
+        return;
+    }
+    if app.vm_detail.is_some() {
+        const PAGE: usize = 10;
+        if key_event.code == KeyCode::Esc || key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Char('q') {
+            app.vm_detail = None;
+        } else if key_matches(app, "down", &key_event) || key_event.code == KeyCode::Char('j') {
+            app.vm_detail_scroll = app.vm_detail_scroll.saturating_add(1);
+        } else if key_matches(app, "up", &key_event) || key_event.code == KeyCode::Char('k') {
+            app.vm_detail_scroll = app.vm_detail_scroll.saturating_sub(1);
+        } else if key_event.code == KeyCode::PageDown {
+            app.vm_detail_scroll = app.vm_detail_scroll.saturating_add(PAGE);
+        } else if key_event.code == KeyCode::PageUp {
+            app.vm_detail_scroll = app.vm_detail_scroll.saturating_sub(PAGE);
+        }
+        return;
+    }
+    if app.show_chat_picker {
+        if key_event.code == KeyCode::Esc || key_matches(app, "load_chat", &key_event) {
+            app.show_chat_picker = false;
+        } else if key_matches(app, "down", &key_event) || key_event.code == KeyCode::Char('j') {
+            let count = app.saved_chats.len();
+            if count > 0 {
+                let next = app.chat_picker_state.selected().map_or(0, |i| (i + 1) % count);
+                app.chat_picker_state.select(Some(next));
+            }
+        } else if key_matches(app, "up", &key_event) || key_event.code == KeyCode::Char('k') {
+            let count = app.saved_chats.len();
+            if count > 0 {
+                let previous = app.chat_picker_state.selected().map_or(0, |i| (i + count - 1) % count);
+                app.chat_picker_state.select(Some(previous));
+            }
+        } else if key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Enter {
+            app.load_selected_chat();
         }
         return;
     }
     if app.show_about_modal {
+        let page = app.config.interface.about_modal_readme_lines.max(1);
         if key_matches(app, "help", &key_event) || key_event.code == KeyCode::Esc || key_event.code == KeyCode::Char('q') {
             app.show_about_modal = false;
+        } else if key_matches(app, "down", &key_event) || key_event.code == KeyCode::Char('j') {
+            app.about_scroll = app.about_scroll.saturating_add(1);
+        } else if key_matches(app, "up", &key_event) || key_event.code == KeyCode::Char('k') {
+            app.about_scroll = app.about_scroll.saturating_sub(1);
+        } else if key_event.code == KeyCode::PageDown {
+            app.about_scroll = app.about_scroll.saturating_add(page);
+        } else if key_event.code == KeyCode::PageUp {
+            app.about_scroll = app.about_scroll.saturating_sub(page);
+        } else if key_event.code == KeyCode::Home {
+            app.about_scroll = 0;
+        } else if key_event.code == KeyCode::End {
+            app.about_scroll = usize::MAX;
         }
         return;
     }
@@ -181,15 +817,10 @@ pub fn on_key(app: &mut App, key_event: KeyEvent) {
                     app.menu_previous();
                 } else if key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Enter {
                     if let Some(selected) = app.menu_state.selected() {
-                        let item_name = match selected {
-                            0 => "About",
-                            1 => "Preferences",
-                            2 => "Quit",
-                            _ => "",
-                        };
-                        match item_name {
+                        match MAIN_MENU_ITEMS.get(selected).copied().unwrap_or_default() {
                             "About" => {
                                 app.show_about_modal = true;
+                                app.about_scroll = 0;
                                 app.show_menu = false;
                             },
                             "Preferences" => {
@@ -212,16 +843,12 @@ pub fn on_key(app: &mut App, key_event: KeyEvent) {
                     app.menu_previous();
                 } else if key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Enter {
                      if let Some(selected) = app.menu_sub_state.selected() {
-                        let item_name = match selected {
-                            0 => "Key Bindings",
-                            1 => "Back",
-                            _ => "",
-                        };
-                        match item_name {
+                        match PREFERENCES_MENU_ITEMS.get(selected).copied().unwrap_or_default() {
                             "Key Bindings" => {
-                                app.show_keybindings_modal = true;
+                                app.open_keybindings_modal();
                                 app.show_menu = false;
                             },
+                            "Theme" => app.open_theme_picker(),
                             "Back" => {
                                 app.menu_level = 0;
                                 app.menu_state.select(Some(1));
@@ -231,6 +858,20 @@ pub fn on_key(app: &mut App, key_event: KeyEvent) {
                     }
                 }
             },
+            2 => { // Theme picker; a theme is applied on Enter and the picker stays open to try others
+                if key_matches(app, "menu", &key_event) || key_event.code == KeyCode::Esc {
+                    app.menu_level = 1;
+                    app.menu_sub_state.select(Some(1)); // Reselect "Theme"
+                } else if key_matches(app, "down", &key_event) || key_event.code == KeyCode::Char('j') {
+                    app.menu_next();
+                } else if key_matches(app, "up", &key_event) || key_event.code == KeyCode::Char('k') {
+                    app.menu_previous();
+                } else if (key_matches(app, "enter", &key_event) || key_event.code == KeyCode::Enter)
+                    && !app.refuse_in_read_only("changing the theme")
+                {
+                    app.apply_selected_theme();
+                }
+            },
             _ => {} // Should not happen
         }
         return;
@@ -240,13 +881,29 @@ pub fn on_key(app: &mut App, key_event: KeyEvent) {
         InputMode::Normal => handle_normal_mode_key(app, key_event),
         InputMode::Editing => handle_editing_mode_key(app, key_event),
         InputMode::VmWizard => handle_vm_wizard_mode_key(app, key_event),
-        InputMode::ConfirmingDestroy => handle_confirm_destroy_mode_key(app, key_event),
+        InputMode::ConfirmingVmAction => handle_confirm_vm_action_mode_key(app, key_event),
+        InputMode::ConfirmingModelDelete => handle_confirm_model_delete_mode_key(app, key_event),
     }
 }
 
 fn handle_normal_mode_key(app: &mut App, key_event: KeyEvent) {
+    app.status_message = None;
+    app.status_message_is_info = false;
+    if app.read_only {
+        let view = app.active_view;
+        let refused = app.key_actions.iter()
+            .filter(|action| action.is_available_in(view) && action.is_blocked_in_read_only(view))
+            .find(|action| key_matches(app, action.id, &key_event))
+            .map(|action| format!("\"{}\"", action.label));
+        if let Some(label) = refused {
+            app.refuse_in_read_only(&label);
+            return;
+        }
+    }
     if key_matches(app, "quit", &key_event) {
         app.should_quit = true;
+    } else if key_matches(app, "help", &key_event) {
+        app.open_keybindings_modal();
     } else if key_matches(app, "next_tab", &key_event) {
         app.active_view = app.active_view.next();
     } else if key_matches(app, "prev_tab", &key_event) {
@@ -263,6 +920,8 @@ fn handle_normal_mode_key(app: &mut App, key_event: KeyEvent) {
             AppView::OllamaModelList => app.select_next_item_in_ollama_list(),
             #[cfg(feature = "bedrock_integration")]
             AppView::BedrockModelList => app.select_next_item_in_bedrock_list(),
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => app.select_next_item_in_openai_list(),
             AppView::Chat => app.scroll_chat_down(),
             AppView::Logs => app.scroll_logs_down(),
         }
@@ -272,70 +931,280 @@ fn handle_normal_mode_key(app: &mut App, key_event: KeyEvent) {
             AppView::OllamaModelList => app.select_previous_item_in_ollama_list(),
             #[cfg(feature = "bedrock_integration")]
             AppView::BedrockModelList => app.select_previous_item_in_bedrock_list(),
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => app.select_previous_item_in_openai_list(),
             AppView::Chat => app.scroll_chat_up(),
             AppView::Logs => app.scroll_logs_up(),
         }
     } else if key_matches(app, "enter", &key_event) {
         match app.active_view {
+            AppView::VmList => {
+                // Enter starts (or unpauses) the selected VM if it isn't running
+                let startable_vm = app.selected_vm()
+                    .filter(|vm| matches!(vm.state, VmState::Stopped | VmState::Suspended | VmState::Error(_)))
+                    .map(|vm| vm.name.clone());
+                if let Some(vm_name) = startable_vm {
+                    app.event_sender.send(AppEvent::ResumeVm(vm_name)).unwrap();
+                }
+            },
             #[cfg(feature = "ollama_integration")]
             AppView::OllamaModelList => {
-                if let Some(selected_index) = app.ollama_model_list_state.selected() {
-                    let selected_model = &app.ollama_models[selected_index];
-                    let selected_model_name = selected_model.name.clone();
-                    if app.active_chat.as_ref().map_or(true, |c| c.model_name != selected_model_name) {
-                        app.active_chat = Some(ChatSession {
-                            model_name: selected_model_name.clone(),
-                            messages: vec![ChatMessage {
-                                sender: "System".to_string(),
-                                content: app.get_active_system_prompt(&selected_model_name),
-                                timestamp: "".to_string(),
-                                thought: None,
-                            }],
-                            is_streaming: false,
-                        });
-                    }
-                    app.active_view = AppView::Chat;
-                    app.chat_list_state.select(None);
+                let selected_model_name = app.ollama_model_list_state.selected()
+                    .and_then(|idx| app.visible_ollama_models().get(idx).map(|m| m.name.clone()));
+                if let Some(selected_model_name) = selected_model_name {
+                    app.open_chat_with_model(ChatProvider::Ollama, &selected_model_name);
                 }
             },
+            #[cfg(feature = "bedrock_integration")]
+            AppView::BedrockModelList => {
+                let selected_model_id = app.bedrock_model_list_state.selected()
+                    .and_then(|idx| app.bedrock_model_view_mode.apply(&app.bedrock_models).get(idx).map(|m| m.model_id().to_string()));
+                if let Some(model_id) = selected_model_id {
+                    app.open_bedrock_model(&model_id);
+                }
+            },
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => {
+                if let Some(model_id) = app.selected_openai_model_id() {
+                    app.open_chat_with_model(ChatProvider::OpenAiCompat, &model_id);
+                }
+            },
+            AppView::Chat => app.begin_chat_input(),
+            AppView::Logs => app.open_log_detail(),
+            #[allow(unreachable_patterns)] // Reached only without ollama_integration
             _ => {}
         }
     } else if key_matches(app, "edit", &key_event) {
         match app.active_view {
+            AppView::Chat => app.begin_chat_input(),
+            AppView::VmList => app.begin_vm_resize_input(),
             #[cfg(feature = "ollama_integration")]
             AppView::OllamaModelList => {
-                if let Some(selected_index) = app.ollama_model_list_state.selected() {
-                    let model_name = app.ollama_models[selected_index].name.clone();
+                let selected_model_name = app.ollama_model_list_state.selected()
+                    .and_then(|idx| app.visible_ollama_models().get(idx).map(|m| m.name.clone()));
+                if let Some(model_name) = selected_model_name {
                     let prompt = app.get_active_system_prompt(&model_name);
                     app.editing_system_prompt_for_model = Some(model_name);
-                    app.current_input = prompt;
+                    app.system_prompt_input.set_text(prompt);
                     app.input_mode = InputMode::Editing;
-                    app.reset_cursor_position();
                 }
             },
             _ => {}
         }
     } else if key_matches(app, "refresh", &key_event) {
-        app.event_sender.send(AppEvent::FetchVms).unwrap();
-        #[cfg(feature = "ollama_integration")]
-        app.event_sender.send(AppEvent::FetchOllamaModels).unwrap();
-        #[cfg(feature = "bedrock_integration")]
-        app.event_sender.send(AppEvent::FetchBedrockModels).unwrap();
-    } else if key_matches(app, "delete", &key_event) {
+        app.request_data_refresh();
+    } else if key_matches(app, "destroy_vm", &key_event) {
         match app.active_view {
             AppView::VmList => {
-                if let Some(selected_index) = app.vm_list_state.selected() {
-                    let vm_name = app.vms[selected_index].name.clone();
-                    app.vm_to_destroy = Some(vm_name);
-                    app.input_mode = InputMode::ConfirmingDestroy;
+                // Marked VMs are destroyed together, in list order; with none marked, the selected one is
+                let vm_names: Vec<String> = if app.marked_vms.is_empty() {
+                    app.selected_vm()
+                        .map(|vm| vm.name.clone())
+                        .into_iter()
+                        .collect()
+                } else {
+                    app.vms.iter()
+                        .filter(|vm| app.marked_vms.contains(&vm.name))
+                        .map(|vm| vm.name.clone())
+                        .collect()
+                };
+                if !vm_names.is_empty() {
+                    request_vm_action(app, VmAction::Destroy, vm_names);
                 }
             }
             _ => {}
         }
+    } else if key_matches(app, "clone_vm", &key_event) {
+        if app.active_view == AppView::VmList {
+            match app.selected_vm().cloned() {
+                // A running source's disk would change under the overlay
+                Some(vm) if matches!(vm.state, VmState::Running | VmState::Suspended) => {
+                    app.status_message = Some(format!("Shut down VM '{}' before cloning it.", vm.name));
+                }
+                Some(vm) => {
+                    let new_name = app.next_clone_name(&vm.name);
+                    app.status_message = Some(format!("Cloning VM '{}' as '{}'…", vm.name, new_name));
+                    app.status_message_is_info = true;
+                    app.event_sender.send(AppEvent::CloneVm { source_name: vm.name, new_name }).unwrap();
+                }
+                None => {}
+            }
+        }
+    } else if key_matches(app, "mark_vm", &key_event) {
+        if app.active_view == AppView::VmList {
+            let visible_count = app.visible_vms().len();
+            if let (Some(idx), Some(vm_name)) = (app.vm_list_state.selected(), app.selected_vm().map(|vm| vm.name.clone())) {
+                if !app.marked_vms.remove(&vm_name) {
+                    app.marked_vms.insert(vm_name);
+                }
+                // Marking moves down (without wrapping), so a run of VMs can be marked by holding the key
+                if idx + 1 < visible_count {
+                    app.vm_list_state.select(Some(idx + 1));
+                }
+            }
+        }
+    } else if key_matches(app, "shutdown_vm", &key_event) {
+        if app.active_view == AppView::VmList {
+            let running_vm = app.selected_vm()
+                .filter(|vm| matches!(vm.state, VmState::Running | VmState::Suspended))
+                .map(|vm| vm.name.clone());
+            if let Some(vm_name) = running_vm {
+                request_vm_action(app, VmAction::Shutdown, vec![vm_name]);
+            }
+        }
+    } else if key_matches(app, "suspend_vm", &key_event) {
+        if app.active_view == AppView::VmList {
+            // Suspending is undone by resuming, so neither direction asks for confirmation
+            let event = app.selected_vm().and_then(|vm| match vm.state {
+                VmState::Running => Some(AppEvent::SuspendVm(vm.name.clone())),
+                VmState::Suspended => Some(AppEvent::ResumeVm(vm.name.clone())),
+                _ => None,
+            });
+            match event {
+                Some(event) => app.event_sender.send(event).unwrap(),
+                None => {
+                    if let Some(vm) = app.selected_vm() {
+                        app.status_message = Some(format!("VM '{}' is not running.", vm.name));
+                    }
+                }
+            }
+        }
+    } else if key_matches(app, "ssh_vm", &key_event) {
+        if app.active_view == AppView::VmList {
+            let selected_vm = app.selected_vm()
+                .cloned();
+            match selected_vm {
+                Some(vm) if !matches!(vm.state, VmState::Running) => {
+                    app.status_message = Some(format!("VM '{}' is not running.", vm.name));
+                }
+                Some(vm) if vm.ip_address.is_none() && !app.ssh_manager.config.hosts.contains_key(&vm.name) => {
+                    app.status_message = Some(format!("VM '{}' has no known IP address.", vm.name));
+                }
+                Some(vm) => app.pending_ssh_target = Some(vm),
+                None => {}
+            }
+        }
+    } else if key_matches(app, "console_vm", &key_event) {
+        if app.active_view == AppView::VmList {
+            let selected_vm = app.selected_vm()
+                .cloned();
+            match selected_vm {
+                Some(vm) if !matches!(vm.state, VmState::Running) => {
+                    app.status_message = Some(format!("VM '{}' is not running.", vm.name));
+                }
+                Some(vm) => {
+                    if !app.refuse_when_attached("the VM console") {
+                        app.pending_console_target = Some(vm);
+                    }
+                }
+                None => {}
+            }
+        }
+    } else if app.active_view == AppView::Logs && key_matches(app, "log_level", &key_event) {
+        app.cycle_log_level_filter();
+    } else if app.active_view == AppView::Logs && key_matches(app, "wrap_logs", &key_event) {
+        app.log_wrap = !app.log_wrap;
+        app.log_hscroll = 0;
+    } else if app.active_view == AppView::Logs && !app.log_wrap && matches!(key_event.code, KeyCode::Left | KeyCode::Right) {
+        const HSCROLL_STEP: usize = 8;
+        app.log_hscroll = if key_event.code == KeyCode::Left {
+            app.log_hscroll.saturating_sub(HSCROLL_STEP)
+        } else {
+            app.log_hscroll.saturating_add(HSCROLL_STEP)
+        };
+    } else if app.active_view == AppView::Logs && key_matches(app, "search", &key_event) {
+        app.begin_log_search();
+    } else if app.active_view == AppView::VmList && key_matches(app, "search", &key_event) {
+        app.begin_vm_search();
+    } else if app.active_view == AppView::VmList && key_matches(app, "filter", &key_event) {
+        app.cycle_vm_state_filter();
+    } else if matches!(app.active_view, AppView::Chat | AppView::Logs) && key_matches(app, "copy", &key_event) {
+        app.copy_selection_to_clipboard();
+    } else if app.active_view == AppView::OllamaModelList && key_matches(app, "edit_params", &key_event) {
+        #[cfg(feature = "ollama_integration")]
+        {
+            let selected_model_name = app.ollama_model_list_state.selected()
+                .and_then(|idx| app.visible_ollama_models().get(idx).map(|m| m.name.clone()));
+            if let Some(model_name) = selected_model_name {
+                app.begin_model_params_input(model_name);
+            }
+        }
+    } else if app.active_view == AppView::OllamaModelList && key_matches(app, "pull_model", &key_event) {
+        app.begin_pull_model_input();
+    } else if app.active_view == AppView::OllamaModelList && key_matches(app, "delete_model", &key_event) {
+        #[cfg(feature = "ollama_integration")]
+        {
+            let selected_model = app.ollama_model_list_state.selected()
+                .and_then(|idx| app.visible_ollama_models().get(idx).map(|m| (m.name.clone(), m.size)));
+            if let Some((model_name, size)) = selected_model {
+                app.pending_model_delete = Some((model_name, size));
+                app.input_mode = InputMode::ConfirmingModelDelete;
+            }
+        }
+    } else if app.active_view == AppView::Chat && key_matches(app, "cancel_stream", &key_event) {
+        app.cancel_chat_stream();
+    } else if app.active_view == AppView::Chat && key_matches(app, "save_chat", &key_event) {
+        if let Some(path) = app.save_active_chat() {
+            app.notify(ToastLevel::Success, format!("Saved chat to {}", path.display()), TOAST_TTL);
+        }
+    } else if app.active_view == AppView::Chat && key_matches(app, "load_chat", &key_event) {
+        app.open_chat_picker();
+    } else if app.active_view == AppView::Chat && key_matches(app, "toggle_thought", &key_event) {
+        app.toggle_selected_thought();
+    } else if app.active_view == AppView::Chat && key_matches(app, "new_chat", &key_event) {
+        app.new_chat();
+    } else if app.active_view == AppView::Chat && key_matches(app, "clear_chat", &key_event) {
+        app.clear_active_chat();
+    } else if app.active_view == AppView::Chat && key_matches(app, "close_chat", &key_event) {
+        app.close_active_chat();
+    } else if app.active_view == AppView::Chat && key_matches(app, "next_chat", &key_event) {
+        app.next_chat();
+    } else if app.active_view == AppView::Chat && key_matches(app, "prev_chat", &key_event) {
+        app.previous_chat();
+    } else if let (AppView::Chat, KeyCode::Char(digit @ '1'..='9')) = (app.active_view, key_event.code) {
+        // Open chats are numbered in the Chat Info pane
+        app.switch_chat(digit as usize - '1' as usize);
+    } else if key_matches(app, "toggle_mouse", &key_event) {
+        app.mouse_capture_enabled = !app.mouse_capture_enabled;
+    } else if key_matches(app, "toggle_quick_actions", &key_event) {
+        app.quick_actions = !app.quick_actions;
+        if app.quick_actions {
+            app.notify(ToastLevel::Warning, "Quick actions on: VMs are destroyed and shut down without confirmation".to_string(), TOAST_TTL);
+        } else {
+            app.notify(ToastLevel::Info, "Quick actions off: VM destroy and shutdown ask first".to_string(), TOAST_TTL);
+        }
+    } else if key_matches(app, "toggle_favorite", &key_event) {
+        match app.active_view {
+            #[cfg(feature = "ollama_integration")]
+            AppView::OllamaModelList => {
+                let selected_model_name = app.ollama_model_list_state.selected()
+                    .and_then(|idx| app.visible_ollama_models().get(idx).map(|m| m.name.clone()));
+                if let Some(model_name) = selected_model_name {
+                    app.toggle_favorite_model(&model_name);
+                }
+            },
+            #[cfg(feature = "bedrock_integration")]
+            AppView::BedrockModelList => {
+                let selected_model_id = app.bedrock_model_list_state.selected()
+                    .and_then(|idx| app.bedrock_model_view_mode.apply(&app.bedrock_models).get(idx).map(|m| m.model_id().to_string()));
+                if let Some(model_id) = selected_model_id {
+                    app.toggle_favorite_model(&model_id);
+                }
+            },
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => {
+                if let Some(model_id) = app.selected_openai_model_id() {
+                    app.toggle_favorite_model(&model_id);
+                }
+            },
+            _ => {}
+        }
+    } else if key_matches(app, "favorites_only", &key_event) {
+        app.toggle_favorites_only();
     } else if key_matches(app, "new_vm", &key_event) {
-        app.show_new_vm_popup = true;
-        app.input_mode = InputMode::VmWizard;
-        app.active_new_vm_input_idx = 0;
+        app.open_new_vm_wizard();
+    } else if key_matches(app, "command_palette", &key_event) {
+        app.open_command_palette();
     }
 
     // View-specific key handling for Bedrock
@@ -348,121 +1217,268 @@ fn handle_normal_mode_key(app: &mut App, key_event: KeyEvent) {
             let next_idx = (idx + 1) % filters.len();
             app.current_bedrock_filter = filters[next_idx].to_string();
         } else if key_matches(app, "bedrock_sort", &key_event) {
-            // Currently, only one sort is implemented, so we can just log or do nothing.
-            // When more are added, this can cycle like the filters.
-            let sorts = ["alphabetical"]; // The only sort option for now
-            let idx = sorts.iter().position(|&s| s == app.current_bedrock_sort).unwrap_or(0);
-            let next_idx = (idx + 1) % sorts.len();
-            app.current_bedrock_sort = sorts[next_idx].to_string();
+            let idx = BEDROCK_SORTS.iter().position(|&s| s == app.current_bedrock_sort).unwrap_or(0);
+            let next_idx = (idx + 1) % BEDROCK_SORTS.len();
+            app.current_bedrock_sort = BEDROCK_SORTS[next_idx].to_string();
+        } else if key_matches(app, "bedrock_access", &key_event) {
+            if let Some(model_id) = app.selected_bedrock_model_id() {
+                app.notify(ToastLevel::Info, format!("Checking access to '{}'…", model_id), TOAST_TTL);
+                app.check_bedrock_model_access(model_id);
+            }
+        } else if key_matches(app, "bedrock_region", &key_event) {
+            app.cycle_bedrock_region();
+        } else if key_matches(app, "bedrock_profile", &key_event) {
+            app.cycle_bedrock_profile();
         }
     }
 }
 
-fn handle_editing_mode_key(app: &mut App, key_event: KeyEvent) {
+// The palette's filter takes every printable key, so only arrows move the selection
+fn handle_command_palette_key(app: &mut App, key_event: KeyEvent) {
+    let match_count = app.command_palette_matches().len();
     match key_event.code {
+        KeyCode::Esc => app.show_command_palette = false,
+        KeyCode::Down if match_count > 0 => {
+            let next = app.command_palette_state.selected().map_or(0, |i| (i + 1) % match_count);
+            app.command_palette_state.select(Some(next));
+        }
+        KeyCode::Up if match_count > 0 => {
+            let previous = app.command_palette_state.selected().map_or(0, |i| (i + match_count - 1) % match_count);
+            app.command_palette_state.select(Some(previous));
+        }
         KeyCode::Enter => {
-            if let Some(model_name) = app.editing_system_prompt_for_model.take() {
-                app.editable_ollama_model_prompts.insert(model_name, app.current_input.clone());
+            let binding = app.command_palette_state.selected()
+                .and_then(|idx| app.command_palette_matches().get(idx).map(|action| action.id))
+                .and_then(|id| app.keybinding_map.get(id).copied());
+            app.show_command_palette = false;
+            // Replaying the bound key keeps the palette and the keybinding in lockstep
+            if let Some((code, mods)) = binding {
+                handle_normal_mode_key(app, KeyEvent::new(code, mods));
             }
-            app.input_mode = InputMode::Normal;
-        }
-        KeyCode::Char(c) => {
-            let char_idx = app.input_cursor_char_idx;
-            app.current_input.insert(char_idx, c);
-            app.input_cursor_char_idx += 1;
         }
         KeyCode::Backspace => {
-            if app.input_cursor_char_idx > 0 {
-                app.input_cursor_char_idx -= 1;
-                let char_idx = app.input_cursor_char_idx;
-                app.current_input.remove(char_idx);
-            }
+            app.command_palette_query.pop();
+            app.command_palette_state.select(Some(0));
         }
-        KeyCode::Left => {
-            if app.input_cursor_char_idx > 0 {
-                app.input_cursor_char_idx -= 1;
-            }
+        KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.command_palette_query.push(c);
+            app.command_palette_state.select(Some(0));
         }
-        KeyCode::Right => {
-            if app.input_cursor_char_idx < app.current_input.len() {
-                app.input_cursor_char_idx += 1;
+        _ => {}
+    }
+}
+
+fn handle_editing_mode_key(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Enter => {
+            if app.editing_params_for_model.is_some() {
+                app.save_model_params_input();
+            } else if app.editing_resources_for_vm.is_some() {
+                app.submit_vm_resize_input();
+            } else if app.editing_pull_model_name {
+                app.editing_pull_model_name = false;
+                app.input_mode = InputMode::Normal;
+                app.start_model_pull();
+            } else if let Some(model_name) = app.editing_system_prompt_for_model.take() {
+                app.editable_ollama_model_prompts.insert(model_name, app.system_prompt_input.take());
+                app.input_mode = InputMode::Normal;
+            } else if app.active_view == AppView::Chat {
+                app.send_chat_message();
+            } else {
+                app.input_mode = InputMode::Normal;
             }
         }
         KeyCode::Esc => {
+            // The chat input keeps its draft; an abandoned system prompt edit is discarded,
+            // and Esc from a log search clears the search
+            if app.editing_params_for_model.take().is_some() {
+                app.model_params_input.clear();
+            } else if app.editing_resources_for_vm.take().is_some() {
+                app.vm_resources_input.clear();
+            } else if app.editing_pull_model_name {
+                app.editing_pull_model_name = false;
+                app.pull_model_input.clear();
+            } else if app.editing_system_prompt_for_model.take().is_some() {
+                app.system_prompt_input.clear();
+            } else if app.active_view == AppView::Logs {
+                app.log_search_input.clear();
+            } else if app.active_view == AppView::VmList {
+                let previous_name = app.selected_vm().map(|vm| vm.name.clone());
+                app.vm_search_input.clear();
+                app.reselect_vm(previous_name);
+            }
             app.input_mode = InputMode::Normal;
-            app.editing_system_prompt_for_model = None;
         }
-        // Add Up/Down arrow handling later if needed for multi-line
-        _ => {}
+        // Up/Down browse previously sent messages
+        KeyCode::Up if app.is_editing_chat() => app.chat_input.history_previous(),
+        KeyCode::Down if app.is_editing_chat() => app.chat_input.history_next(),
+        code => {
+            // The VM list narrows as the search is typed, so keep the highlighted VM if it still matches
+            let previous_vm_name = app.selected_vm().map(|vm| vm.name.clone());
+            let Some(input) = app.active_text_input_mut() else { return; };
+            match code {
+                KeyCode::Char(c) => input.insert_char(c),
+                KeyCode::Backspace => input.backspace(),
+                KeyCode::Delete => input.delete(),
+                KeyCode::Left => input.move_left(),
+                KeyCode::Right => input.move_right(),
+                KeyCode::Home => input.move_home(),
+                KeyCode::End => input.move_end(),
+                _ => {}
+            }
+            if app.active_view == AppView::VmList {
+                app.reselect_vm(previous_vm_name);
+            }
+        }
     }
-     app.input_bar_cursor_needs_to_be_visible = true;
 }
 
 
 fn handle_vm_wizard_mode_key(app: &mut App, key_event: KeyEvent) {
-    let current_field = match app.active_new_vm_input_idx {
-        0 => &mut app.new_vm_name,
-        1 => {
-            if key_event.code == KeyCode::Enter || key_event.code == KeyCode::Char(' ') {
-                app.new_vm_use_iso = !app.new_vm_use_iso;
-            }
-            // Dummy mutable ref for the match arm
-            &mut String::new()
-        },
-        2 if app.new_vm_use_iso => &mut app.new_vm_iso_path,
-        2 if !app.new_vm_use_iso => &mut app.new_vm_source_image_path,
-        3 => &mut app.new_vm_disk_path,
-        4 => &mut app.new_vm_cpu,
-        5 => &mut app.new_vm_ram_mb,
-        6 => &mut app.new_vm_disk_gb,
-        _ => return,
-    };
-
+    if app.new_vm_preview.is_some() {
+        handle_vm_preview_key(app, key_event);
+        return;
+    }
+    let field_idx = app.active_new_vm_input_idx;
     match key_event.code {
-        KeyCode::Char(c) => {
-            // Only modify string fields
-            if app.active_new_vm_input_idx != 1 {
-                 current_field.push(c);
-            }
-        },
-        KeyCode::Backspace => {
-            if app.active_new_vm_input_idx != 1 {
-                current_field.pop();
-            }
-        },
         KeyCode::Tab => {
-            app.active_new_vm_input_idx = (app.active_new_vm_input_idx + 1) % 7;
-        },
+            app.active_new_vm_input_idx = (field_idx + 1) % new_vm_field::COUNT;
+        }
         KeyCode::BackTab => {
-            app.active_new_vm_input_idx = (app.active_new_vm_input_idx + 6) % 7;
-        },
-        KeyCode::Enter => {
-            // Check if on the checkbox, if so, Tab acts as Enter for other fields
-             if app.active_new_vm_input_idx != 1 {
-                app.active_new_vm_input_idx = (app.active_new_vm_input_idx + 1) % 7;
-             }
+            app.active_new_vm_input_idx = (field_idx + new_vm_field::COUNT - 1) % new_vm_field::COUNT;
         }
         KeyCode::Esc => {
             app.show_new_vm_popup = false;
             app.input_mode = InputMode::Normal;
-        },
+        }
+        // Enter and Space toggle the checkboxes; Enter anywhere else submits the wizard
+        KeyCode::Enter | KeyCode::Char(' ') if field_idx == new_vm_field::USE_ISO => {
+            app.new_vm_use_iso = !app.new_vm_use_iso;
+        }
+        KeyCode::Enter | KeyCode::Char(' ') if field_idx == new_vm_field::PERSISTENT => {
+            app.new_vm_persistent = !app.new_vm_persistent;
+        }
+        KeyCode::Enter => app.submit_new_vm_wizard(true),
+        KeyCode::Char('p') if key_event.modifiers.contains(KeyModifiers::CONTROL) => app.submit_new_vm_wizard(false),
+        KeyCode::Char(_) | KeyCode::Backspace => {
+            let text_field = match field_idx {
+                new_vm_field::NAME => &mut app.new_vm_name,
+                new_vm_field::SOURCE_IMAGE => &mut app.new_vm_source_image_path,
+                new_vm_field::DISK_PATH => &mut app.new_vm_disk_path,
+                new_vm_field::CPU => &mut app.new_vm_cpu,
+                new_vm_field::RAM => &mut app.new_vm_ram_mb,
+                new_vm_field::DISK_SIZE => &mut app.new_vm_disk_gb,
+                // The ISO path is greyed out and read-only unless booting from ISO
+                new_vm_field::ISO_PATH if app.new_vm_use_iso => &mut app.new_vm_iso_path,
+                _ => return,
+            };
+            match key_event.code {
+                KeyCode::Char(c) => text_field.push(c),
+                _ => {
+                    text_field.pop();
+                }
+            }
+        }
         _ => {}
     }
 }
 
+// Keys for the dry-run preview shown over the wizard. An existing disk must be explicitly
+// reused or overwritten; one that belongs to another VM can only be backed out of.
+fn handle_vm_preview_key(app: &mut App, key_event: KeyEvent) {
+    let Some((_, preview)) = &app.new_vm_preview else { return };
+    let disk_free = !preview.disk_exists;
+    let disk_unused = preview.disk_used_by.is_empty();
+    let existing_disk = match key_event.code {
+        KeyCode::Esc => {
+            app.new_vm_preview = None;
+            return;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.new_vm_preview_scroll = app.new_vm_preview_scroll.saturating_add(1);
+            return;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.new_vm_preview_scroll = app.new_vm_preview_scroll.saturating_sub(1);
+            return;
+        }
+        KeyCode::PageDown => {
+            app.new_vm_preview_scroll = app.new_vm_preview_scroll.saturating_add(10);
+            return;
+        }
+        KeyCode::PageUp => {
+            app.new_vm_preview_scroll = app.new_vm_preview_scroll.saturating_sub(10);
+            return;
+        }
+        KeyCode::Enter if disk_free => ExistingDisk::Refuse,
+        KeyCode::Char('r') if !disk_free && disk_unused => ExistingDisk::Reuse,
+        KeyCode::Char('o') if !disk_free && disk_unused => ExistingDisk::Overwrite,
+        _ => return,
+    };
+    if let Some((mut vm_config, _)) = app.new_vm_preview.take() {
+        vm_config.existing_disk = existing_disk;
+        app.create_vm_from_wizard(vm_config);
+    }
+}
+
+/// Asks to confirm a VM action, or with quick actions on, goes straight ahead.
+fn request_vm_action(app: &mut App, action: VmAction, vm_names: Vec<String>) {
+    if app.quick_actions {
+        run_vm_action(app, action, vm_names);
+    } else {
+        app.pending_vm_action = Some((action, vm_names));
+        app.input_mode = InputMode::ConfirmingVmAction;
+    }
+}
 
-fn handle_confirm_destroy_mode_key(app: &mut App, key_event: KeyEvent) {
+fn run_vm_action(app: &mut App, action: VmAction, vm_names: Vec<String>) {
+    if action == VmAction::Destroy {
+        app.marked_vms.clear();
+    }
+    for vm_name in vm_names {
+        app.record_vm_action_decision(action, &vm_name, true);
+        let event = match action {
+            VmAction::Destroy => AppEvent::DestroyVm(vm_name),
+            VmAction::Shutdown => AppEvent::ShutdownVm(vm_name),
+        };
+        app.event_sender.send(event).unwrap();
+    }
+}
+
+fn handle_confirm_vm_action_mode_key(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            if let Some((action, vm_names)) = app.pending_vm_action.take() {
+                run_vm_action(app, action, vm_names);
+            }
+            app.input_mode = InputMode::Normal;
+        },
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            if let Some((action, vm_names)) = app.pending_vm_action.take() {
+                for vm_name in &vm_names {
+                    app.record_vm_action_decision(action, vm_name, false);
+                }
+            }
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+} 
+
+fn handle_confirm_model_delete_mode_key(app: &mut App, key_event: KeyEvent) {
     match key_event.code {
         KeyCode::Char('y') | KeyCode::Char('Y') => {
-            if let Some(vm_name) = app.vm_to_destroy.take() {
-                app.event_sender.send(AppEvent::DestroyVm(vm_name)).unwrap();
+            if let Some((model_name, _)) = app.pending_model_delete.take() {
+                // Chats with the deleted model can't continue, so close them (saving them first)
+                app.close_chats_with_model(&model_name);
+                app.event_sender.send(AppEvent::DeleteModel(model_name)).unwrap();
             }
             app.input_mode = InputMode::Normal;
         },
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-            app.vm_to_destroy = None;
+            app.pending_model_delete = None;
             app.input_mode = InputMode::Normal;
         }
         _ => {}
     }
-} 
\ No newline at end of file
+}
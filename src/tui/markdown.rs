@@ -0,0 +1,243 @@
+// src/tui/markdown.rs
+// Renders the markdown subset models commonly emit (headings, emphasis, lists, quotes and
+// code) as styled, wrapped ratatui lines. This is deliberately not a CommonMark parser.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use textwrap::core::display_width;
+
+use super::theme::AppTheme;
+
+/// Converts `text` into lines no wider than `width` columns, using `base` for body text.
+pub fn render_markdown(text: &str, width: usize, base: Style, theme: &AppTheme) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for raw_line in text.lines() {
+        let trimmed = raw_line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            push_code_line(&mut lines, raw_line, width, theme.chat_code_block);
+            continue;
+        }
+        if trimmed.is_empty() {
+            lines.push(Line::from(""));
+            continue;
+        }
+
+        let indent = " ".repeat(raw_line.len() - trimmed.len());
+        if let Some((level, heading)) = parse_heading(trimmed) {
+            let mut style = theme.chat_heading;
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            lines.extend(wrap_words(parse_inline(heading, style, theme), width, "", ""));
+        } else if is_rule(trimmed) {
+            lines.push(Line::from(Span::styled("─".repeat(width), Style::default().fg(theme.tertiary_foreground))));
+        } else if let Some(quoted) = trimmed.strip_prefix('>') {
+            let style = base.add_modifier(Modifier::ITALIC).fg(theme.secondary_foreground);
+            let prefix = format!("{}│ ", indent);
+            lines.extend(wrap_words(parse_inline(quoted.trim_start(), style, theme), width, &prefix, &prefix));
+        } else if let Some((marker, item)) = parse_list_item(trimmed) {
+            let first_prefix = format!("{}{} ", indent, marker);
+            let rest_prefix = " ".repeat(display_width(&first_prefix));
+            lines.extend(wrap_words(parse_inline(item, base, theme), width, &first_prefix, &rest_prefix));
+        } else {
+            lines.extend(wrap_words(parse_inline(trimmed, base, theme), width, &indent, &indent));
+        }
+    }
+    lines
+}
+
+// Code is shown verbatim, hard-wrapped, and padded so the background forms a block
+fn push_code_line(lines: &mut Vec<Line<'static>>, raw_line: &str, width: usize, style: Style) {
+    let expanded = raw_line.replace('\t', "    ");
+    let chars: Vec<char> = expanded.chars().collect();
+    if chars.is_empty() {
+        lines.push(Line::from(Span::styled(" ".repeat(width), style)));
+        return;
+    }
+    for chunk in chars.chunks(width) {
+        let mut segment: String = chunk.iter().collect();
+        let padding = width.saturating_sub(display_width(&segment));
+        segment.push_str(&" ".repeat(padding));
+        lines.push(Line::from(Span::styled(segment, style)));
+    }
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        Some((level, line[level..].trim()))
+    } else {
+        None
+    }
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.trim_end();
+    let first = line.chars().next();
+    line.len() >= 3 && matches!(first, Some('-' | '*' | '_')) && line.chars().all(|c| Some(c) == first)
+}
+
+// Returns the marker to display ("•" for bullets, "3." for numbered items) and the item text
+fn parse_list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some(("•".to_string(), item));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && digits <= 3 {
+        let rest = &line[digits..];
+        if rest.starts_with(". ") || rest.starts_with(") ") {
+            return Some((format!("{}.", &line[..digits]), &rest[2..]));
+        }
+    }
+    None
+}
+
+// Splits a line into styled runs for **bold**, *italic*, and `code`. A marker only counts
+// if it is closed later on the same line, so stray asterisks are shown as typed.
+fn parse_inline(text: &str, base: Style, theme: &AppTheme) -> Vec<(String, Style)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut runs: Vec<(String, Style)> = Vec::new();
+    let mut current = String::new();
+    let mut bold = false;
+    let mut italic = false;
+
+    let closes_later = |from: usize, marker: &[char]| {
+        (from..chars.len()).any(|j| chars[j..].starts_with(marker))
+    };
+    let style_for = |bold: bool, italic: bool| {
+        let mut style = base;
+        if bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        style
+    };
+    let flush = |runs: &mut Vec<(String, Style)>, current: &mut String, style: Style| {
+        if !current.is_empty() {
+            runs.push((std::mem::take(current), style));
+        }
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '`' {
+            if let Some(len) = chars[i + 1..].iter().position(|&ch| ch == '`') {
+                flush(&mut runs, &mut current, style_for(bold, italic));
+                runs.push((chars[i + 1..i + 1 + len].iter().collect(), theme.chat_inline_code));
+                i += len + 2;
+                continue;
+            }
+        }
+        let double = (c == '*' || c == '_') && chars.get(i + 1) == Some(&c);
+        if double && (bold || closes_later(i + 2, &[c, c])) {
+            flush(&mut runs, &mut current, style_for(bold, italic));
+            bold = !bold;
+            i += 2;
+            continue;
+        }
+        if c == '*' || c == '_' {
+            // Underscores inside words (snake_case) are literal
+            let prev_is_word = i > 0 && chars[i - 1].is_alphanumeric();
+            let next = chars.get(i + 1);
+            let opens = !italic
+                && !(c == '_' && prev_is_word)
+                && next.map_or(false, |n| !n.is_whitespace())
+                && closes_later(i + 1, &[c]);
+            let closes = italic && next.map_or(true, |n| !(c == '_' && n.is_alphanumeric()));
+            if opens || closes {
+                flush(&mut runs, &mut current, style_for(bold, italic));
+                italic = !italic;
+                i += 1;
+                continue;
+            }
+        }
+        current.push(c);
+        i += 1;
+    }
+    flush(&mut runs, &mut current, style_for(bold, italic));
+    runs
+}
+
+// Greedy word wrap that keeps each run's style. Words longer than a line are split.
+fn wrap_words(runs: Vec<(String, Style)>, width: usize, first_prefix: &str, rest_prefix: &str) -> Vec<Line<'static>> {
+    // A word is the styled pieces between whitespace, so "**a**b" stays together
+    let mut words: Vec<Vec<(String, Style)>> = Vec::new();
+    let mut word: Vec<(String, Style)> = Vec::new();
+    for (text, style) in runs {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            } else {
+                match word.last_mut() {
+                    Some((piece, piece_style)) if *piece_style == style => piece.push(c),
+                    _ => word.push((c.to_string(), style)),
+                }
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let mut lines = Vec::new();
+    let mut spans: Vec<Span<'static>> = vec![Span::raw(first_prefix.to_string())];
+    let mut line_width = display_width(first_prefix);
+    let mut line_has_words = false;
+    for word in words {
+        let word_width: usize = word.iter().map(|(piece, _)| display_width(piece)).sum();
+        let space = usize::from(line_has_words);
+        if line_has_words && line_width + space + word_width > width {
+            lines.push(Line::from(std::mem::take(&mut spans)));
+            spans.push(Span::raw(rest_prefix.to_string()));
+            line_width = display_width(rest_prefix);
+            line_has_words = false;
+        }
+        if line_has_words {
+            spans.push(Span::raw(" "));
+            line_width += 1;
+        }
+        for (piece, style) in word {
+            let mut chunk = String::new();
+            for c in piece.chars() {
+                let c_width = display_width(c.encode_utf8(&mut [0; 4]));
+                if line_width + c_width > width && (line_has_words || !chunk.is_empty()) {
+                    spans.push(Span::styled(std::mem::take(&mut chunk), style));
+                    lines.push(Line::from(std::mem::take(&mut spans)));
+                    spans.push(Span::raw(rest_prefix.to_string()));
+                    line_width = display_width(rest_prefix);
+                    line_has_words = false;
+                }
+                chunk.push(c);
+                line_width += c_width;
+            }
+            if !chunk.is_empty() {
+                spans.push(Span::styled(chunk, style));
+                line_has_words = true;
+            }
+        }
+    }
+    lines.push(Line::from(spans));
+    lines
+}
+
+// TODO: Add tests for render_markdown:
+// - Headings, bullets, numbered items and block quotes get their prefixes and styles.
+// - Bold/italic/inline code runs, including unclosed markers and snake_case identifiers.
+// - Fenced code is kept verbatim, hard-wrapped and padded to the width.
+// - Wrapping keeps styles and continuation indents, and splits overlong words.
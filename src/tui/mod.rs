@@ -1,6 +1,7 @@
 use anyhow::Result;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
+    cursor::Show,
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -11,15 +12,23 @@ use tracing;
 
 // New module organization
 pub mod app;
+pub mod chat_store;
+pub mod chat_stream;
+pub mod command_palette;
+pub mod daemon;
 pub mod events;
+pub mod markdown;
+pub mod text_input;
 pub mod theme;
 pub mod tracing_layer;
 pub mod ui;
+pub mod ui_state;
 pub mod widgets;
 pub mod view_mode;
 
 // Re-export necessary components
 pub use app::{App, UILogEntry};
+use daemon::DaemonLink;
 use events::run_app_loop;
 
 // Import necessary concrete types for the function signature
@@ -30,6 +39,7 @@ use crate::{
     ollama_manager::OllamaManager,
     policy::PolicyEngine,
     session_manager::SessionManager,
+    ssh_manager::SshManager,
 };
 #[cfg(feature = "bedrock_integration")]
 use crate::bedrock_manager::BedrockManager;
@@ -46,13 +56,20 @@ pub async fn run_tui(
     policy_engine: Arc<PolicyEngine>,
     libvirt_manager: Arc<Mutex<LibvirtManager>>,
     audit_engine: Arc<AuditEngine>,
+    ssh_manager: Arc<SshManager>,
     ollama_manager: Arc<Mutex<OllamaManager>>,
+    read_only: bool,
+    daemon: Option<DaemonLink>, // Set by `hydravisor attach`: VM state comes from this daemon
     log_receiver: mpsc::UnboundedReceiver<UILogEntry>,
 ) -> Result<()> {
     // setup terminal
+    install_panic_hook();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if config.interface.mouse_capture {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -63,7 +80,10 @@ pub async fn run_tui(
         policy_engine,
         libvirt_manager,
         audit_engine,
+        ssh_manager,
         ollama_manager,
+        read_only,
+        daemon,
         log_receiver,
     );
 
@@ -71,13 +91,7 @@ pub async fn run_tui(
     let res = run_app_loop(&mut terminal, app).await;
 
     // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    restore_terminal()?;
 
     if let Err(err) = res {
         // Use tracing::error for consistency, as this will also be captured by the TUI log collector
@@ -94,14 +108,21 @@ pub async fn run_tui(
     policy_engine: Arc<PolicyEngine>,
     libvirt_manager: Arc<Mutex<LibvirtManager>>,
     audit_engine: Arc<AuditEngine>,
+    ssh_manager: Arc<SshManager>,
     ollama_manager: Arc<Mutex<OllamaManager>>,
     bedrock_manager: Arc<Mutex<BedrockManager>>,
+    read_only: bool,
+    daemon: Option<DaemonLink>, // Set by `hydravisor attach`: VM state comes from this daemon
     log_receiver: mpsc::UnboundedReceiver<UILogEntry>,
 ) -> Result<()> {
     // setup terminal
+    install_panic_hook();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if config.interface.mouse_capture {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -112,8 +133,11 @@ pub async fn run_tui(
         policy_engine,
         libvirt_manager,
         audit_engine,
+        ssh_manager,
         ollama_manager,
         bedrock_manager,
+        read_only,
+        daemon,
         log_receiver,
     );
 
@@ -121,13 +145,7 @@ pub async fn run_tui(
     let res = run_app_loop(&mut terminal, app).await;
 
     // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    restore_terminal()?;
 
     if let Err(err) = res {
         // Use tracing::error for consistency, as this will also be captured by the TUI log collector
@@ -135,4 +153,21 @@ pub async fn run_tui(
     }
 
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Leaves raw mode and the alternate screen and shows the cursor again.
+fn restore_terminal() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    Ok(())
+}
+
+// A panic inside the TUI would otherwise leave the shell in raw mode with the message hidden
+// on the alternate screen, so the terminal is restored before the default hook prints it.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+}
@@ -0,0 +1,155 @@
+// src/tui/text_input.rs
+
+/// An editable line of text with its own cursor, scroll offset, and submission history.
+///
+/// Each editing context (chat input, system prompt editing, ...) owns one of these so
+/// switching between them never bleeds state from one into another.
+#[derive(Clone, Debug, Default)]
+pub struct TextInput {
+    buffer: String,
+    cursor_char_idx: usize, // Cursor position in characters, not bytes
+    pub scroll: u16, // Scroll offset of the wrapped text in the input bar
+    pub last_wrapped_line_count: usize, // For clamping scroll
+    pub cursor_needs_to_be_visible: bool, // Flag for auto-scroll logic
+    history: Vec<String>,
+    history_idx: Option<usize>, // Position while browsing history, None when editing a fresh entry
+    draft_before_history: String, // The unsubmitted text to restore after browsing history
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self {
+            cursor_needs_to_be_visible: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.buffer
+    }
+
+    pub fn cursor_char_idx(&self) -> usize {
+        self.cursor_char_idx
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Replaces the contents and moves the cursor to the end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.buffer = text.into();
+        self.cursor_char_idx = self.buffer.chars().count();
+        self.scroll = 0;
+        self.history_idx = None;
+        self.cursor_needs_to_be_visible = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text(String::new());
+    }
+
+    /// Clears the input and returns what it held, without recording it in history.
+    pub fn take(&mut self) -> String {
+        let text = std::mem::take(&mut self.buffer);
+        self.clear();
+        text
+    }
+
+    /// Clears the input and returns what it held, recording non-empty entries in history.
+    pub fn submit(&mut self) -> String {
+        let text = self.take();
+        if !text.trim().is_empty() && self.history.last() != Some(&text) {
+            self.history.push(text.clone());
+        }
+        self.draft_before_history.clear();
+        text
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let byte_idx = self.byte_idx(self.cursor_char_idx);
+        self.buffer.insert(byte_idx, c);
+        self.cursor_char_idx += 1;
+        self.cursor_needs_to_be_visible = true;
+    }
+
+    /// Deletes the character before the cursor.
+    pub fn backspace(&mut self) {
+        if self.cursor_char_idx > 0 {
+            self.cursor_char_idx -= 1;
+            let byte_idx = self.byte_idx(self.cursor_char_idx);
+            self.buffer.remove(byte_idx);
+            self.cursor_needs_to_be_visible = true;
+        }
+    }
+
+    /// Deletes the character under the cursor.
+    pub fn delete(&mut self) {
+        if self.cursor_char_idx < self.buffer.chars().count() {
+            let byte_idx = self.byte_idx(self.cursor_char_idx);
+            self.buffer.remove(byte_idx);
+            self.cursor_needs_to_be_visible = true;
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor_char_idx = self.cursor_char_idx.saturating_sub(1);
+        self.cursor_needs_to_be_visible = true;
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor_char_idx = (self.cursor_char_idx + 1).min(self.buffer.chars().count());
+        self.cursor_needs_to_be_visible = true;
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor_char_idx = 0;
+        self.cursor_needs_to_be_visible = true;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor_char_idx = self.buffer.chars().count();
+        self.cursor_needs_to_be_visible = true;
+    }
+
+    /// Steps back to the previous history entry, stashing the current text the first time.
+    pub fn history_previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let idx = match self.history_idx {
+            Some(0) => return,
+            Some(idx) => idx - 1,
+            None => {
+                self.draft_before_history = self.buffer.clone();
+                self.history.len() - 1
+            }
+        };
+        self.show_history_entry(Some(idx));
+    }
+
+    /// Steps forward through history, restoring the stashed text after the newest entry.
+    pub fn history_next(&mut self) {
+        match self.history_idx {
+            Some(idx) if idx + 1 < self.history.len() => self.show_history_entry(Some(idx + 1)),
+            Some(_) => self.show_history_entry(None),
+            None => {}
+        }
+    }
+
+    fn show_history_entry(&mut self, idx: Option<usize>) {
+        let text = match idx {
+            Some(idx) => self.history[idx].clone(),
+            None => std::mem::take(&mut self.draft_before_history),
+        };
+        self.set_text(text);
+        self.history_idx = idx;
+    }
+
+    fn byte_idx(&self, char_idx: usize) -> usize {
+        self.buffer
+            .char_indices()
+            .nth(char_idx)
+            .map_or(self.buffer.len(), |(byte_idx, _)| byte_idx)
+    }
+}
@@ -1,78 +1,153 @@
-use ratatui::style::{Color, Style, Stylize}; 
+use anyhow::{Context, Result};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::{Deserialize, Deserializer};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::info;
 
-#[derive(Clone, Debug)]
+// The TUI palette. theme.toml may override any subset of fields; the rest keep their defaults.
+// Colors are named ("dark-gray"), indexed ("244"), or hex ("#1e1e1e"); styles are tables like
+// `{ fg = "#ffffff", bg = "blue", bold = true }`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct AppTheme {
+    #[serde(deserialize_with = "deserialize_color")]
     pub primary_background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub secondary_background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub tertiary_background: Color,
 
+    #[serde(deserialize_with = "deserialize_color")]
     pub primary_foreground: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub secondary_foreground: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub tertiary_foreground: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub quaternary_foreground: Color,
     
+    #[serde(deserialize_with = "deserialize_color")]
     pub border_primary: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub border_secondary: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub border_accent: Color,
 
+    #[serde(deserialize_with = "deserialize_color")]
     pub success_text: Color,
 
+    #[serde(deserialize_with = "deserialize_color")]
     pub list_highlight_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub list_highlight_fg: Color,
 
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_foreground: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_view_name_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_normal_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_editing_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_vm_wizard_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_confirm_destroy_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_normal_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_editing_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_vm_wizard_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_mode_confirm_destroy_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_view_vm_list_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_view_ollama_model_list_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_view_bedrock_model_list_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_view_chat_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub status_bar_view_logs_fg: Color,
 
+    #[serde(deserialize_with = "deserialize_style")]
     pub input_bar_title: Style,
 
+    #[serde(deserialize_with = "deserialize_style")]
     pub vm_list_name_active: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub vm_list_name_inactive: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub vm_list_status_running: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub vm_list_status_stopped: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub vm_list_status_other: Style,
 
+    #[serde(deserialize_with = "deserialize_style")]
     pub ollama_model_list_name: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub ollama_model_list_details_title: Style,
 
+    #[serde(deserialize_with = "deserialize_style")]
     pub chat_user_message_name: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub chat_model_message_name: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub chat_system_message_name: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub chat_heading: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub chat_code_block: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub chat_inline_code: Style,
 
+    #[serde(deserialize_with = "deserialize_style")]
     pub log_level_trace: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub log_level_debug: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub log_level_info: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub log_level_warn: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub log_level_error: Style,
 
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_background: Color,
+    #[serde(deserialize_with = "deserialize_style")]
     pub popup_title: Style,
+    #[serde(deserialize_with = "deserialize_style")]
     pub popup_text: Style,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_input_bg_active: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_input_fg_active: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_input_border_active: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_input_bg_inactive: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_input_fg_inactive: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_input_border_inactive: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_button_bg_active: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_button_fg_active: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_button_bg_inactive: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub popup_button_fg_inactive: Color,
 
+    #[serde(deserialize_with = "deserialize_color")]
     pub error_text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
     pub help_text: Color,
 }
 
@@ -131,6 +206,9 @@ impl Default for AppTheme {
             chat_model_message_name: Style::default().fg(Color::Rgb(150, 150, 255)).bold(),
             // chat_system_message_name: Style::default().fg(Color::Rgb(255, 120, 120)).bold(),
             chat_system_message_name: Style::default().fg(Color::Rgb(255, 120, 120)).bold(),
+            chat_heading: Style::default().fg(Color::Rgb(255, 220, 0)).bold(),
+            chat_code_block: Style::default().fg(Color::Rgb(200, 200, 200)).bg(Color::Rgb(40, 40, 40)),
+            chat_inline_code: Style::default().fg(Color::Rgb(230, 160, 90)).bg(Color::Rgb(40, 40, 40)),
 
             log_level_trace: Style::default().fg(Color::Rgb(120, 120, 120)),
             log_level_debug: Style::default().fg(Color::Rgb(150, 150, 255)),
@@ -156,4 +234,141 @@ impl Default for AppTheme {
             help_text: Color::Rgb(120, 120, 120),
         }
     }
-} 
\ No newline at end of file
+} 
+
+// Presets compiled into the binary; the theme picker lists them before any user themes
+const BUILTIN_THEMES: &[(&str, &str)] = &[
+    ("dark", include_str!("themes/dark.toml")),
+    ("light", include_str!("themes/light.toml")),
+    ("high-contrast", include_str!("themes/high-contrast.toml")),
+];
+
+/// A theme the picker can apply: a bundled preset, or a `.toml` palette in the themes directory.
+#[derive(Clone, Debug)]
+pub struct NamedTheme {
+    pub name: String,
+    pub path: Option<PathBuf>, // None for bundled presets
+}
+
+impl NamedTheme {
+    /// Reads the palette. User themes are re-read from disk each time, so reapplying one picks up edits.
+    pub fn load(&self) -> Result<AppTheme> {
+        match &self.path {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read theme file at {:?}", path))?;
+                toml::from_str(&content).with_context(|| format!("Failed to parse theme file at {:?}", path))
+            }
+            None => {
+                let (_, content) = BUILTIN_THEMES.iter()
+                    .find(|(name, _)| *name == self.name)
+                    .with_context(|| format!("No built-in theme named '{}'", self.name))?;
+                toml::from_str(content).with_context(|| format!("Failed to parse built-in theme '{}'", self.name))
+            }
+        }
+    }
+}
+
+/// The bundled presets followed by `<themes_dir>/*.toml`, named after their file stems.
+/// A user theme named like a preset replaces it.
+pub fn available_themes(themes_dir: Option<&Path>) -> Vec<NamedTheme> {
+    let mut themes: Vec<NamedTheme> = BUILTIN_THEMES.iter()
+        .map(|(name, _)| NamedTheme { name: name.to_string(), path: None })
+        .collect();
+    let Some(entries) = themes_dir.and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return themes;
+    };
+    let mut user_themes: Vec<NamedTheme> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some(NamedTheme { name, path: Some(path) })
+        })
+        .collect();
+    user_themes.sort_by(|a, b| a.name.cmp(&b.name));
+    for theme in user_themes {
+        match themes.iter_mut().find(|existing| existing.name == theme.name) {
+            Some(existing) => *existing = theme,
+            None => themes.push(theme),
+        }
+    }
+    themes
+}
+
+impl AppTheme {
+    /// Loads the palette the TUI starts with: the theme named by `[interface] theme` if set,
+    /// otherwise theme.toml.
+    pub fn load_configured(theme_name: Option<&str>, themes_dir: Option<&Path>, theme_file: Option<&Path>) -> Result<Self> {
+        let Some(theme_name) = theme_name else {
+            return Self::load(theme_file);
+        };
+        let theme = available_themes(themes_dir)
+            .into_iter()
+            .find(|theme| theme.name == theme_name)
+            .with_context(|| format!("No theme named '{}'", theme_name))?
+            .load()?;
+        info!("Loaded theme '{}'", theme_name);
+        Ok(theme)
+    }
+
+    /// Loads theme.toml, keeping the built-in palette when the file is absent.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) if path.exists() => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read theme file at {:?}", path))?;
+                let theme = toml::from_str(&content)
+                    .with_context(|| format!("Failed to parse theme file at {:?}", path))?;
+                info!("Loaded theme from {}", path.display());
+                Ok(theme)
+            }
+            _ => Ok(AppTheme::default()),
+        }
+    }
+}
+
+fn parse_color<E: serde::de::Error>(value: &str) -> Result<Color, E> {
+    Color::from_str(value).map_err(|_| E::custom(format!("invalid color '{}': expected a name, an index, or #rrggbb", value)))
+}
+
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    parse_color(&String::deserialize(deserializer)?)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleSpec {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    underlined: bool,
+    #[serde(default)]
+    dim: bool,
+}
+
+fn deserialize_style<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Style, D::Error> {
+    let spec = StyleSpec::deserialize(deserializer)?;
+    let mut style = Style::default();
+    if let Some(fg) = &spec.fg {
+        style = style.fg(parse_color::<D::Error>(fg)?);
+    }
+    if let Some(bg) = &spec.bg {
+        style = style.bg(parse_color::<D::Error>(bg)?);
+    }
+    for (enabled, modifier) in [
+        (spec.bold, Modifier::BOLD),
+        (spec.italic, Modifier::ITALIC),
+        (spec.underlined, Modifier::UNDERLINED),
+        (spec.dim, Modifier::DIM),
+    ] {
+        if enabled {
+            style = style.add_modifier(modifier);
+        }
+    }
+    Ok(style)
+}
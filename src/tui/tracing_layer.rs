@@ -10,18 +10,20 @@ struct LogEntryVisitor {
     level: Level,
     target: String,
     message: Option<String>,
-    // Potentially: file: Option<String>, line: Option<u32>
+    // Source location; events forwarded from the `log` crate carry it as fields instead of metadata
+    file: Option<String>,
+    line: Option<u32>,
 }
 
 impl LogEntryVisitor {
-    fn new(level: Level, target: String) -> Self {
+    fn new(level: Level, target: String, file: Option<String>, line: Option<u32>) -> Self {
         Self {
             timestamp: Local::now().format("%H:%M:%S").to_string(),
             level,
             target,
             message: None,
-            // file: None,
-            // line: None,
+            file,
+            line,
         }
     }
 }
@@ -31,22 +33,20 @@ impl tracing::field::Visit for LogEntryVisitor {
         if field.name() == "message" {
             self.message = Some(format!("{:?}", value));
         }
-        // Potentially extract file and line here if they are passed as fields
-        // Example:
-        // if field.name() == "log.file" {
-        //     self.file = Some(format!("{:?}", value));
-        // }
-        // if field.name() == "log.line" {
-        //     if let Ok(line_val) = format!("{:?}", value).parse::<u32>() {
-        //         self.line = Some(line_val);
-        //     }
-        // }
     }
 
     // Add other record_ methods if needed for different field types (e.g., record_str)
     fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
-        if field.name() == "message" {
-            self.message = Some(value.to_string());
+        match field.name() {
+            "message" => self.message = Some(value.to_string()),
+            "log.file" => self.file = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        if field.name() == "log.line" {
+            self.line = u32::try_from(value).ok();
         }
     }
 }
@@ -67,7 +67,7 @@ impl<S: Subscriber> Layer<S> for TuiLogCollectorLayer {
         let level = *meta.level();
         let target = meta.target().to_string();
 
-        let mut visitor = LogEntryVisitor::new(level, target.clone());
+        let mut visitor = LogEntryVisitor::new(level, target.clone(), meta.file().map(str::to_string), meta.line());
         event.record(&mut visitor);
 
         if let Some(message) = visitor.message {
@@ -76,8 +76,8 @@ impl<S: Subscriber> Layer<S> for TuiLogCollectorLayer {
                 level: visitor.level,
                 target: visitor.target, // Use the cloned target from visitor
                 message,
-                // file: visitor.file,
-                // line: visitor.line,
+                file: visitor.file,
+                line: visitor.line,
             };
 
             // Send to TUI. If the receiver is dropped, this will fail silently.
@@ -7,19 +7,25 @@ use super::app::App;
 use super::widgets::{
     about_modal::AboutModalWidget,
     chat::ChatWidget,
+    confirm_vm_action_popup::ConfirmVmActionPopupWidget,
     input_bar::InputBarWidget,
     logs::LogsWidget,
     menu::MenuWidget,
     new_vm_popup::NewVmPopupWidget,
     ollama_model_list::OllamaModelListWidget,
     status_bar::StatusBarWidget,
+    toasts::ToastsWidget,
     vm_list::VmListWidget,
+    vm_preview_popup::VmPreviewPopupWidget,
 };
 #[cfg(feature = "bedrock_integration")]
 use super::widgets::bedrock_model_list::BedrockModelListWidget;
+#[cfg(feature = "openai_compat")]
+use super::widgets::openai_model_list::OpenAiModelListWidget;
 use super::app::AppView;
 
 pub fn ui(f: &mut Frame, app: &mut App) {
+    app.list_hit_area = None; // Set again by whichever list this frame draws
     // The main layout defines a status bar at the top, content in the middle,
     // and an input bar at the bottom.
     let input_bar_height = InputBarWidget::calculate_height(app, f.size().width);
@@ -48,6 +54,10 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         AppView::BedrockModelList => {
             BedrockModelListWidget::render(f, app, main_content_area);
         }
+        #[cfg(feature = "openai_compat")]
+        AppView::OpenAiModelList => {
+            OpenAiModelListWidget::render(f, app, main_content_area);
+        }
         AppView::Chat => {
             ChatWidget::render(f, app, main_content_area);
         }
@@ -62,9 +72,31 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     if app.show_new_vm_popup {
         NewVmPopupWidget::render(f, app, f.size());
     }
+    if app.new_vm_preview.is_some() {
+        VmPreviewPopupWidget::render(f, app, f.size());
+    }
+    if app.pending_vm_action.is_some() {
+        ConfirmVmActionPopupWidget::render(f, app, f.size());
+    }
+    if app.pending_model_delete.is_some() {
+        use super::widgets::confirm_model_delete_popup::ConfirmModelDeletePopupWidget;
+        ConfirmModelDeletePopupWidget::render(f, app, f.size());
+    }
+    if app.log_detail.is_some() {
+        use super::widgets::log_detail_popup::LogDetailPopupWidget;
+        LogDetailPopupWidget::render(f, app, f.size());
+    }
+    if app.vm_detail.is_some() {
+        use super::widgets::vm_detail_popup::VmDetailPopupWidget;
+        VmDetailPopupWidget::render(f, app, f.size());
+    }
     if app.show_about_modal {
         AboutModalWidget::render(f, app, f.size());
     }
+    if app.show_chat_picker {
+        use super::widgets::chat_picker_popup::ChatPickerPopupWidget;
+        ChatPickerPopupWidget::render(f, app, f.size());
+    }
     if app.show_keybindings_modal {
         use super::widgets::keybindings_modal::KeybindingsModalWidget;
         KeybindingsModalWidget::render(f, app, f.size());
@@ -74,4 +106,11 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     if app.show_menu {
         MenuWidget::render(f, app, f.size());
     }
+    if app.show_command_palette {
+        use super::widgets::command_palette_popup::CommandPalettePopupWidget;
+        CommandPalettePopupWidget::render(f, app, f.size());
+    }
+
+    // Toasts sit above the content and popups, below the status bar
+    ToastsWidget::render(f, app, main_content_area);
 } 
\ No newline at end of file
@@ -0,0 +1,69 @@
+// src/tui/ui_state.rs
+// Remembers where the TUI was left (the active view, each list's selected item and the last chat
+// model) in the XDG state dir, so the next launch opens in the same place.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use xdg::BaseDirectories;
+
+use crate::config::{APP_NAME, PROFILES_DIR_NAME};
+use super::app::ChatProvider;
+
+const UI_STATE_FILE_NAME: &str = "ui_state.json";
+
+// Everything is optional, so a file from an older build (or one with other features) still loads
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct UiState {
+    pub active_view: Option<String>,        // AppView::id()
+    pub selected_items: HashMap<String, String>, // Name or id of each list's selected item, by AppView::id()
+    pub last_chat: Option<LastChat>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LastChat {
+    pub provider: ChatProvider,
+    pub model_name: String,
+}
+
+impl UiState {
+    /// `$XDG_STATE_HOME/hydravisor/ui_state.json`, namespaced by profile.
+    fn path(profile: Option<&str>) -> Result<PathBuf> {
+        let state_home = BaseDirectories::with_prefix(APP_NAME)?.get_state_home();
+        Ok(match profile {
+            Some(profile) => state_home.join(PROFILES_DIR_NAME).join(profile).join(UI_STATE_FILE_NAME),
+            None => state_home.join(UI_STATE_FILE_NAME),
+        })
+    }
+
+    /// The state saved by the last run. A missing or unreadable file gives the defaults.
+    pub fn load(profile: Option<&str>) -> Self {
+        let Ok(path) = Self::path(profile) else {
+            return UiState::default();
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return UiState::default(); // First run
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable UI state file {:?}: {}", path, e);
+            UiState::default()
+        })
+    }
+
+    pub fn save(&self, profile: Option<&str>) -> Result<PathBuf> {
+        let path = Self::path(profile)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create UI state directory {:?}", dir))?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write UI state file {:?}", path))?;
+        Ok(path)
+    }
+}
+
+// TODO: Add tests for UiState:
+// - save/load round-trip, and load() on a missing or corrupt file.
+// - Unknown view ids and fields from other builds are tolerated.
@@ -1,10 +1,12 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    layout::Rect,
     style::{Style},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, ListState, Paragraph},
     Frame,
 };
 use crate::tui::App;
+use crate::tui::markdown::render_markdown;
+use super::{centered_rect_with_height, render_list_scrollbar};
 
 pub struct AboutModalWidget;
 
@@ -15,44 +17,34 @@ impl AboutModalWidget {
         }
 
         let theme = &app.theme;
+        // The configured line count sets the modal's height; the README scrolls within it
+        let height = (app.config.interface.about_modal_readme_lines.max(1) as u16).saturating_add(2);
+        let popup_area = centered_rect_with_height(60, height, area);
         let block = Block::default()
-            .title("About Hydravisor")
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.border_accent))
             .style(Style::default().bg(theme.popup_background));
-        
-        let popup_area = centered_rect(60, 50, area);
-        f.render_widget(Clear, popup_area); //this clears the background
-        f.render_widget(block.clone(), popup_area);
+        let inner_area = block.inner(popup_area);
 
-        let about_text = app.readme_content.clone();
+        // One column is kept free for the scrollbar
+        let content_width = inner_area.width.saturating_sub(1) as usize;
+        let lines = render_markdown(&app.readme_content, content_width, Style::default().fg(theme.primary_foreground), theme);
+        let visible = inner_area.height as usize;
+        let max_scroll = lines.len().saturating_sub(visible);
+        let scroll = app.about_scroll.min(max_scroll);
+        let overflows = max_scroll > 0;
+        let title = if overflows {
+            format!("About Hydravisor ({}-{} of {})", scroll + 1, (scroll + visible).min(lines.len()), lines.len())
+        } else {
+            "About Hydravisor".to_string()
+        };
 
-        let paragraph = Paragraph::new(about_text)
-            .style(Style::default().fg(theme.primary_foreground))
-            .block(Block::default().borders(Borders::NONE))
-            .alignment(Alignment::Left);
-        
-        let inner_area = block.inner(popup_area);
+        f.render_widget(Clear, popup_area); //this clears the background
+        f.render_widget(block.title(title), popup_area);
+        let paragraph = Paragraph::new(lines).scroll((scroll.min(u16::MAX as usize) as u16, 0));
         f.render_widget(paragraph, inner_area);
+        render_list_scrollbar(f, popup_area, theme, &ListState::default().with_offset(scroll), max_scroll + 1, overflows);
+
+        app.about_scroll = scroll;
     }
 }
-
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ].as_ref())
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ].as_ref())
-        .split(popup_layout[1])[1]
-} 
\ No newline at end of file
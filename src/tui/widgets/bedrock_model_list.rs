@@ -9,24 +9,34 @@ use ratatui::{
     Frame,
 };
 use crate::tui::App;
+use crate::tui::app::{AppView, ListHitArea};
 use crate::tui::view_mode::list::{ListViewMode, ListFilter, ListSorter};
 use std::rc::Rc;
 use crate::config::BedrockFiltersConfig;
+use crate::bedrock_manager::ModelAccess;
+use std::collections::HashMap;
 
-struct AvailableToUseFilter;
+fn is_active(item: &aws_sdk_bedrock::types::FoundationModelSummary) -> bool {
+    item.model_lifecycle()
+        .map(|lc| lc.status())
+        .map_or(false, |s| s == &FoundationModelLifecycleStatus::Active)
+}
+// Active models not known to lack access; models never checked are assumed usable
+struct AvailableToUseFilter {
+    access: HashMap<String, ModelAccess>,
+}
 impl ListFilter<aws_sdk_bedrock::types::FoundationModelSummary> for AvailableToUseFilter {
     fn filter(&self, item: &aws_sdk_bedrock::types::FoundationModelSummary) -> bool {
-        item.model_lifecycle()
-            .map(|lc| lc.status())
-            .map_or(false, |s| s == &FoundationModelLifecycleStatus::Active)
+        is_active(item) && self.access.get(item.model_id()).map_or(true, |access| *access == ModelAccess::Granted)
     }
 }
-struct AvailableToRequestAccessFilter;
+// Inactive models, and active ones whose access check found access can be requested
+struct AvailableToRequestAccessFilter {
+    access: HashMap<String, ModelAccess>,
+}
 impl ListFilter<aws_sdk_bedrock::types::FoundationModelSummary> for AvailableToRequestAccessFilter {
     fn filter(&self, item: &aws_sdk_bedrock::types::FoundationModelSummary) -> bool {
-        item.model_lifecycle()
-            .map(|lc| lc.status())
-            .map_or(true, |s| s != &FoundationModelLifecycleStatus::Active)
+        !is_active(item) || self.access.get(item.model_id()) == Some(&ModelAccess::Requestable)
     }
 }
 struct AlphabeticalSorter;
@@ -35,6 +45,61 @@ impl ListSorter<aws_sdk_bedrock::types::FoundationModelSummary> for Alphabetical
         a.model_name().unwrap_or("").cmp(b.model_name().unwrap_or(""))
     }
 }
+// Sorters are applied in order with a stable sort, so the alphabetical pass runs first
+// and breaks ties for the provider, lifecycle and streaming sorts below
+struct ProviderSorter;
+impl ListSorter<aws_sdk_bedrock::types::FoundationModelSummary> for ProviderSorter {
+    fn compare(&self, a: &aws_sdk_bedrock::types::FoundationModelSummary, b: &aws_sdk_bedrock::types::FoundationModelSummary) -> std::cmp::Ordering {
+        a.provider_name().unwrap_or("").cmp(b.provider_name().unwrap_or(""))
+    }
+}
+// Active models before legacy ones; models without lifecycle info go last
+struct LifecycleSorter;
+impl LifecycleSorter {
+    fn rank(item: &aws_sdk_bedrock::types::FoundationModelSummary) -> u8 {
+        match item.model_lifecycle().map(|lc| lc.status()) {
+            Some(FoundationModelLifecycleStatus::Active) => 0,
+            Some(FoundationModelLifecycleStatus::Legacy) => 1,
+            _ => 2,
+        }
+    }
+}
+impl ListSorter<aws_sdk_bedrock::types::FoundationModelSummary> for LifecycleSorter {
+    fn compare(&self, a: &aws_sdk_bedrock::types::FoundationModelSummary, b: &aws_sdk_bedrock::types::FoundationModelSummary) -> std::cmp::Ordering {
+        Self::rank(a).cmp(&Self::rank(b))
+    }
+}
+struct StreamingFirstSorter;
+impl ListSorter<aws_sdk_bedrock::types::FoundationModelSummary> for StreamingFirstSorter {
+    fn compare(&self, a: &aws_sdk_bedrock::types::FoundationModelSummary, b: &aws_sdk_bedrock::types::FoundationModelSummary) -> std::cmp::Ordering {
+        let a_streaming = a.response_streaming_supported().unwrap_or(false);
+        let b_streaming = b.response_streaming_supported().unwrap_or(false);
+        b_streaming.cmp(&a_streaming)
+    }
+}
+
+/// Sort names cycled by the `bedrock_sort` key, in order.
+pub const BEDROCK_SORTS: [&str; 4] = ["alphabetical", "provider", "lifecycle", "streaming_first"];
+
+struct FavoritesOnlyFilter {
+    favorites: Vec<String>,
+}
+impl ListFilter<aws_sdk_bedrock::types::FoundationModelSummary> for FavoritesOnlyFilter {
+    fn filter(&self, item: &aws_sdk_bedrock::types::FoundationModelSummary) -> bool {
+        self.favorites.iter().any(|f| f == item.model_id())
+    }
+}
+// Applied last so favorites float to the top while keeping the active sort within each group
+struct FavoritesFirstSorter {
+    favorites: Vec<String>,
+}
+impl ListSorter<aws_sdk_bedrock::types::FoundationModelSummary> for FavoritesFirstSorter {
+    fn compare(&self, a: &aws_sdk_bedrock::types::FoundationModelSummary, b: &aws_sdk_bedrock::types::FoundationModelSummary) -> std::cmp::Ordering {
+        let a_fav = self.favorites.iter().any(|f| f == a.model_id());
+        let b_fav = self.favorites.iter().any(|f| f == b.model_id());
+        b_fav.cmp(&a_fav)
+    }
+}
 
 pub struct BedrockModelListWidget;
 
@@ -44,15 +109,22 @@ impl BedrockModelListWidget {
         // Set up filters and sorters dynamically
         app.bedrock_model_view_mode.filters.clear();
         match app.current_bedrock_filter.as_str() {
-            "available_to_use" => app.bedrock_model_view_mode.add_filter(Rc::new(AvailableToUseFilter)),
-            "available_to_request_access" => app.bedrock_model_view_mode.add_filter(Rc::new(AvailableToRequestAccessFilter)),
+            "available_to_use" => app.bedrock_model_view_mode.add_filter(Rc::new(AvailableToUseFilter { access: app.bedrock_model_access.clone() })),
+            "available_to_request_access" => app.bedrock_model_view_mode.add_filter(Rc::new(AvailableToRequestAccessFilter { access: app.bedrock_model_access.clone() })),
             _ => {},
         }
+        if app.show_favorites_only {
+            app.bedrock_model_view_mode.add_filter(Rc::new(FavoritesOnlyFilter { favorites: app.config.favorite_models.clone() }));
+        }
         app.bedrock_model_view_mode.sorters.clear();
+        app.bedrock_model_view_mode.add_sorter(Rc::new(AlphabeticalSorter));
         match app.current_bedrock_sort.as_str() {
-            "alphabetical" => app.bedrock_model_view_mode.add_sorter(Rc::new(AlphabeticalSorter)),
+            "provider" => app.bedrock_model_view_mode.add_sorter(Rc::new(ProviderSorter)),
+            "lifecycle" => app.bedrock_model_view_mode.add_sorter(Rc::new(LifecycleSorter)),
+            "streaming_first" => app.bedrock_model_view_mode.add_sorter(Rc::new(StreamingFirstSorter)),
             _ => {},
         }
+        app.bedrock_model_view_mode.add_sorter(Rc::new(FavoritesFirstSorter { favorites: app.config.favorite_models.clone() }));
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -65,6 +137,7 @@ impl BedrockModelListWidget {
         let left_pane_block = Block::default()
             .title(Line::from(vec![
                 Span::styled("Bedrock Models ", Style::default().fg(theme.primary_foreground).bold()),
+                Span::styled(format!("({}, {}) ", app.bedrock_region, app.bedrock_profile), Style::default().fg(theme.tertiary_foreground)),
                 Span::styled(filter_label, Style::default().fg(theme.secondary_foreground)),
                 Span::raw(" | "),
                 Span::styled(sort_label, Style::default().fg(theme.secondary_foreground)),
@@ -78,13 +151,19 @@ impl BedrockModelListWidget {
             .iter()
             .map(|model| {
                 let model_name = model.model_name().unwrap_or("Unknown Model");
-                ListItem::new(Line::from(Span::styled(model_name.to_string(), Style::default().fg(theme.primary_foreground))))
+                let marker = if app.config.is_favorite_model(model.model_id()) { "★ " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(marker, Style::default().fg(theme.quaternary_foreground)),
+                    Span::styled(model_name.to_string(), Style::default().fg(theme.primary_foreground)),
+                ]))
             })
             .collect();
+        let item_count = model_items.len();
         let model_list = List::new(model_items)
             .highlight_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg))
             .highlight_symbol(">> ");
         f.render_stateful_widget(model_list, left_pane_content_area, &mut app.bedrock_model_list_state);
+        app.list_hit_area = Some(ListHitArea::single_line(AppView::BedrockModelList, left_pane_content_area, &app.bedrock_model_list_state, item_count));
         let right_pane_block = Block::default()
             .title(Line::from(Span::styled("Model Details", Style::default().fg(theme.primary_foreground))))
             .borders(Borders::ALL)
@@ -99,10 +178,15 @@ impl BedrockModelListWidget {
                 let customizations = format!("{:?}", model.customizations_supported());
                 let inference_types = format!("{:?}", model.inference_types_supported());
                 let streaming = format!("{}", model.response_streaming_supported().unwrap_or(false));
+                let access = match app.bedrock_model_access.get(model_id) {
+                    Some(access) => access.label().to_string(),
+                    None => format!("not checked (press {})", app.config.keybindings.bedrock.access),
+                };
                 let details_lines = vec![
                     Line::from(vec![Span::styled("Name: ", theme.ollama_model_list_details_title.clone()), Span::raw(model_name)]),
                     Line::from(vec![Span::styled("ID: ", theme.ollama_model_list_details_title.clone()), Span::raw(model_id)]),
                     Line::from(vec![Span::styled("Provider: ", theme.ollama_model_list_details_title.clone()), Span::raw(provider_name)]),
+                    Line::from(vec![Span::styled("Access: ", theme.ollama_model_list_details_title.clone()), Span::raw(access)]),
                     Line::from(vec![Span::styled("Customizations: ", theme.ollama_model_list_details_title.clone()), Span::raw(customizations)]),
                     Line::from(vec![Span::styled("Inference Types: ", theme.ollama_model_list_details_title.clone()), Span::raw(inference_types)]),
                     Line::from(vec![Span::styled("Response Streaming: ", theme.ollama_model_list_details_title.clone()), Span::raw(streaming)]),
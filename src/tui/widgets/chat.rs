@@ -8,6 +8,8 @@ use ratatui::{
     Frame,
 };
 use crate::tui::App;
+use crate::tui::markdown::render_markdown;
+use super::render_list_scrollbar;
 use textwrap;
 
 pub struct ChatWidget;
@@ -33,12 +35,42 @@ impl ChatWidget {
         let left_pane_content_area = left_pane_block.inner(chunks[0]);
         f.render_widget(left_pane_block, chunks[0]);
 
-        let chat_info_display_text = if let Some(chat_session) = &app.active_chat {
-            let info_lines = vec![
+        let chat_info_display_text = if let Some(chat_session) = app.active_chat() {
+            let mut info_lines = vec![
                 Line::from(vec![Span::styled("Model: ", Style::default().fg(theme.secondary_foreground)), Span::styled(&chat_session.model_name, Style::default().fg(theme.tertiary_foreground).bold())]),
                 Line::from(vec![Span::styled("Messages: ", Style::default().fg(theme.secondary_foreground)), Span::styled(chat_session.messages.len().to_string(), Style::default().fg(theme.tertiary_foreground))]),
                 Line::from(vec![Span::styled("Streaming: ", Style::default().fg(theme.secondary_foreground)), Span::styled(if chat_session.is_streaming { "Yes" } else { "No" }, Style::default().fg(theme.tertiary_foreground))]),
             ];
+            // Timing of the latest reply, live while it streams
+            if let Some(stats) = &chat_session.stream_stats {
+                let first_token = stats.time_to_first_token()
+                    .map(|d| format!(" (first token {:.1}s)", d.as_secs_f64()))
+                    .unwrap_or_default();
+                let speed = stats.tokens_per_second()
+                    .map(|rate| format!("~{:.1} tok/s", rate))
+                    .unwrap_or_else(|| "-".to_string());
+                info_lines.extend([
+                    Line::from(vec![Span::styled("Elapsed: ", Style::default().fg(theme.secondary_foreground)), Span::styled(format!("{:.1}s{}", stats.elapsed().as_secs_f64(), first_token), Style::default().fg(theme.tertiary_foreground))]),
+                    Line::from(vec![Span::styled("Speed: ", Style::default().fg(theme.secondary_foreground)), Span::styled(format!("{} ({} tokens)", speed, stats.tokens), Style::default().fg(theme.tertiary_foreground))]),
+                ]);
+            }
+            // Every open conversation; the number switches to it
+            info_lines.push(Line::from(""));
+            info_lines.push(Line::from(Span::styled("Open chats:", Style::default().fg(theme.secondary_foreground))));
+            for (idx, chat) in app.chats.iter().enumerate() {
+                let style = if idx == app.active_chat_idx {
+                    Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg)
+                } else {
+                    Style::default().fg(theme.tertiary_foreground)
+                };
+                let number = if idx < 9 { format!("{}.", idx + 1) } else { "  ".to_string() };
+                let user_messages = chat.messages.iter().filter(|m| m.sender == "user").count();
+                let status = if chat.is_streaming { ", streaming".to_string() } else { String::new() };
+                info_lines.push(Line::from(Span::styled(
+                    format!("{} {} ({} sent{})", number, chat.model_name, user_messages, status),
+                    style,
+                )));
+            }
             Text::from(info_lines)
         } else {
             Text::from(Line::from(Span::styled("No active chat. Select model and press <Enter>.", Style::default().fg(theme.secondary_foreground))))
@@ -46,7 +78,7 @@ impl ChatWidget {
         f.render_widget(Paragraph::new(chat_info_display_text).wrap(ratatui::widgets::Wrap { trim: true }), left_pane_content_area);
 
         // Right Pane: Chat Messages
-        let right_pane_title_str = if let Some(chat) = &app.active_chat {
+        let right_pane_title_str = if let Some(chat) = app.active_chat() {
             format!("Chat with {} ({})", chat.model_name, if chat.is_streaming {"streaming..."} else {"idle"})
         } else {
             "Chat Area".to_string()
@@ -60,7 +92,7 @@ impl ChatWidget {
 
         let content_width = messages_area.width.saturating_sub(2) as usize;
 
-        if let Some(chat_session) = &mut app.active_chat {
+        if let Some(chat_session) = app.chats.get(app.active_chat_idx) {
             let message_items: Vec<ListItem> = chat_session.messages.iter().enumerate().map(|(idx, msg)| {
                 let sender_style = if msg.sender == "user" {
                     theme.chat_user_message_name.clone()
@@ -88,15 +120,21 @@ impl ChatWidget {
                     Line::from(Span::styled(formatted_timestamp_str, Style::default().fg(theme.secondary_foreground))),
                 ];
 
-                // Render thought if present
+                // Render thought if present; collapsed to a summary line unless expanded
                 if let Some(thought_text) = &msg.thought {
                     if !thought_text.is_empty() {
                         lines_for_list_item.push(Line::from("")); // Add a blank line before thought
-                        let wrapped_thought: Vec<Line> = textwrap::fill(thought_text, content_width)
-                            .lines()
-                            .map(|line_str| Line::from(Span::styled(line_str.to_string(), Style::default().fg(theme.secondary_foreground).italic())))
-                            .collect();
-                        lines_for_list_item.extend(wrapped_thought);
+                        let thought_style = Style::default().fg(theme.secondary_foreground).italic();
+                        let wrapped_thought = textwrap::fill(thought_text, content_width);
+                        if msg.thought_expanded {
+                            lines_for_list_item.extend(wrapped_thought.lines().map(|line_str| Line::from(Span::styled(line_str.to_string(), thought_style))));
+                        } else {
+                            let line_count = wrapped_thought.lines().count();
+                            lines_for_list_item.push(Line::from(Span::styled(
+                                format!("[thinking… {} line{}]", line_count, if line_count == 1 { "" } else { "s" }),
+                                thought_style,
+                            )));
+                        }
                     }
                 }
                 
@@ -121,11 +159,16 @@ impl ChatWidget {
                     };
                     // ---- END REVERT DIAGNOSTIC FOR TEXT ----
 
-                    let wrapped_content_lines: Vec<Line> = textwrap::fill(&current_content_str, content_width)
-                        .lines()
-                        .map(|line_str| Line::from(Span::styled(line_str.to_string(), final_text_style)))
-                        .collect();
-                    lines_for_list_item.extend(wrapped_content_lines);
+                    // Model replies are markdown; user input is shown as typed
+                    if msg.sender == "user" {
+                        let wrapped_content_lines: Vec<Line> = textwrap::fill(&current_content_str, content_width)
+                            .lines()
+                            .map(|line_str| Line::from(Span::styled(line_str.to_string(), final_text_style)))
+                            .collect();
+                        lines_for_list_item.extend(wrapped_content_lines);
+                    } else {
+                        lines_for_list_item.extend(render_markdown(&current_content_str, content_width, final_text_style, theme));
+                    }
                 } else if current_content_str.is_empty() && msg.thought.is_some() && !msg.thought.as_ref().unwrap_or(&String::new()).is_empty() {
                     // If only thought exists and content is empty (e.g. after extraction)
                     // lines_for_list_item.push(Line::from(Span::styled("(Thought processed, no further output)", Style::default().fg(Color::DarkGray))));
@@ -137,6 +180,9 @@ impl ChatWidget {
                 ListItem::new(Text::from(lines_for_list_item))
             }).collect();
             
+            let item_count = message_items.len();
+            let overflows = message_items.iter().map(|item| item.height()).sum::<usize>() > messages_area.height as usize;
+
             // ---- START REVERT DIAGNOSTIC FOR LIST ----
             let chat_list = List::new(message_items)
                 .style(Style::default()) // Revert list background to default/transparent
@@ -145,6 +191,7 @@ impl ChatWidget {
                 .highlight_symbol("> ");
 
             f.render_stateful_widget(chat_list, messages_area, &mut app.chat_list_state);
+            render_list_scrollbar(f, chunks[1], &app.theme, &app.chat_list_state, item_count, overflows);
         } else {
             f.render_widget(Paragraph::new("No active chat. Select a model and press Enter.").style(Style::default().fg(theme.secondary_foreground)).wrap(ratatui::widgets::Wrap { trim: true }), messages_area);
         }
@@ -0,0 +1,52 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+use crate::tui::app::App;
+use super::centered_rect;
+
+pub struct ChatPickerPopupWidget;
+
+impl ChatPickerPopupWidget {
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+        let theme = &app.theme;
+
+        let block = Block::default()
+            .title("Load Conversation (Enter: Resume, Esc: Close)")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_accent))
+            .style(Style::default().bg(theme.popup_background))
+            .title_alignment(Alignment::Center);
+
+        let popup_area = centered_rect(60, 60, area);
+        f.render_widget(Clear, popup_area);
+
+        if app.saved_chats.is_empty() {
+            let placeholder = Paragraph::new("No saved conversations yet.")
+                .block(block)
+                .style(Style::default().fg(theme.secondary_foreground));
+            f.render_widget(placeholder, popup_area);
+            return;
+        }
+
+        let items: Vec<ListItem> = app.saved_chats.iter().map(|chat| {
+            let modified = chat.modified.as_deref()
+                .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(chat.model_name.clone(), theme.ollama_model_list_name.add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {} messages  {}", chat.message_count, modified), Style::default().fg(theme.secondary_foreground)),
+            ]))
+        }).collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg))
+            .highlight_symbol("> ");
+        f.render_stateful_widget(list, popup_area, &mut app.chat_picker_state);
+    }
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+use crate::tui::app::App;
+use crate::tui::command_palette::format_keybinding;
+use super::centered_rect;
+
+pub struct CommandPalettePopupWidget;
+
+impl CommandPalettePopupWidget {
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+        let theme = &app.theme;
+
+        let block = Block::default()
+            .title("Command Palette (Enter: Run, Esc: Close)")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_accent))
+            .style(Style::default().bg(theme.popup_background))
+            .title_alignment(Alignment::Center);
+
+        let popup_area = centered_rect(50, 50, area);
+        f.render_widget(Clear, popup_area);
+        let inner_area = block.inner(popup_area);
+        f.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Filter
+                Constraint::Length(1), // Spacer
+                Constraint::Min(1),    // Matches
+            ])
+            .split(inner_area);
+
+        let query = Paragraph::new(Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.border_accent).add_modifier(Modifier::BOLD)),
+            Span::styled(app.command_palette_query.clone(), Style::default().fg(theme.primary_foreground)),
+        ]));
+        f.render_widget(query, chunks[0]);
+        f.set_cursor(chunks[0].x + 2 + app.command_palette_query.chars().count() as u16, chunks[0].y);
+
+        let width = chunks[2].width.saturating_sub(2) as usize; // Less the highlight symbol
+        let items: Vec<ListItem> = app.command_palette_matches()
+            .into_iter()
+            .map(|action| {
+                let key = app.keybinding_map.get(action.id)
+                    .map(|&(code, mods)| format_keybinding(code, mods))
+                    .unwrap_or_default();
+                let padding = width.saturating_sub(action.label.chars().count() + key.chars().count()).max(1);
+                ListItem::new(Line::from(vec![
+                    Span::styled(action.label, Style::default().fg(theme.primary_foreground)),
+                    Span::raw(" ".repeat(padding)),
+                    Span::styled(key, Style::default().fg(theme.secondary_foreground)),
+                ]))
+            })
+            .collect();
+
+        if items.is_empty() {
+            f.render_widget(
+                Paragraph::new("No matching actions.").style(Style::default().fg(theme.secondary_foreground)),
+                chunks[2],
+            );
+            return;
+        }
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg))
+            .highlight_symbol("> ");
+        f.render_stateful_widget(list, chunks[2], &mut app.command_palette_state);
+    }
+}
@@ -0,0 +1,60 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::tui::app::App;
+use crate::format::format_bytes;
+use super::centered_rect;
+
+pub struct ConfirmModelDeletePopupWidget;
+
+impl ConfirmModelDeletePopupWidget {
+    pub fn render(f: &mut Frame, app: &App, area: Rect) {
+        let Some((model_name, size)) = &app.pending_model_delete else {
+            return;
+        };
+        let theme = &app.theme;
+
+        let block = Block::default()
+            .title("Delete Model")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error_text))
+            .style(Style::default().bg(theme.popup_background))
+            .title_alignment(Alignment::Center);
+
+        let popup_area = centered_rect(50, 30, area);
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block.clone(), popup_area);
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw("Delete model "),
+                Span::styled(model_name.clone(), Style::default().fg(theme.error_text).add_modifier(Modifier::BOLD)),
+                Span::raw("?"),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("This removes it from Ollama and frees {}. It can be pulled again later.", format_bytes(*size)),
+                Style::default().fg(theme.secondary_foreground),
+            )),
+        ];
+        if app.chats.iter().any(|chat| &chat.model_name == model_name) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Open chats with this model will be saved and closed.",
+                Style::default().fg(theme.quaternary_foreground),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Press y to confirm, n or Esc to cancel.", Style::default().fg(theme.help_text))));
+
+        let paragraph = Paragraph::new(lines)
+            .style(Style::default().fg(theme.primary_foreground))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, block.inner(popup_area));
+    }
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::tui::app::{App, VmAction};
+use super::centered_rect;
+
+pub struct ConfirmVmActionPopupWidget;
+
+impl ConfirmVmActionPopupWidget {
+    pub fn render(f: &mut Frame, app: &App, area: Rect) {
+        let Some((action, vm_names)) = &app.pending_vm_action else {
+            return;
+        };
+        let theme = &app.theme;
+
+        // Destroy is irreversible, so it gets the error colour; power off is routine.
+        let (title, question, explanation, accent) = match action {
+            VmAction::Destroy if vm_names.len() > 1 => (
+                "Destroy VMs",
+                "Destroy these VMs",
+                "This forcibly powers each VM off, removes its definition, and deletes its disk images. This cannot be undone.",
+                theme.error_text,
+            ),
+            VmAction::Destroy => (
+                "Destroy VM",
+                "Destroy VM ",
+                "This forcibly powers the VM off, removes its definition, and deletes its disk images. This cannot be undone.",
+                theme.error_text,
+            ),
+            VmAction::Shutdown => (
+                "Power Off VM",
+                "Power off VM ",
+                "This sends an ACPI shutdown request so the guest can shut down cleanly. The VM stays defined and can be started again.",
+                theme.border_accent,
+            ),
+        };
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent))
+            .style(Style::default().bg(theme.popup_background))
+            .title_alignment(Alignment::Center);
+
+        let name_style = Style::default().fg(accent).add_modifier(Modifier::BOLD);
+        let mut lines = if let [vm_name] = vm_names.as_slice() {
+            vec![Line::from(vec![
+                Span::raw(question),
+                Span::styled(vm_name.clone(), name_style),
+                Span::raw("?"),
+            ])]
+        } else {
+            let mut lines = vec![Line::from(format!("{} ({})?", question, vm_names.len())), Line::from("")];
+            lines.extend(vm_names.iter().map(|name| Line::from(Span::styled(name.clone(), name_style))));
+            lines
+        };
+        lines.extend([
+            Line::from(""),
+            Line::from(Span::styled(explanation, Style::default().fg(theme.secondary_foreground))),
+            Line::from(""),
+            Line::from(Span::styled("Press y to confirm, n or Esc to cancel.", Style::default().fg(theme.help_text))),
+        ]);
+
+        // Grow with the list of names, up to most of the screen
+        let percent_y = (30 + 4 * vm_names.len().saturating_sub(1)).min(80) as u16;
+        let popup_area = centered_rect(50, percent_y, area);
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block.clone(), popup_area);
+
+        let paragraph = Paragraph::new(lines)
+            .style(Style::default().fg(theme.primary_foreground))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, block.inner(popup_area));
+    }
+}
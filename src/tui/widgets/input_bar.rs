@@ -7,6 +7,7 @@ use ratatui::{
     Frame,
 };
 use crate::tui::app::{App, InputMode, AppView};
+use std::sync::Arc;
 use textwrap;
 
 pub struct InputBarWidget;
@@ -15,18 +16,47 @@ const CURSOR_CHAR: char = '|';
 
 impl InputBarWidget {
     pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
-        let theme = &app.theme;
+        let theme = Arc::clone(&app.theme);
 
         let is_editing_mode = app.input_mode == InputMode::Editing || app.editing_system_prompt_for_model.is_some();
         
-        let title = if let Some(model_name) = &app.editing_system_prompt_for_model {
+        let title = if let Some(model_name) = &app.editing_params_for_model {
+            Line::from(vec![
+                Span::styled("Generation Params for ", theme.input_bar_title),
+                Span::styled(model_name.clone(), theme.input_bar_title.patch(Style::default().add_modifier(Modifier::BOLD))),
+                Span::styled(" (empty value = Ollama default):", theme.input_bar_title),
+            ])
+        } else if let Some(vm_name) = &app.editing_resources_for_vm {
+            let vm = app.vms.iter().find(|vm| &vm.name == vm_name);
+            let cpus = vm.and_then(|vm| vm.cpu_cores_used).map(|cpus| format!("{} vCPUs", cpus));
+            let memory = vm.and_then(|vm| vm.memory_max_kb).map(|kb| format!("{} MB", kb / 1024));
+            let disk = vm.and_then(|vm| vm.disk_virtual_gb).map(|gb| format!("{:.1} GB", gb));
+            Line::from(vec![
+                Span::styled("Resize VM ", theme.input_bar_title),
+                Span::styled(vm_name.clone(), theme.input_bar_title.patch(Style::default().add_modifier(Modifier::BOLD))),
+                Span::styled(format!(
+                    " (now {}, {}, {}; empty value = unchanged):",
+                    cpus.as_deref().unwrap_or("? vCPUs"),
+                    memory.as_deref().unwrap_or("? MB"),
+                    disk.as_deref().unwrap_or("? GB"),
+                ), theme.input_bar_title),
+            ])
+        } else if app.editing_pull_model_name {
+            Line::from(Span::styled("Pull Model (Enter: Pull, Esc: Cancel):", theme.input_bar_title))
+        } else if let Some(model_name) = &app.editing_system_prompt_for_model {
             Line::from(vec![
                 Span::styled("Editing System Prompt for ", theme.input_bar_title),
                 Span::styled(model_name.clone(), theme.input_bar_title.patch(Style::default().add_modifier(Modifier::BOLD))),
                 Span::styled(":", theme.input_bar_title),
             ])
-        } else if app.active_view == AppView::Chat && app.active_chat.is_some() && is_editing_mode {
+        } else if app.active_view == AppView::Chat && app.active_chat().is_some() && is_editing_mode {
             Line::from(Span::styled("Chat Input (Esc: Normal Mode):", theme.input_bar_title))
+        } else if app.active_view == AppView::Logs {
+            Line::from(Span::styled(if is_editing_mode { "Search Logs (Enter: Keep, Esc: Clear):" } else { "Log Search:" }, theme.input_bar_title))
+        } else if app.active_view == AppView::VmList {
+            Line::from(Span::styled(if is_editing_mode { "Search VMs by Name (Enter: Keep, Esc: Clear):" } else { "VM Search:" }, theme.input_bar_title))
+        } else if app.active_view == AppView::Chat && !app.chat_input.is_empty() {
+            Line::from(Span::styled("Chat Draft (Enter: Resume Editing):", theme.input_bar_title))
         } else {
             Line::from(Span::styled("Input:", theme.input_bar_title)) 
         };
@@ -44,14 +74,20 @@ impl InputBarWidget {
         app.last_input_text_area_width = text_area_width;
         app.input_bar_visible_height = area.height.saturating_sub(2).max(1); 
 
+        let visible_height = app.input_bar_visible_height;
+        let Some(input) = app.active_text_input_mut() else {
+            f.render_widget(Paragraph::new("").block(input_block), area);
+            return;
+        };
+
         // 1. Create the text to be displayed and wrapped, with the cursor character inserted.
         let text_for_wrapping = if is_editing_mode {
-            let mut current_chars: Vec<char> = app.current_input.chars().collect();
-            let cursor_pos = app.input_cursor_char_idx.min(current_chars.len());
+            let mut current_chars: Vec<char> = input.text().chars().collect();
+            let cursor_pos = input.cursor_char_idx().min(current_chars.len());
             current_chars.insert(cursor_pos, CURSOR_CHAR);
             current_chars.into_iter().collect::<String>()
         } else {
-            app.current_input.clone()
+            input.text().to_string()
         };
         
         // 2. Wrap the text. This is the single source of truth for all calculations.
@@ -59,10 +95,10 @@ impl InputBarWidget {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        app.input_bar_last_wrapped_line_count = wrapped_lines.len();
+        input.last_wrapped_line_count = wrapped_lines.len();
 
         // 3. Find the cursor's line and adjust scroll if needed.
-        if app.input_bar_cursor_needs_to_be_visible && is_editing_mode {
+        if input.cursor_needs_to_be_visible && is_editing_mode {
             // Find the line containing the cursor character.
             let mut calculated_cursor_line = wrapped_lines
                 .iter()
@@ -75,25 +111,25 @@ impl InputBarWidget {
             }
 
             // "Scroll into view" logic.
-            if app.input_bar_visible_height > 0 {
-                let current_scroll_top = app.input_bar_scroll as usize;
-                let current_scroll_bottom = current_scroll_top + (app.input_bar_visible_height as usize).saturating_sub(1);
+            if visible_height > 0 {
+                let current_scroll_top = input.scroll as usize;
+                let current_scroll_bottom = current_scroll_top + (visible_height as usize).saturating_sub(1);
 
                 if calculated_cursor_line < current_scroll_top {
-                    app.input_bar_scroll = calculated_cursor_line as u16;
+                    input.scroll = calculated_cursor_line as u16;
                 } else if calculated_cursor_line > current_scroll_bottom {
-                    app.input_bar_scroll = (calculated_cursor_line.saturating_sub(app.input_bar_visible_height.saturating_sub(1) as usize)) as u16;
+                    input.scroll = (calculated_cursor_line.saturating_sub(visible_height.saturating_sub(1) as usize)) as u16;
                 }
             } else {
-                app.input_bar_scroll = 0;
+                input.scroll = 0;
             }
-            app.input_bar_cursor_needs_to_be_visible = false; 
+            input.cursor_needs_to_be_visible = false; 
         }
 
         // 4. Clamp scroll to the maximum possible value based on the final wrapped lines.
-        let max_scroll = (app.input_bar_last_wrapped_line_count as u16)
-            .saturating_sub(app.input_bar_visible_height);
-        app.input_bar_scroll = app.input_bar_scroll.min(max_scroll);
+        let max_scroll = (input.last_wrapped_line_count as u16)
+            .saturating_sub(visible_height);
+        input.scroll = input.scroll.min(max_scroll);
         
         // 5. Render the paragraph.
         let display_lines: Vec<Line> = wrapped_lines
@@ -105,20 +141,15 @@ impl InputBarWidget {
         let paragraph = Paragraph::new(text_to_display)
             .block(input_block)
             .style(Style::default().fg(theme.primary_foreground))
-            .scroll((app.input_bar_scroll, 0));
+            .scroll((input.scroll, 0));
         
         f.render_widget(paragraph, area);
     }
 
     pub fn calculate_height(app: &App, width: u16) -> u16 {
         let text_area_width = width.saturating_sub(2).max(1);
-        let text = if app.editing_system_prompt_for_model.is_some() || app.input_mode == InputMode::Editing {
-            &app.current_input
-        } else {
-            // In normal mode, if there's no active editing, we can consider it empty
-            // for layout purposes, or show a placeholder. Let's use a single line.
-            ""
-        };
+        // Outside of an editing context (e.g. the VM list) the bar is empty and uses a single line.
+        let text = app.active_text_input().map_or("", |input| input.text());
 
         if text.is_empty() {
             return 3; // Default height for an empty input bar
@@ -1,51 +1,97 @@
 use ratatui::{
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Style, Modifier},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, ListState, Paragraph},
     Frame,
 };
 use crate::tui::App;
+use crate::tui::command_palette::{format_keybinding, help_action_rows, help_sections};
+use super::{centered_rect, render_list_scrollbar};
 
 pub struct KeybindingsModalWidget;
 
 impl KeybindingsModalWidget {
-    pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
         let theme = &app.theme;
-        let popup_area = Rect {
-            x: area.x + area.width / 4,
-            y: area.y + area.height / 4,
-            width: area.width / 2,
-            height: area.height / 2,
-        };
-        f.render_widget(Clear, popup_area);
+        let popup_area = centered_rect(60, 75, area);
         let block = Block::default()
-            .title("Keybindings")
             .borders(Borders::ALL)
             .style(Style::default().fg(theme.primary_foreground).bg(theme.primary_background))
             .title_alignment(Alignment::Center);
-        f.render_widget(block.clone(), popup_area);
         let inner = block.inner(popup_area);
-        let kb = &app.config.keybindings;
-        let lines = vec![
-            Line::from(vec![Span::styled("Quit: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.quit)]),
-            Line::from(vec![Span::styled("Help: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.help)]),
-            Line::from(vec![Span::styled("Menu: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.menu)]),
-            Line::from(vec![Span::styled("Next Tab: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.next_tab)]),
-            Line::from(vec![Span::styled("Prev Tab: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.prev_tab)]),
-            Line::from(vec![Span::styled("New VM: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.new_vm)]),
-            Line::from(vec![Span::styled("Destroy VM: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.destroy_vm)]),
-            Line::from(vec![Span::styled("Edit: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.edit)]),
-            Line::from(vec![Span::styled("Enter: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.enter)]),
-            Line::from(vec![Span::styled("Up: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.up)]),
-            Line::from(vec![Span::styled("Down: ", Style::default().add_modifier(Modifier::BOLD)), Span::raw(&kb.down)]),
-            Line::from("")
-        ];
-        let mut lines = lines;
-        lines.push(Line::from(vec![Span::styled("Press Esc to close", Style::default().fg(theme.help_text))]));
-        let para = Paragraph::new(lines)
-            .alignment(Alignment::Left)
-            .block(Block::default());
-        f.render_widget(para, inner);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+
+        // One section per view; blank lines between them keep help_section_line's offsets right
+        let selected_line = help_action_rows().get(app.keybindings_selected).map(|(line, _, _)| *line);
+        let mut lines: Vec<Line> = Vec::new();
+        for section in help_sections() {
+            let mut title = vec![Span::styled(section.title, Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED))];
+            if section.view == Some(app.active_view) {
+                title.push(Span::styled("  (current view)", Style::default().fg(theme.help_text)));
+            }
+            lines.push(Line::from(title));
+            for (action, label) in section.actions {
+                let selected = selected_line == Some(lines.len());
+                let binding = if selected && app.keybinding_capture {
+                    "press a key…".to_string()
+                } else {
+                    app.keybinding_map
+                        .get(action)
+                        .map(|(code, mods)| format_keybinding(*code, *mods))
+                        .unwrap_or_else(|| "unbound".to_string())
+                };
+                let mut spans = vec![Span::raw(format!("  {:<12} ", binding)), Span::raw(label)];
+                // Unparseable and conflicting bindings are flagged with the reason
+                if let Some(issue) = app.keybinding_issues.get(action) {
+                    spans[0].style = theme.log_level_error;
+                    spans.push(Span::styled(format!("  ⚠ {}", issue), theme.log_level_error));
+                }
+                let mut line = Line::from(spans);
+                if selected {
+                    line.patch_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg));
+                }
+                lines.push(line);
+            }
+            lines.push(Line::from(""));
+        }
+        lines.pop();
+
+        let visible = chunks[0].height as usize;
+        let max_scroll = lines.len().saturating_sub(visible);
+        // Keep the selected row in view
+        let mut scroll = app.keybindings_scroll.min(max_scroll);
+        if let Some(line) = selected_line {
+            if line < scroll {
+                scroll = line.saturating_sub(1); // Show the section title too where possible
+            } else if line >= scroll + visible {
+                scroll = (line + 1).saturating_sub(visible);
+            }
+        }
+        let overflows = max_scroll > 0;
+        let title = if overflows {
+            format!("Keybindings ({}-{} of {})", scroll + 1, (scroll + visible).min(lines.len()), lines.len())
+        } else {
+            "Keybindings".to_string()
+        };
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block.title(title), popup_area);
+        let para = Paragraph::new(lines).scroll((scroll.min(u16::MAX as usize) as u16, 0));
+        f.render_widget(para, chunks[0]);
+        let footer_text = if app.keybinding_capture {
+            "Press the new key · Esc to cancel"
+        } else {
+            "↑/↓ select · Enter rebind · Del reset to default · Esc close"
+        };
+        let footer = Paragraph::new(Span::styled(footer_text, Style::default().fg(theme.help_text)))
+            .alignment(Alignment::Center);
+        f.render_widget(footer, chunks[1]);
+        render_list_scrollbar(f, popup_area, theme, &ListState::default().with_offset(scroll), max_scroll + 1, overflows);
+
+        app.keybindings_scroll = scroll;
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, ListState, Paragraph},
+    Frame,
+};
+use tracing::Level;
+
+use crate::tui::App;
+use crate::tui::command_palette::format_keybinding;
+use super::{centered_rect, render_list_scrollbar};
+
+// Shows one log entry in full: its fields, then the whole message wrapped to the popup's width
+pub struct LogDetailPopupWidget;
+
+impl LogDetailPopupWidget {
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+        let Some(entry) = &app.log_detail else {
+            return;
+        };
+        let theme = &app.theme;
+        let popup_area = centered_rect(70, 60, area);
+        let block = Block::default()
+            .title(Line::from(Span::styled("Log Entry", Style::default().fg(theme.primary_foreground).bold())))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_accent))
+            .style(Style::default().bg(theme.popup_background));
+        let inner_area = block.inner(popup_area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
+        let level_style = match entry.level {
+            Level::ERROR => theme.log_level_error,
+            Level::WARN => theme.log_level_warn,
+            Level::INFO => theme.log_level_info,
+            Level::DEBUG => theme.log_level_debug,
+            Level::TRACE => theme.log_level_trace,
+        };
+        let field = |name: &'static str, value: String, style: Style| {
+            Line::from(vec![Span::styled(format!("{:<10}", name), Style::default().fg(theme.secondary_foreground)), Span::styled(value, style)])
+        };
+        let mut lines = vec![
+            field("Timestamp", entry.timestamp.clone(), Style::default().fg(theme.primary_foreground)),
+            field("Level", entry.level.as_str().to_string(), level_style),
+            field("Target", entry.target.clone(), Style::default().fg(theme.tertiary_foreground)),
+            field("Location", entry.location().unwrap_or_else(|| "unknown".to_string()), Style::default().fg(theme.tertiary_foreground)),
+            Line::from(""),
+        ];
+        // One column is kept free for the scrollbar
+        let width = chunks[0].width.saturating_sub(1).max(1) as usize;
+        lines.extend(
+            textwrap::wrap(&entry.message, width)
+                .into_iter()
+                .map(|line| Line::from(Span::styled(line.into_owned(), Style::default().fg(theme.primary_foreground)))),
+        );
+
+        let visible = chunks[0].height as usize;
+        let max_scroll = lines.len().saturating_sub(visible);
+        let scroll = app.log_detail_scroll.min(max_scroll);
+        let overflows = max_scroll > 0;
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        f.render_widget(Paragraph::new(lines).scroll((scroll.min(u16::MAX as usize) as u16, 0)), chunks[0]);
+        let copy_key = app.keybinding_map
+            .get("copy")
+            .map(|(code, mods)| format!(" | {}: copy", format_keybinding(*code, *mods)))
+            .unwrap_or_default();
+        f.render_widget(
+            Paragraph::new(format!("Esc/Enter: close | Up/Down: scroll{}", copy_key)).style(Style::default().fg(theme.help_text)),
+            chunks[1],
+        );
+        render_list_scrollbar(f, popup_area, theme, &ListState::default().with_offset(scroll), max_scroll + 1, overflows);
+
+        app.log_detail_scroll = scroll;
+    }
+}
@@ -1,32 +1,56 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use textwrap::core::display_width;
 use tracing::Level; // For matching log levels
 
 use crate::tui::App;
+use crate::tui::app::{AppView, ListHitArea};
+use super::render_list_scrollbar;
 
 pub struct LogsWidget;
 
+// Below this, wrapped messages start on their own line instead of beside the timestamp
+const MIN_WRAPPED_MESSAGE_WIDTH: usize = 20;
+
 impl LogsWidget {
     pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
         let theme = &app.theme;
 
+        let title = if app.log_wrap {
+            "Logs (wrapped)".to_string()
+        } else if app.log_hscroll > 0 {
+            format!("Logs (from column {})", app.log_hscroll + 1)
+        } else {
+            "Logs".to_string()
+        };
         let title_block = Block::default()
-            .title(Line::from(Span::styled("Logs", Style::default().fg(theme.primary_foreground).bold())))
+            .title(Line::from(Span::styled(title, Style::default().fg(theme.primary_foreground).bold())))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.border_primary));
 
-        if app.log_entries.is_empty() {
-            let placeholder = Paragraph::new("No log entries yet.")
+        let visible_entries = app.visible_log_entries();
+        if visible_entries.is_empty() {
+            let placeholder_text = if app.log_entries.is_empty() {
+                "No log entries yet."
+            } else {
+                "No log entries match the current filter."
+            };
+            let placeholder = Paragraph::new(placeholder_text)
                 .block(title_block)
                 .style(Style::default().fg(theme.secondary_foreground));
             f.render_widget(placeholder, area);
         } else {
-            let log_items: Vec<ListItem> = app.log_entries.iter().map(|log_entry| {
+            let item_count = visible_entries.len();
+            // Width left for text inside the borders, after the highlight symbol
+            let text_width = area.width.saturating_sub(2 + 2) as usize;
+            let mut line_count = 0;
+            let mut item_heights: Vec<u16> = Vec::with_capacity(item_count);
+            let log_items: Vec<ListItem> = visible_entries.into_iter().map(|log_entry| {
                 let level_style = match log_entry.level {
                     Level::ERROR => theme.log_level_error.clone(),
                     Level::WARN => theme.log_level_warn.clone(),
@@ -43,14 +67,51 @@ impl LogsWidget {
                     format!("{:<5} ", log_entry.level.as_str()),
                     level_style,
                 );
+                // Warnings and errors also say where they were logged; every entry does in the detail popup
+                let location = log_entry.location().filter(|_| log_entry.level <= Level::WARN);
                 let target_span = Span::styled(
-                    format!("[{}] ", log_entry.target),
+                    match location {
+                        Some(location) => format!("[{} {}] ", log_entry.target, location),
+                        None => format!("[{}] ", log_entry.target),
+                    },
                     Style::default().fg(theme.tertiary_foreground),
                 );
-                let message_span = Span::styled(log_entry.message.clone(), Style::default().fg(theme.primary_foreground));
+                let message_style = Style::default().fg(theme.primary_foreground);
 
-                let line = Line::from(vec![timestamp_span, level_span, target_span, message_span]);
-                ListItem::new(line)
+                if !app.log_wrap {
+                    // Truncated lines scroll sideways through the message, keeping the prefix in view
+                    let message: String = log_entry.message.chars().skip(app.log_hscroll).collect();
+                    let message = if app.log_hscroll > 0 { format!("…{}", message) } else { message };
+                    line_count += 1;
+                    item_heights.push(1);
+                    return ListItem::new(Line::from(vec![timestamp_span, level_span, target_span, Span::styled(message, message_style)]));
+                }
+
+                // Continuation lines are indented under the message, unless that leaves too little room
+                let prefix_width = display_width(&timestamp_span.content) + display_width(&level_span.content) + display_width(&target_span.content);
+                let (indent, message_width) = if text_width >= prefix_width + MIN_WRAPPED_MESSAGE_WIDTH {
+                    (prefix_width, text_width - prefix_width)
+                } else {
+                    (2, text_width.saturating_sub(2).max(1))
+                };
+                let mut lines: Vec<Line> = Vec::new();
+                let mut first = vec![timestamp_span, level_span, target_span];
+                if indent == prefix_width {
+                    let mut wrapped = textwrap::wrap(&log_entry.message, message_width).into_iter();
+                    first.push(Span::styled(wrapped.next().map(|l| l.into_owned()).unwrap_or_default(), message_style));
+                    lines.push(Line::from(first));
+                    lines.extend(wrapped.map(|l| Line::from(Span::styled(format!("{}{}", " ".repeat(indent), l), message_style))));
+                } else {
+                    lines.push(Line::from(first));
+                    lines.extend(
+                        textwrap::wrap(&log_entry.message, message_width)
+                            .into_iter()
+                            .map(|l| Line::from(Span::styled(format!("{}{}", " ".repeat(indent), l), message_style))),
+                    );
+                }
+                line_count += lines.len();
+                item_heights.push(lines.len() as u16);
+                ListItem::new(lines)
             }).collect();
 
             let log_list = List::new(log_items)
@@ -59,6 +120,16 @@ impl LogsWidget {
                 .highlight_symbol("> ");
 
             f.render_stateful_widget(log_list, area, &mut app.log_list_state);
+            let offset = app.log_list_state.offset().min(item_heights.len());
+            app.list_hit_area = Some(ListHitArea {
+                view: AppView::Logs,
+                area: area.inner(&Margin { vertical: 1, horizontal: 1 }),
+                offset,
+                item_count,
+                item_heights: item_heights.split_off(offset),
+            });
+            let overflows = line_count > area.height.saturating_sub(2) as usize;
+            render_list_scrollbar(f, area, &app.theme, &app.log_list_state, item_count, overflows);
         }
     }
 }
@@ -6,6 +6,7 @@ use ratatui::{
     Frame,
 };
 use crate::tui::App;
+use crate::tui::app::{MAIN_MENU_ITEMS, PREFERENCES_MENU_ITEMS};
 
 pub struct MenuWidget;
 
@@ -16,21 +17,30 @@ impl MenuWidget {
         }
 
         let theme = &app.theme;
-        // Menu structure: Main -> Preferences -> Keybindings
-        let main_items = vec![
-            ListItem::new(Line::from(vec![Span::raw("About")])),
-            ListItem::new(Line::from(vec![Span::raw("Preferences")])),
-            ListItem::new(Line::from(vec![Span::raw("Quit")])),
-        ];
-        let prefs_items = vec![
-            ListItem::new(Line::from(vec![Span::raw("Keybindings")])),
-        ];
-
-        let (items, title, menu_height) = match app.menu_level {
-            0 => (main_items, "Menu", 6),
-            1 => (prefs_items, "Preferences", 4),
-            _ => (main_items, "Menu", 6),
+        // Menu structure: Main -> Preferences -> Key Bindings / Theme
+        let to_items = |names: &[&str]| -> Vec<ListItem> {
+            names.iter().map(|name| ListItem::new(Line::from(vec![Span::raw(name.to_string())]))).collect()
+        };
+        let (items, title) = match app.menu_level {
+            1 => (to_items(&PREFERENCES_MENU_ITEMS), "Preferences"),
+            2 => {
+                // The theme in use is marked; with none picked, theme.toml is in use
+                let current = app.config.interface.theme.as_deref();
+                let items = app.theme_choices.iter()
+                    .map(|choice| {
+                        let mark = if Some(choice.name.as_str()) == current { "✓ " } else { "  " };
+                        let origin = if choice.path.is_some() { "" } else { " (built-in)" };
+                        ListItem::new(Line::from(vec![
+                            Span::raw(format!("{}{}", mark, choice.name)),
+                            Span::styled(origin, Style::default().fg(theme.secondary_foreground)),
+                        ]))
+                    })
+                    .collect();
+                (items, "Theme")
+            }
+            _ => (to_items(&MAIN_MENU_ITEMS), "Menu"),
         };
+        let menu_height = (items.len() as u16).saturating_add(2).min(area.height.saturating_sub(1));
 
         let list = List::new(items)
             .block(
@@ -46,15 +56,15 @@ impl MenuWidget {
         let menu_area = Rect {
             x: area.x + 5,
             y: area.y + 1,
-            width: 24,
+            width: 30,
             height: menu_height,
         };
 
         f.render_widget(Clear, menu_area); //this clears the background
-        if app.menu_level == 0 {
-            f.render_stateful_widget(list, menu_area, &mut app.menu_state);
-        } else if app.menu_level == 1 {
-            f.render_stateful_widget(list, menu_area, &mut app.menu_sub_state);
+        match app.menu_level {
+            1 => f.render_stateful_widget(list, menu_area, &mut app.menu_sub_state),
+            2 => f.render_stateful_widget(list, menu_area, &mut app.theme_picker_state),
+            _ => f.render_stateful_widget(list, menu_area, &mut app.menu_state),
         }
     }
 } 
\ No newline at end of file
@@ -1,16 +1,94 @@
 pub mod about_modal;
 pub mod chat;
+pub mod chat_picker_popup;
+pub mod command_palette_popup;
+pub mod confirm_model_delete_popup;
+pub mod confirm_vm_action_popup;
 pub mod input_bar;
+pub mod log_detail_popup;
 pub mod logs;
 pub mod menu;
 pub mod new_vm_popup;
 pub mod ollama_model_list;
 pub mod status_bar;
+pub mod toasts;
+pub mod vm_detail_popup;
 pub mod vm_list;
+pub mod vm_preview_popup;
 pub mod keybindings_modal;
 
 #[cfg(feature = "bedrock_integration")]
 pub mod bedrock_model_list;
+#[cfg(feature = "openai_compat")]
+pub mod openai_model_list;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::Style,
+    widgets::{ListState, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+use super::theme::AppTheme;
+
+/// Draws a scrollbar over the right border of a bordered list at `area`, positioned at the
+/// selected item (or the scroll offset when nothing is selected). Nothing is drawn unless
+/// the list overflows its area.
+pub fn render_list_scrollbar(f: &mut Frame, area: Rect, theme: &AppTheme, list_state: &ListState, item_count: usize, overflows: bool) {
+    if !overflows || item_count == 0 {
+        return;
+    }
+    let position = list_state.selected().unwrap_or(list_state.offset()).min(item_count - 1);
+    let mut scrollbar_state = ScrollbarState::new(item_count).position(position);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.tertiary_foreground))
+        .thumb_style(Style::default().fg(theme.border_accent));
+    f.render_stateful_widget(scrollbar, area.inner(&Margin { vertical: 1, horizontal: 0 }), &mut scrollbar_state);
+}
+
+/// A popup area `percent_x` wide and `percent_y` tall, centered in `r`.
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// Like `centered_rect`, but `height` rows tall (or the whole height, if smaller).
+pub fn centered_rect_with_height(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let height = height.min(r.height);
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length((r.height - height) / 2),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
 
 // We can re-export widget structs here later, e.g.:
 // pub use status_bar::StatusBarWidget;
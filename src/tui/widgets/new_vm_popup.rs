@@ -5,6 +5,8 @@ use ratatui::{
     Frame,
 };
 use crate::tui::App;
+use crate::tui::app::new_vm_field;
+use super::centered_rect;
 
 pub struct NewVmPopupWidget;
 
@@ -39,65 +41,86 @@ impl NewVmPopupWidget {
                 Constraint::Length(1), // Spacer
                 Constraint::Length(1), // ISO Checkbox
                 Constraint::Length(3), // ISO Path
-                Constraint::Min(1),    // Spacer
+                Constraint::Length(1), // Persistent Checkbox
+                Constraint::Min(1),    // Validation message
                 Constraint::Length(1), // Instructions
             ].as_ref())
             .split(inner_area);
         
         let active_input_style = Style::default().fg(theme.popup_input_fg_active);
+        // The field a rejected submit complained about gets an error-coloured border
+        let field_block = |title: String, field_idx: usize| {
+            let block = Block::default().borders(Borders::ALL).title(title);
+            if app.new_vm_error_field == Some(field_idx) {
+                block.border_style(theme.log_level_error)
+            } else {
+                block
+            }
+        };
 
         let mut name_input = Paragraph::new(app.new_vm_name.as_str())
-            .block(Block::default().borders(Borders::ALL).title("VM Name"));
-        if app.active_new_vm_input_idx == 0 {
+            .block(field_block("VM Name".to_string(), new_vm_field::NAME));
+        if app.active_new_vm_input_idx == new_vm_field::NAME {
             name_input = name_input.style(active_input_style);
         }
         
         let mut source_image_input = Paragraph::new(app.new_vm_source_image_path.as_str())
-            .block(Block::default().borders(Borders::ALL).title("Source Image Path (optional)"));
-        if app.active_new_vm_input_idx == 1 {
+            .block(field_block("Source Image Path (optional)".to_string(), new_vm_field::SOURCE_IMAGE));
+        if app.active_new_vm_input_idx == new_vm_field::SOURCE_IMAGE {
             source_image_input = source_image_input.style(active_input_style);
         }
 
         let mut disk_path_input = Paragraph::new(app.new_vm_disk_path.as_str())
-            .block(Block::default().borders(Borders::ALL).title("Disk Image Path"));
-        if app.active_new_vm_input_idx == 2 {
+            .block(field_block("Disk Image Path".to_string(), new_vm_field::DISK_PATH));
+        if app.active_new_vm_input_idx == new_vm_field::DISK_PATH {
             disk_path_input = disk_path_input.style(active_input_style);
         }
         
+        let vm_limits = &app.policy_engine.config.defaults.vm;
         let mut cpu_input = Paragraph::new(app.new_vm_cpu.as_str())
-            .block(Block::default().borders(Borders::ALL).title("CPUs"));
-        if app.active_new_vm_input_idx == 3 {
+            .block(field_block(format!("CPUs (max {})", vm_limits.max_cpus), new_vm_field::CPU));
+        if app.active_new_vm_input_idx == new_vm_field::CPU {
             cpu_input = cpu_input.style(active_input_style);
         }
         
         let mut ram_input = Paragraph::new(app.new_vm_ram_mb.as_str())
-            .block(Block::default().borders(Borders::ALL).title("Memory (e.g., 4GB or 4096MB)"));
-        if app.active_new_vm_input_idx == 4 {
+            .block(field_block(format!("Memory (e.g., 4GB or 4096MB, max {} MB)", vm_limits.max_mem_mb), new_vm_field::RAM));
+        if app.active_new_vm_input_idx == new_vm_field::RAM {
             ram_input = ram_input.style(active_input_style);
         }
         
         let mut disk_size_input = Paragraph::new(app.new_vm_disk_gb.as_str())
-            .block(Block::default().borders(Borders::ALL).title("Disk Size (GB)"));
-        if app.active_new_vm_input_idx == 5 {
+            .block(field_block("Disk Size (GB)".to_string(), new_vm_field::DISK_SIZE));
+        if app.active_new_vm_input_idx == new_vm_field::DISK_SIZE {
             disk_size_input = disk_size_input.style(active_input_style);
         }
 
         let iso_checkbox_text = if app.new_vm_use_iso { "[x] Boot from ISO" } else { "[ ] Boot from ISO" };
         let mut iso_checkbox = Paragraph::new(iso_checkbox_text);
-        if app.active_new_vm_input_idx == 6 {
+        if app.active_new_vm_input_idx == new_vm_field::USE_ISO {
             iso_checkbox = iso_checkbox.style(active_input_style);
         }
 
         let mut iso_path_input = Paragraph::new(app.new_vm_iso_path.as_str())
-            .block(Block::default().borders(Borders::ALL).title("ISO Path"));
+            .block(field_block("ISO Path".to_string(), new_vm_field::ISO_PATH));
 
         if !app.new_vm_use_iso {
             iso_path_input = iso_path_input.style(Style::default().fg(theme.tertiary_foreground));
         }
-        if app.active_new_vm_input_idx == 7 && app.new_vm_use_iso {
+        if app.active_new_vm_input_idx == new_vm_field::ISO_PATH && app.new_vm_use_iso {
             iso_path_input = iso_path_input.style(active_input_style);
         }
 
+        let persistent_checkbox_text = if app.new_vm_persistent {
+            "[x] Persistent (survives host reboot)"
+        } else {
+            "[ ] Persistent (unchecked: transient, removed once stopped)"
+        };
+        let mut persistent_checkbox = Paragraph::new(persistent_checkbox_text);
+        if app.active_new_vm_input_idx == new_vm_field::PERSISTENT {
+            persistent_checkbox = persistent_checkbox.style(active_input_style);
+        }
+
         f.render_widget(name_input, chunks[0]);
         f.render_widget(source_image_input, chunks[1]);
         f.render_widget(disk_path_input, chunks[2]);
@@ -106,31 +129,19 @@ impl NewVmPopupWidget {
         f.render_widget(disk_size_input, chunks[5]);
         f.render_widget(iso_checkbox, chunks[7]);
         f.render_widget(iso_path_input, chunks[8]);
+        f.render_widget(persistent_checkbox, chunks[9]);
         
-        let instructions = Paragraph::new("Press Tab to switch fields, Space to toggle checkbox, Enter to create, Esc to cancel.")
+        if let Some(error) = &app.new_vm_error {
+            let message = Paragraph::new(format!("Cannot create VM: {}", error))
+                .style(theme.log_level_error)
+                .alignment(Alignment::Center)
+                .wrap(ratatui::widgets::Wrap { trim: true });
+            f.render_widget(message, chunks[10]);
+        }
+
+        let instructions = Paragraph::new("Press Tab to switch fields, Space to toggle checkbox, Enter to create, Ctrl+p to preview, Esc to cancel.")
             .style(Style::default().fg(theme.secondary_foreground))
             .alignment(Alignment::Center);
-        f.render_widget(instructions, chunks[10]);
+        f.render_widget(instructions, chunks[11]);
     }
 }
-
-/// Helper for creating a centered popup.
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-} 
\ No newline at end of file
@@ -2,11 +2,13 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
     Frame,
 };
 use crate::tui::App;
+use crate::tui::app::{AppView, ListHitArea};
 use textwrap;
+use crate::format::format_bytes;
 
 pub struct OllamaModelListWidget;
 
@@ -24,20 +26,54 @@ impl OllamaModelListWidget {
 
         // Left Pane: Ollama Model List
         let left_pane_block = Block::default()
-            .title(Line::from(Span::styled("Ollama Models", Style::default().fg(theme.primary_foreground).bold())))
+            .title(Line::from(Span::styled(
+                if app.show_favorites_only { "Ollama Models (favorites)" } else { "Ollama Models" },
+                Style::default().fg(theme.primary_foreground).bold(),
+            )))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.border_primary));
-        let left_pane_content_area = left_pane_block.inner(chunks[0]);
+        let mut left_pane_content_area = left_pane_block.inner(chunks[0]);
         f.render_widget(left_pane_block, chunks[0]);
 
+        // A download in progress takes a gauge at the bottom of the list pane
+        if let Some((model_name, progress)) = &app.model_pull {
+            let pane_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(3)])
+                .split(left_pane_content_area);
+            left_pane_content_area = pane_chunks[0];
+            let label = match progress.percent() {
+                Some(percent) => format!("{}: {} {}%", model_name, progress.status, percent),
+                None => format!("{}: {}", model_name, progress.status),
+            };
+            let gauge = Gauge::default()
+                .block(Block::default().title("Pulling").borders(Borders::TOP).border_style(Style::default().fg(theme.border_secondary)))
+                .gauge_style(Style::default().fg(theme.border_accent).bg(theme.secondary_background))
+                .percent(progress.percent().unwrap_or(0))
+                .label(Span::styled(label, Style::default().fg(theme.primary_foreground)));
+            f.render_widget(gauge, pane_chunks[1]);
+        }
+
         #[cfg(feature = "ollama_integration")] {
-            let model_items: Vec<ListItem> = app.ollama_models.iter()
-                .map(|model| ListItem::new(Line::from(Span::styled(model.name.clone(), Style::default().fg(theme.primary_foreground)))))
+            let model_items: Vec<ListItem> = app.visible_ollama_models().iter()
+                .map(|model| {
+                    let marker = if app.config.is_favorite_model(&model.name) { "★ " } else { "  " };
+                    let mut spans = vec![
+                        Span::styled(marker, Style::default().fg(theme.quaternary_foreground)),
+                        Span::styled(model.name.clone(), Style::default().fg(theme.primary_foreground)),
+                    ];
+                    if app.running_ollama_model(&model.name).is_some() {
+                        spans.push(Span::styled(" ● loaded", Style::default().fg(theme.success_text)));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
                 .collect();
+            let item_count = model_items.len();
             let model_list = List::new(model_items)
                 .highlight_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg))
                 .highlight_symbol(">> ");
             f.render_stateful_widget(model_list, left_pane_content_area, &mut app.ollama_model_list_state);
+            app.list_hit_area = Some(ListHitArea::single_line(AppView::OllamaModelList, left_pane_content_area, &app.ollama_model_list_state, item_count));
         }
         #[cfg(not(feature = "ollama_integration"))] {
             let placeholder_items: Vec<ListItem> = app.ollama_models.iter()
@@ -56,14 +92,45 @@ impl OllamaModelListWidget {
 
         #[cfg(feature = "ollama_integration")] {
             if let Some(selected_idx) = app.ollama_model_list_state.selected() {
-                if let Some(model) = app.ollama_models.get(selected_idx) {
+                if let Some(model) = app.visible_ollama_models().get(selected_idx).copied() {
                     let mut details_lines = vec![
                         Line::from(vec![Span::styled("Name: ", theme.ollama_model_list_details_title.clone()), Span::raw(&model.name)]),
                         Line::from(vec![Span::styled("Modified: ", theme.ollama_model_list_details_title.clone()), Span::raw(&model.modified_at)]),
                         Line::from(vec![Span::styled("Size: ", theme.ollama_model_list_details_title.clone()), Span::raw(format!("{}", model.size))]),
-                        Line::from(""),
                     ];
 
+                    match app.running_ollama_model(&model.name) {
+                        Some(running) => {
+                            details_lines.push(Line::from(vec![
+                                Span::styled("Loaded: ", theme.ollama_model_list_details_title.clone()),
+                                Span::styled("Yes", Style::default().fg(theme.success_text)),
+                                Span::raw(format!(" ({} in memory, {} VRAM)", format_bytes(running.size), format_bytes(running.size_vram))),
+                            ]));
+                            if let Some(expires_at) = &running.expires_at {
+                                details_lines.push(Line::from(vec![
+                                    Span::styled("Unloads at: ", theme.ollama_model_list_details_title.clone()),
+                                    Span::raw(expires_at.clone()),
+                                ]));
+                            }
+                        }
+                        None => {
+                            details_lines.push(Line::from(vec![
+                                Span::styled("Loaded: ", theme.ollama_model_list_details_title.clone()),
+                                Span::styled("No", Style::default().fg(theme.secondary_foreground)),
+                            ]));
+                        }
+                    }
+                    let params = app.config.get_model_params(&model.name);
+                    details_lines.push(Line::from(vec![
+                        Span::styled("Params: ", theme.ollama_model_list_details_title.clone()),
+                        if params.is_empty() {
+                            Span::styled("Ollama defaults", Style::default().fg(theme.secondary_foreground))
+                        } else {
+                            Span::raw(params.to_edit_string().split_whitespace().filter(|pair| !pair.ends_with('=')).collect::<Vec<_>>().join(" "))
+                        },
+                    ]));
+                    details_lines.push(Line::from(""));
+
                     // Simplified logic for the tag:
                     let active_system_prompt_tag_str = if app.editable_ollama_model_prompts.contains_key(&model.name) {
                         "(Model specific)".to_string()
@@ -0,0 +1,93 @@
+#![cfg(feature = "openai_compat")]
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use crate::tui::App;
+use crate::tui::app::{AppView, ListHitArea};
+
+pub struct OpenAiModelListWidget;
+
+impl OpenAiModelListWidget {
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(60),
+            ].as_ref())
+            .split(area);
+        let provider_config = &app.config.providers.openai_compat;
+        let left_pane_block = Block::default()
+            .title(Line::from(vec![
+                Span::styled("OpenAI-compatible Models ", Style::default().fg(theme.primary_foreground).bold()),
+                Span::styled(provider_config.base_url.clone(), Style::default().fg(theme.secondary_foreground)),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_primary));
+        let left_pane_content_area = left_pane_block.inner(chunks[0]);
+        f.render_widget(left_pane_block, chunks[0]);
+
+        // Owned, since the list state is borrowed mutably while these are still needed
+        let models: Vec<_> = app.visible_openai_models().into_iter().cloned().collect();
+        if models.is_empty() {
+            let message = if !provider_config.enabled {
+                "Disabled. Set [providers.openai_compat] enabled = true in config.toml."
+            } else if !app.openai_connected {
+                "Server not reachable; see Logs."
+            } else {
+                "The server reported no models."
+            };
+            f.render_widget(
+                Paragraph::new(message).wrap(ratatui::widgets::Wrap { trim: true }).style(Style::default().fg(theme.secondary_foreground)),
+                left_pane_content_area,
+            );
+        } else {
+            let model_items: Vec<ListItem> = models
+                .iter()
+                .map(|model| {
+                    let marker = if app.config.is_favorite_model(&model.id) { "★ " } else { "  " };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(marker, Style::default().fg(theme.quaternary_foreground)),
+                        Span::styled(model.id.clone(), Style::default().fg(theme.primary_foreground)),
+                    ]))
+                })
+                .collect();
+            let item_count = model_items.len();
+            let model_list = List::new(model_items)
+                .highlight_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg))
+                .highlight_symbol(">> ");
+            f.render_stateful_widget(model_list, left_pane_content_area, &mut app.openai_model_list_state);
+            app.list_hit_area = Some(ListHitArea::single_line(AppView::OpenAiModelList, left_pane_content_area, &app.openai_model_list_state, item_count));
+        }
+
+        let right_pane_block = Block::default()
+            .title(Line::from(Span::styled("Model Details", Style::default().fg(theme.primary_foreground))))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_secondary));
+        let right_pane_content_area = right_pane_block.inner(chunks[1]);
+        f.render_widget(right_pane_block, chunks[1]);
+        let selected = app.openai_model_list_state.selected().and_then(|idx| models.get(idx));
+        if let Some(model) = selected {
+            let created = model.created
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|created| created.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "N/A".to_string());
+            let source = if model.from_config { "config.toml (not reported by the server)" } else { "Server /models" };
+            let details_lines = vec![
+                Line::from(vec![Span::styled("ID: ", theme.ollama_model_list_details_title), Span::raw(model.id.clone())]),
+                Line::from(vec![Span::styled("Owned By: ", theme.ollama_model_list_details_title), Span::raw(model.owned_by.clone().unwrap_or_else(|| "N/A".to_string()))]),
+                Line::from(vec![Span::styled("Created: ", theme.ollama_model_list_details_title), Span::raw(created)]),
+                Line::from(vec![Span::styled("Listed From: ", theme.ollama_model_list_details_title), Span::raw(source)]),
+            ];
+            f.render_widget(Paragraph::new(Text::from(details_lines)).wrap(ratatui::widgets::Wrap { trim: false }).style(Style::default().fg(theme.primary_foreground)), right_pane_content_area);
+        } else {
+            f.render_widget(Paragraph::new("No model selected").style(Style::default().fg(theme.secondary_foreground)), right_pane_content_area);
+        }
+    }
+}
@@ -6,10 +6,12 @@ use ratatui::{
     Frame,
 };
 use chrono::Local;
-use crate::tui::app::{App, InputMode, AppView};
+use crate::tui::app::{App, InputMode, AppView, VmStateFilter};
 
 pub struct StatusBarWidget;
 
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
 impl StatusBarWidget {
     pub fn render(f: &mut Frame, app: &App, area: Rect) {
         let theme = &app.theme;
@@ -36,7 +38,7 @@ impl StatusBarWidget {
             InputMode::Normal => theme.status_bar_mode_normal_bg,
             InputMode::Editing => theme.status_bar_mode_editing_bg,
             InputMode::VmWizard => theme.status_bar_mode_vm_wizard_bg,
-            InputMode::ConfirmingDestroy => theme.status_bar_mode_confirm_destroy_bg,
+            InputMode::ConfirmingVmAction | InputMode::ConfirmingModelDelete => theme.status_bar_mode_confirm_destroy_bg,
         };
 
         let view_name_fg = match app.active_view {
@@ -44,35 +46,87 @@ impl StatusBarWidget {
             AppView::OllamaModelList => theme.status_bar_view_ollama_model_list_fg,
             #[cfg(feature = "bedrock_integration")]
             AppView::BedrockModelList => theme.status_bar_view_bedrock_model_list_fg,
+            #[cfg(feature = "openai_compat")]
+            AppView::OpenAiModelList => theme.status_bar_view_name_fg,
             AppView::Chat => theme.status_bar_view_chat_fg,
             AppView::Logs => theme.status_bar_view_logs_fg,
         };
 
-        let status_spans_left = Line::from(vec![
+        let mut status_spans_left = vec![
             Span::styled("H", outlined_h_style),
             Span::styled("ydravisor | ", status_bar_style),
+        ];
+        // Always in view while on, so nobody destroys a VM by surprise
+        if app.quick_actions {
+            status_spans_left.push(Span::styled("QUICK", Style::default().fg(theme.primary_foreground).bg(theme.status_bar_mode_confirm_destroy_bg).bold()));
+            status_spans_left.push(Span::styled(" | ", status_bar_style));
+        }
+        if app.read_only {
+            status_spans_left.push(Span::styled("READ-ONLY", Style::default().fg(theme.primary_foreground).bg(theme.status_bar_mode_vm_wizard_bg).bold()));
+            status_spans_left.push(Span::styled(" | ", status_bar_style));
+        }
+        if let Some(daemon) = &app.daemon {
+            status_spans_left.push(Span::styled("Attached: ", status_bar_style));
+            status_spans_left.push(Span::styled(daemon.socket_path().display().to_string(), Style::default().fg(theme.quaternary_foreground).bg(theme.status_bar_background).bold()));
+            status_spans_left.push(Span::styled(" | ", status_bar_style));
+        }
+        if let Some(profile) = &app.config.profile {
+            status_spans_left.push(Span::styled("Profile: ", status_bar_style));
+            status_spans_left.push(Span::styled(profile.clone(), Style::default().fg(theme.quaternary_foreground).bg(theme.status_bar_background).bold()));
+            status_spans_left.push(Span::styled(" | ", status_bar_style));
+        }
+        // While the VM list is filtered, show how many VMs match out of all of them
+        let visible_vm_count = app.visible_vms().len();
+        let vm_count = if visible_vm_count == app.vms.len() {
+            app.vms.len().to_string()
+        } else {
+            format!("{}/{}", visible_vm_count, app.vms.len())
+        };
+        status_spans_left.extend(vec![
             Span::styled("View: ", status_bar_style),
             Span::styled(format!("{:?}", app.active_view), 
                          Style::default().fg(view_name_fg).bg(theme.status_bar_background).bold()),
             Span::styled(" | Input: ", status_bar_style),
             Span::styled(format!("{:?}", app.input_mode), 
                          Style::default().fg(theme.primary_foreground).bg(view_mode_bg).bold()),
-            Span::styled(format!(" | VMs: {} ", app.vms.len()), status_bar_style),
+            Span::styled(format!(" | VMs: {} ", vm_count), status_bar_style),
             Span::styled(format!("| Ollama: {} ", 
                 if cfg!(feature = "ollama_integration") { app.ollama_models.len().to_string() } else { "N/A".to_string() }), 
                 status_bar_style),
         ]);
+        if let Some(message) = &app.status_message {
+            let message_fg = if app.status_message_is_info { theme.success_text } else { theme.error_text };
+            status_spans_left.push(Span::styled(format!("| {}", message), Style::default().fg(message_fg).bg(theme.status_bar_background).bold()));
+        }
         
-        f.render_widget(Paragraph::new(status_spans_left).style(status_bar_style), status_bar_layout[0]);
+        f.render_widget(Paragraph::new(Line::from(status_spans_left)).style(status_bar_style), status_bar_layout[0]);
 
         let mut status_spans_right = vec![];
+        if app.active_view == AppView::Logs {
+            if app.log_min_level != tracing::Level::TRACE {
+                status_spans_right.push(Span::styled(format!("Level: {}+", app.log_min_level), Style::default().fg(theme.quaternary_foreground)));
+                status_spans_right.push(Span::raw(" | "));
+            }
+            if !app.log_search_input.is_empty() {
+                status_spans_right.push(Span::styled(format!("Search: \"{}\"", app.log_search_input.text()), Style::default().fg(theme.quaternary_foreground)));
+                status_spans_right.push(Span::raw(" | "));
+            }
+        }
         if app.active_view == AppView::VmList {
+            if app.vm_state_filter != VmStateFilter::All {
+                status_spans_right.push(Span::styled(format!("State: {:?}", app.vm_state_filter), Style::default().fg(theme.quaternary_foreground)));
+                status_spans_right.push(Span::raw(" | "));
+            }
+            if !app.vm_search_input.is_empty() {
+                status_spans_right.push(Span::styled(format!("Search: \"{}\"", app.vm_search_input.text()), Style::default().fg(theme.quaternary_foreground)));
+                status_spans_right.push(Span::raw(" | "));
+            }
             let (status_text, status_style) = if app.libvirt_connected {
                 ("Connected", Style::default().fg(theme.success_text))
             } else {
                 ("Disconnected", Style::default().fg(theme.error_text))
             };
-            status_spans_right.push(Span::styled("Libvirt: ", status_bar_style));
+            status_spans_right.push(Span::styled(if app.daemon.is_some() { "Daemon: " } else { "Libvirt: " }, status_bar_style));
             status_spans_right.push(Span::styled(status_text, status_style));
             status_spans_right.push(Span::raw(" | "));
         }
@@ -87,8 +141,35 @@ impl StatusBarWidget {
             status_spans_right.push(Span::styled(status_text, status_style));
             status_spans_right.push(Span::raw(" | "));
         }
+        #[cfg(feature = "openai_compat")]
+        if app.active_view == AppView::OpenAiModelList && app.config.providers.openai_compat.enabled {
+            let (status_text, status_style) = if app.openai_connected {
+                ("Connected", Style::default().fg(theme.success_text))
+            } else {
+                ("Disconnected", Style::default().fg(theme.error_text))
+            };
+            status_spans_right.push(Span::styled("OpenAI: ", status_bar_style));
+            status_spans_right.push(Span::styled(status_text, status_style));
+            status_spans_right.push(Span::raw(" | "));
+        }
+
+        if !app.mouse_capture_enabled {
+            status_spans_right.push(Span::styled("Mouse: off", Style::default().fg(theme.secondary_foreground)));
+            status_spans_right.push(Span::raw(" | "));
+        }
+
+        // Stepped by the clock, one frame per redraw tick, rather than by per-frame state
+        let now = Local::now();
+        let background_tasks = app.background_tasks.count();
+        if background_tasks > 0 {
+            let tick_ms = app.config.interface.refresh_interval_ms.max(1) as i64;
+            let frame = SPINNER_FRAMES[(now.timestamp_millis() / tick_ms) as usize % SPINNER_FRAMES.len()];
+            let label = if background_tasks == 1 { "task" } else { "tasks" };
+            status_spans_right.push(Span::styled(format!("{} {} {}", frame, background_tasks, label), Style::default().fg(theme.quaternary_foreground)));
+            status_spans_right.push(Span::raw(" | "));
+        }
 
-        status_spans_right.push(Span::from(Local::now().format("%H:%M:%S").to_string()));
+        status_spans_right.push(Span::from(now.format("%H:%M:%S").to_string()));
 
         f.render_widget(
             Paragraph::new(Line::from(status_spans_right))
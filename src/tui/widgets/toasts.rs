@@ -0,0 +1,56 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::time::Instant;
+use crate::tui::app::{App, ToastLevel};
+
+const TOAST_WIDTH: u16 = 48;
+
+pub struct ToastsWidget;
+
+impl ToastsWidget {
+    /// Stacks live toasts in the top-right of `area`, newest on top, until the space runs out.
+    pub fn render(f: &mut Frame, app: &App, area: Rect) {
+        let theme = &app.theme;
+        let now = Instant::now();
+        let width = TOAST_WIDTH.min(area.width);
+        let text_width = width.saturating_sub(2).max(1) as usize;
+        let mut y = area.y;
+
+        for toast in app.toasts.iter().rev().filter(|toast| toast.expires_at > now) {
+            let (title, color) = match toast.level {
+                ToastLevel::Info => ("Info", theme.log_level_info.fg.unwrap_or(theme.primary_foreground)),
+                ToastLevel::Success => ("Done", theme.success_text),
+                ToastLevel::Warning => ("Warning", theme.log_level_warn.fg.unwrap_or(theme.primary_foreground)),
+                ToastLevel::Error => ("Error", theme.error_text),
+            };
+            let lines: Vec<Line> = textwrap::wrap(&toast.text, text_width)
+                .into_iter()
+                .map(|line| Line::from(line.into_owned()))
+                .collect();
+            let height = lines.len() as u16 + 2;
+            if y + height > area.bottom() {
+                break;
+            }
+            let toast_area = Rect::new(area.right().saturating_sub(width), y, width, height);
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(theme.popup_background));
+            f.render_widget(Clear, toast_area);
+            f.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .style(Style::default().fg(theme.primary_foreground))
+                    .wrap(Wrap { trim: true }),
+                toast_area,
+            );
+            y += height;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, ListState, Paragraph},
+    Frame,
+};
+
+use crate::format::format_bytes;
+use crate::tui::App;
+use super::{centered_rect, render_list_scrollbar};
+
+// Shows what `hydravisor vm info` reports for one VM: its state and resources, then its disks,
+// network interfaces and consoles
+pub struct VmDetailPopupWidget;
+
+impl VmDetailPopupWidget {
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+        let Some(details) = &app.vm_detail else {
+            return;
+        };
+        let theme = &app.theme;
+        let popup_area = centered_rect(70, 60, area);
+        let block = Block::default()
+            .title(Line::from(Span::styled(format!("VM: {}", details.name), Style::default().fg(theme.primary_foreground).bold())))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_accent))
+            .style(Style::default().bg(theme.popup_background));
+        let inner_area = block.inner(popup_area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
+        let value_style = Style::default().fg(theme.primary_foreground);
+        let field = |name: &'static str, value: String| {
+            Line::from(vec![Span::styled(format!("{:<12}", name), Style::default().fg(theme.secondary_foreground)), Span::styled(value, value_style)])
+        };
+        let heading = |name: &'static str| Line::from(Span::styled(name, Style::default().fg(theme.secondary_foreground).bold()));
+        let item = |text: String| Line::from(Span::styled(format!("  {}", text), Style::default().fg(theme.tertiary_foreground)));
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+
+        let mut lines = vec![
+            field("UUID", details.instance_id.clone()),
+            field("State", details.state.label()),
+            field("Persistent", yes_no(details.persistent)),
+            field("Autostart", yes_no(details.autostart)),
+            field("vCPUs", details.vcpus.to_string()),
+            field("Memory", format!("{} MB / {} MB max", details.memory_kb / 1024, details.memory_max_kb / 1024)),
+            Line::from(""),
+            heading("Disks"),
+        ];
+        if details.disks.is_empty() {
+            lines.push(item("(none)".to_string()));
+        }
+        for disk in &details.disks {
+            lines.push(item(format!(
+                "{:<6} {:<7} {}{}",
+                disk.target,
+                disk.device,
+                disk.source.as_deref().unwrap_or("—"),
+                if disk.readonly { " (read-only)" } else { "" }
+            )));
+            if let (Some(capacity), Some(allocation)) = (disk.capacity_bytes, disk.allocation_bytes) {
+                lines.push(item(format!("{:<15}{} virtual, {} allocated", "", format_bytes(capacity), format_bytes(allocation))));
+            }
+            if let Some(backing_file) = &disk.backing_file {
+                lines.push(item(format!("{:<15}backed by {}", "", backing_file)));
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Network interfaces"));
+        if details.interfaces.is_empty() {
+            lines.push(item("(none)".to_string()));
+        }
+        for iface in &details.interfaces {
+            let ip_addresses = if iface.ip_addresses.is_empty() { "—".to_string() } else { iface.ip_addresses.join(", ") };
+            lines.push(item(format!(
+                "{:<17} {:<10} {:<8} {}",
+                iface.mac,
+                iface.source.as_deref().unwrap_or("—"),
+                iface.model.as_deref().unwrap_or("—"),
+                ip_addresses
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Consoles"));
+        if details.consoles.is_empty() {
+            lines.push(item("(none)".to_string()));
+        }
+        for console in &details.consoles {
+            lines.push(item(match console.kind.as_str() {
+                "serial" => format!(
+                    "serial port {} {}",
+                    console.port.as_deref().unwrap_or("?"),
+                    console.pty_path.as_deref().map_or_else(String::new, |pty| format!("on {}", pty))
+                ),
+                kind => format!("{} {}:{}", kind, console.listen.as_deref().unwrap_or("127.0.0.1"), console.port.as_deref().unwrap_or("?")),
+            }));
+        }
+
+        let visible = chunks[0].height as usize;
+        let max_scroll = lines.len().saturating_sub(visible);
+        let scroll = app.vm_detail_scroll.min(max_scroll);
+        let overflows = max_scroll > 0;
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        f.render_widget(Paragraph::new(lines).scroll((scroll.min(u16::MAX as usize) as u16, 0)), chunks[0]);
+        f.render_widget(
+            Paragraph::new("Esc/Enter: close | Up/Down: scroll").style(Style::default().fg(theme.help_text)),
+            chunks[1],
+        );
+        render_list_scrollbar(f, popup_area, theme, &ListState::default().with_offset(scroll), max_scroll + 1, overflows);
+
+        app.vm_detail_scroll = scroll;
+    }
+}
@@ -2,11 +2,13 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
     Frame,
 };
 use crate::tui::App;
+use crate::tui::app::{AppView, ListHitArea};
 use crate::libvirt_manager::VmState;
+use crate::format::format_bytes;
 
 pub struct VmListWidget;
 
@@ -23,14 +25,23 @@ impl VmListWidget {
             .split(area);
 
         // Left Pane: VM List
+        let list_title = if app.marked_vms.is_empty() {
+            "VMs".to_string()
+        } else {
+            format!("VMs ({} marked)", app.marked_vms.len())
+        };
         let left_pane_block = Block::default()
-            .title(Line::from(Span::styled("VMs", Style::default().fg(theme.primary_foreground).bold())))
+            .title(Line::from(Span::styled(list_title, Style::default().fg(theme.primary_foreground).bold())))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.border_primary));
         let left_pane_content_area = left_pane_block.inner(chunks[0]);
         f.render_widget(left_pane_block, chunks[0]);
 
-        let vm_items: Vec<ListItem> = app.vms.iter()
+        let visible_vms = app.visible_vms();
+        if visible_vms.is_empty() && !app.vms.is_empty() {
+            f.render_widget(Paragraph::new("No VMs match the filter").style(Style::default().fg(theme.secondary_foreground)), left_pane_content_area);
+        }
+        let vm_items: Vec<ListItem> = visible_vms.iter()
             .map(|vm| {
                 let state_style = match vm.state {
                     VmState::Running => theme.vm_list_status_running,
@@ -38,11 +49,20 @@ impl VmListWidget {
                     VmState::Suspended => theme.vm_list_status_other,
                     _ => theme.vm_list_status_other,
                 };
+                let marked = app.marked_vms.contains(&vm.name);
+                let (mark, name_style) = if marked {
+                    ("✓ ", Style::default().fg(theme.border_accent).bold())
+                } else {
+                    ("  ", Style::default().fg(theme.primary_foreground))
+                };
                 let content = Line::from(vec![
-                    Span::styled(format!("{} ", vm.name), Style::default().fg(theme.primary_foreground)),
+                    Span::styled(mark, Style::default().fg(theme.border_accent)),
+                    Span::styled(format!("{} ", vm.name), name_style),
                     Span::styled(format!("({:.7})", vm.instance_id), Style::default().fg(theme.secondary_foreground)),
                     Span::raw(" - "),
-                    Span::styled(format!("{:?}", vm.state), state_style),
+                    Span::styled(vm.state.label(), state_style),
+                    Span::raw(" - "),
+                    Span::styled(vm.ip_address.clone().unwrap_or_else(|| "—".to_string()), Style::default().fg(theme.secondary_foreground)),
                 ]);
                 ListItem::new(content)
             })
@@ -52,6 +72,7 @@ impl VmListWidget {
             .highlight_style(Style::default().fg(theme.list_highlight_fg).bg(theme.list_highlight_bg))
             .highlight_symbol(">> ");
         f.render_stateful_widget(vm_list, left_pane_content_area, &mut app.vm_list_state);
+        app.list_hit_area = Some(ListHitArea::single_line(AppView::VmList, left_pane_content_area, &app.vm_list_state, app.visible_vms().len()));
 
         // Right Pane: VM Details
         let right_pane_block = Block::default()
@@ -61,22 +82,67 @@ impl VmListWidget {
         let right_pane_content_area = right_pane_block.inner(chunks[1]);
         f.render_widget(right_pane_block, chunks[1]);
 
-        if let Some(selected_idx) = app.vm_list_state.selected() {
-            if let Some(vm) = app.vms.get(selected_idx) {
-                let details_text = vec![
-                    Line::from(vec![Span::styled("Name: ", Style::default().fg(theme.secondary_foreground)), Span::raw(&vm.name)]),
-                    Line::from(vec![Span::styled("ID:   ", Style::default().fg(theme.secondary_foreground)), Span::raw(format!("{}", vm.instance_id))]),
-                    Line::from(vec![Span::styled("State: ", Style::default().fg(theme.secondary_foreground)), Span::styled(format!("{:?}", vm.state), match vm.state {
-                        VmState::Running => theme.vm_list_status_running,
-                        VmState::Stopped => theme.vm_list_status_stopped,
-                        VmState::Suspended => theme.vm_list_status_other,
-                        _ => theme.vm_list_status_other,
-                    })]),
-                    Line::from(vec![Span::styled("CPUs: ", Style::default().fg(theme.secondary_foreground)), Span::raw(format!("{:?}", vm.cpu_cores_used.map(|c| c.to_string()).unwrap_or_else(|| "N/A".to_string())))]),
-                    Line::from(vec![Span::styled("Max Mem: ", Style::default().fg(theme.secondary_foreground)), Span::raw(format!("{:?} KB", vm.memory_max_kb.map(|m| m.to_string()).unwrap_or_else(|| "N/A".to_string())))]),
-                    Line::from(vec![Span::styled("Used Mem: ", Style::default().fg(theme.secondary_foreground)), Span::raw(format!("{:?} KB", vm.memory_used_kb.map(|m| m.to_string()).unwrap_or_else(|| "N/A".to_string())))]),
-                ];
-                f.render_widget(Paragraph::new(Text::from(details_text)).style(Style::default().fg(theme.primary_foreground)), right_pane_content_area);
+        if let Some(vm) = app.selected_vm() {
+            let details_text = vec![
+                Line::from(vec![Span::styled("Name: ", Style::default().fg(theme.secondary_foreground)), Span::raw(&vm.name)]),
+                Line::from(vec![Span::styled("ID:   ", Style::default().fg(theme.secondary_foreground)), Span::raw(format!("{}", vm.instance_id))]),
+                Line::from(vec![Span::styled("State: ", Style::default().fg(theme.secondary_foreground)), Span::styled(vm.state.label(), match vm.state {
+                    VmState::Running => theme.vm_list_status_running,
+                    VmState::Stopped => theme.vm_list_status_stopped,
+                    VmState::Suspended => theme.vm_list_status_other,
+                    _ => theme.vm_list_status_other,
+                })]),
+                Line::from(vec![Span::styled("IP:   ", Style::default().fg(theme.secondary_foreground)), Span::raw(vm.ip_address.clone().unwrap_or_else(|| "—".to_string()))]),
+                Line::from(vec![Span::styled("vCPUs: ", Style::default().fg(theme.secondary_foreground)), Span::raw(vm.cpu_cores_used.map(|c| c.to_string()).unwrap_or_else(|| "N/A".to_string()))]),
+                Line::from(vec![Span::styled("Managed: ", Style::default().fg(theme.secondary_foreground)), Span::raw(match (vm.managed, &vm.role) {
+                    (true, Some(role)) => format!("yes, role {}", role),
+                    (true, None) => "yes".to_string(),
+                    (false, _) => "no (see `vm import`)".to_string(),
+                })]),
+            ];
+            let detail_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(details_text.len() as u16),
+                    Constraint::Length(2), // Memory gauge
+                    Constraint::Length(2), // CPU gauge
+                    Constraint::Length(2), // Disk gauge
+                    Constraint::Min(0),
+                ])
+                .split(right_pane_content_area);
+            f.render_widget(Paragraph::new(Text::from(details_text)).style(Style::default().fg(theme.primary_foreground)), detail_chunks[0]);
+
+            // Usage is only meaningful while the domain is running
+            let running = matches!(vm.state, VmState::Running);
+            let memory = match (vm.memory_used_kb, vm.memory_max_kb) {
+                (Some(used), Some(max)) if running && max > 0 => (
+                    used as f64 / max as f64,
+                    format!("{} / {}", format_bytes(used * 1024), format_bytes(max * 1024)),
+                ),
+                (_, Some(max)) => (0.0, format!("— / {}", format_bytes(max * 1024))),
+                _ => (0.0, "N/A".to_string()),
+            };
+            let cpu = match app.vm_cpu_usage.get(&vm.instance_id) {
+                Some(&percent) if running => (percent / 100.0, format!("{:.1}%", percent)),
+                _ if running => (0.0, "sampling…".to_string()),
+                _ => (0.0, "—".to_string()),
+            };
+            // Allocated against virtual size, so thin-provisioned overlays show how much is really used
+            let disk = match (vm.disk_allocated_gb, vm.disk_virtual_gb) {
+                (Some(allocated), Some(virtual_gb)) if virtual_gb > 0.0 => (
+                    allocated / virtual_gb,
+                    format!("{:.1} GB used / {:.1} GB", allocated, virtual_gb),
+                ),
+                _ => (0.0, "N/A".to_string()),
+            };
+            let gauges = [("Memory", memory), ("CPU", cpu), ("Disk", disk)];
+            for ((title, (ratio, label)), chunk) in gauges.into_iter().zip([detail_chunks[1], detail_chunks[2], detail_chunks[3]]) {
+                let gauge = Gauge::default()
+                    .block(Block::default().title(title).borders(Borders::TOP).border_style(Style::default().fg(theme.border_secondary)))
+                    .gauge_style(Style::default().fg(theme.border_accent).bg(theme.secondary_background))
+                    .ratio(ratio.clamp(0.0, 1.0))
+                    .label(Span::styled(label, Style::default().fg(theme.primary_foreground)));
+                f.render_widget(gauge, chunk);
             }
         } else {
             f.render_widget(Paragraph::new("No VM selected").style(Style::default().fg(theme.secondary_foreground)), right_pane_content_area);
@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::tui::App;
+use super::centered_rect;
+
+pub struct VmPreviewPopupWidget;
+
+impl VmPreviewPopupWidget {
+    /// Shows the new-VM wizard's dry run: the disk path (and whether it already exists) and
+    /// the exact domain XML that would be defined.
+    pub fn render(f: &mut Frame, app: &mut App, area: Rect) {
+        let Some((vm_config, preview)) = &app.new_vm_preview else {
+            return;
+        };
+        let theme = &app.theme;
+
+        let (disk_note, disk_style, instructions) = if !preview.disk_exists {
+            ("new", Style::default().fg(theme.success_text), "Enter: create VM   ↑/↓: scroll   Esc: back to the wizard".to_string())
+        } else if preview.disk_used_by.is_empty() {
+            (
+                "already exists",
+                theme.log_level_warn,
+                "r: reuse the existing disk   o: overwrite it (its data is lost)   Esc: back to the wizard".to_string(),
+            )
+        } else {
+            (
+                "already exists",
+                Style::default().fg(theme.error_text),
+                format!("The disk is used by VM '{}'. Esc: back to the wizard to choose another path", preview.disk_used_by.join("', '")),
+            )
+        };
+
+        let block = Block::default()
+            .title(format!("Dry Run: {}", vm_config.instance_id))
+            .borders(Borders::ALL)
+            .border_style(disk_style)
+            .title_alignment(Alignment::Center)
+            .style(Style::default().bg(theme.popup_background));
+        let popup_area = centered_rect(70, 70, area);
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block.clone(), popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Disk path
+                Constraint::Min(1),    // Domain XML
+                Constraint::Length(2), // Instructions
+            ])
+            .split(block.inner(popup_area));
+
+        let disk_line = Line::from(vec![
+            Span::styled("Disk: ", Style::default().fg(theme.secondary_foreground)),
+            Span::styled(preview.disk_path.clone(), Style::default().fg(theme.primary_foreground).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" ({})", disk_note), disk_style),
+        ]);
+        f.render_widget(Paragraph::new(disk_line).wrap(Wrap { trim: false }), chunks[0]);
+
+        // Clamp the scroll so the last line of XML can't scroll out of view
+        let xml_lines: Vec<Line> = preview.domain_xml.lines().map(|line| Line::from(line.to_string())).collect();
+        let max_scroll = xml_lines.len().saturating_sub(chunks[1].height as usize) as u16;
+        app.new_vm_preview_scroll = app.new_vm_preview_scroll.min(max_scroll);
+        let xml = Paragraph::new(xml_lines)
+            .style(Style::default().fg(theme.primary_foreground))
+            .block(Block::default().borders(Borders::TOP).title("Domain XML").border_style(Style::default().fg(theme.border_secondary)))
+            .scroll((app.new_vm_preview_scroll, 0));
+        f.render_widget(xml, chunks[1]);
+
+        let instructions = Paragraph::new(instructions)
+            .style(Style::default().fg(app.theme.help_text))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(instructions, chunks[2]);
+    }
+}
@@ -0,0 +1,100 @@
+// src/vm_registry.rs
+// Remembers libvirt domains created outside Hydravisor that `vm import` brought under its
+// management, as one JSON sidecar per domain UUID under the XDG data dir.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use xdg::BaseDirectories;
+
+use crate::config::{APP_NAME, PROFILES_DIR_NAME};
+
+const DOMAINS_DIR_NAME: &str = "domains";
+
+// What Hydravisor keeps about an imported domain. Domains it created carry their labels in their
+// own XML metadata instead.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImportedVm {
+    pub uuid: String,
+    pub name: String,        // At import time; the UUID is what identifies the domain
+    pub imported_at: String, // RFC 3339
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>, // A role from policy.toml's [roles]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+}
+
+pub struct VmRegistry {
+    dir: PathBuf,
+}
+
+impl VmRegistry {
+    /// Opens the sidecar directory, `$XDG_DATA_HOME/hydravisor/domains`, namespaced by profile.
+    pub fn new(profile: Option<&str>) -> Result<Self> {
+        let data_home = BaseDirectories::with_prefix(APP_NAME)?.get_data_home();
+        let dir = match profile {
+            Some(profile) => data_home.join(PROFILES_DIR_NAME).join(profile).join(DOMAINS_DIR_NAME),
+            None => data_home.join(DOMAINS_DIR_NAME),
+        };
+        Ok(VmRegistry { dir })
+    }
+
+    fn path(&self, uuid: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", uuid))
+    }
+
+    /// Every imported domain, by UUID. Unreadable sidecars are skipped.
+    pub fn all(&self) -> HashMap<String, ImportedVm> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return HashMap::new(); // Nothing imported yet
+        };
+        entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|path| {
+                let imported = fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| serde_json::from_str::<ImportedVm>(&content).map_err(anyhow::Error::from));
+                match imported {
+                    Ok(imported) => Some((imported.uuid.clone(), imported)),
+                    Err(e) => {
+                        tracing::warn!("Skipping unreadable imported VM file {:?}: {}", path, e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The import record of one domain, if it has a readable one.
+    pub fn get(&self, uuid: &str) -> Option<ImportedVm> {
+        let content = fs::read_to_string(self.path(uuid)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Writes `<uuid>.json`, replacing an earlier import of the same domain.
+    pub fn save(&self, imported: &ImportedVm) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create imported VM directory {:?}", self.dir))?;
+        let path = self.path(&imported.uuid);
+        fs::write(&path, serde_json::to_string_pretty(imported)?)
+            .with_context(|| format!("Failed to write imported VM file {:?}", path))?;
+        Ok(path)
+    }
+
+    /// Deletes a domain's sidecar, returning whether there was one.
+    pub fn remove(&self, uuid: &str) -> Result<bool> {
+        let path = self.path(uuid);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(anyhow::anyhow!("Failed to remove imported VM file {:?}: {}", path, e)),
+        }
+    }
+}
+
+// TODO: Add tests for VmRegistry:
+// - save/all round-trip, and all() on a missing directory.
+// - remove() reports whether a sidecar existed.